@@ -1,34 +1,51 @@
 use crate::{
+    client_builder::{ClientBuilder, RetryPolicy, Timeouts},
+    encoding::{
+        parse_consensus_version, Encoding, VersionedJson, VersionedSsz, SSZ_MIME_TYPE,
+        SSZ_OR_JSON_ACCEPT,
+    },
+    events::{Backoff, Event, EventStream},
     types::{
-        ApiResult, AttestationDuty, BalanceSummary, BeaconHeaderSummary,
-        BeaconProposerRegistration, BlockId, BroadcastValidation, CommitteeDescriptor,
-        CommitteeFilter, CommitteeSummary, ConnectionOrientation, CoordinateWithMetadata,
-        DepositContract, DepositSnapshot, FinalityCheckpoints, GenesisDetails, HealthStatus,
-        NetworkIdentity, PeerDescription, PeerState, PeerSummary, ProposerDuty, PublicKeyOrIndex,
-        RootData, StateId, SubmitSignedBeaconBlock, SyncCommitteeDescriptor, SyncCommitteeDuty,
-        SyncCommitteeSummary, SyncStatus, Topic, ValidatorLiveness, ValidatorStatus,
-        ValidatorSummary, Value, VersionData, VersionedValue,
+        ApiResult, AttestationDuty, AttestationRewards, BalanceSummary, BeaconHeaderSummary,
+        BeaconProposerRegistration, BlockContents, BlockId, BlockProposal, BlockRewards,
+        BroadcastValidation, CommitteeDescriptor, CommitteeFilter, CommitteeSummary,
+        ConnectionOrientation, CoordinateWithMetadata, DepositContract, DepositSnapshot,
+        FinalityCheckpoints, GenesisDetails, HealthStatus, NetworkIdentity, PeerDescription,
+        PeerState, PeerSummary, PendingDeposit, ProduceBlockResponse, ProposerDuty,
+        PublicKeyOrIndex, RootData, SingleAttestation, StateId, SubmitSignedBeaconBlock,
+        SyncCommitteeDescriptor, SyncCommitteeDuty, SyncCommitteeReward, SyncCommitteeSummary,
+        SyncStatus, Topic, ValidatorLiveness, ValidatorStatus, ValidatorSummary, Value,
+        VersionData, VersionedValue,
     },
-    ApiError, Error,
+    ApiError, Error, ETH_CONSENSUS_VERSION_HEADER,
 };
 use ethereum_consensus::{
     altair::SyncCommitteeMessage,
     builder::SignedValidatorRegistration,
     capella::{SignedBlsToExecutionChange, Withdrawal},
+    deneb::mainnet::MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    electra::{PendingConsolidation, PendingPartialWithdrawal},
     networking::PeerId,
     phase0::{AttestationData, Fork, ProposerSlashing, SignedVoluntaryExit},
     primitives::{
         BlobIndex, Bytes32, CommitteeIndex, Epoch, RandaoReveal, Root, Slot, ValidatorIndex,
     },
+    ssz::prelude::{serialize, Deserialize, List, SimpleSerialize, SimpleSerializeError, U256},
     Fork as Version,
 };
-use http::StatusCode;
+use http::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Method, StatusCode,
+};
 use itertools::Itertools;
-use mev_share_sse::{client::EventStream, EventClient};
 use std::collections::HashMap;
+use tracing::warn;
 use url::Url;
 
 pub const CONSENSUS_VERSION_HEADER: &str = "eth-consensus-version";
+pub const EXECUTION_PAYLOAD_BLINDED_HEADER: &str = "eth-execution-payload-blinded";
+pub const EXECUTION_PAYLOAD_VALUE_HEADER: &str = "eth-execution-payload-value";
+pub const CONSENSUS_BLOCK_VALUE_HEADER: &str = "eth-consensus-block-value";
 
 pub async fn api_error_or_ok(response: reqwest::Response) -> Result<(), Error> {
     match response.status() {
@@ -40,7 +57,7 @@ pub async fn api_error_or_ok(response: reqwest::Response) -> Result<(), Error> {
     }
 }
 
-async fn api_error_or_value<T: serde::de::DeserializeOwned>(
+pub(crate) async fn api_error_or_value<T: serde::de::DeserializeOwned>(
     response: reqwest::Response,
 ) -> Result<T, Error> {
     match response.status() {
@@ -55,35 +72,103 @@ async fn api_error_or_value<T: serde::de::DeserializeOwned>(
     }
 }
 
+// Returns `true` if the body of `response` is SSZ-encoded.
+fn is_ssz(response: &reqwest::Response) -> bool {
+    response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.starts_with(SSZ_MIME_TYPE))
+        .unwrap_or_default()
+}
+
+fn consensus_version(response: &reqwest::Response) -> Result<Version, Error> {
+    response
+        .headers()
+        .get(CONSENSUS_VERSION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_consensus_version)
+        .ok_or_else(|| {
+            Error::MissingExpectedData(format!("`{ETH_CONSENSUS_VERSION_HEADER}` header"))
+        })
+}
+
+fn response_header<'a>(response: &'a reqwest::Response, name: &str) -> Result<&'a str, Error> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(|| Error::MissingExpectedData(format!("`{name}` header")))
+}
+
+fn invalid_header(name: &str) -> Error {
+    Error::MissingExpectedData(format!("valid `{name}` header"))
+}
+
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS | StatusCode::BAD_GATEWAY | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
 pub trait ClientTypes: Clone {
-    type SignedContributionAndProof: serde::Serialize;
+    type SignedContributionAndProof: serde::Serialize + serde::de::DeserializeOwned;
     type SyncCommitteeContribution: serde::Serialize + serde::de::DeserializeOwned;
-    type BlindedBeaconBlock: serde::Serialize + serde::de::DeserializeOwned;
-    type SignedBlindedBeaconBlock: serde::Serialize + serde::de::DeserializeOwned;
-    type Attestation: serde::Serialize + serde::de::DeserializeOwned;
-    type AttesterSlashing: serde::Serialize + serde::de::DeserializeOwned;
-    type BeaconBlock: serde::Serialize + serde::de::DeserializeOwned;
-    type BeaconState: serde::Serialize + serde::de::DeserializeOwned;
-    type SignedAggregateAndProof: serde::Serialize;
-    type SignedBeaconBlock: serde::Serialize + serde::de::DeserializeOwned;
-    type Blob: serde::Serialize + serde::de::DeserializeOwned;
-    type BlobSidecar: serde::Serialize + serde::de::DeserializeOwned;
-    type LightClientBootstrap: serde::Serialize + serde::de::DeserializeOwned;
-    type LightClientUpdate: serde::Serialize + serde::de::DeserializeOwned;
-    type LightClientFinalityUpdate: serde::Serialize + serde::de::DeserializeOwned;
-    type LightClientOptimisticUpdate: serde::Serialize + serde::de::DeserializeOwned;
+    type BlindedBeaconBlock: serde::Serialize
+        + serde::de::DeserializeOwned
+        + SimpleSerialize
+        + VersionedSsz
+        + VersionedJson;
+    type SignedBlindedBeaconBlock: serde::Serialize
+        + serde::de::DeserializeOwned
+        + SimpleSerialize
+        + VersionedSsz;
+    type Attestation: serde::Serialize + serde::de::DeserializeOwned + VersionedJson;
+    type AttesterSlashing: serde::Serialize + serde::de::DeserializeOwned + VersionedJson;
+    type BeaconBlock: serde::Serialize
+        + serde::de::DeserializeOwned
+        + SimpleSerialize
+        + VersionedSsz;
+    type BeaconState: serde::Serialize
+        + serde::de::DeserializeOwned
+        + SimpleSerialize
+        + VersionedSsz;
+    type SignedAggregateAndProof: serde::Serialize + serde::de::DeserializeOwned;
+    type SignedBeaconBlock: serde::Serialize
+        + serde::de::DeserializeOwned
+        + SimpleSerialize
+        + VersionedSsz;
+    type Blob: serde::Serialize + serde::de::DeserializeOwned + SimpleSerialize;
+    type BlobSidecar: serde::Serialize + serde::de::DeserializeOwned + SimpleSerialize + Clone;
+    type LightClientBootstrap: serde::Serialize + serde::de::DeserializeOwned + VersionedJson;
+    type LightClientUpdate: serde::Serialize + serde::de::DeserializeOwned + VersionedJson;
+    type LightClientFinalityUpdate: serde::Serialize + serde::de::DeserializeOwned + VersionedJson;
+    type LightClientOptimisticUpdate: serde::Serialize + serde::de::DeserializeOwned + VersionedJson;
 }
 
 #[derive(Clone)]
 pub struct Client<C> {
     pub http: reqwest::Client,
     pub endpoint: Url,
+    encoding: Encoding,
+    event_backoff: Backoff,
+    timeouts: Timeouts,
+    retry_policy: RetryPolicy,
     _phantom: std::marker::PhantomData<C>,
 }
 
 impl<C: ClientTypes> Client<C> {
     pub fn new_with_client<U: Into<Url>>(client: reqwest::Client, endpoint: U) -> Self {
-        Self { http: client, endpoint: endpoint.into(), _phantom: std::marker::PhantomData }
+        Self {
+            http: client,
+            endpoint: endpoint.into(),
+            encoding: Encoding::default(),
+            event_backoff: Backoff::default(),
+            timeouts: Timeouts::default(),
+            retry_policy: RetryPolicy::default(),
+            _phantom: std::marker::PhantomData,
+        }
     }
 
     pub fn new<U: Into<Url>>(endpoint: U) -> Self {
@@ -91,6 +176,69 @@ impl<C: ClientTypes> Client<C> {
         Self::new_with_client(client, endpoint)
     }
 
+    pub fn builder<U: Into<Url>>(endpoint: U) -> ClientBuilder<C> {
+        ClientBuilder::new(endpoint)
+    }
+
+    /// Sets the encoding preferred for endpoints that support SSZ.
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Sets how event streams reconnect after the connection to the node drops.
+    pub fn with_event_backoff(mut self, backoff: Backoff) -> Self {
+        self.event_backoff = backoff;
+        self
+    }
+
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sends `request`, applying the timeout configured for its class of endpoint (unless the
+    /// request sets its own) and retrying `GET` requests according to the retry policy.
+    pub async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, Error> {
+        let mut request = request.build()?;
+        if request.timeout().is_none() {
+            let path = request.url().path();
+            let path =
+                path.strip_prefix(self.endpoint.path().trim_end_matches('/')).unwrap_or(path);
+            let timeout = self.timeouts.for_path(path);
+            *request.timeout_mut() = timeout;
+        }
+        let max_retries =
+            if request.method() == Method::GET { self.retry_policy.max_retries } else { 0 };
+        let mut attempt = 0;
+        loop {
+            let retry = if attempt < max_retries { request.try_clone() } else { None };
+            let result = self.http.execute(request).await;
+            let Some(retry) = retry else { return Ok(result?) };
+            match result {
+                Ok(response) if !is_retryable_status(response.status()) => return Ok(response),
+                Err(err) if !(err.is_timeout() || err.is_connect()) => return Err(err.into()),
+                Ok(response) => {
+                    let status = response.status();
+                    warn!(%status, url = %response.url(), attempt, "retrying request")
+                }
+                Err(err) => warn!(%err, attempt, "retrying request"),
+            }
+            tokio::time::sleep(self.retry_policy.delay(attempt)).await;
+            request = retry;
+            attempt += 1;
+        }
+    }
+
     pub async fn get<T: serde::Serialize + serde::de::DeserializeOwned>(
         &self,
         path: &str,
@@ -104,7 +252,7 @@ impl<C: ClientTypes> Client<C> {
 
     pub async fn http_get(&self, path: &str) -> Result<reqwest::Response, Error> {
         let target = self.endpoint.join(path)?;
-        let response = self.http.get(target).send().await?;
+        let response = self.send(self.http.get(target)).await?;
         Ok(response)
     }
 
@@ -123,10 +271,75 @@ impl<C: ClientTypes> Client<C> {
         argument: &T,
     ) -> Result<reqwest::Response, Error> {
         let target = self.endpoint.join(path)?;
-        let response = self.http.post(target).json(argument).send().await?;
+        let response = self.send(self.http.post(target).json(argument)).await?;
         Ok(response)
     }
 
+    // Sends `request`, asking for an SSZ response if preferred and retrying with JSON if the node
+    // answers `406 Not Acceptable`.
+    async fn send_negotiated(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, Error> {
+        if self.encoding == Encoding::Json {
+            return self.send(request).await
+        }
+        let fallback = request.try_clone();
+        let response = self.send(request.header(ACCEPT, SSZ_OR_JSON_ACCEPT)).await?;
+        match fallback {
+            Some(fallback) if response.status() == StatusCode::NOT_ACCEPTABLE => {
+                self.send(fallback).await
+            }
+            _ => Ok(response),
+        }
+    }
+
+    async fn get_versioned<T: serde::de::DeserializeOwned + VersionedSsz>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, Error> {
+        let response = self.send_negotiated(request).await?;
+        if is_ssz(&response) {
+            let version = consensus_version(&response)?;
+            let encoding = response.bytes().await?;
+            return T::deserialize_versioned(version, &encoding)
+        }
+        let result: ApiResult<VersionedValue<T>> = response.json().await?;
+        match result {
+            ApiResult::Ok(result) => Ok(result.data),
+            ApiResult::Err(err) => Err(err.into()),
+        }
+    }
+
+    // Sends `request` and deserializes the `data` of the response according to its `version`.
+    async fn get_versioned_json<T: VersionedJson>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, Error> {
+        let response = self.send(request).await?;
+        let result: ApiResult<VersionedValue<serde_json::Value>> = response.json().await?;
+        match result {
+            ApiResult::Ok(result) => T::deserialize_versioned_json(result.version, result.data),
+            ApiResult::Err(err) => Err(err.into()),
+        }
+    }
+
+    // Builds a request to publish a signed block to `path`; the body is left to the caller.
+    fn publish_block_request(
+        &self,
+        path: &str,
+        version: Version,
+        broadcast_validation: Option<BroadcastValidation>,
+    ) -> Result<reqwest::RequestBuilder, Error> {
+        let target = self.endpoint.join(path)?;
+        let mut request =
+            self.http.post(target).header(CONSENSUS_VERSION_HEADER, version.to_string());
+        if let Some(validation) = broadcast_validation {
+            request = request.query(&[("broadcast_validation", validation)]);
+        }
+        Ok(request)
+    }
+
     /* beacon namespace */
     pub async fn get_genesis_details(&self) -> Result<GenesisDetails, Error> {
         let details: Value<GenesisDetails> = self.get("eth/v1/beacon/genesis").await?;
@@ -171,7 +384,7 @@ impl<C: ClientTypes> Client<C> {
             let filters = filters.iter().join(",");
             request = request.query(&[("status", filters)]);
         }
-        let response = request.send().await?;
+        let response = self.send(request).await?;
 
         let result: ApiResult<Value<Vec<ValidatorSummary>>> = response.json().await?;
         match result {
@@ -203,7 +416,7 @@ impl<C: ClientTypes> Client<C> {
             let filters = filters.iter().join(",");
             request = request.query(&[("id", filters)]);
         }
-        let response = request.send().await?;
+        let response = self.send(request).await?;
 
         let result: ApiResult<Value<Vec<BalanceSummary>>> = response.json().await?;
         match result {
@@ -233,7 +446,7 @@ impl<C: ClientTypes> Client<C> {
         if let Some(slot) = filter.slot {
             request = request.query(&[("slot", slot)]);
         }
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: ApiResult<Value<Vec<CommitteeSummary>>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -252,7 +465,7 @@ impl<C: ClientTypes> Client<C> {
         if let Some(epoch) = epoch {
             request = request.query(&[("epoch", epoch)]);
         }
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: ApiResult<Value<SyncCommitteeSummary>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -267,7 +480,7 @@ impl<C: ClientTypes> Client<C> {
         if let Some(epoch) = epoch {
             request = request.query(&[("epoch", epoch)]);
         }
-        let response = request.send().await?;
+        let response = self.send(request).await?;
 
         let result: ApiResult<Value<Bytes32>> = response.json().await?;
         match result {
@@ -276,6 +489,30 @@ impl<C: ClientTypes> Client<C> {
         }
     }
 
+    pub async fn get_pending_deposits(&self, id: StateId) -> Result<Vec<PendingDeposit>, Error> {
+        let result: Value<_> =
+            self.get(&format!("eth/v1/beacon/states/{id}/pending_deposits")).await?;
+        Ok(result.data)
+    }
+
+    pub async fn get_pending_partial_withdrawals(
+        &self,
+        id: StateId,
+    ) -> Result<Vec<PendingPartialWithdrawal>, Error> {
+        let result: Value<_> =
+            self.get(&format!("eth/v1/beacon/states/{id}/pending_partial_withdrawals")).await?;
+        Ok(result.data)
+    }
+
+    pub async fn get_pending_consolidations(
+        &self,
+        id: StateId,
+    ) -> Result<Vec<PendingConsolidation>, Error> {
+        let result: Value<_> =
+            self.get(&format!("eth/v1/beacon/states/{id}/pending_consolidations")).await?;
+        Ok(result.data)
+    }
+
     pub async fn get_beacon_header_at_head(&self) -> Result<BeaconHeaderSummary, Error> {
         let result: Value<BeaconHeaderSummary> = self.get("eth/v1/beacon/headers").await?;
         Ok(result.data)
@@ -288,7 +525,7 @@ impl<C: ClientTypes> Client<C> {
         let target = self.endpoint.join("eth/v1/beacon/headers")?;
         let mut request = self.http.get(target);
         request = request.query(&[("slot", slot)]);
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: ApiResult<Value<BeaconHeaderSummary>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -303,7 +540,7 @@ impl<C: ClientTypes> Client<C> {
         let target = self.endpoint.join("eth/v1/beacon/headers")?;
         let mut request = self.http.get(target);
         request = request.query(&[("parent_root", format!("{parent_root:?}"))]);
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: ApiResult<Value<BeaconHeaderSummary>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -330,16 +567,20 @@ impl<C: ClientTypes> Client<C> {
         version: Version,
         broadcast_validation: Option<BroadcastValidation>,
     ) -> Result<(), Error> {
-        let target = self.endpoint.join("eth/v2/beacon/blinded_blocks")?;
-        let mut request = self
-            .http
-            .post(target)
-            .json(block)
-            .header(CONSENSUS_VERSION_HEADER, version.to_string());
-        if let Some(validation) = broadcast_validation {
-            request = request.query(&[("broadcast_validation", validation)]);
+        let path = "eth/v2/beacon/blinded_blocks";
+        if self.encoding == Encoding::Ssz {
+            let body = serialize(block).map_err(SimpleSerializeError::from)?;
+            let request = self
+                .publish_block_request(path, version, broadcast_validation)?
+                .header(CONTENT_TYPE, SSZ_MIME_TYPE)
+                .body(body);
+            let response = self.send(request).await?;
+            if response.status() != StatusCode::UNSUPPORTED_MEDIA_TYPE {
+                return api_error_or_ok(response).await
+            }
         }
-        let response = request.send().await?;
+        let request = self.publish_block_request(path, version, broadcast_validation)?.json(block);
+        let response = self.send(request).await?;
         api_error_or_ok(response).await
     }
 
@@ -358,24 +599,28 @@ impl<C: ClientTypes> Client<C> {
         version: Version,
         broadcast_validation: Option<BroadcastValidation>,
     ) -> Result<(), Error> {
-        let target = self.endpoint.join("eth/v2/beacon/blocks")?;
-        let mut request = self
-            .http
-            .post(target)
-            .json(&request)
-            .header(CONSENSUS_VERSION_HEADER, version.to_string());
-        if let Some(validation) = broadcast_validation {
-            request = request.query(&[("broadcast_validation", validation)]);
+        let path = "eth/v2/beacon/blocks";
+        if self.encoding == Encoding::Ssz {
+            let body = request.to_ssz_bytes()?;
+            let request = self
+                .publish_block_request(path, version, broadcast_validation)?
+                .header(CONTENT_TYPE, SSZ_MIME_TYPE)
+                .body(body);
+            let response = self.send(request).await?;
+            if response.status() != StatusCode::UNSUPPORTED_MEDIA_TYPE {
+                return api_error_or_ok(response).await
+            }
         }
-        let response = request.send().await?;
+        let request =
+            self.publish_block_request(path, version, broadcast_validation)?.json(&request);
+        let response = self.send(request).await?;
         api_error_or_ok(response).await
     }
 
     // v2 endpoint
     pub async fn get_beacon_block(&self, id: BlockId) -> Result<C::SignedBeaconBlock, Error> {
-        let result: VersionedValue<C::SignedBeaconBlock> =
-            self.get(&format!("eth/v2/beacon/blocks/{id}")).await?;
-        Ok(result.data)
+        let target = self.endpoint.join(&format!("eth/v2/beacon/blocks/{id}"))?;
+        self.get_versioned(self.http.get(target)).await
     }
 
     pub async fn get_beacon_block_root(&self, id: BlockId) -> Result<Root, Error> {
@@ -403,7 +648,14 @@ impl<C: ClientTypes> Client<C> {
         if !indices.is_empty() {
             request = request.query(&[("indices", indices)]);
         }
-        let response = request.send().await?;
+        let response = self.send_negotiated(request).await?;
+        if is_ssz(&response) {
+            let encoding = response.bytes().await?;
+            let sidecars =
+                List::<C::BlobSidecar, MAX_BLOB_COMMITMENTS_PER_BLOCK>::deserialize(&encoding)
+                    .map_err(SimpleSerializeError::from)?;
+            return Ok(sidecars.to_vec())
+        }
         let result: ApiResult<Value<_>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -420,18 +672,17 @@ impl<C: ClientTypes> Client<C> {
         &self,
         id: BlockId,
     ) -> Result<C::SignedBlindedBeaconBlock, Error> {
-        let result: VersionedValue<C::SignedBlindedBeaconBlock> =
-            self.get(&format!("eth/v1/beacon/blinded_blocks/{id}")).await?;
-        Ok(result.data)
+        let target = self.endpoint.join(&format!("eth/v1/beacon/blinded_blocks/{id}"))?;
+        self.get_versioned(self.http.get(target)).await
     }
 
     pub async fn get_light_client_bootstrap(
         &self,
         block: Root,
     ) -> Result<C::LightClientBootstrap, Error> {
-        let result: Value<_> =
-            self.get(&format!("eth/v1/beacon/light_client/bootstrap/{block:?}")).await?;
-        Ok(result.data)
+        let path = format!("eth/v1/beacon/light_client/bootstrap/{block:?}");
+        let target = self.endpoint.join(&path)?;
+        self.get_versioned_json(self.http.get(target)).await
     }
 
     pub async fn get_light_client_updates(
@@ -443,10 +694,16 @@ impl<C: ClientTypes> Client<C> {
         let mut request = self.http.get(target);
         request = request.query(&[("start_period", start), ("count", count)]);
 
-        let response = request.send().await?;
-        let result: ApiResult<Value<_>> = response.json().await?;
+        // each update in the response carries its own `version`
+        let response = self.send(request).await?;
+        let result: ApiResult<Vec<VersionedValue<serde_json::Value>>> = response.json().await?;
         match result {
-            ApiResult::Ok(result) => Ok(result.data),
+            ApiResult::Ok(result) => result
+                .into_iter()
+                .map(|update| {
+                    C::LightClientUpdate::deserialize_versioned_json(update.version, update.data)
+                })
+                .collect(),
             ApiResult::Err(err) => Err(err.into()),
         }
     }
@@ -454,14 +711,48 @@ impl<C: ClientTypes> Client<C> {
     pub async fn get_light_client_finality_update(
         &self,
     ) -> Result<C::LightClientFinalityUpdate, Error> {
-        let result: Value<_> = self.get("eth/v1/beacon/light_client/finality_update").await?;
-        Ok(result.data)
+        let target = self.endpoint.join("eth/v1/beacon/light_client/finality_update")?;
+        self.get_versioned_json(self.http.get(target)).await
     }
 
     pub async fn get_light_client_optimistic_update(
         &self,
     ) -> Result<C::LightClientOptimisticUpdate, Error> {
-        let result: Value<_> = self.get("eth/v1/beacon/light_client/optimistic_update").await?;
+        let target = self.endpoint.join("eth/v1/beacon/light_client/optimistic_update")?;
+        self.get_versioned_json(self.http.get(target)).await
+    }
+
+    pub async fn get_block_rewards(&self, block_id: BlockId) -> Result<BlockRewards, Error> {
+        let result: Value<_> =
+            self.get(&format!("eth/v1/beacon/rewards/blocks/{block_id}")).await?;
+        Ok(result.data)
+    }
+
+    /// Returns the attestation rewards of the given validators in `epoch`, or of all validators
+    /// if `validator_ids` is empty.
+    pub async fn get_attestation_rewards(
+        &self,
+        epoch: Epoch,
+        validator_ids: &[PublicKeyOrIndex],
+    ) -> Result<AttestationRewards, Error> {
+        let endpoint = format!("eth/v1/beacon/rewards/attestations/{epoch}");
+        let validator_ids = validator_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        let response = self.http_post(&endpoint, &validator_ids).await?;
+        let result: Value<AttestationRewards> = api_error_or_value(response).await?;
+        Ok(result.data)
+    }
+
+    /// Returns the sync committee rewards of the given validators in the block with `block_id`,
+    /// or of the whole sync committee if `validator_ids` is empty.
+    pub async fn get_sync_committee_rewards(
+        &self,
+        block_id: BlockId,
+        validator_ids: &[PublicKeyOrIndex],
+    ) -> Result<Vec<SyncCommitteeReward>, Error> {
+        let endpoint = format!("eth/v1/beacon/rewards/sync_committee/{block_id}");
+        let validator_ids = validator_ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        let response = self.http_post(&endpoint, &validator_ids).await?;
+        let result: Value<Vec<SyncCommitteeReward>> = api_error_or_value(response).await?;
         Ok(result.data)
     }
 
@@ -479,7 +770,7 @@ impl<C: ClientTypes> Client<C> {
         if let Some(committee_index) = committee_index {
             request = request.query(&[("committee_index", committee_index)]);
         }
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: ApiResult<Value<Vec<C::Attestation>>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -487,10 +778,44 @@ impl<C: ClientTypes> Client<C> {
         }
     }
 
+    // v2 endpoint
+    pub async fn get_attestations_from_pool_v2(
+        &self,
+        slot: Option<Slot>,
+        committee_index: Option<CommitteeIndex>,
+    ) -> Result<Vec<C::Attestation>, Error> {
+        let target = self.endpoint.join("eth/v2/beacon/pool/attestations")?;
+        let mut request = self.http.get(target);
+        if let Some(slot) = slot {
+            request = request.query(&[("slot", slot)]);
+        }
+        if let Some(committee_index) = committee_index {
+            request = request.query(&[("committee_index", committee_index)]);
+        }
+        self.get_versioned_json(request).await
+    }
+
     pub async fn post_attestations(&self, attestations: &[C::Attestation]) -> Result<(), Error> {
         self.post("eth/v1/beacon/pool/attestations", attestations).await
     }
 
+    // v2 endpoint
+    /// Submits attestations in the `SingleAttestation` format used from `electra` onwards.
+    pub async fn post_single_attestations(
+        &self,
+        version: Version,
+        attestations: &[SingleAttestation],
+    ) -> Result<(), Error> {
+        let target = self.endpoint.join("eth/v2/beacon/pool/attestations")?;
+        let request = self
+            .http
+            .post(target)
+            .header(CONSENSUS_VERSION_HEADER, version.to_string())
+            .json(attestations);
+        let response = self.send(request).await?;
+        api_error_or_ok(response).await
+    }
+
     pub async fn get_attester_slashings_from_pool(
         &self,
     ) -> Result<Vec<C::AttesterSlashing>, Error> {
@@ -499,6 +824,14 @@ impl<C: ClientTypes> Client<C> {
         Ok(result.data)
     }
 
+    // v2 endpoint
+    pub async fn get_attester_slashings_from_pool_v2(
+        &self,
+    ) -> Result<Vec<C::AttesterSlashing>, Error> {
+        let target = self.endpoint.join("eth/v2/beacon/pool/attester_slashings")?;
+        self.get_versioned_json(self.http.get(target)).await
+    }
+
     pub async fn post_attester_slashing(
         &self,
         attester_slashing: &C::AttesterSlashing,
@@ -506,6 +839,22 @@ impl<C: ClientTypes> Client<C> {
         self.post("eth/v1/beacon/pool/attester_slashings", attester_slashing).await
     }
 
+    // v2 endpoint
+    pub async fn post_attester_slashing_v2(
+        &self,
+        version: Version,
+        attester_slashing: &C::AttesterSlashing,
+    ) -> Result<(), Error> {
+        let target = self.endpoint.join("eth/v2/beacon/pool/attester_slashings")?;
+        let request = self
+            .http
+            .post(target)
+            .header(CONSENSUS_VERSION_HEADER, version.to_string())
+            .json(attester_slashing);
+        let response = self.send(request).await?;
+        api_error_or_ok(response).await
+    }
+
     pub async fn get_proposer_slashings_from_pool(&self) -> Result<Vec<ProposerSlashing>, Error> {
         let result: Value<Vec<ProposerSlashing>> =
             self.get("eth/v1/beacon/pool/proposer_slashings").await?;
@@ -567,7 +916,7 @@ impl<C: ClientTypes> Client<C> {
             request = request.query(&[("proposal_slot", slot)]);
         }
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: ApiResult<Value<_>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -594,9 +943,8 @@ impl<C: ClientTypes> Client<C> {
     /* debug namespace */
     // v2 endpoint
     pub async fn get_state(&self, id: StateId) -> Result<C::BeaconState, Error> {
-        let result: VersionedValue<C::BeaconState> =
-            self.get(&format!("eth/v2/debug/beacon/states/{id}")).await?;
-        Ok(result.data)
+        let target = self.endpoint.join(&format!("eth/v2/debug/beacon/states/{id}"))?;
+        self.get_versioned(self.http.get(target)).await
     }
 
     // v2 endpoint
@@ -607,13 +955,31 @@ impl<C: ClientTypes> Client<C> {
     }
 
     /* events namespace */
-    pub async fn get_events<T: Topic>(&self) -> Result<EventStream<T::Data>, Error> {
+    pub async fn get_events<T: Topic>(&self) -> Result<EventStream<T::Data>, Error>
+    where
+        T::Data: Send + 'static,
+    {
         let name = T::NAME;
         let path = format!("/eth/v1/events?topics={name}");
-        let target = self.endpoint.join(&path)?.to_string();
+        let target = self.endpoint.join(&path)?;
+        Ok(EventStream::new(self.http.clone(), target, self.event_backoff.clone(), |_, data| {
+            Ok(serde_json::from_str(data)?)
+        }))
+    }
 
-        let client = EventClient::default();
-        Ok(client.subscribe(&target).await?)
+    /// Subscribes to each of `topics` (see [`crate::ALL_TOPICS`]) on a single stream.
+    pub async fn get_events_for_topics(
+        &self,
+        topics: &[&str],
+    ) -> Result<EventStream<Event<C>>, Error>
+    where
+        C: 'static,
+        Event<C>: Send,
+    {
+        let topics = topics.iter().join(",");
+        let path = format!("/eth/v1/events?topics={topics}");
+        let target = self.endpoint.join(&path)?;
+        Ok(EventStream::new(self.http.clone(), target, self.event_backoff.clone(), Event::decode))
     }
 
     /* node namespace */
@@ -636,7 +1002,7 @@ impl<C: ClientTypes> Client<C> {
         if !connection_orientations.is_empty() {
             request = request.query(&[("direction", connection_orientations.iter().join(","))]);
         }
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: ApiResult<Value<Vec<PeerDescription>>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -669,7 +1035,7 @@ impl<C: ClientTypes> Client<C> {
         let path = "eth/v1/node/health";
         let target = self.endpoint.join(path)?;
         let request = self.http.get(target);
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result = match response.status() {
             StatusCode::OK => HealthStatus::Ready,
             StatusCode::PARTIAL_CONTENT => HealthStatus::Syncing,
@@ -728,7 +1094,7 @@ impl<C: ClientTypes> Client<C> {
         slot: Slot,
         randao_reveal: RandaoReveal,
         graffiti: Option<Bytes32>,
-    ) -> Result<C::BeaconBlock, Error> {
+    ) -> Result<BlockContents<C::BeaconBlock, C::Blob>, Error> {
         let path = format!("eth/v2/validator/blocks/{slot}");
         let target = self.endpoint.join(&path)?;
         let mut request = self.http.get(target);
@@ -736,14 +1102,93 @@ impl<C: ClientTypes> Client<C> {
         if let Some(graffiti) = graffiti {
             request = request.query(&[("graffiti", format!("{graffiti:?}"))]);
         }
-        let response = request.send().await?;
-        let result: ApiResult<VersionedValue<C::BeaconBlock>> = response.json().await?;
+        let response = self.send_negotiated(request).await?;
+        if is_ssz(&response) {
+            let version = consensus_version(&response)?;
+            let encoding = response.bytes().await?;
+            return BlockContents::deserialize_versioned(version, &encoding)
+        }
+        let result: ApiResult<VersionedValue<serde_json::Value>> = response.json().await?;
         match result {
-            ApiResult::Ok(result) => Ok(result.data),
+            ApiResult::Ok(result) => {
+                Ok(BlockContents::deserialize_versioned_json(result.version, result.data)?)
+            }
             ApiResult::Err(err) => Err(err.into()),
         }
     }
 
+    // v3 endpoint
+    /// Requests a block proposal for `slot` which the node may build with a local execution payload
+    /// or with a payload from a builder, in which case the returned block is blinded.
+    ///
+    /// `builder_boost_factor` is the percentage the node should apply to the builder's bid when
+    /// comparing it with the local payload, e.g. `0` to always prefer the local payload.
+    pub async fn get_block_proposal_v3(
+        &self,
+        slot: Slot,
+        randao_reveal: RandaoReveal,
+        graffiti: Option<Bytes32>,
+        builder_boost_factor: Option<u64>,
+    ) -> Result<BlockProposal<C::BeaconBlock, C::BlindedBeaconBlock, C::Blob>, Error> {
+        let path = format!("eth/v3/validator/blocks/{slot}");
+        let target = self.endpoint.join(&path)?;
+        let mut request = self.http.get(target);
+        request = request.query(&[("randao_reveal", format!("{randao_reveal:?}"))]);
+        if let Some(graffiti) = graffiti {
+            request = request.query(&[("graffiti", format!("{graffiti:?}"))]);
+        }
+        if let Some(builder_boost_factor) = builder_boost_factor {
+            request = request.query(&[("builder_boost_factor", builder_boost_factor)]);
+        }
+        let response = self.send_negotiated(request).await?;
+        if !response.status().is_success() {
+            let api_err = response.json::<ApiError>().await?;
+            return Err(Error::Api(api_err))
+        }
+
+        let is_blinded = response_header(&response, EXECUTION_PAYLOAD_BLINDED_HEADER)?
+            .parse::<bool>()
+            .map_err(|_| invalid_header(EXECUTION_PAYLOAD_BLINDED_HEADER))?;
+        let execution_payload_value = response_header(&response, EXECUTION_PAYLOAD_VALUE_HEADER)?
+            .parse::<U256>()
+            .map_err(|_| invalid_header(EXECUTION_PAYLOAD_VALUE_HEADER))?;
+        let consensus_block_value = response_header(&response, CONSENSUS_BLOCK_VALUE_HEADER)?
+            .parse::<U256>()
+            .map_err(|_| invalid_header(CONSENSUS_BLOCK_VALUE_HEADER))?;
+
+        let (version, block) = if is_ssz(&response) {
+            let version = consensus_version(&response)?;
+            let encoding = response.bytes().await?;
+            let block = if is_blinded {
+                ProduceBlockResponse::Blinded(C::BlindedBeaconBlock::deserialize_versioned(
+                    version, &encoding,
+                )?)
+            } else {
+                ProduceBlockResponse::Full(BlockContents::deserialize_versioned(
+                    version, &encoding,
+                )?)
+            };
+            (version, block)
+        } else {
+            let result: VersionedValue<serde_json::Value> = response.json().await?;
+            let version = result.version;
+            let block = if is_blinded {
+                ProduceBlockResponse::Blinded(C::BlindedBeaconBlock::deserialize_versioned_json(
+                    version,
+                    result.data,
+                )?)
+            } else {
+                ProduceBlockResponse::Full(BlockContents::deserialize_versioned_json(
+                    version,
+                    result.data,
+                )?)
+            };
+            (version, block)
+        };
+
+        Ok(BlockProposal { version, block, execution_payload_value, consensus_block_value })
+    }
+
     pub async fn get_blinded_block_proposal(
         &self,
         slot: Slot,
@@ -757,12 +1202,7 @@ impl<C: ClientTypes> Client<C> {
         if let Some(graffiti) = graffiti {
             request = request.query(&[("graffiti", format!("{graffiti:?}"))]);
         }
-        let response = request.send().await?;
-        let result: ApiResult<VersionedValue<C::BlindedBeaconBlock>> = response.json().await?;
-        match result {
-            ApiResult::Ok(result) => Ok(result.data),
-            ApiResult::Err(err) => Err(err.into()),
-        }
+        self.get_versioned(request).await
     }
 
     pub async fn get_attestation_data(
@@ -774,7 +1214,7 @@ impl<C: ClientTypes> Client<C> {
         let mut request = self.http.get(target);
         request = request.query(&[("slot", slot)]);
         request = request.query(&[("committee_index", committee_index)]);
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: ApiResult<Value<AttestationData>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -791,7 +1231,7 @@ impl<C: ClientTypes> Client<C> {
         let mut request = self.http.get(target);
         request = request.query(&[("attestation_data_root", format!("{attestation_data_root:?}"))]);
         request = request.query(&[("slot", slot)]);
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: ApiResult<Value<C::Attestation>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -799,6 +1239,21 @@ impl<C: ClientTypes> Client<C> {
         }
     }
 
+    // v2 endpoint
+    pub async fn get_attestation_aggregate_v2(
+        &self,
+        attestation_data_root: Root,
+        slot: Slot,
+        committee_index: CommitteeIndex,
+    ) -> Result<C::Attestation, Error> {
+        let target = self.endpoint.join("eth/v2/validator/aggregate_attestation")?;
+        let mut request = self.http.get(target);
+        request = request.query(&[("attestation_data_root", format!("{attestation_data_root:?}"))]);
+        request = request.query(&[("slot", slot)]);
+        request = request.query(&[("committee_index", committee_index)]);
+        self.get_versioned_json(request).await
+    }
+
     pub async fn post_aggregates_with_proofs(
         &self,
         aggregates_with_proofs: &[C::SignedAggregateAndProof],
@@ -806,6 +1261,22 @@ impl<C: ClientTypes> Client<C> {
         self.post("eth/v1/validator/aggregate_and_proofs", aggregates_with_proofs).await
     }
 
+    // v2 endpoint
+    pub async fn post_aggregates_with_proofs_v2(
+        &self,
+        version: Version,
+        aggregates_with_proofs: &[C::SignedAggregateAndProof],
+    ) -> Result<(), Error> {
+        let target = self.endpoint.join("eth/v2/validator/aggregate_and_proofs")?;
+        let request = self
+            .http
+            .post(target)
+            .header(CONSENSUS_VERSION_HEADER, version.to_string())
+            .json(aggregates_with_proofs);
+        let response = self.send(request).await?;
+        api_error_or_ok(response).await
+    }
+
     pub async fn subscribe_subnets_for_attestation_committees(
         &self,
         committee_descriptors: &[CommitteeDescriptor],
@@ -831,7 +1302,7 @@ impl<C: ClientTypes> Client<C> {
         request = request.query(&[("slot", slot)]);
         request = request.query(&[("subcommittee_index", subcommittee_index)]);
         request = request.query(&[("beacon_block_root", format!("{beacon_block_root:?}"))]);
-        let response = request.send().await?;
+        let response = self.send(request).await?;
         let result: ApiResult<Value<C::SyncCommitteeContribution>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
@@ -866,8 +1337,9 @@ impl<C: ClientTypes> Client<C> {
         epoch: Epoch,
         indices: &[ValidatorIndex],
     ) -> Result<Vec<ValidatorLiveness>, Error> {
-        let response =
-            self.http_post(&format!("eth/v1/validator/liveness/{epoch}"), indices).await?;
+        let endpoint = format!("eth/v1/validator/liveness/{epoch}");
+        let indices = indices.iter().map(|index| index.to_string()).collect::<Vec<_>>();
+        let response = self.http_post(&endpoint, &indices).await?;
         let result: ApiResult<Value<_>> = response.json().await?;
         match result {
             ApiResult::Ok(result) => Ok(result.data),
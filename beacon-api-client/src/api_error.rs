@@ -22,8 +22,16 @@ pub enum ApiError {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct IndexedError {
-    index: usize,
-    message: String,
+    pub index: usize,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn code(&self) -> StatusCode {
+        match self {
+            Self::IndexedError { code, .. } | Self::ErrorMessage { code, .. } => *code,
+        }
+    }
 }
 
 impl fmt::Display for ApiError {
@@ -0,0 +1,236 @@
+//! A client for the builder API that relays serve to proposers.
+use crate::{
+    api_client::{api_error_or_ok, api_error_or_value, ClientTypes},
+    encoding::VersionedJson,
+    types::VersionedValue,
+    Error, ETH_CONSENSUS_VERSION_HEADER,
+};
+use ethereum_consensus::{
+    builder::{
+        compute_builder_domain, ExecutionPayloadResponse, SignedBuilderBid,
+        SignedValidatorRegistration,
+    },
+    primitives::{BlsPublicKey, Domain, Hash32, Slot},
+    serde::try_bytes_from_hex_str,
+    state_transition::Context,
+    Fork as Version,
+};
+use http::StatusCode;
+use std::marker::PhantomData;
+use url::Url;
+
+/// A bid signed by a builder.
+pub trait SignedBid: serde::Serialize + VersionedJson {
+    fn public_key(&self) -> &BlsPublicKey;
+    fn verify_signature(&self, domain: Domain) -> Result<(), ethereum_consensus::Error>;
+}
+
+/// The types a [`BuilderClient`] exchanges with relays in addition to those of [`ClientTypes`].
+pub trait BuilderTypes: ClientTypes {
+    type SignedBuilderBid: SignedBid;
+    type ExecutionPayloadResponse: serde::Serialize + VersionedJson;
+}
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    > VersionedJson
+    for SignedBuilderBid<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES, MAX_BLOB_COMMITMENTS_PER_BLOCK>
+{
+    fn deserialize_versioned_json(
+        version: Version,
+        data: serde_json::Value,
+    ) -> Result<Self, Error> {
+        Ok(Self::deserialize_versioned(version, data)?)
+    }
+}
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    > SignedBid
+    for SignedBuilderBid<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES, MAX_BLOB_COMMITMENTS_PER_BLOCK>
+{
+    fn public_key(&self) -> &BlsPublicKey {
+        self.public_key()
+    }
+
+    fn verify_signature(&self, domain: Domain) -> Result<(), ethereum_consensus::Error> {
+        self.verify_signature(domain)
+    }
+}
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const BYTES_PER_BLOB: usize,
+    > VersionedJson
+    for ExecutionPayloadResponse<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        BYTES_PER_BLOB,
+    >
+{
+    fn deserialize_versioned_json(
+        version: Version,
+        data: serde_json::Value,
+    ) -> Result<Self, Error> {
+        Ok(Self::deserialize_versioned(version, data)?)
+    }
+}
+
+/// A client for a relay serving the builder API.
+///
+/// Relays are conventionally identified by their public key in the userinfo of their URL
+/// (e.g. `https://0xabcd...@relay.example`); if present, bids from any other builder are
+/// rejected. The signature of every bid is verified against the builder domain.
+#[derive(Clone)]
+pub struct BuilderClient<C> {
+    http: reqwest::Client,
+    endpoint: Url,
+    relay_public_key: Option<BlsPublicKey>,
+    builder_domain: Domain,
+    _phantom: PhantomData<C>,
+}
+
+impl<C: BuilderTypes> BuilderClient<C> {
+    pub fn new<U: Into<Url>>(endpoint: U, context: &Context) -> Result<Self, Error> {
+        Self::new_with_client(reqwest::Client::new(), endpoint, context)
+    }
+
+    pub fn new_with_client<U: Into<Url>>(
+        client: reqwest::Client,
+        endpoint: U,
+        context: &Context,
+    ) -> Result<Self, Error> {
+        let mut endpoint = endpoint.into();
+        let relay_public_key = if endpoint.username().is_empty() {
+            None
+        } else {
+            let public_key = try_bytes_from_hex_str(endpoint.username())
+                .ok()
+                .and_then(|bytes| BlsPublicKey::try_from(bytes.as_slice()).ok())
+                .ok_or_else(|| {
+                    let username = endpoint.username().to_string();
+                    Error::InvalidRelayPublicKey(username)
+                })?;
+            // NOTE: this only fails for URLs that cannot have credentials in the first place
+            let _ = endpoint.set_username("");
+            Some(public_key)
+        };
+        let builder_domain = compute_builder_domain(context)?;
+        Ok(Self { http: client, endpoint, relay_public_key, builder_domain, _phantom: PhantomData })
+    }
+
+    pub fn endpoint(&self) -> &Url {
+        &self.endpoint
+    }
+
+    pub fn relay_public_key(&self) -> Option<&BlsPublicKey> {
+        self.relay_public_key.as_ref()
+    }
+
+    pub async fn check_status(&self) -> Result<(), Error> {
+        let target = self.endpoint.join("eth/v1/builder/status")?;
+        let response = self.http.get(target).send().await?;
+        api_error_or_ok(response).await
+    }
+
+    pub async fn register_validators(
+        &self,
+        registrations: &[SignedValidatorRegistration],
+    ) -> Result<(), Error> {
+        let target = self.endpoint.join("eth/v1/builder/validators")?;
+        let response = self.http.post(target).json(registrations).send().await?;
+        api_error_or_ok(response).await
+    }
+
+    /// Requests a bid for the block at `slot` building on `parent_hash` for the proposer with
+    /// `public_key`, returning `None` if the relay has no bid.
+    pub async fn get_header(
+        &self,
+        slot: Slot,
+        parent_hash: &Hash32,
+        public_key: &BlsPublicKey,
+    ) -> Result<Option<C::SignedBuilderBid>, Error> {
+        let path = format!("eth/v1/builder/header/{slot}/{parent_hash:?}/{public_key:?}");
+        let target = self.endpoint.join(&path)?;
+        let response = self.http.get(target).send().await?;
+        if response.status() == StatusCode::NO_CONTENT {
+            return Ok(None)
+        }
+        let result: VersionedValue<serde_json::Value> = api_error_or_value(response).await?;
+        let bid = C::SignedBuilderBid::deserialize_versioned_json(result.version, result.data)?;
+        if let Some(relay_public_key) = &self.relay_public_key {
+            if bid.public_key() != relay_public_key {
+                return Err(Error::UnexpectedBuilder {
+                    expected: relay_public_key.clone(),
+                    provided: bid.public_key().clone(),
+                })
+            }
+        }
+        bid.verify_signature(self.builder_domain).map_err(Error::InvalidBidSignature)?;
+        Ok(Some(bid))
+    }
+
+    /// Submits `block` to the relay, which returns the execution payload it commits to.
+    pub async fn submit_blinded_block(
+        &self,
+        block: &C::SignedBlindedBeaconBlock,
+        version: Version,
+    ) -> Result<C::ExecutionPayloadResponse, Error> {
+        let target = self.endpoint.join("eth/v1/builder/blinded_blocks")?;
+        let request = self
+            .http
+            .post(target)
+            .header(ETH_CONSENSUS_VERSION_HEADER, version.to_string())
+            .json(block);
+        let response = request.send().await?;
+        let result: VersionedValue<serde_json::Value> = api_error_or_value(response).await?;
+        C::ExecutionPayloadResponse::deserialize_versioned_json(result.version, result.data)
+    }
+
+    /// Submits `block` to the relay, which publishes it without returning the execution payload.
+    pub async fn submit_blinded_block_v2(
+        &self,
+        block: &C::SignedBlindedBeaconBlock,
+        version: Version,
+    ) -> Result<(), Error> {
+        let target = self.endpoint.join("eth/v2/builder/blinded_blocks")?;
+        let request = self
+            .http
+            .post(target)
+            .header(ETH_CONSENSUS_VERSION_HEADER, version.to_string())
+            .json(block);
+        let response = request.send().await?;
+        api_error_or_ok(response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets::mainnet::MainnetClientTypes;
+
+    #[test]
+    fn test_relay_public_key_from_endpoint() {
+        let context = Context::for_mainnet();
+        let public_key = "0x8da04bbe26b2bbc6b042f4db18a36f1b4714123706065ed3946a3c3aeb681f98d3e67a3483b088612cb9b0c5322723a0";
+        let endpoint = Url::parse(&format!("https://{public_key}@relay.example")).unwrap();
+        let client = BuilderClient::<MainnetClientTypes>::new(endpoint, &context).unwrap();
+        assert_eq!(format!("{:?}", client.relay_public_key().unwrap()), public_key);
+        assert_eq!(client.endpoint().as_str(), "https://relay.example/");
+
+        let endpoint = Url::parse("https://0xdeadbeef@relay.example").unwrap();
+        let result = BuilderClient::<MainnetClientTypes>::new(endpoint, &context);
+        assert!(matches!(result, Err(Error::InvalidRelayPublicKey(..))));
+    }
+}
@@ -1,5 +1,13 @@
-use crate::types::StateId;
-use clap::{Args, Parser, Subcommand};
+use crate::types::{
+    BlockId, ConnectionOrientation, PeerState, PublicKeyOrIndex, StateId, ValidatorStatus,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use ethereum_consensus::{
+    networking::PeerId,
+    primitives::{BlobIndex, CommitteeIndex, Epoch, Root, Slot, ValidatorIndex},
+    serde::try_bytes_from_hex_str,
+};
+use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
@@ -7,33 +15,383 @@ use clap::{Args, Parser, Subcommand};
 pub struct CliConfig {
     #[arg(long)]
     pub endpoint: String,
+    #[arg(long, alias = "network", value_enum, default_value_t = Preset::Mainnet)]
+    pub preset: Preset,
+    #[arg(
+        long,
+        help = "Write the SSZ encoding of the response instead of JSON (only for blocks, blinded \
+                blocks, blob sidecars and states)"
+    )]
+    pub ssz: bool,
+    #[arg(long, short, help = "Write the response to this file instead of stdout")]
+    pub output: Option<PathBuf>,
     #[command(subcommand)]
     pub namespace: Namespace,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Preset {
+    Mainnet,
+    Minimal,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum Namespace {
     #[clap(subcommand)]
     Beacon(BeaconMethod),
+    #[clap(subcommand)]
+    Builder(BuilderMethod),
+    #[clap(subcommand)]
+    Config(ConfigMethod),
+    #[clap(subcommand)]
+    Debug(DebugMethod),
+    #[clap(subcommand)]
+    Node(NodeMethod),
+    #[clap(subcommand)]
+    Validator(ValidatorMethod),
+    Events(EventsArg),
 }
 
 #[derive(Debug, Subcommand)]
 pub enum BeaconMethod {
     Genesis,
     Root(StateIdArg),
+    Fork(StateIdArg),
+    FinalityCheckpoints(StateIdArg),
+    Validators(ValidatorsArg),
+    Validator(ValidatorArg),
+    Balances(BalancesArg),
+    Committees(CommitteesArg),
+    SyncCommittees(EpochFilterArg),
+    Randao(EpochFilterArg),
+    PendingDeposits(StateIdArg),
+    PendingPartialWithdrawals(StateIdArg),
+    PendingConsolidations(StateIdArg),
+    Header(BlockIdArg),
+    Block(BlockIdArg),
+    BlockRoot(BlockIdArg),
+    BlockAttestations(BlockIdArg),
+    BlindedBlock(BlockIdArg),
+    BlobSidecars(BlobSidecarsArg),
+    DepositSnapshot,
+    LightClientBootstrap(RootArg),
+    LightClientUpdates(LightClientUpdatesArg),
+    LightClientFinalityUpdate,
+    LightClientOptimisticUpdate,
+    BlockRewards(BlockIdArg),
+    AttestationRewards(AttestationRewardsArg),
+    SyncCommitteeRewards(SyncCommitteeRewardsArg),
+    PoolAttestations(PoolAttestationsArg),
+    PoolAttesterSlashings,
+    PoolProposerSlashings,
+    PoolVoluntaryExits,
+    PoolBlsToExecutionChanges,
 }
 
-#[derive(Args, Debug)]
-pub struct StateIdArg {
-    #[arg(
-        value_parser = clap::value_parser!(StateId),
-        long_help = "Identifier for the state under consideration. Possible values are:
+#[derive(Debug, Subcommand)]
+pub enum BuilderMethod {
+    ExpectedWithdrawals(ExpectedWithdrawalsArg),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigMethod {
+    ForkSchedule,
+    Spec,
+    DepositContract,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DebugMethod {
+    State(StateIdArg),
+    Heads,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum NodeMethod {
+    Identity,
+    Peers(PeersArg),
+    Peer(PeerArg),
+    PeerCount,
+    Version,
+    Syncing,
+    Health,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ValidatorMethod {
+    AttesterDuties(DutiesArg),
+    ProposerDuties(EpochArg),
+    SyncDuties(DutiesArg),
+    AttestationData(AttestationDataArg),
+    AggregateAttestation(AggregateAttestationArg),
+    SyncCommitteeContribution(SyncCommitteeContributionArg),
+    Liveness(DutiesArg),
+}
+
+const STATE_ID_HELP: &str = "Identifier for the state under consideration. Possible values are:
     head
     genesis
     finalized
     justified
     <slot>
-    <hex-encoded root with 0x prefix>",
+    <hex-encoded root with 0x prefix>";
+
+const BLOCK_ID_HELP: &str = "Identifier for the block under consideration. Possible values are:
+    head
+    genesis
+    finalized
+    <slot>
+    <hex-encoded root with 0x prefix>";
+
+fn parse_root(s: &str) -> Result<Root, String> {
+    let bytes = try_bytes_from_hex_str(s).map_err(|err| format!("could not parse root: {err}"))?;
+    Root::try_from(bytes.as_slice()).map_err(|err| format!("could not parse root: {err}"))
+}
+
+// Parses the name of a variant of a type using its `serde` representation.
+fn parse_serde_name<T: serde::de::DeserializeOwned>(s: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(s.to_string())).map_err(|err| err.to_string())
+}
+
+#[derive(Args, Debug)]
+pub struct StateIdArg {
+    #[arg(value_parser = clap::value_parser!(StateId), long_help = STATE_ID_HELP)]
+    pub state_id: StateId,
+}
+
+#[derive(Args, Debug)]
+pub struct BlockIdArg {
+    #[arg(value_parser = clap::value_parser!(BlockId), long_help = BLOCK_ID_HELP)]
+    pub block_id: BlockId,
+}
+
+#[derive(Args, Debug)]
+pub struct RootArg {
+    #[arg(value_parser = parse_root)]
+    pub root: Root,
+}
+
+#[derive(Args, Debug)]
+pub struct EpochArg {
+    pub epoch: Epoch,
+}
+
+#[derive(Args, Debug)]
+pub struct ValidatorsArg {
+    #[arg(value_parser = clap::value_parser!(StateId), long_help = STATE_ID_HELP)]
+    pub state_id: StateId,
+    #[arg(
+        long = "id",
+        value_parser = clap::value_parser!(PublicKeyOrIndex),
+        help = "Validator index or hex-encoded public key; may be repeated"
     )]
+    pub ids: Vec<PublicKeyOrIndex>,
+    #[arg(long = "status", value_parser = parse_serde_name::<ValidatorStatus>)]
+    pub statuses: Vec<ValidatorStatus>,
+}
+
+#[derive(Args, Debug)]
+pub struct ValidatorArg {
+    #[arg(value_parser = clap::value_parser!(StateId), long_help = STATE_ID_HELP)]
+    pub state_id: StateId,
+    #[arg(value_parser = clap::value_parser!(PublicKeyOrIndex))]
+    pub id: PublicKeyOrIndex,
+}
+
+#[derive(Args, Debug)]
+pub struct BalancesArg {
+    #[arg(value_parser = clap::value_parser!(StateId), long_help = STATE_ID_HELP)]
+    pub state_id: StateId,
+    #[arg(long = "id", value_parser = clap::value_parser!(PublicKeyOrIndex))]
+    pub ids: Vec<PublicKeyOrIndex>,
+}
+
+#[derive(Args, Debug)]
+pub struct CommitteesArg {
+    #[arg(value_parser = clap::value_parser!(StateId), long_help = STATE_ID_HELP)]
     pub state_id: StateId,
+    #[arg(long)]
+    pub epoch: Option<Epoch>,
+    #[arg(long)]
+    pub index: Option<CommitteeIndex>,
+    #[arg(long)]
+    pub slot: Option<Slot>,
+}
+
+#[derive(Args, Debug)]
+pub struct EpochFilterArg {
+    #[arg(value_parser = clap::value_parser!(StateId), long_help = STATE_ID_HELP)]
+    pub state_id: StateId,
+    #[arg(long)]
+    pub epoch: Option<Epoch>,
+}
+
+#[derive(Args, Debug)]
+pub struct BlobSidecarsArg {
+    #[arg(value_parser = clap::value_parser!(BlockId), long_help = BLOCK_ID_HELP)]
+    pub block_id: BlockId,
+    #[arg(long = "index")]
+    pub indices: Vec<BlobIndex>,
+}
+
+#[derive(Args, Debug)]
+pub struct LightClientUpdatesArg {
+    pub start: u64,
+    pub count: u64,
+}
+
+#[derive(Args, Debug)]
+pub struct AttestationRewardsArg {
+    pub epoch: Epoch,
+    #[arg(
+        long = "id",
+        value_parser = clap::value_parser!(PublicKeyOrIndex),
+        help = "Validator index or hex-encoded public key; may be repeated"
+    )]
+    pub ids: Vec<PublicKeyOrIndex>,
+}
+
+#[derive(Args, Debug)]
+pub struct SyncCommitteeRewardsArg {
+    #[arg(value_parser = clap::value_parser!(BlockId), long_help = BLOCK_ID_HELP)]
+    pub block_id: BlockId,
+    #[arg(
+        long = "id",
+        value_parser = clap::value_parser!(PublicKeyOrIndex),
+        help = "Validator index or hex-encoded public key; may be repeated"
+    )]
+    pub ids: Vec<PublicKeyOrIndex>,
+}
+
+#[derive(Args, Debug)]
+pub struct PoolAttestationsArg {
+    #[arg(long)]
+    pub slot: Option<Slot>,
+    #[arg(long)]
+    pub committee_index: Option<CommitteeIndex>,
+}
+
+#[derive(Args, Debug)]
+pub struct ExpectedWithdrawalsArg {
+    #[arg(value_parser = clap::value_parser!(StateId), long_help = STATE_ID_HELP)]
+    pub state_id: StateId,
+    #[arg(long)]
+    pub slot: Option<Slot>,
+}
+
+#[derive(Args, Debug)]
+pub struct PeersArg {
+    #[arg(long = "state", value_parser = parse_serde_name::<PeerState>)]
+    pub states: Vec<PeerState>,
+    #[arg(long = "direction", value_parser = parse_serde_name::<ConnectionOrientation>)]
+    pub directions: Vec<ConnectionOrientation>,
+}
+
+#[derive(Args, Debug)]
+pub struct PeerArg {
+    #[arg(value_parser = clap::value_parser!(PeerId))]
+    pub peer_id: PeerId,
+}
+
+#[derive(Args, Debug)]
+pub struct DutiesArg {
+    pub epoch: Epoch,
+    #[arg(required = true, num_args = 1..)]
+    pub indices: Vec<ValidatorIndex>,
+}
+
+#[derive(Args, Debug)]
+pub struct AttestationDataArg {
+    pub slot: Slot,
+    pub committee_index: CommitteeIndex,
+}
+
+#[derive(Args, Debug)]
+pub struct AggregateAttestationArg {
+    #[arg(value_parser = parse_root)]
+    pub attestation_data_root: Root,
+    pub slot: Slot,
+}
+
+#[derive(Args, Debug)]
+pub struct SyncCommitteeContributionArg {
+    pub slot: Slot,
+    pub subcommittee_index: usize,
+    #[arg(value_parser = parse_root)]
+    pub beacon_block_root: Root,
+}
+
+#[derive(Args, Debug)]
+pub struct EventsArg {
+    #[arg(
+        long = "topic",
+        required = true,
+        help = "Topic to subscribe to; may be repeated (see `beacon_api_client::ALL_TOPICS`)"
+    )]
+    pub topics: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    fn parse(args: &[&str]) -> Result<CliConfig, clap::Error> {
+        let args = ["beacon-api-client", "--endpoint", "http://localhost:5052"].iter().chain(args);
+        CliConfig::try_parse_from(args)
+    }
+
+    #[test]
+    fn test_command() {
+        CliConfig::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_identifiers() {
+        let config =
+            parse(&["--preset", "minimal", "--ssz", "beacon", "block", "finalized"]).unwrap();
+        assert!(matches!(config.preset, Preset::Minimal));
+        assert!(config.ssz);
+        assert!(matches!(
+            config.namespace,
+            Namespace::Beacon(BeaconMethod::Block(BlockIdArg { block_id: BlockId::Finalized }))
+        ));
+
+        let root = Root::try_from([0xab; 32].as_slice()).unwrap();
+        let root_hex = format!("0x{}", "ab".repeat(32));
+        let config =
+            parse(&["beacon", "validators", &root_hex, "--id", "12", "--status", "active_ongoing"])
+                .unwrap();
+        match config.namespace {
+            Namespace::Beacon(BeaconMethod::Validators(arg)) => {
+                assert!(matches!(arg.state_id, StateId::Root(r) if r == root));
+                assert!(matches!(arg.ids[..], [PublicKeyOrIndex::Index(12)]));
+                assert_eq!(arg.statuses, vec![ValidatorStatus::ActiveOngoing]);
+            }
+            namespace => panic!("unexpected namespace: {namespace:?}"),
+        }
+
+        assert!(parse(&["beacon", "header", "latest"]).is_err());
+        assert!(parse(&["beacon", "validator", "head", "0x1234"]).is_err());
+        assert!(parse(&["beacon", "validators", "head", "--status", "unknown"]).is_err());
+        assert!(parse(&["validator", "aggregate-attestation", "0x1234", "1"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_required_arguments() {
+        let config = parse(&["events", "--topic", "head", "--topic", "block"]).unwrap();
+        match config.namespace {
+            Namespace::Events(arg) => assert_eq!(arg.topics, vec!["head", "block"]),
+            namespace => panic!("unexpected namespace: {namespace:?}"),
+        }
+        assert!(parse(&["events"]).is_err());
+
+        let config = parse(&["validator", "liveness", "3", "1", "2"]).unwrap();
+        assert!(matches!(
+            config.namespace,
+            Namespace::Validator(ValidatorMethod::Liveness(DutiesArg { epoch: 3, ref indices }))
+                if indices == &[1, 2]
+        ));
+        assert!(parse(&["validator", "liveness", "3"]).is_err());
+    }
 }
@@ -1,15 +1,293 @@
 mod config;
-use crate::mainnet::Client;
-pub use config::CliConfig;
-use config::{BeaconMethod, Namespace::Beacon};
+use crate::{mainnet, minimal, Client, ClientTypes, CommitteeFilter, Encoding, Error, Event};
+use config::{
+    BeaconMethod, BuilderMethod, ConfigMethod, DebugMethod, Namespace, NodeMethod, ValidatorMethod,
+};
+pub use config::{CliConfig, Preset};
+use ethereum_consensus::ssz::prelude::{serialize, SimpleSerialize, SimpleSerializeError};
+use futures_util::StreamExt;
+use std::{
+    fs::File,
+    io::{self, Write},
+};
+use thiserror::Error;
+use url::Url;
 
-pub async fn run_cli(client: &Client, args: &CliConfig) {
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error(transparent)]
+    Client(#[from] Error),
+    #[error("could not write output: {0}")]
+    Io(#[from] io::Error),
+    #[error("SSZ output is not supported for this method")]
+    SszNotSupported,
+}
+
+fn writer(args: &CliConfig) -> Result<Box<dyn Write>, io::Error> {
+    let writer: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(io::stdout().lock()),
+    };
+    Ok(writer)
+}
+
+fn write_json<T: serde::Serialize>(args: &CliConfig, value: &T) -> Result<(), CliError> {
+    if args.ssz {
+        return Err(CliError::SszNotSupported)
+    }
+    let mut writer = writer(args)?;
+    serde_json::to_writer_pretty(&mut writer, value).map_err(Error::from)?;
+    writeln!(writer)?;
+    Ok(())
+}
+
+fn write_ssz_or_json<T: serde::Serialize + SimpleSerialize>(
+    args: &CliConfig,
+    values: &[T],
+) -> Result<(), CliError> {
+    if !args.ssz {
+        return match values {
+            [value] => write_json(args, value),
+            values => write_json(args, &values),
+        }
+    }
+    let mut writer = writer(args)?;
+    // NOTE: a list of fixed-size values (e.g. blob sidecars) is encoded as their concatenation
+    for value in values {
+        let encoding = serialize(value).map_err(SimpleSerializeError::from).map_err(Error::from)?;
+        writer.write_all(&encoding)?;
+    }
+    Ok(())
+}
+
+pub async fn run_cli(args: &CliConfig) -> Result<(), CliError> {
+    let endpoint = Url::parse(&args.endpoint).map_err(Error::from)?;
+    let encoding = if args.ssz { Encoding::Ssz } else { Encoding::Json };
+    match args.preset {
+        Preset::Mainnet => {
+            let client = mainnet::Client::new(endpoint).with_encoding(encoding);
+            run(&client, args).await
+        }
+        Preset::Minimal => {
+            let client = minimal::Client::new(endpoint).with_encoding(encoding);
+            run(&client, args).await
+        }
+    }
+}
+
+async fn run<C: ClientTypes + 'static>(client: &Client<C>, args: &CliConfig) -> Result<(), CliError>
+where
+    Event<C>: Send,
+{
     match &args.namespace {
-        Beacon(BeaconMethod::Genesis) => {
-            println!("{:?}", &client.get_genesis_details().await.unwrap());
+        Namespace::Beacon(method) => run_beacon(client, method, args).await,
+        Namespace::Builder(BuilderMethod::ExpectedWithdrawals(arg)) => {
+            let withdrawals =
+                client.get_expected_withdrawals(arg.state_id.clone(), arg.slot).await?;
+            write_json(args, &withdrawals)
+        }
+        Namespace::Config(method) => match method {
+            ConfigMethod::ForkSchedule => write_json(args, &client.get_fork_schedule().await?),
+            ConfigMethod::Spec => write_json(args, &client.get_spec().await?),
+            ConfigMethod::DepositContract => {
+                write_json(args, &client.get_deposit_contract_address().await?)
+            }
+        },
+        Namespace::Debug(method) => match method {
+            DebugMethod::State(arg) => {
+                let state = client.get_state(arg.state_id.clone()).await?;
+                write_ssz_or_json(args, &[state])
+            }
+            DebugMethod::Heads => write_json(args, &client.get_heads().await?),
+        },
+        Namespace::Node(method) => match method {
+            NodeMethod::Identity => write_json(args, &client.get_node_identity().await?),
+            NodeMethod::Peers(arg) => {
+                let peers = client.get_node_peers(&arg.states, &arg.directions).await?;
+                write_json(args, &peers)
+            }
+            NodeMethod::Peer(arg) => write_json(args, &client.get_peer(arg.peer_id).await?),
+            NodeMethod::PeerCount => write_json(args, &client.get_peer_summary().await?),
+            NodeMethod::Version => write_json(args, &client.get_node_version().await?),
+            NodeMethod::Syncing => write_json(args, &client.get_sync_status().await?),
+            NodeMethod::Health => write_json(args, &client.get_health().await?),
+        },
+        Namespace::Validator(method) => run_validator(client, method, args).await,
+        Namespace::Events(arg) => {
+            if args.ssz {
+                return Err(CliError::SszNotSupported)
+            }
+            let topics = arg.topics.iter().map(String::as_str).collect::<Vec<_>>();
+            let mut events = client.get_events_for_topics(&topics).await?;
+            let mut writer = writer(args)?;
+            while let Some(event) = events.next().await {
+                let event = event?;
+                let data = event.data_to_json().map_err(Error::from)?;
+                let line = serde_json::json!({ "topic": event.topic(), "data": data });
+                writeln!(writer, "{line}")?;
+                writer.flush()?;
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn run_beacon<C: ClientTypes>(
+    client: &Client<C>,
+    method: &BeaconMethod,
+    args: &CliConfig,
+) -> Result<(), CliError> {
+    match method {
+        BeaconMethod::Genesis => write_json(args, &client.get_genesis_details().await?),
+        BeaconMethod::Root(arg) => {
+            write_json(args, &client.get_state_root(arg.state_id.clone()).await?)
+        }
+        BeaconMethod::Fork(arg) => write_json(args, &client.get_fork(arg.state_id.clone()).await?),
+        BeaconMethod::FinalityCheckpoints(arg) => {
+            write_json(args, &client.get_finality_checkpoints(arg.state_id.clone()).await?)
+        }
+        BeaconMethod::Validators(arg) => {
+            let validators =
+                client.get_validators(arg.state_id.clone(), &arg.ids, &arg.statuses).await?;
+            write_json(args, &validators)
+        }
+        BeaconMethod::Validator(arg) => {
+            let validator = client.get_validator(arg.state_id.clone(), arg.id.clone()).await?;
+            write_json(args, &validator)
+        }
+        BeaconMethod::Balances(arg) => {
+            write_json(args, &client.get_balances(arg.state_id.clone(), &arg.ids).await?)
+        }
+        BeaconMethod::Committees(arg) => {
+            let filter = CommitteeFilter { epoch: arg.epoch, index: arg.index, slot: arg.slot };
+            write_json(args, &client.get_committees(arg.state_id.clone(), filter).await?)
+        }
+        BeaconMethod::SyncCommittees(arg) => {
+            write_json(args, &client.get_sync_committees(arg.state_id.clone(), arg.epoch).await?)
+        }
+        BeaconMethod::Randao(arg) => {
+            write_json(args, &client.get_randao(arg.state_id.clone(), arg.epoch).await?)
+        }
+        BeaconMethod::PendingDeposits(arg) => {
+            write_json(args, &client.get_pending_deposits(arg.state_id.clone()).await?)
+        }
+        BeaconMethod::PendingPartialWithdrawals(arg) => {
+            let withdrawals = client.get_pending_partial_withdrawals(arg.state_id.clone()).await?;
+            write_json(args, &withdrawals)
+        }
+        BeaconMethod::PendingConsolidations(arg) => {
+            write_json(args, &client.get_pending_consolidations(arg.state_id.clone()).await?)
+        }
+        BeaconMethod::Header(arg) => {
+            write_json(args, &client.get_beacon_header(arg.block_id.clone()).await?)
+        }
+        BeaconMethod::Block(arg) => {
+            let block = client.get_beacon_block(arg.block_id.clone()).await?;
+            write_ssz_or_json(args, &[block])
+        }
+        BeaconMethod::BlockRoot(arg) => {
+            write_json(args, &client.get_beacon_block_root(arg.block_id.clone()).await?)
+        }
+        BeaconMethod::BlockAttestations(arg) => {
+            let attestations =
+                client.get_attestations_from_beacon_block(arg.block_id.clone()).await?;
+            write_json(args, &attestations)
+        }
+        BeaconMethod::BlindedBlock(arg) => {
+            let block = client.get_blinded_block(arg.block_id.clone()).await?;
+            write_ssz_or_json(args, &[block])
+        }
+        BeaconMethod::BlobSidecars(arg) => {
+            let sidecars = client.get_blob_sidecars(arg.block_id.clone(), &arg.indices).await?;
+            if args.ssz {
+                write_ssz_or_json(args, &sidecars)
+            } else {
+                write_json(args, &sidecars)
+            }
+        }
+        BeaconMethod::DepositSnapshot => write_json(args, &client.get_deposit_snapshot().await?),
+        BeaconMethod::LightClientBootstrap(arg) => {
+            write_json(args, &client.get_light_client_bootstrap(arg.root).await?)
+        }
+        BeaconMethod::LightClientUpdates(arg) => {
+            write_json(args, &client.get_light_client_updates(arg.start, arg.count).await?)
+        }
+        BeaconMethod::LightClientFinalityUpdate => {
+            write_json(args, &client.get_light_client_finality_update().await?)
+        }
+        BeaconMethod::LightClientOptimisticUpdate => {
+            write_json(args, &client.get_light_client_optimistic_update().await?)
+        }
+        BeaconMethod::BlockRewards(arg) => {
+            write_json(args, &client.get_block_rewards(arg.block_id.clone()).await?)
+        }
+        BeaconMethod::AttestationRewards(arg) => {
+            write_json(args, &client.get_attestation_rewards(arg.epoch, &arg.ids).await?)
+        }
+        BeaconMethod::SyncCommitteeRewards(arg) => {
+            let rewards = client.get_sync_committee_rewards(arg.block_id.clone(), &arg.ids).await?;
+            write_json(args, &rewards)
+        }
+        BeaconMethod::PoolAttestations(arg) => {
+            let attestations =
+                client.get_attestations_from_pool(arg.slot, arg.committee_index).await?;
+            write_json(args, &attestations)
+        }
+        BeaconMethod::PoolAttesterSlashings => {
+            write_json(args, &client.get_attester_slashings_from_pool().await?)
+        }
+        BeaconMethod::PoolProposerSlashings => {
+            write_json(args, &client.get_proposer_slashings_from_pool().await?)
+        }
+        BeaconMethod::PoolVoluntaryExits => {
+            write_json(args, &client.get_voluntary_exits_from_pool().await?)
+        }
+        BeaconMethod::PoolBlsToExecutionChanges => {
+            write_json(args, &client.get_bls_to_execution_changes().await?)
+        }
+    }
+}
+
+async fn run_validator<C: ClientTypes>(
+    client: &Client<C>,
+    method: &ValidatorMethod,
+    args: &CliConfig,
+) -> Result<(), CliError> {
+    match method {
+        ValidatorMethod::AttesterDuties(arg) => {
+            let (dependent_root, duties) =
+                client.get_attester_duties(arg.epoch, &arg.indices).await?;
+            let response = serde_json::json!({ "dependent_root": dependent_root, "data": duties });
+            write_json(args, &response)
+        }
+        ValidatorMethod::ProposerDuties(arg) => {
+            let (dependent_root, duties) = client.get_proposer_duties(arg.epoch).await?;
+            let response = serde_json::json!({ "dependent_root": dependent_root, "data": duties });
+            write_json(args, &response)
+        }
+        ValidatorMethod::SyncDuties(arg) => {
+            write_json(args, &client.get_sync_committee_duties(arg.epoch, &arg.indices).await?)
+        }
+        ValidatorMethod::AttestationData(arg) => {
+            write_json(args, &client.get_attestation_data(arg.slot, arg.committee_index).await?)
+        }
+        ValidatorMethod::AggregateAttestation(arg) => {
+            let aggregate =
+                client.get_attestation_aggregate(arg.attestation_data_root, arg.slot).await?;
+            write_json(args, &aggregate)
+        }
+        ValidatorMethod::SyncCommitteeContribution(arg) => {
+            let contribution = client
+                .get_sync_committee_contribution(
+                    arg.slot,
+                    arg.subcommittee_index,
+                    arg.beacon_block_root,
+                )
+                .await?;
+            write_json(args, &contribution)
         }
-        Beacon(BeaconMethod::Root(arg)) => {
-            println!("{}", &client.get_state_root(arg.state_id.clone()).await.unwrap())
+        ValidatorMethod::Liveness(arg) => {
+            write_json(args, &client.post_liveness(arg.epoch, &arg.indices).await?)
         }
     }
 }
@@ -0,0 +1,282 @@
+//! Doppelganger protection: before a validator client signs with a key it watches the network for
+//! a few epochs and keeps the key disabled if another instance is seen performing its duties.
+use crate::{
+    api_client::{Client, ClientTypes},
+    Error,
+};
+use ethereum_consensus::{
+    clock::{Clock, TimeProvider},
+    primitives::{Epoch, ValidatorIndex},
+};
+use futures_util::StreamExt;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+use tracing::{debug, warn};
+
+/// The number of liveness checks in a row that may fail before a [`DoppelgangerDetector`] gives up.
+pub const DEFAULT_MAX_LIVENESS_FAILURES: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoppelgangerStatus {
+    /// The validator is being watched and must not sign yet.
+    Pending,
+    /// The validator was not seen live while watched and may sign.
+    Safe,
+    /// The validator was seen live elsewhere and must not sign.
+    Detected,
+}
+
+/// The signing gate shared between a [`DoppelgangerDetector`] and the duty loop consulting it.
+///
+/// Validators the gate does not know about are not under protection and may always sign.
+#[derive(Debug, Clone, Default)]
+pub struct DoppelgangerGate {
+    statuses: Arc<RwLock<HashMap<ValidatorIndex, DoppelgangerStatus>>>,
+}
+
+impl DoppelgangerGate {
+    pub fn status(&self, index: ValidatorIndex) -> Option<DoppelgangerStatus> {
+        // NOTE: no code panics while holding the lock so it is never poisoned
+        self.statuses.read().expect("can read statuses").get(&index).copied()
+    }
+
+    pub fn is_signing_enabled(&self, index: ValidatorIndex) -> bool {
+        matches!(self.status(index), None | Some(DoppelgangerStatus::Safe))
+    }
+
+    /// Returns the validators seen live elsewhere.
+    pub fn detected(&self) -> Vec<ValidatorIndex> {
+        let statuses = self.statuses.read().expect("can read statuses");
+        let mut detected = statuses
+            .iter()
+            .filter(|(_, status)| **status == DoppelgangerStatus::Detected)
+            .map(|(index, _)| *index)
+            .collect::<Vec<_>>();
+        detected.sort_unstable();
+        detected
+    }
+
+    fn set_status(&self, indices: &[ValidatorIndex], status: DoppelgangerStatus) {
+        let mut statuses = self.statuses.write().expect("can write statuses");
+        for index in indices {
+            statuses.insert(*index, status);
+        }
+    }
+}
+
+/// Watches a set of validators for liveness with `Client::post_liveness` before enabling them in
+/// its [`DoppelgangerGate`].
+///
+/// The detector checks the epoch before the one it starts in, the (partial) starting epoch and
+/// then `epochs` further epochs, each once the following epoch has begun. Validators seen live
+/// are marked as detected, the rest become safe once every epoch has been checked.
+pub struct DoppelgangerDetector<C: ClientTypes, T: TimeProvider + Send + Sync> {
+    client: Client<C>,
+    clock: Clock<T>,
+    indices: Vec<ValidatorIndex>,
+    epochs: u64,
+    max_failures: usize,
+    gate: DoppelgangerGate,
+}
+
+impl<C: ClientTypes, T: TimeProvider + Send + Sync> DoppelgangerDetector<C, T> {
+    /// Watches `indices` for `epochs` epochs, registering them as pending in a new gate.
+    pub fn new(
+        client: Client<C>,
+        clock: Clock<T>,
+        indices: Vec<ValidatorIndex>,
+        epochs: u64,
+    ) -> Self {
+        Self::new_with_gate(client, clock, indices, epochs, DoppelgangerGate::default())
+    }
+
+    /// Like [`Self::new`] but registers `indices` in an existing `gate`, e.g. for keys imported
+    /// into a running validator client.
+    pub fn new_with_gate(
+        client: Client<C>,
+        clock: Clock<T>,
+        indices: Vec<ValidatorIndex>,
+        epochs: u64,
+        gate: DoppelgangerGate,
+    ) -> Self {
+        gate.set_status(&indices, DoppelgangerStatus::Pending);
+        Self { client, clock, indices, epochs, max_failures: DEFAULT_MAX_LIVENESS_FAILURES, gate }
+    }
+
+    /// Fails [`Self::run`] once `max_failures` liveness checks in a row have failed, instead of
+    /// the default of [`DEFAULT_MAX_LIVENESS_FAILURES`].
+    pub fn with_max_failures(mut self, max_failures: usize) -> Self {
+        self.max_failures = max_failures;
+        self
+    }
+
+    pub fn gate(&self) -> DoppelgangerGate {
+        self.gate.clone()
+    }
+
+    /// Watches the validators until every epoch has been checked, returning the indices seen
+    /// live. A failed check is retried on the next slot and `run` only fails once
+    /// `max_failures` checks in a row have failed; the validators stay pending meanwhile, so
+    /// signing is never enabled in error.
+    pub async fn run(self) -> Result<Vec<ValidatorIndex>, Error> {
+        let mut slots = self.clock.clone().into_stream();
+        let Some(slot) = slots.next().await else { return Ok(vec![]) };
+        let start_epoch = self.clock.epoch_for(slot);
+        let last_epoch = start_epoch + self.epochs;
+        let mut pending = self.indices.clone();
+        // NOTE: the epoch before `start_epoch` is already complete so it is checked right away
+        let mut next_epoch = start_epoch.saturating_sub(1);
+        let mut current_epoch = start_epoch;
+        let mut failures = 0;
+        loop {
+            // NOTE: the liveness of an epoch is only complete once the next epoch has begun
+            while next_epoch < current_epoch && next_epoch <= last_epoch && !pending.is_empty() {
+                match self.check(next_epoch, &mut pending).await {
+                    Ok(()) => {
+                        next_epoch += 1;
+                        failures = 0;
+                    }
+                    Err(err) if failures + 1 < self.max_failures => {
+                        failures += 1;
+                        warn!(%err, epoch = next_epoch, failures, "could not check liveness");
+                        break
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+            if next_epoch > last_epoch || pending.is_empty() {
+                break
+            }
+            let Some(slot) = slots.next().await else { break };
+            current_epoch = self.clock.epoch_for(slot);
+        }
+        if next_epoch > last_epoch {
+            self.gate.set_status(&pending, DoppelgangerStatus::Safe);
+        }
+        let mut detected = self.indices.clone();
+        detected.retain(|index| !pending.contains(index));
+        Ok(detected)
+    }
+
+    // Checks the liveness of `pending` in `epoch`, removing the validators seen live.
+    async fn check(&self, epoch: Epoch, pending: &mut Vec<ValidatorIndex>) -> Result<(), Error> {
+        if pending.is_empty() {
+            return Ok(())
+        }
+        let liveness = self.client.post_liveness(epoch, pending).await?;
+        let live = liveness
+            .into_iter()
+            .filter(|liveness| liveness.is_live)
+            .map(|liveness| liveness.index)
+            .collect::<Vec<_>>();
+        if live.is_empty() {
+            debug!(epoch, "no doppelganger detected");
+            return Ok(())
+        }
+        warn!(epoch, ?live, "doppelganger detected, validators remain disabled");
+        self.gate.set_status(&live, DoppelgangerStatus::Detected);
+        pending.retain(|index| !live.contains(index));
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use crate::{
+        presets::mainnet::{self, MainnetClientTypes},
+        ApiError, BeaconNodeProvider, MockBeaconNode, MockServer, ValidatorLiveness,
+    };
+    use ethereum_consensus::clock::TokioTimeProvider;
+    use http::StatusCode;
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    const GENESIS_TIME: u64 = 1_606_824_023;
+
+    // reports the validator with index 1 as live in every epoch, after failing the first
+    // `failures` requests
+    struct Provider {
+        failures: AtomicUsize,
+    }
+
+    impl BeaconNodeProvider<MainnetClientTypes> for Provider {
+        fn liveness(
+            &self,
+            _epoch: Epoch,
+            indices: &[ValidatorIndex],
+        ) -> Result<Vec<ValidatorLiveness>, ApiError> {
+            let failed = self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok();
+            if failed {
+                let message = "node is syncing".to_string();
+                return Err(ApiError::ErrorMessage {
+                    code: StatusCode::SERVICE_UNAVAILABLE,
+                    message,
+                })
+            }
+            let liveness =
+                indices.iter().map(|&index| ValidatorLiveness { index, is_live: index == 1 });
+            Ok(liveness.collect())
+        }
+    }
+
+    // Returns a detector watching validators 0 and 1 for one epoch against a node failing
+    // `failures` liveness requests, along with the server of the node.
+    fn new_detector(
+        failures: usize,
+    ) -> (MockServer, DoppelgangerDetector<MainnetClientTypes, TokioTimeProvider>) {
+        let node = MockBeaconNode::<MainnetClientTypes, _>::new(Provider {
+            failures: AtomicUsize::new(failures),
+        });
+        let server = node.spawn(([127, 0, 0, 1], 0).into()).unwrap();
+        let client = mainnet::Client::new(server.endpoint());
+        // start in the middle of epoch 2, with one slot of 12 seconds per epoch
+        let now = Duration::from_secs(GENESIS_TIME + 2 * 12 + 6);
+        let clock = Clock::new(GENESIS_TIME, 12, 1, TokioTimeProvider::new(now));
+        (server, DoppelgangerDetector::new(client, clock, vec![0, 1], 1))
+    }
+
+    // NOTE: with paused time `tokio` skips ahead to each slot without waiting on the wall clock
+    #[tokio::test(start_paused = true)]
+    async fn test_doppelganger_detector() {
+        let (_server, detector) = new_detector(0);
+        let gate = detector.gate();
+        assert!(!gate.is_signing_enabled(0));
+        assert!(!gate.is_signing_enabled(1));
+        assert!(gate.is_signing_enabled(2));
+
+        let detected = detector.run().await.unwrap();
+        assert_eq!(detected, vec![1]);
+        assert_eq!(gate.status(0), Some(DoppelgangerStatus::Safe));
+        assert_eq!(gate.status(1), Some(DoppelgangerStatus::Detected));
+        assert_eq!(gate.detected(), vec![1]);
+        assert!(gate.is_signing_enabled(0));
+        assert!(!gate.is_signing_enabled(1));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_doppelganger_detector_retries() {
+        // transient failures are retried on the following slots
+        let (_server, detector) = new_detector(2);
+        let detector = detector.with_max_failures(3);
+        let gate = detector.gate();
+        let detected = detector.run().await.unwrap();
+        assert_eq!(detected, vec![1]);
+        assert_eq!(gate.status(0), Some(DoppelgangerStatus::Safe));
+
+        // too many failures in a row end the detector and keep the validators disabled
+        let (_server, detector) = new_detector(3);
+        let detector = detector.with_max_failures(3);
+        let gate = detector.gate();
+        assert!(detector.run().await.is_err());
+        assert_eq!(gate.status(0), Some(DoppelgangerStatus::Pending));
+        assert_eq!(gate.status(1), Some(DoppelgangerStatus::Pending));
+    }
+}
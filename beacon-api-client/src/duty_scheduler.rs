@@ -0,0 +1,794 @@
+//! A minimal validator client: [`DutyScheduler`] follows the slots of a [`Clock`], fetches the
+//! duties of a set of validators from a beacon node and performs each duty at the time the honest
+//! validator spec prescribes, leaving the signing of every message to a [`DutySigner`].
+use crate::{
+    api_client::{Client, ClientTypes},
+    doppelganger::DoppelgangerGate,
+    events::{HeadEvent, HeadTopic},
+    types::{
+        AttestationDuty, BlockContents, ProduceBlockResponse, ProposerDuty, SingleAttestation,
+        SubmitSignedBeaconBlock,
+    },
+    Error,
+};
+use ethereum_consensus::{
+    clock::{Clock, TimeProvider},
+    phase0::{is_aggregator, AttestationData},
+    primitives::{
+        BlsPublicKey, BlsSignature, Bytes32, CommitteeIndex, Epoch, RandaoReveal, Root, Slot,
+        ValidatorIndex,
+    },
+    ssz::prelude::HashTreeRoot,
+    state_transition::Context,
+    Error as ConsensusError, Fork as Version,
+};
+use futures_util::stream::{FuturesUnordered, StreamExt};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use tokio::time::sleep;
+use tracing::{debug, warn};
+
+/// Produces the signed messages of the validators driven by a [`DutyScheduler`].
+///
+/// The scheduler never handles keys: implementations compute the signing domains, enforce any
+/// slashing protection and assemble the fork-specific containers, so remote signers fit as well as
+/// local keys.
+pub trait DutySigner<C: ClientTypes> {
+    fn sign_randao_reveal(
+        &self,
+        public_key: &BlsPublicKey,
+        epoch: Epoch,
+    ) -> Result<RandaoReveal, Error>;
+
+    fn sign_block(
+        &self,
+        duty: &ProposerDuty,
+        block: C::BeaconBlock,
+    ) -> Result<C::SignedBeaconBlock, Error>;
+
+    /// Signs the attestation of `duty` before `electra`.
+    fn sign_attestation(
+        &self,
+        duty: &AttestationDuty,
+        data: AttestationData,
+    ) -> Result<C::Attestation, Error>;
+
+    /// Signs the attestation of `duty` from `electra` onwards.
+    fn sign_single_attestation(
+        &self,
+        duty: &AttestationDuty,
+        data: AttestationData,
+    ) -> Result<SingleAttestation, Error>;
+
+    /// Signs the slot of `duty`, which decides if the validator aggregates its committee.
+    fn sign_selection_proof(&self, duty: &AttestationDuty) -> Result<BlsSignature, Error>;
+
+    fn sign_aggregate_and_proof(
+        &self,
+        duty: &AttestationDuty,
+        aggregate: C::Attestation,
+        selection_proof: BlsSignature,
+    ) -> Result<C::SignedAggregateAndProof, Error>;
+}
+
+// Returns `true` if attestations and aggregates of `version` use the v2 endpoints.
+fn uses_single_attestations(version: Version) -> bool {
+    matches!(version, Version::Electra | Version::Fulu)
+}
+
+// The duties of an epoch along with the dependent root the beacon node computed them from.
+struct Duties<T> {
+    dependent_root: Root,
+    duties: Vec<T>,
+}
+
+impl<T> Duties<T> {
+    fn is_stale(&self, dependent_root: Root) -> bool {
+        self.dependent_root != dependent_root
+    }
+}
+
+#[derive(Default)]
+struct DutyCache {
+    attester: HashMap<Epoch, Duties<AttestationDuty>>,
+    proposer: HashMap<Epoch, Duties<ProposerDuty>>,
+}
+
+impl DutyCache {
+    fn attester_duties_at(&self, epoch: Epoch, slot: Slot) -> Vec<AttestationDuty> {
+        self.attester
+            .get(&epoch)
+            .map(|duties| duties.duties.iter().filter(|duty| duty.slot == slot).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    fn proposer_duties_at(&self, epoch: Epoch, slot: Slot) -> Vec<ProposerDuty> {
+        self.proposer
+            .get(&epoch)
+            .map(|duties| duties.duties.iter().filter(|duty| duty.slot == slot).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    // Drops the duties computed from a dependent root other than the ones of the new head in
+    // `epoch`, returning `true` if any were dropped.
+    fn invalidate(&mut self, epoch: Epoch, event: &HeadEvent) -> bool {
+        let mut invalidated = false;
+        let mut invalidate_attester = |epoch, dependent_root| {
+            if self.attester.get(&epoch).is_some_and(|duties| duties.is_stale(dependent_root)) {
+                self.attester.remove(&epoch);
+                invalidated = true;
+            }
+        };
+        // the attester duties of `epoch` depend on the last block before `epoch - 1`, while the
+        // proposer duties of `epoch` and the attester duties of `epoch + 1` depend on the last
+        // block before `epoch`
+        invalidate_attester(epoch, event.previous_duty_dependent_root);
+        invalidate_attester(epoch + 1, event.current_duty_dependent_root);
+        let dependent_root = event.current_duty_dependent_root;
+        if self.proposer.get(&epoch).is_some_and(|duties| duties.is_stale(dependent_root)) {
+            self.proposer.remove(&epoch);
+            invalidated = true;
+        }
+        invalidated
+    }
+
+    // Drops the duties of the epochs before `epoch`.
+    fn prune(&mut self, epoch: Epoch) {
+        self.attester.retain(|duty_epoch, _| *duty_epoch >= epoch);
+        self.proposer.retain(|duty_epoch, _| *duty_epoch >= epoch);
+    }
+}
+
+/// Performs the duties of a set of validators against the beacon node of a [`Client`].
+///
+/// For each slot of its [`Clock`], the scheduler proposes a block at the start of the slot,
+/// attests once a third of the slot has passed and aggregates after two thirds, fetching the
+/// duties of the current and next epoch as needed. The duties are kept with their dependent roots
+/// and fetched again whenever a `head` event reports a different root, i.e. after a reorg.
+///
+/// Attestations and aggregates go to the v1 endpoints before `electra` and to the v2 endpoints,
+/// with attestations in the `SingleAttestation` format, from `electra` onwards, following the fork
+/// schedule of the [`Context`].
+///
+/// Validators a [`DoppelgangerGate`] given with [`Self::with_doppelganger_gate`] does not enable
+/// skip their duties.
+///
+/// NOTE: sync committee duties are out of scope, so members of a sync committee publish neither
+/// sync committee messages nor contributions.
+pub struct DutyScheduler<C: ClientTypes, T: TimeProvider + Send + Sync, S> {
+    client: Client<C>,
+    clock: Clock<T>,
+    context: Arc<Context>,
+    signer: S,
+    indices: Vec<ValidatorIndex>,
+    graffiti: Option<Bytes32>,
+    gate: DoppelgangerGate,
+    duties: Mutex<DutyCache>,
+}
+
+impl<C: ClientTypes, T: TimeProvider + Send + Sync, S: DutySigner<C>> DutyScheduler<C, T, S> {
+    pub fn new(
+        client: Client<C>,
+        clock: Clock<T>,
+        context: Arc<Context>,
+        signer: S,
+        indices: Vec<ValidatorIndex>,
+    ) -> Self {
+        Self {
+            client,
+            clock,
+            context,
+            signer,
+            indices,
+            graffiti: None,
+            gate: Default::default(),
+            duties: Default::default(),
+        }
+    }
+
+    pub fn with_graffiti(mut self, graffiti: Bytes32) -> Self {
+        self.graffiti = Some(graffiti);
+        self
+    }
+
+    pub fn with_doppelganger_gate(mut self, gate: DoppelgangerGate) -> Self {
+        self.gate = gate;
+        self
+    }
+
+    fn duties(&self) -> MutexGuard<'_, DutyCache> {
+        // NOTE: no code panics while holding the lock so it is never poisoned
+        self.duties.lock().expect("can lock duties")
+    }
+
+    /// Performs duties until the slots of the clock run out, which only fails if the scheduler
+    /// cannot subscribe to `head` events. Errors performing a duty are logged and skipped.
+    pub async fn run(self) -> Result<(), Error> {
+        let mut head_events = self.client.get_events::<HeadTopic>().await?;
+        let mut slots = self.clock.clone().into_stream();
+        // NOTE: the work for each slot and head event runs alongside the others so that a slow
+        // request never delays the duties of another slot
+        let mut pending = FuturesUnordered::new();
+        let mut head_updates = FuturesUnordered::new();
+        loop {
+            tokio::select! {
+                slot = slots.next() => {
+                    let Some(slot) = slot else { return Ok(()) };
+                    self.duties().prune(self.clock.epoch_for(slot));
+                    pending.push(self.on_slot(slot));
+                }
+                Some(event) = head_events.next() => match event {
+                    Ok(event) => head_updates.push(self.on_head(event)),
+                    Err(err) => warn!(%err, "could not read head event"),
+                },
+                Some(_) = pending.next(), if !pending.is_empty() => {}
+                Some(_) = head_updates.next(), if !head_updates.is_empty() => {}
+            }
+        }
+    }
+
+    async fn on_slot(&self, slot: Slot) {
+        let epoch = self.clock.epoch_for(slot);
+        if let Err(err) = self.fetch_duties(epoch).await {
+            warn!(%err, epoch, "could not fetch duties");
+        }
+        self.perform_duties(slot).await
+    }
+
+    async fn on_head(&self, event: HeadEvent) {
+        let epoch = self.clock.epoch_for(event.slot);
+        if !self.duties().invalidate(epoch, &event) {
+            return
+        }
+        debug!(slot = event.slot, block = ?event.block, "dependent root changed, fetching duties");
+        if let Err(err) = self.fetch_duties(epoch).await {
+            warn!(%err, epoch, "could not fetch duties");
+        }
+    }
+
+    // Fetches the attester duties of `epoch` and `epoch + 1` and the proposer duties of `epoch`
+    // unless they are already known.
+    async fn fetch_duties(&self, epoch: Epoch) -> Result<(), Error> {
+        for epoch in [epoch, epoch + 1] {
+            if self.duties().attester.contains_key(&epoch) {
+                continue
+            }
+            let (dependent_root, duties) =
+                self.client.get_attester_duties(epoch, &self.indices).await?;
+            self.duties().attester.insert(epoch, Duties { dependent_root, duties });
+        }
+        if !self.duties().proposer.contains_key(&epoch) {
+            let (dependent_root, mut duties) = self.client.get_proposer_duties(epoch).await?;
+            duties.retain(|duty| self.indices.contains(&duty.validator_index));
+            self.duties().proposer.insert(epoch, Duties { dependent_root, duties });
+        }
+        Ok(())
+    }
+
+    async fn perform_duties(&self, slot: Slot) {
+        tokio::join!(self.propose(slot), self.attest_and_aggregate(slot));
+    }
+
+    async fn propose(&self, slot: Slot) {
+        let mut duties = self.duties().proposer_duties_at(self.clock.epoch_for(slot), slot);
+        duties.retain(|duty| self.gate.is_signing_enabled(duty.validator_index));
+        for duty in duties {
+            if let Err(err) = self.propose_block(slot, &duty).await {
+                let validator_index = duty.validator_index;
+                warn!(%err, slot, validator_index, "could not propose block");
+            }
+        }
+    }
+
+    async fn propose_block(&self, slot: Slot, duty: &ProposerDuty) -> Result<(), Error> {
+        let randao_reveal =
+            self.signer.sign_randao_reveal(&duty.public_key, self.clock.epoch_for(slot))?;
+        // NOTE: a `builder_boost_factor` of 0 asks for a locally built payload as the scheduler
+        // does not sign blinded blocks
+        let proposal = self
+            .client
+            .get_block_proposal_v3(slot, randao_reveal, self.graffiti.clone(), Some(0))
+            .await?;
+        let BlockContents { block, kzg_proofs, blobs } = match proposal.block {
+            ProduceBlockResponse::Full(contents) => contents,
+            ProduceBlockResponse::Blinded(..) => {
+                return Err(Error::MissingExpectedData(
+                    "expected a full block but the node returned a blinded block".to_string(),
+                ))
+            }
+        };
+        let signed_block = self.signer.sign_block(duty, block)?;
+        if BlockContents::<C::BeaconBlock, C::Blob>::is_bare_block(proposal.version) {
+            self.client.post_signed_beacon_block(&signed_block).await
+        } else {
+            let request = SubmitSignedBeaconBlock {
+                signed_block: &signed_block,
+                kzg_proofs: Some(&kzg_proofs),
+                blobs: Some(&blobs),
+            };
+            self.client.post_signed_beacon_block_v2(request, proposal.version, None).await
+        }
+    }
+
+    async fn attest_and_aggregate(&self, slot: Slot) {
+        sleep(self.clock.duration_until_attestation_deadline(slot)).await;
+        // NOTE: read the duties only now so that a reorg early in the slot is accounted for
+        let mut duties = self.duties().attester_duties_at(self.clock.epoch_for(slot), slot);
+        duties.retain(|duty| self.gate.is_signing_enabled(duty.validator_index));
+        if duties.is_empty() {
+            return
+        }
+        let version = self.context.fork_for(slot);
+        let data = match self.attest(slot, version, &duties).await {
+            Ok(data) => data,
+            Err(err) => {
+                warn!(%err, slot, "could not attest");
+                return
+            }
+        };
+        sleep(self.clock.duration_until_aggregation(slot)).await;
+        if let Err(err) = self.aggregate(slot, version, &duties, &data).await {
+            warn!(%err, slot, "could not aggregate attestations");
+        }
+    }
+
+    // Publishes the attestations of `duties`, returning the attestation data of each committee.
+    // A duty that fails, e.g. because its attestation cannot be signed, is logged and skipped.
+    async fn attest(
+        &self,
+        slot: Slot,
+        version: Version,
+        duties: &[AttestationDuty],
+    ) -> Result<HashMap<CommitteeIndex, AttestationData>, Error> {
+        let mut data = HashMap::new();
+        let mut attestations = vec![];
+        let mut single_attestations = vec![];
+        for duty in duties {
+            let validator_index = duty.validator_index;
+            if !data.contains_key(&duty.committee_index) {
+                match self.client.get_attestation_data(slot, duty.committee_index).await {
+                    Ok(committee_data) => {
+                        data.insert(duty.committee_index, committee_data);
+                    }
+                    Err(err) => {
+                        warn!(%err, slot, validator_index, "could not fetch attestation data");
+                        continue
+                    }
+                }
+            }
+            let committee_data = data[&duty.committee_index].clone();
+            let signed = if uses_single_attestations(version) {
+                self.signer
+                    .sign_single_attestation(duty, committee_data)
+                    .map(|attestation| single_attestations.push(attestation))
+            } else {
+                self.signer
+                    .sign_attestation(duty, committee_data)
+                    .map(|attestation| attestations.push(attestation))
+            };
+            if let Err(err) = signed {
+                warn!(%err, slot, validator_index, "could not sign attestation");
+            }
+        }
+        if !attestations.is_empty() {
+            self.client.post_attestations(&attestations).await?;
+        }
+        if !single_attestations.is_empty() {
+            self.client.post_single_attestations(version, &single_attestations).await?;
+        }
+        Ok(data)
+    }
+
+    // Publishes the aggregates of the `duties` that select the validator as an aggregator. A duty
+    // that fails is logged and skipped.
+    async fn aggregate(
+        &self,
+        slot: Slot,
+        version: Version,
+        duties: &[AttestationDuty],
+        data: &HashMap<CommitteeIndex, AttestationData>,
+    ) -> Result<(), Error> {
+        let mut aggregates = vec![];
+        for duty in duties {
+            let Some(committee_data) = data.get(&duty.committee_index) else { continue };
+            match self.aggregate_and_proof(slot, version, duty, committee_data).await {
+                Ok(Some(aggregate)) => aggregates.push(aggregate),
+                Ok(None) => {}
+                Err(err) => {
+                    let validator_index = duty.validator_index;
+                    warn!(%err, slot, validator_index, "could not aggregate attestations");
+                }
+            }
+        }
+        if aggregates.is_empty() {
+            return Ok(())
+        }
+        if uses_single_attestations(version) {
+            self.client.post_aggregates_with_proofs_v2(version, &aggregates).await
+        } else {
+            self.client.post_aggregates_with_proofs(&aggregates).await
+        }
+    }
+
+    // Returns the signed aggregate of the committee of `duty`, or `None` if the validator is not
+    // an aggregator.
+    async fn aggregate_and_proof(
+        &self,
+        slot: Slot,
+        version: Version,
+        duty: &AttestationDuty,
+        committee_data: &AttestationData,
+    ) -> Result<Option<C::SignedAggregateAndProof>, Error> {
+        let selection_proof = self.signer.sign_selection_proof(duty)?;
+        if !is_aggregator(duty.committee_length, &selection_proof, &self.context) {
+            return Ok(None)
+        }
+        let data_root = committee_data.hash_tree_root().map_err(ConsensusError::from)?;
+        let aggregate = if uses_single_attestations(version) {
+            let committee_index = duty.committee_index;
+            self.client.get_attestation_aggregate_v2(data_root, slot, committee_index).await?
+        } else {
+            self.client.get_attestation_aggregate(data_root, slot).await?
+        };
+        let aggregate = self.signer.sign_aggregate_and_proof(duty, aggregate, selection_proof)?;
+        Ok(Some(aggregate))
+    }
+}
+
+#[cfg(all(test, feature = "server"))]
+mod tests {
+    use super::*;
+    use crate::{
+        events::Event,
+        presets::mainnet::{self, MainnetClientTypes},
+        types::BlockProposal,
+        ApiError, BeaconNodeProvider, MockBeaconNode, MockServer,
+    };
+    use ethereum_consensus::{
+        capella::mainnet as capella,
+        clock::TokioTimeProvider,
+        deneb::mainnet as deneb,
+        electra::mainnet as electra,
+        phase0::mainnet as phase0,
+        ssz::prelude::U256,
+        types::mainnet::{BeaconBlock, BlindedBeaconBlock, SignedBeaconBlock},
+    };
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+    use tokio::task::JoinHandle;
+
+    const SLOTS_PER_EPOCH: Slot = 32;
+    const SECONDS_PER_SLOT: u64 = 12;
+    const GENESIS_TIME: u64 = 1_606_824_023;
+    const PROPOSER: ValidatorIndex = 7;
+    // the validator whose attestations fail to sign
+    const FAULTY: ValidatorIndex = 8;
+    const PROPOSAL_SLOT: Slot = 3;
+
+    #[derive(Default)]
+    struct Provider {
+        dependent_root: Mutex<Root>,
+        duty_requests: AtomicUsize,
+        blocks: Mutex<Vec<SignedBeaconBlock>>,
+        attestations: Mutex<Vec<mainnet::Attestation>>,
+        single_attestations: Mutex<Vec<SingleAttestation>>,
+        aggregates: Mutex<Vec<mainnet::SignedAggregateAndProof>>,
+    }
+
+    impl BeaconNodeProvider<MainnetClientTypes> for Arc<Provider> {
+        fn proposer_duties(&self, epoch: Epoch) -> Result<(Root, Vec<ProposerDuty>), ApiError> {
+            let mut duties = vec![];
+            if epoch == 0 {
+                duties.push(ProposerDuty {
+                    public_key: Default::default(),
+                    validator_index: PROPOSER,
+                    slot: PROPOSAL_SLOT,
+                });
+            }
+            Ok((*self.dependent_root.lock().unwrap(), duties))
+        }
+
+        // assigns each validator to a committee of its own in every slot of `epoch`, so that
+        // every validator aggregates
+        fn attester_duties(
+            &self,
+            epoch: Epoch,
+            indices: &[ValidatorIndex],
+        ) -> Result<(Root, Vec<AttestationDuty>), ApiError> {
+            self.duty_requests.fetch_add(1, Ordering::SeqCst);
+            let duties = (0..SLOTS_PER_EPOCH)
+                .flat_map(|offset| {
+                    indices.iter().map(move |&validator_index| AttestationDuty {
+                        public_key: Default::default(),
+                        validator_index,
+                        committee_index: validator_index,
+                        committee_length: 1,
+                        committees_at_slot: indices.len(),
+                        validator_committee_index: 0,
+                        slot: epoch * SLOTS_PER_EPOCH + offset,
+                    })
+                })
+                .collect();
+            Ok((*self.dependent_root.lock().unwrap(), duties))
+        }
+
+        fn attestation_data(
+            &self,
+            slot: Slot,
+            index: CommitteeIndex,
+        ) -> Result<AttestationData, ApiError> {
+            Ok(AttestationData { slot, index, ..Default::default() })
+        }
+
+        fn aggregate_attestation(
+            &self,
+            _attestation_data_root: Root,
+            slot: Slot,
+        ) -> Result<mainnet::Attestation, ApiError> {
+            let data = AttestationData { slot, ..Default::default() };
+            Ok(mainnet::Attestation::Phase0(phase0::Attestation { data, ..Default::default() }))
+        }
+
+        fn aggregate_attestation_v2(
+            &self,
+            _attestation_data_root: Root,
+            slot: Slot,
+            _committee_index: CommitteeIndex,
+        ) -> Result<(Version, mainnet::Attestation), ApiError> {
+            let data = AttestationData { slot, ..Default::default() };
+            let aggregate = electra::Attestation { data, ..Default::default() };
+            Ok((Version::Electra, mainnet::Attestation::Electra(aggregate)))
+        }
+
+        fn block_proposal(
+            &self,
+            slot: Slot,
+            _randao_reveal: &RandaoReveal,
+            _graffiti: Option<Bytes32>,
+        ) -> Result<BlockProposal<BeaconBlock, BlindedBeaconBlock, deneb::Blob>, ApiError> {
+            let block =
+                capella::BeaconBlock { slot, proposer_index: PROPOSER, ..Default::default() };
+            let contents = BlockContents::from_block(BeaconBlock::Capella(block));
+            Ok(BlockProposal {
+                version: Version::Capella,
+                block: ProduceBlockResponse::Full(contents),
+                execution_payload_value: U256::from(0),
+                consensus_block_value: U256::from(0),
+            })
+        }
+
+        fn submit_block(&self, block: SignedBeaconBlock) -> Result<(), ApiError> {
+            self.blocks.lock().unwrap().push(block);
+            Ok(())
+        }
+
+        fn submit_attestations(
+            &self,
+            attestations: Vec<mainnet::Attestation>,
+        ) -> Result<(), ApiError> {
+            self.attestations.lock().unwrap().extend(attestations);
+            Ok(())
+        }
+
+        fn submit_single_attestations(
+            &self,
+            attestations: Vec<SingleAttestation>,
+        ) -> Result<(), ApiError> {
+            self.single_attestations.lock().unwrap().extend(attestations);
+            Ok(())
+        }
+
+        fn submit_aggregates(
+            &self,
+            aggregates: Vec<mainnet::SignedAggregateAndProof>,
+        ) -> Result<(), ApiError> {
+            self.aggregates.lock().unwrap().extend(aggregates);
+            Ok(())
+        }
+    }
+
+    struct Signer;
+
+    impl DutySigner<MainnetClientTypes> for Signer {
+        fn sign_randao_reveal(&self, _: &BlsPublicKey, _: Epoch) -> Result<RandaoReveal, Error> {
+            Ok(Default::default())
+        }
+
+        fn sign_block(
+            &self,
+            _: &ProposerDuty,
+            block: BeaconBlock,
+        ) -> Result<SignedBeaconBlock, Error> {
+            let BeaconBlock::Capella(message) = block else {
+                return Err(Error::Signer("unexpected fork".to_string()))
+            };
+            let signed_block =
+                capella::SignedBeaconBlock { message, signature: Default::default() };
+            Ok(SignedBeaconBlock::Capella(signed_block))
+        }
+
+        fn sign_attestation(
+            &self,
+            duty: &AttestationDuty,
+            data: AttestationData,
+        ) -> Result<mainnet::Attestation, Error> {
+            if duty.validator_index == FAULTY {
+                return Err(Error::Signer("slashable attestation".to_string()))
+            }
+            Ok(mainnet::Attestation::Phase0(phase0::Attestation { data, ..Default::default() }))
+        }
+
+        fn sign_single_attestation(
+            &self,
+            duty: &AttestationDuty,
+            data: AttestationData,
+        ) -> Result<SingleAttestation, Error> {
+            if duty.validator_index == FAULTY {
+                return Err(Error::Signer("slashable attestation".to_string()))
+            }
+            Ok(SingleAttestation {
+                committee_index: duty.committee_index,
+                attester_index: duty.validator_index,
+                data,
+                signature: Default::default(),
+            })
+        }
+
+        fn sign_selection_proof(&self, _: &AttestationDuty) -> Result<BlsSignature, Error> {
+            Ok(Default::default())
+        }
+
+        fn sign_aggregate_and_proof(
+            &self,
+            duty: &AttestationDuty,
+            aggregate: mainnet::Attestation,
+            selection_proof: BlsSignature,
+        ) -> Result<mainnet::SignedAggregateAndProof, Error> {
+            let aggregator_index = duty.validator_index;
+            let signature = BlsSignature::default();
+            let aggregate = match aggregate {
+                mainnet::Attestation::Phase0(aggregate) => {
+                    let message =
+                        phase0::AggregateAndProof { aggregator_index, aggregate, selection_proof };
+                    let aggregate = phase0::SignedAggregateAndProof { message, signature };
+                    mainnet::SignedAggregateAndProof::Phase0(aggregate)
+                }
+                mainnet::Attestation::Electra(aggregate) => {
+                    let message =
+                        electra::AggregateAndProof { aggregator_index, aggregate, selection_proof };
+                    let aggregate = electra::SignedAggregateAndProof { message, signature };
+                    mainnet::SignedAggregateAndProof::Electra(aggregate)
+                }
+            };
+            Ok(aggregate)
+        }
+    }
+
+    fn aggregator_index(aggregate: &mainnet::SignedAggregateAndProof) -> ValidatorIndex {
+        match aggregate {
+            mainnet::SignedAggregateAndProof::Phase0(aggregate) => {
+                aggregate.message.aggregator_index
+            }
+            mainnet::SignedAggregateAndProof::Electra(aggregate) => {
+                aggregate.message.aggregator_index
+            }
+        }
+    }
+
+    // Polls `condition` until it holds, failing if it does not within a few slots.
+    async fn wait_until(mut condition: impl FnMut() -> bool) {
+        let poll = async {
+            while !condition() {
+                sleep(Duration::from_millis(100)).await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(4 * SECONDS_PER_SLOT), poll).await.unwrap();
+    }
+
+    // Runs a scheduler for `PROPOSER` and `FAULTY` against a mock node from shortly after the
+    // start of the third slot.
+    fn spawn_scheduler(
+        context: Context,
+    ) -> (
+        Arc<Provider>,
+        MockBeaconNode<MainnetClientTypes, Arc<Provider>>,
+        MockServer,
+        JoinHandle<()>,
+    ) {
+        let provider = Arc::new(Provider::default());
+        let node = MockBeaconNode::<MainnetClientTypes, _>::new(provider.clone());
+        let server = node.spawn(([127, 0, 0, 1], 0).into()).unwrap();
+        let client = mainnet::Client::new(server.endpoint());
+
+        let genesis_time = Duration::from_secs(GENESIS_TIME);
+        let now = genesis_time + Duration::from_secs(2 * SECONDS_PER_SLOT + 1);
+        let clock = Clock::new(
+            GENESIS_TIME,
+            SECONDS_PER_SLOT,
+            SLOTS_PER_EPOCH,
+            TokioTimeProvider::new(now),
+        );
+        let indices = vec![PROPOSER, FAULTY];
+        let scheduler = DutyScheduler::new(client, clock, Arc::new(context), Signer, indices);
+        let task = tokio::spawn(async move { scheduler.run().await.unwrap() });
+        (provider, node, server, task)
+    }
+
+    // NOTE: with paused time `tokio` skips ahead to the next timer whenever the runtime is idle,
+    // so the slots pass without waiting on the wall clock
+    #[tokio::test(start_paused = true)]
+    async fn test_duty_scheduler() {
+        let (provider, node, _server, task) = spawn_scheduler(Context::for_mainnet());
+
+        wait_until(|| !provider.blocks.lock().unwrap().is_empty()).await;
+        let blocks = provider.blocks.lock().unwrap().clone();
+        assert_eq!(blocks.len(), 1);
+        let SignedBeaconBlock::Capella(block) = &blocks[0] else { panic!("unexpected block") };
+        assert_eq!(block.message.slot, PROPOSAL_SLOT);
+
+        // the attestations of the faulty validator are skipped without affecting the others
+        wait_until(|| !provider.aggregates.lock().unwrap().is_empty()).await;
+        let attestations = provider.attestations.lock().unwrap().clone();
+        assert!(!attestations.is_empty());
+        for attestation in &attestations {
+            let mainnet::Attestation::Phase0(attestation) = attestation else {
+                panic!("unexpected attestation")
+            };
+            assert_eq!(attestation.data.index, PROPOSER);
+        }
+        assert!(provider.single_attestations.lock().unwrap().is_empty());
+        // the faulty validator still aggregates its committee
+        let aggregates = provider.aggregates.lock().unwrap().clone();
+        let aggregators = aggregates.iter().map(aggregator_index).collect::<Vec<_>>();
+        assert!(aggregators.contains(&PROPOSER) && aggregators.contains(&FAULTY));
+        assert!(aggregates
+            .iter()
+            .all(|aggregate| matches!(aggregate, mainnet::SignedAggregateAndProof::Phase0(..))));
+
+        let requests = provider.duty_requests.load(Ordering::SeqCst);
+        // a head with a new dependent root makes the scheduler fetch its duties again
+        let dependent_root = Root::try_from([1u8; 32].as_ref()).unwrap();
+        *provider.dependent_root.lock().unwrap() = dependent_root;
+        let event = HeadEvent {
+            slot: PROPOSAL_SLOT,
+            block: Default::default(),
+            state: Default::default(),
+            epoch_transition: false,
+            previous_duty_dependent_root: dependent_root,
+            current_duty_dependent_root: dependent_root,
+            execution_optimistic: false,
+        };
+        wait_until(|| {
+            node.emit(&Event::Head(event.clone())).unwrap();
+            provider.duty_requests.load(Ordering::SeqCst) > requests
+        })
+        .await;
+        task.abort();
+    }
+    #[tokio::test(start_paused = true)]
+    async fn test_duty_scheduler_electra() {
+        let mut context = Context::for_mainnet();
+        context.electra_fork_epoch = 0;
+        let (provider, _node, _server, task) = spawn_scheduler(context);
+
+        wait_until(|| !provider.aggregates.lock().unwrap().is_empty()).await;
+        assert!(provider.attestations.lock().unwrap().is_empty());
+        let attestations = provider.single_attestations.lock().unwrap().clone();
+        assert!(!attestations.is_empty());
+        for attestation in &attestations {
+            assert_eq!(attestation.attester_index, PROPOSER);
+            assert_eq!(attestation.committee_index, PROPOSER);
+        }
+        let aggregates = provider.aggregates.lock().unwrap().clone();
+        let aggregators = aggregates.iter().map(aggregator_index).collect::<Vec<_>>();
+        assert!(aggregators.contains(&PROPOSER) && aggregators.contains(&FAULTY));
+        assert!(aggregates
+            .iter()
+            .all(|aggregate| matches!(aggregate, mainnet::SignedAggregateAndProof::Electra(..))));
+        task.abort();
+    }
+}
@@ -0,0 +1,630 @@
+use crate::{
+    types::{
+        Attestation, AttesterSlashing, BlockContents, LightClientBootstrap,
+        LightClientFinalityUpdate, LightClientOptimisticUpdate, LightClientUpdate,
+    },
+    Error,
+};
+use ethereum_consensus::{
+    crypto::KzgProof,
+    ssz::prelude::{Deserialize, SimpleSerialize, SimpleSerializeError},
+    types::{
+        BeaconBlock, BeaconState, BlindedBeaconBlock, SignedBeaconBlock, SignedBlindedBeaconBlock,
+    },
+    Fork as Version,
+};
+
+pub const JSON_MIME_TYPE: &str = "application/json";
+pub const SSZ_MIME_TYPE: &str = "application/octet-stream";
+// Prefer SSZ but let the node answer with JSON if it cannot provide SSZ for a given endpoint.
+pub(crate) const SSZ_OR_JSON_ACCEPT: &str = "application/octet-stream;q=1.0,application/json;q=0.9";
+
+/// The encoding a `Client` prefers for the request and response bodies of endpoints that support
+/// SSZ (blocks, states, blob sidecars and block proposals).
+///
+/// Regardless of the preference, the client falls back to JSON when the node does not support
+/// SSZ for a given endpoint.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    #[default]
+    Json,
+    Ssz,
+}
+
+/// Types whose SSZ encoding depends on the fork given by the `Eth-Consensus-Version` header.
+///
+/// NOTE: the polymorphic blocks and states from `ethereum_consensus::types` only have variants
+/// up to Deneb, so their implementations return `Error::UnsupportedVersion` for Electra and
+/// later forks. Decode those with the fork's own types, e.g. `electra::mainnet::SignedBeaconBlock`.
+pub trait VersionedSsz: Sized {
+    fn deserialize_versioned(version: Version, encoding: &[u8]) -> Result<Self, Error>;
+}
+
+/// Types whose JSON encoding depends on the fork given by the `version` field of a response.
+pub trait VersionedJson: Sized {
+    fn deserialize_versioned_json(
+        version: Version,
+        value: serde_json::Value,
+    ) -> Result<Self, Error>;
+}
+
+impl<T: VersionedJson> VersionedJson for Vec<T> {
+    fn deserialize_versioned_json(
+        version: Version,
+        value: serde_json::Value,
+    ) -> Result<Self, Error> {
+        let values: Vec<serde_json::Value> = serde_json::from_value(value)?;
+        values.into_iter().map(|value| T::deserialize_versioned_json(version, value)).collect()
+    }
+}
+
+impl<Phase0, Electra> VersionedJson for Attestation<Phase0, Electra>
+where
+    Phase0: serde::de::DeserializeOwned,
+    Electra: serde::de::DeserializeOwned,
+{
+    fn deserialize_versioned_json(
+        version: Version,
+        value: serde_json::Value,
+    ) -> Result<Self, Error> {
+        let value = match version {
+            Version::Electra | Version::Fulu => Self::Electra(serde_json::from_value(value)?),
+            _ => Self::Phase0(serde_json::from_value(value)?),
+        };
+        Ok(value)
+    }
+}
+
+impl<Phase0, Electra> VersionedJson for AttesterSlashing<Phase0, Electra>
+where
+    Phase0: serde::de::DeserializeOwned,
+    Electra: serde::de::DeserializeOwned,
+{
+    fn deserialize_versioned_json(
+        version: Version,
+        value: serde_json::Value,
+    ) -> Result<Self, Error> {
+        let value = match version {
+            Version::Electra | Version::Fulu => Self::Electra(serde_json::from_value(value)?),
+            _ => Self::Phase0(serde_json::from_value(value)?),
+        };
+        Ok(value)
+    }
+}
+
+macro_rules! impl_versioned_json_for_light_client {
+    ($($name:ident),+) => {
+        $(
+            impl<Altair, Capella, Deneb> VersionedJson for $name<Altair, Capella, Deneb>
+            where
+                Altair: serde::de::DeserializeOwned,
+                Capella: serde::de::DeserializeOwned,
+                Deneb: serde::de::DeserializeOwned,
+            {
+                fn deserialize_versioned_json(
+                    version: Version,
+                    value: serde_json::Value,
+                ) -> Result<Self, Error> {
+                    let value = match version {
+                        Version::Altair | Version::Bellatrix => {
+                            Self::Altair(serde_json::from_value(value)?)
+                        }
+                        Version::Capella => Self::Capella(serde_json::from_value(value)?),
+                        // NOTE: later forks reuse the `deneb` representation
+                        Version::Deneb | Version::Electra | Version::Fulu => {
+                            Self::Deneb(serde_json::from_value(value)?)
+                        }
+                        version => return Err(Error::UnsupportedVersion(version)),
+                    };
+                    Ok(value)
+                }
+            }
+        )+
+    };
+}
+
+impl_versioned_json_for_light_client!(
+    LightClientBootstrap,
+    LightClientUpdate,
+    LightClientFinalityUpdate,
+    LightClientOptimisticUpdate
+);
+
+impl<BeaconBlock, Blob> VersionedJson for BlockContents<BeaconBlock, Blob>
+where
+    BeaconBlock: serde::Serialize + serde::de::DeserializeOwned,
+    Blob: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn deserialize_versioned_json(
+        version: Version,
+        value: serde_json::Value,
+    ) -> Result<Self, Error> {
+        if Self::is_bare_block(version) {
+            Ok(Self::from_block(serde_json::from_value(value)?))
+        } else {
+            Ok(serde_json::from_value(value)?)
+        }
+    }
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    > VersionedJson
+    for BlindedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >
+{
+    fn deserialize_versioned_json(
+        version: Version,
+        value: serde_json::Value,
+    ) -> Result<Self, Error> {
+        let value = match version {
+            Version::Bellatrix => Self::Bellatrix(serde_json::from_value(value)?),
+            Version::Capella => Self::Capella(serde_json::from_value(value)?),
+            Version::Deneb => Self::Deneb(serde_json::from_value(value)?),
+            version => return Err(Error::UnsupportedVersion(version)),
+        };
+        Ok(value)
+    }
+}
+
+fn deserialize<T: Deserialize>(encoding: &[u8]) -> Result<T, Error> {
+    Ok(T::deserialize(encoding).map_err(SimpleSerializeError::from)?)
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    > VersionedSsz
+    for SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >
+{
+    fn deserialize_versioned(version: Version, encoding: &[u8]) -> Result<Self, Error> {
+        let value = match version {
+            Version::Phase0 => Self::Phase0(deserialize(encoding)?),
+            Version::Altair => Self::Altair(deserialize(encoding)?),
+            Version::Bellatrix => Self::Bellatrix(deserialize(encoding)?),
+            Version::Capella => Self::Capella(deserialize(encoding)?),
+            Version::Deneb => Self::Deneb(deserialize(encoding)?),
+            version => return Err(Error::UnsupportedVersion(version)),
+        };
+        Ok(value)
+    }
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    > VersionedSsz
+    for BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >
+{
+    fn deserialize_versioned(version: Version, encoding: &[u8]) -> Result<Self, Error> {
+        let value = match version {
+            Version::Phase0 => Self::Phase0(deserialize(encoding)?),
+            Version::Altair => Self::Altair(deserialize(encoding)?),
+            Version::Bellatrix => Self::Bellatrix(deserialize(encoding)?),
+            Version::Capella => Self::Capella(deserialize(encoding)?),
+            Version::Deneb => Self::Deneb(deserialize(encoding)?),
+            version => return Err(Error::UnsupportedVersion(version)),
+        };
+        Ok(value)
+    }
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    > VersionedSsz
+    for BlindedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >
+{
+    fn deserialize_versioned(version: Version, encoding: &[u8]) -> Result<Self, Error> {
+        let value = match version {
+            Version::Bellatrix => Self::Bellatrix(deserialize(encoding)?),
+            Version::Capella => Self::Capella(deserialize(encoding)?),
+            Version::Deneb => Self::Deneb(deserialize(encoding)?),
+            version => return Err(Error::UnsupportedVersion(version)),
+        };
+        Ok(value)
+    }
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    > VersionedSsz
+    for SignedBlindedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >
+{
+    fn deserialize_versioned(version: Version, encoding: &[u8]) -> Result<Self, Error> {
+        let value = match version {
+            Version::Bellatrix => Self::Bellatrix(deserialize(encoding)?),
+            Version::Capella => Self::Capella(deserialize(encoding)?),
+            Version::Deneb => Self::Deneb(deserialize(encoding)?),
+            version => return Err(Error::UnsupportedVersion(version)),
+        };
+        Ok(value)
+    }
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+    > VersionedSsz
+    for BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >
+{
+    fn deserialize_versioned(version: Version, encoding: &[u8]) -> Result<Self, Error> {
+        let value = match version {
+            Version::Phase0 => Self::Phase0(deserialize(encoding)?),
+            Version::Altair => Self::Altair(deserialize(encoding)?),
+            Version::Bellatrix => Self::Bellatrix(deserialize(encoding)?),
+            Version::Capella => Self::Capella(deserialize(encoding)?),
+            Version::Deneb => Self::Deneb(deserialize(encoding)?),
+            version => return Err(Error::UnsupportedVersion(version)),
+        };
+        Ok(value)
+    }
+}
+
+// Size of the fixed part of the `BlockContents` container: one 4-byte offset for each of its
+// variable-size fields.
+const BLOCK_CONTENTS_FIXED_SIZE: usize = 3 * 4;
+const KZG_PROOF_SIZE: usize = 48;
+
+fn read_offset(encoding: &[u8], index: usize) -> Result<usize, Error> {
+    let start = index * 4;
+    let bytes = encoding
+        .get(start..start + 4)
+        .ok_or_else(|| Error::MalformedSsz("`BlockContents` is missing an offset".to_string()))?;
+    let offset = u32::from_le_bytes(bytes.try_into().expect("slice has correct length"));
+    Ok(offset as usize)
+}
+
+impl<BeaconBlock, Blob> VersionedSsz for BlockContents<BeaconBlock, Blob>
+where
+    BeaconBlock: VersionedSsz,
+    Blob: SimpleSerialize,
+{
+    fn deserialize_versioned(version: Version, encoding: &[u8]) -> Result<Self, Error> {
+        if Self::is_bare_block(version) {
+            let block = BeaconBlock::deserialize_versioned(version, encoding)?;
+            return Ok(Self::from_block(block))
+        }
+
+        let block_offset = read_offset(encoding, 0)?;
+        let proofs_offset = read_offset(encoding, 1)?;
+        let blobs_offset = read_offset(encoding, 2)?;
+        if block_offset != BLOCK_CONTENTS_FIXED_SIZE ||
+            block_offset > proofs_offset ||
+            proofs_offset > blobs_offset ||
+            blobs_offset > encoding.len()
+        {
+            return Err(Error::MalformedSsz("`BlockContents` has invalid offsets".to_string()))
+        }
+
+        let block =
+            BeaconBlock::deserialize_versioned(version, &encoding[block_offset..proofs_offset])?;
+
+        let proofs = &encoding[proofs_offset..blobs_offset];
+        if proofs.len() % KZG_PROOF_SIZE != 0 {
+            return Err(Error::MalformedSsz(format!(
+                "`kzg_proofs` has length {} which is not a multiple of {KZG_PROOF_SIZE}",
+                proofs.len()
+            )))
+        }
+        let kzg_proofs = proofs
+            .chunks_exact(KZG_PROOF_SIZE)
+            .map(deserialize::<KzgProof>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // blobs are fixed-size so there is one blob for each proof
+        let blobs = &encoding[blobs_offset..];
+        let blob_count = kzg_proofs.len();
+        let blobs = if blob_count == 0 {
+            if !blobs.is_empty() {
+                return Err(Error::MalformedSsz("`blobs` given without `kzg_proofs`".to_string()))
+            }
+            vec![]
+        } else {
+            if blobs.len() % blob_count != 0 {
+                return Err(Error::MalformedSsz(format!(
+                    "`blobs` has length {} which does not divide into {blob_count} blobs",
+                    blobs.len()
+                )))
+            }
+            blobs
+                .chunks_exact(blobs.len() / blob_count)
+                .map(deserialize::<Blob>)
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        Ok(Self { block, kzg_proofs, blobs })
+    }
+}
+
+/// Parses the value of the `Eth-Consensus-Version` header.
+pub fn parse_consensus_version(value: &str) -> Option<Version> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase())).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethereum_consensus::{
+        ssz::prelude::serialize,
+        types::mainnet::{SignedBeaconBlock, SignedBlindedBeaconBlock},
+    };
+
+    #[test]
+    fn test_versioned_roundtrip() {
+        let block = SignedBeaconBlock::Capella(Default::default());
+        let encoding = serialize(&block).unwrap();
+        let recovered =
+            SignedBeaconBlock::deserialize_versioned(Version::Capella, &encoding).unwrap();
+        assert_eq!(recovered, block);
+
+        let block = SignedBlindedBeaconBlock::Deneb(Default::default());
+        let encoding = serialize(&block).unwrap();
+        assert!(SignedBlindedBeaconBlock::deserialize_versioned(Version::Deneb, &encoding).is_ok());
+        assert!(matches!(
+            SignedBlindedBeaconBlock::deserialize_versioned(Version::Altair, &encoding),
+            Err(Error::UnsupportedVersion(Version::Altair))
+        ));
+    }
+
+    #[test]
+    fn test_versioned_ssz_stops_at_deneb() {
+        use ethereum_consensus::types::mainnet::{BeaconBlock, BeaconState};
+
+        let encoding = serialize(&SignedBeaconBlock::Deneb(Default::default())).unwrap();
+        for version in [Version::Electra, Version::Fulu] {
+            assert!(matches!(
+                SignedBeaconBlock::deserialize_versioned(version, &encoding),
+                Err(Error::UnsupportedVersion(v)) if v == version
+            ));
+            assert!(matches!(
+                BeaconBlock::deserialize_versioned(version, &encoding),
+                Err(Error::UnsupportedVersion(v)) if v == version
+            ));
+            assert!(matches!(
+                BeaconState::deserialize_versioned(version, &encoding),
+                Err(Error::UnsupportedVersion(v)) if v == version
+            ));
+        }
+    }
+
+    #[test]
+    fn test_block_contents_roundtrip() {
+        use crate::types::SubmitSignedBeaconBlock;
+        use ethereum_consensus::deneb::mainnet::Blob;
+
+        let block = SignedBeaconBlock::Deneb(Default::default());
+        let kzg_proofs = vec![KzgProof::default(); 2];
+        let blobs = vec![Blob::default(); 2];
+        let request = SubmitSignedBeaconBlock {
+            signed_block: &block,
+            kzg_proofs: Some(&kzg_proofs),
+            blobs: Some(&blobs),
+        };
+        let encoding = request.to_ssz_bytes().unwrap();
+        let recovered = BlockContents::<SignedBeaconBlock, Blob>::deserialize_versioned(
+            Version::Deneb,
+            &encoding,
+        )
+        .unwrap();
+        assert_eq!(recovered.block, block);
+        assert_eq!(recovered.kzg_proofs, kzg_proofs);
+        assert_eq!(recovered.blobs, blobs);
+
+        let block = SignedBeaconBlock::Capella(Default::default());
+        let encoding = serialize(&block).unwrap();
+        let recovered = BlockContents::<SignedBeaconBlock, Blob>::deserialize_versioned(
+            Version::Capella,
+            &encoding,
+        )
+        .unwrap();
+        assert_eq!(recovered.block, block);
+        assert!(recovered.blobs.is_empty());
+    }
+
+    #[test]
+    fn test_versioned_json_attestation() {
+        use crate::presets::mainnet::Attestation;
+
+        let electra =
+            serde_json::to_value(ethereum_consensus::electra::mainnet::Attestation::default())
+                .unwrap();
+        let phase0 =
+            serde_json::to_value(ethereum_consensus::phase0::mainnet::Attestation::default())
+                .unwrap();
+
+        let attestation =
+            Attestation::deserialize_versioned_json(Version::Electra, electra.clone()).unwrap();
+        assert!(matches!(attestation, Attestation::Electra(..)));
+        let attestation =
+            Attestation::deserialize_versioned_json(Version::Deneb, phase0.clone()).unwrap();
+        assert!(matches!(attestation, Attestation::Phase0(..)));
+
+        // without a version, the latest matching fork is chosen
+        let attestation: Attestation = serde_json::from_value(electra).unwrap();
+        assert!(matches!(attestation, Attestation::Electra(..)));
+        let attestation: Attestation = serde_json::from_value(phase0).unwrap();
+        assert!(matches!(attestation, Attestation::Phase0(..)));
+    }
+
+    #[test]
+    fn test_versioned_json_light_client() {
+        use crate::presets::mainnet::{LightClientFinalityUpdate, LightClientUpdate};
+        use ethereum_consensus::{altair, capella, deneb};
+
+        let altair = serde_json::to_value(altair::mainnet::LightClientUpdate::default()).unwrap();
+        let capella = serde_json::to_value(capella::mainnet::LightClientUpdate::default()).unwrap();
+        let deneb = serde_json::to_value(deneb::mainnet::LightClientUpdate::default()).unwrap();
+
+        for (version, value) in [(Version::Altair, &altair), (Version::Bellatrix, &altair)] {
+            let update = LightClientUpdate::deserialize_versioned_json(version, value.clone());
+            assert!(matches!(update, Ok(LightClientUpdate::Altair(..))));
+        }
+        let update = LightClientUpdate::deserialize_versioned_json(Version::Capella, capella);
+        assert!(matches!(update, Ok(LightClientUpdate::Capella(..))));
+        for version in [Version::Deneb, Version::Electra, Version::Fulu] {
+            let update = LightClientUpdate::deserialize_versioned_json(version, deneb.clone());
+            assert!(matches!(update, Ok(LightClientUpdate::Deneb(..))));
+        }
+        // the `altair` update lacks the execution payload header of later forks
+        let update = LightClientUpdate::deserialize_versioned_json(Version::Deneb, altair);
+        assert!(matches!(update, Err(Error::Json(..))));
+        let update = LightClientUpdate::deserialize_versioned_json(Version::Phase0, deneb);
+        assert!(matches!(update, Err(Error::UnsupportedVersion(Version::Phase0))));
+
+        let finality_update = deneb::mainnet::LightClientFinalityUpdate::default();
+        let finality_update = serde_json::to_value(finality_update).unwrap();
+        let finality_update = LightClientFinalityUpdate::deserialize_versioned_json(
+            Version::Electra,
+            finality_update,
+        );
+        assert!(matches!(finality_update, Ok(LightClientFinalityUpdate::Deneb(..))));
+    }
+
+    #[test]
+    fn test_parse_consensus_version() {
+        assert_eq!(parse_consensus_version("deneb"), Some(Version::Deneb));
+        assert_eq!(parse_consensus_version("Capella"), Some(Version::Capella));
+        assert_eq!(parse_consensus_version("fulu"), Some(Version::Fulu));
+        assert_eq!(parse_consensus_version("foo"), None);
+    }
+}
@@ -0,0 +1,695 @@
+//! Subscriptions to the beacon node event stream at `/eth/v1/events`.
+//!
+//! Each topic has a type implementing [`Topic`] so that a subscription to a single topic yields
+//! its typed payload, while a subscription to several topics yields an [`Event`] tagged by topic.
+//! Streams reconnect with exponential backoff when the connection to the node drops.
+use crate::{
+    api_client::ClientTypes,
+    presets::mainnet::MainnetClientTypes,
+    types::{PayloadAttributesTopic, SingleAttestation, Topic, VersionedValue},
+    ApiError, Error,
+};
+use ethereum_consensus::{
+    capella::SignedBlsToExecutionChange,
+    crypto::KzgCommitment,
+    phase0::{ProposerSlashing, SignedVoluntaryExit},
+    primitives::{BlobIndex, Bytes32, Epoch, Root, Slot},
+};
+use futures_util::stream::{self, Stream};
+use http::header::ACCEPT;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tracing::warn;
+use url::Url;
+
+const EVENT_STREAM_MIME_TYPE: &str = "text/event-stream";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HeadEvent {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub block: Root,
+    pub state: Root,
+    pub epoch_transition: bool,
+    pub previous_duty_dependent_root: Root,
+    pub current_duty_dependent_root: Root,
+    pub execution_optimistic: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockEvent {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub block: Root,
+    pub execution_optimistic: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockGossipEvent {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub block: Root,
+}
+
+pub type SingleAttestationEvent = SingleAttestation;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinalizedCheckpointEvent {
+    pub block: Root,
+    pub state: Root,
+    #[serde(with = "crate::serde::as_str")]
+    pub epoch: Epoch,
+    pub execution_optimistic: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainReorgEvent {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    #[serde(with = "crate::serde::as_str")]
+    pub depth: u64,
+    pub old_head_block: Root,
+    pub new_head_block: Root,
+    pub old_head_state: Root,
+    pub new_head_state: Root,
+    #[serde(with = "crate::serde::as_str")]
+    pub epoch: Epoch,
+    pub execution_optimistic: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobSidecarEvent {
+    pub block_root: Root,
+    #[serde(with = "crate::serde::as_str")]
+    pub index: BlobIndex,
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub kzg_commitment: KzgCommitment,
+    pub versioned_hash: Bytes32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataColumnSidecarEvent {
+    pub block_root: Root,
+    #[serde(with = "crate::serde::as_str")]
+    pub index: u64,
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub kzg_commitments: Vec<KzgCommitment>,
+}
+
+pub struct HeadTopic;
+
+impl Topic for HeadTopic {
+    const NAME: &'static str = "head";
+
+    type Data = HeadEvent;
+}
+
+pub struct BlockTopic;
+
+impl Topic for BlockTopic {
+    const NAME: &'static str = "block";
+
+    type Data = BlockEvent;
+}
+
+pub struct BlockGossipTopic;
+
+impl Topic for BlockGossipTopic {
+    const NAME: &'static str = "block_gossip";
+
+    type Data = BlockGossipEvent;
+}
+
+pub struct AttestationTopic<C>(PhantomData<C>);
+
+impl<C: ClientTypes> Topic for AttestationTopic<C> {
+    const NAME: &'static str = "attestation";
+
+    type Data = C::Attestation;
+}
+
+pub struct SingleAttestationTopic;
+
+impl Topic for SingleAttestationTopic {
+    const NAME: &'static str = "single_attestation";
+
+    type Data = SingleAttestationEvent;
+}
+
+pub struct VoluntaryExitTopic;
+
+impl Topic for VoluntaryExitTopic {
+    const NAME: &'static str = "voluntary_exit";
+
+    type Data = SignedVoluntaryExit;
+}
+
+pub struct BlsToExecutionChangeTopic;
+
+impl Topic for BlsToExecutionChangeTopic {
+    const NAME: &'static str = "bls_to_execution_change";
+
+    type Data = SignedBlsToExecutionChange;
+}
+
+pub struct ProposerSlashingTopic;
+
+impl Topic for ProposerSlashingTopic {
+    const NAME: &'static str = "proposer_slashing";
+
+    type Data = ProposerSlashing;
+}
+
+pub struct AttesterSlashingTopic<C>(PhantomData<C>);
+
+impl<C: ClientTypes> Topic for AttesterSlashingTopic<C> {
+    const NAME: &'static str = "attester_slashing";
+
+    type Data = C::AttesterSlashing;
+}
+
+pub struct FinalizedCheckpointTopic;
+
+impl Topic for FinalizedCheckpointTopic {
+    const NAME: &'static str = "finalized_checkpoint";
+
+    type Data = FinalizedCheckpointEvent;
+}
+
+pub struct ChainReorgTopic;
+
+impl Topic for ChainReorgTopic {
+    const NAME: &'static str = "chain_reorg";
+
+    type Data = ChainReorgEvent;
+}
+
+pub struct ContributionAndProofTopic<C>(PhantomData<C>);
+
+impl<C: ClientTypes> Topic for ContributionAndProofTopic<C> {
+    const NAME: &'static str = "contribution_and_proof";
+
+    type Data = C::SignedContributionAndProof;
+}
+
+pub struct LightClientFinalityUpdateTopic<C>(PhantomData<C>);
+
+impl<C: ClientTypes> Topic for LightClientFinalityUpdateTopic<C> {
+    const NAME: &'static str = "light_client_finality_update";
+
+    type Data = VersionedValue<C::LightClientFinalityUpdate>;
+}
+
+pub struct LightClientOptimisticUpdateTopic<C>(PhantomData<C>);
+
+impl<C: ClientTypes> Topic for LightClientOptimisticUpdateTopic<C> {
+    const NAME: &'static str = "light_client_optimistic_update";
+
+    type Data = VersionedValue<C::LightClientOptimisticUpdate>;
+}
+
+pub struct BlobSidecarTopic;
+
+impl Topic for BlobSidecarTopic {
+    const NAME: &'static str = "blob_sidecar";
+
+    type Data = BlobSidecarEvent;
+}
+
+pub struct DataColumnSidecarTopic;
+
+impl Topic for DataColumnSidecarTopic {
+    const NAME: &'static str = "data_column_sidecar";
+
+    type Data = DataColumnSidecarEvent;
+}
+
+// Defines `Event` with a variant for each topic along with the methods that map between the
+// variants and the names of their topics, so each name is only given by its `Topic::NAME`.
+macro_rules! define_events {
+    ($($variant:ident($topic:ty)),+ $(,)?) => {
+        /// An event from a subscription to several topics, tagged by its topic.
+        pub enum Event<C: ClientTypes> {
+            $($variant(<$topic as Topic>::Data)),+
+        }
+
+        impl<C: ClientTypes> Event<C> {
+            /// The names of every topic, in the order of the variants of `Event`.
+            pub const TOPICS: &'static [&'static str] = &[$(<$topic as Topic>::NAME),+];
+
+            /// The name of the topic of this event.
+            pub fn topic(&self) -> &'static str {
+                match self {
+                    $(Self::$variant(..) => <$topic as Topic>::NAME),+
+                }
+            }
+
+            /// The data of this event as JSON, in the same format the node sent it.
+            pub fn data_to_json(&self) -> Result<serde_json::Value, serde_json::Error> {
+                match self {
+                    $(Self::$variant(data) => serde_json::to_value(data)),+
+                }
+            }
+
+            /// Decodes the `data` of an event given the name of its topic.
+            pub fn decode(topic: &str, data: &str) -> Result<Self, Error> {
+                $(
+                    if topic == <$topic as Topic>::NAME {
+                        return Ok(Self::$variant(serde_json::from_str(data)?))
+                    }
+                )+
+                Err(Error::UnknownTopic(topic.to_string()))
+            }
+        }
+    };
+}
+
+define_events!(
+    Head(HeadTopic),
+    Block(BlockTopic),
+    BlockGossip(BlockGossipTopic),
+    Attestation(AttestationTopic<C>),
+    SingleAttestation(SingleAttestationTopic),
+    VoluntaryExit(VoluntaryExitTopic),
+    BlsToExecutionChange(BlsToExecutionChangeTopic),
+    ProposerSlashing(ProposerSlashingTopic),
+    AttesterSlashing(AttesterSlashingTopic<C>),
+    FinalizedCheckpoint(FinalizedCheckpointTopic),
+    ChainReorg(ChainReorgTopic),
+    ContributionAndProof(ContributionAndProofTopic<C>),
+    LightClientFinalityUpdate(LightClientFinalityUpdateTopic<C>),
+    LightClientOptimisticUpdate(LightClientOptimisticUpdateTopic<C>),
+    PayloadAttributes(PayloadAttributesTopic),
+    BlobSidecar(BlobSidecarTopic),
+    DataColumnSidecar(DataColumnSidecarTopic),
+);
+
+/// The names of every topic that can be given to [`crate::Client::get_events_for_topics`].
+// NOTE: the names do not depend on the client types so any will do
+pub const ALL_TOPICS: &[&str] = Event::<MainnetClientTypes>::TOPICS;
+
+/// Controls how an [`EventStream`] reconnects after the connection to the node drops.
+///
+/// The delay before each attempt doubles from `initial_delay` up to `max_delay`, and resets once
+/// a connection succeeds.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    /// Consecutive failed attempts after which the stream yields the last error and ends;
+    /// `None` retries forever.
+    pub max_retries: Option<usize>,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_retries: None,
+        }
+    }
+}
+
+impl Backoff {
+    fn delay(&self, attempt: usize) -> Duration {
+        let factor = 1u32.checked_shl(attempt as u32).unwrap_or(u32::MAX);
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct RawEvent {
+    name: String,
+    data: String,
+}
+
+// Incremental parser for the `text/event-stream` format.
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    name: Option<String>,
+    data: Option<String>,
+}
+
+impl SseParser {
+    fn feed(&mut self, chunk: &[u8], events: &mut VecDeque<RawEvent>) {
+        self.buffer.extend_from_slice(chunk);
+        while let Some(end) = self.buffer.iter().position(|&byte| byte == b'\n') {
+            let line = self.buffer.drain(..=end).collect::<Vec<_>>();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                if let Some(data) = self.data.take() {
+                    let name = self.name.take().unwrap_or_else(|| "message".to_string());
+                    events.push_back(RawEvent { name, data });
+                }
+                self.name = None;
+                continue
+            }
+            if line.starts_with(':') {
+                continue
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => self.name = Some(value.to_string()),
+                "data" => match self.data.as_mut() {
+                    Some(data) => {
+                        data.push('\n');
+                        data.push_str(value);
+                    }
+                    None => self.data = Some(value.to_string()),
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+struct Subscription<T> {
+    http: reqwest::Client,
+    target: Url,
+    backoff: Backoff,
+    decode: fn(&str, &str) -> Result<T, Error>,
+    response: Option<reqwest::Response>,
+    parser: SseParser,
+    pending: VecDeque<RawEvent>,
+    failed_attempts: usize,
+    terminated: bool,
+}
+
+impl<T> Subscription<T> {
+    async fn connect(&self) -> Result<reqwest::Response, Error> {
+        let response = self
+            .http
+            .get(self.target.clone())
+            .header(ACCEPT, EVENT_STREAM_MIME_TYPE)
+            .send()
+            .await?;
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            let api_err = response.json::<ApiError>().await?;
+            Err(Error::Api(api_err))
+        }
+    }
+
+    async fn next(&mut self) -> Option<Result<T, Error>> {
+        loop {
+            if self.terminated {
+                return None
+            }
+            if let Some(event) = self.pending.pop_front() {
+                return Some((self.decode)(&event.name, &event.data))
+            }
+            let Some(response) = self.response.as_mut() else {
+                if self.failed_attempts > 0 {
+                    tokio::time::sleep(self.backoff.delay(self.failed_attempts - 1)).await;
+                }
+                match self.connect().await {
+                    Ok(response) => {
+                        self.response = Some(response);
+                        self.failed_attempts = 0;
+                    }
+                    Err(err) => {
+                        self.failed_attempts += 1;
+                        let exhausted = self
+                            .backoff
+                            .max_retries
+                            .map(|max_retries| self.failed_attempts > max_retries)
+                            .unwrap_or_default();
+                        if exhausted {
+                            self.terminated = true;
+                            return Some(Err(err))
+                        }
+                        warn!(%err, target = %self.target, "could not connect to event stream");
+                    }
+                }
+                continue
+            };
+            match response.chunk().await {
+                Ok(Some(chunk)) => self.parser.feed(&chunk, &mut self.pending),
+                result => {
+                    if let Err(err) = result {
+                        warn!(%err, target = %self.target, "event stream dropped; reconnecting");
+                    }
+                    // NOTE: any partially received event is discarded with the connection
+                    self.response = None;
+                    self.parser = SseParser::default();
+                    self.failed_attempts = 1;
+                }
+            }
+        }
+    }
+}
+
+/// A stream of events from the beacon node that reconnects when the connection drops.
+///
+/// Items that fail to decode are yielded as errors without ending the stream.
+pub struct EventStream<T> {
+    inner: Pin<Box<dyn Stream<Item = Result<T, Error>> + Send>>,
+}
+
+impl<T: Send + 'static> EventStream<T> {
+    pub(crate) fn new(
+        http: reqwest::Client,
+        target: Url,
+        backoff: Backoff,
+        decode: fn(&str, &str) -> Result<T, Error>,
+    ) -> Self {
+        let subscription = Subscription {
+            http,
+            target,
+            backoff,
+            decode,
+            response: None,
+            parser: SseParser::default(),
+            pending: VecDeque::new(),
+            failed_attempts: 0,
+            terminated: false,
+        };
+        let inner = stream::unfold(subscription, |mut subscription| async move {
+            subscription.next().await.map(|item| (item, subscription))
+        });
+        Self { inner: Box::pin(inner) }
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_parser() {
+        let mut parser = SseParser::default();
+        let mut events = VecDeque::new();
+        parser.feed(b": keep-alive\n\nevent: head\ndata: {\"slot\"", &mut events);
+        assert!(events.is_empty());
+        parser.feed(b":\"1\"}\r\n\r\nevent:block\ndata:a\ndata: b\n\n", &mut events);
+        assert_eq!(
+            events.pop_front(),
+            Some(RawEvent { name: "head".to_string(), data: "{\"slot\":\"1\"}".to_string() })
+        );
+        assert_eq!(
+            events.pop_front(),
+            Some(RawEvent { name: "block".to_string(), data: "a\nb".to_string() })
+        );
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_backoff() {
+        let backoff = Backoff::default();
+        assert_eq!(backoff.delay(0), Duration::from_millis(500));
+        assert_eq!(backoff.delay(2), Duration::from_secs(2));
+        assert_eq!(backoff.delay(100), Duration::from_secs(30));
+    }
+
+    // One event of each topic, in the order of `ALL_TOPICS`.
+    fn events() -> Vec<Event<MainnetClientTypes>> {
+        use crate::{
+            mainnet,
+            types::{PayloadAttributes, PayloadAttributesEvent},
+        };
+        use ethereum_consensus::Fork;
+        use std::collections::HashMap;
+
+        let versioned = |version, data| VersionedValue { version, data, meta: HashMap::new() };
+        let payload_attributes = PayloadAttributesEvent {
+            proposer_index: 7,
+            proposal_slot: 32,
+            parent_block_number: 10,
+            parent_block_root: Default::default(),
+            parent_block_hash: Default::default(),
+            payload_attributes: PayloadAttributes {
+                timestamp: 12,
+                prev_randao: Default::default(),
+                suggested_fee_recipient: Default::default(),
+                withdrawals: Some(vec![]),
+                parent_beacon_block_root: None,
+            },
+        };
+        vec![
+            Event::Head(HeadEvent {
+                slot: 32,
+                block: Default::default(),
+                state: Default::default(),
+                epoch_transition: true,
+                previous_duty_dependent_root: Default::default(),
+                current_duty_dependent_root: Default::default(),
+                execution_optimistic: false,
+            }),
+            Event::Block(BlockEvent {
+                slot: 32,
+                block: Default::default(),
+                execution_optimistic: false,
+            }),
+            Event::BlockGossip(BlockGossipEvent { slot: 32, block: Default::default() }),
+            Event::Attestation(mainnet::Attestation::Electra(Default::default())),
+            Event::SingleAttestation(SingleAttestation {
+                committee_index: 1,
+                attester_index: 7,
+                data: Default::default(),
+                signature: Default::default(),
+            }),
+            Event::VoluntaryExit(Default::default()),
+            Event::BlsToExecutionChange(Default::default()),
+            Event::ProposerSlashing(Default::default()),
+            Event::AttesterSlashing(mainnet::AttesterSlashing::Electra(Default::default())),
+            Event::FinalizedCheckpoint(FinalizedCheckpointEvent {
+                block: Default::default(),
+                state: Default::default(),
+                epoch: 1,
+                execution_optimistic: false,
+            }),
+            Event::ChainReorg(ChainReorgEvent {
+                slot: 32,
+                depth: 2,
+                old_head_block: Default::default(),
+                new_head_block: Default::default(),
+                old_head_state: Default::default(),
+                new_head_state: Default::default(),
+                epoch: 1,
+                execution_optimistic: false,
+            }),
+            Event::ContributionAndProof(Default::default()),
+            Event::LightClientFinalityUpdate(versioned(
+                Fork::Deneb,
+                mainnet::LightClientFinalityUpdate::Deneb(Default::default()),
+            )),
+            Event::LightClientOptimisticUpdate(versioned(
+                Fork::Deneb,
+                mainnet::LightClientOptimisticUpdate::Deneb(Default::default()),
+            )),
+            Event::PayloadAttributes(versioned(Fork::Capella, payload_attributes)),
+            Event::BlobSidecar(BlobSidecarEvent {
+                block_root: Default::default(),
+                index: 1,
+                slot: 32,
+                kzg_commitment: Default::default(),
+                versioned_hash: Default::default(),
+            }),
+            Event::DataColumnSidecar(DataColumnSidecarEvent {
+                block_root: Default::default(),
+                index: 3,
+                slot: 32,
+                kzg_commitments: vec![Default::default()],
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_event_decode() {
+        let events = events();
+        assert_eq!(events.iter().map(Event::topic).collect::<Vec<_>>(), ALL_TOPICS);
+        for event in events {
+            let data = event.data_to_json().unwrap();
+            let decoded =
+                Event::<MainnetClientTypes>::decode(event.topic(), &data.to_string()).unwrap();
+            assert_eq!(decoded.topic(), event.topic());
+            assert_eq!(decoded.data_to_json().unwrap(), data);
+        }
+        assert!(matches!(
+            Event::<MainnetClientTypes>::decode("foo", "{}"),
+            Err(Error::UnknownTopic(topic)) if topic == "foo"
+        ));
+    }
+
+    #[cfg(feature = "server")]
+    mod server {
+        use super::*;
+        use crate::{mainnet, BeaconNodeProvider, MockBeaconNode};
+        use futures_util::StreamExt;
+        use tokio::{
+            sync::mpsc,
+            time::{sleep, timeout, Instant},
+        };
+
+        struct Provider;
+
+        impl BeaconNodeProvider<MainnetClientTypes> for Provider {}
+
+        #[tokio::test]
+        async fn test_event_stream() {
+            let node = MockBeaconNode::<MainnetClientTypes, _>::new(Provider);
+            let server = node.spawn(([127, 0, 0, 1], 0).into()).unwrap();
+            let backoff = Backoff {
+                initial_delay: Duration::from_millis(200),
+                max_delay: Duration::from_secs(1),
+                max_retries: Some(0),
+            };
+            let client =
+                mainnet::Client::new(server.endpoint()).with_event_backoff(backoff.clone());
+            let mut stream = client.get_events_for_topics(ALL_TOPICS).await.unwrap();
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+            tokio::spawn(async move {
+                while let Some(event) = stream.next().await {
+                    if sender.send(event).is_err() {
+                        break
+                    }
+                }
+            });
+            while node.event_subscribers() == 0 {
+                sleep(Duration::from_millis(10)).await;
+            }
+
+            for event in events() {
+                node.emit(&event).unwrap();
+                let received = receiver.recv().await.unwrap().unwrap();
+                assert_eq!(received.topic(), event.topic());
+                assert_eq!(received.data_to_json().unwrap(), event.data_to_json().unwrap());
+            }
+
+            // the stream resubscribes after the initial delay once the node drops it
+            node.close_event_streams();
+            let dropped = Instant::now();
+            let event = events().swap_remove(0);
+            let received = loop {
+                // NOTE: events sent before the old subscription ends are lost with it
+                node.emit(&event).unwrap();
+                if let Ok(received) = timeout(Duration::from_millis(10), receiver.recv()).await {
+                    break received.unwrap().unwrap()
+                }
+            };
+            assert!(dropped.elapsed() >= backoff.initial_delay);
+            assert_eq!(received.topic(), HeadTopic::NAME);
+        }
+    }
+}
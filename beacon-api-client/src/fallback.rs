@@ -0,0 +1,987 @@
+//! A client over several beacon nodes that fails over between them.
+use crate::{
+    api_client::{Client, ClientTypes},
+    events::{Event, EventStream},
+    types::{
+        AttestationDuty, AttestationRewards, BalanceSummary, BeaconHeaderSummary,
+        BeaconProposerRegistration, BlockContents, BlockId, BlockProposal, BlockRewards,
+        BroadcastValidation, CommitteeDescriptor, CommitteeFilter, CommitteeSummary,
+        ConnectionOrientation, CoordinateWithMetadata, DepositContract, DepositSnapshot,
+        FinalityCheckpoints, GenesisDetails, HealthStatus, NetworkIdentity, PeerDescription,
+        PeerState, PeerSummary, PendingDeposit, ProposerDuty, PublicKeyOrIndex, SingleAttestation,
+        StateId, SubmitSignedBeaconBlock, SyncCommitteeDescriptor, SyncCommitteeDuty,
+        SyncCommitteeReward, SyncCommitteeSummary, SyncStatus, Topic, ValidatorLiveness,
+        ValidatorStatus, ValidatorSummary,
+    },
+    Error,
+};
+use ethereum_consensus::{
+    altair::SyncCommitteeMessage,
+    builder::SignedValidatorRegistration,
+    capella::{SignedBlsToExecutionChange, Withdrawal},
+    electra::{PendingConsolidation, PendingPartialWithdrawal},
+    networking::PeerId,
+    phase0::{AttestationData, Fork, ProposerSlashing, SignedVoluntaryExit},
+    primitives::{
+        BlobIndex, Bytes32, CommitteeIndex, Epoch, RandaoReveal, Root, Slot, ValidatorIndex,
+    },
+    Fork as Version,
+};
+use futures_util::future::join_all;
+use http::StatusCode;
+use std::{collections::HashMap, fmt, future::Future, sync::RwLock};
+use tracing::warn;
+use url::Url;
+
+/// The status of an endpoint as of its last health check.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EndpointStatus {
+    /// The node is healthy and not syncing.
+    Synced,
+    /// The node has not been checked yet.
+    #[default]
+    Unknown,
+    /// The node is reachable but syncing or not yet initialized.
+    Syncing,
+    /// The node could not be reached or returned an error.
+    Offline,
+}
+
+/// An error returned by a specific endpoint.
+#[derive(Debug)]
+pub struct EndpointError {
+    pub endpoint: Url,
+    pub error: Error,
+}
+
+impl fmt::Display for EndpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.endpoint, self.error)
+    }
+}
+
+// Errors caused by the request itself, which every endpoint would return.
+fn is_request_error(error: &Error) -> bool {
+    match error {
+        Error::Api(err) => err.code().is_client_error() && err.code() != StatusCode::NOT_FOUND,
+        _ => false,
+    }
+}
+
+/// A client over several beacon nodes exposing the same methods as [`Client`].
+///
+/// Reads are sent to endpoints in order of their last known status (synced nodes first,
+/// preserving the configured order among nodes with the same status), moving to the next endpoint
+/// on failure. Writes are either handled the same way or, if `broadcast` is set, sent to every
+/// endpoint, succeeding if any endpoint accepted them. If every endpoint fails, the error of each
+/// is returned in [`Error::AllEndpointsFailed`].
+///
+/// Endpoint statuses are refreshed with [`FallbackClient::check_health`], which callers are
+/// expected to run periodically; an endpoint that fails to respond to a request is also marked
+/// offline until its next health check.
+pub struct FallbackClient<C> {
+    clients: Vec<Client<C>>,
+    statuses: RwLock<Vec<EndpointStatus>>,
+    broadcast: bool,
+}
+
+impl<C: ClientTypes> FallbackClient<C> {
+    pub fn new(clients: Vec<Client<C>>) -> Self {
+        let statuses = RwLock::new(vec![EndpointStatus::default(); clients.len()]);
+        Self { clients, statuses, broadcast: false }
+    }
+
+    pub fn from_endpoints<U: Into<Url>>(endpoints: impl IntoIterator<Item = U>) -> Self {
+        Self::new(endpoints.into_iter().map(Client::new).collect())
+    }
+
+    /// Sets whether writes (e.g. publishing blocks and attestations) are sent to every endpoint.
+    pub fn with_broadcast(mut self, broadcast: bool) -> Self {
+        self.broadcast = broadcast;
+        self
+    }
+
+    pub fn clients(&self) -> &[Client<C>] {
+        &self.clients
+    }
+
+    /// Returns each endpoint with its status as of the last health check.
+    pub fn statuses(&self) -> Vec<(Url, EndpointStatus)> {
+        let statuses = self.statuses.read().unwrap();
+        self.clients
+            .iter()
+            .map(|client| client.endpoint.clone())
+            .zip(statuses.iter().copied())
+            .collect()
+    }
+
+    /// Queries the health and sync status of every endpoint, updating the order in which they are
+    /// used.
+    pub async fn check_health(&self) -> Vec<(Url, EndpointStatus)> {
+        let checks = self.clients.iter().map(|client| async move {
+            match client.get_health().await {
+                Ok(HealthStatus::Ready) => match client.get_sync_status().await {
+                    Ok(status) if !status.is_syncing => EndpointStatus::Synced,
+                    Ok(_) => EndpointStatus::Syncing,
+                    Err(_) => EndpointStatus::Offline,
+                },
+                Ok(HealthStatus::Syncing | HealthStatus::NotInitialized) => EndpointStatus::Syncing,
+                Ok(HealthStatus::Unknown) | Err(_) => EndpointStatus::Offline,
+            }
+        });
+        let statuses = join_all(checks).await;
+        *self.statuses.write().unwrap() = statuses;
+        self.statuses()
+    }
+
+    fn mark_offline(&self, index: usize) {
+        self.statuses.write().unwrap()[index] = EndpointStatus::Offline;
+    }
+
+    // The preferred endpoint, if any are configured.
+    fn preferred_client(&self) -> Result<&Client<C>, Error> {
+        let index = self.ordered_indices().first().copied().ok_or(Error::NoEndpoints)?;
+        Ok(&self.clients[index])
+    }
+
+    // Indices of the clients in the order they should be tried.
+    fn ordered_indices(&self) -> Vec<usize> {
+        let statuses = self.statuses.read().unwrap();
+        let mut indices = (0..self.clients.len()).collect::<Vec<_>>();
+        indices.sort_by_key(|&index| statuses[index]);
+        indices
+    }
+
+    /// Runs `f` against each endpoint in turn until one succeeds.
+    ///
+    /// Client errors returned by the API (e.g. for an invalid request) are returned immediately
+    /// as every endpoint would be expected to respond in the same way. Server errors and
+    /// `404 Not Found` (e.g. for data a lagging node does not have yet) move on to the next
+    /// endpoint.
+    pub async fn request<'a, T, F, Fut>(&'a self, f: F) -> Result<T, Error>
+    where
+        F: Fn(&'a Client<C>) -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        let mut errors = vec![];
+        for index in self.ordered_indices() {
+            let client = &self.clients[index];
+            match f(client).await {
+                Ok(value) => return Ok(value),
+                Err(error) if is_request_error(&error) => return Err(error),
+                Err(error) => {
+                    if !matches!(error, Error::Api(..)) {
+                        self.mark_offline(index);
+                    }
+                    errors.push(EndpointError { endpoint: client.endpoint.clone(), error });
+                }
+            }
+        }
+        Err(Error::AllEndpointsFailed(errors))
+    }
+
+    /// Runs `f` against every endpoint concurrently, succeeding if any endpoint succeeds.
+    pub async fn broadcast<'a, F, Fut>(&'a self, f: F) -> Result<(), Error>
+    where
+        F: Fn(&'a Client<C>) -> Fut,
+        Fut: Future<Output = Result<(), Error>>,
+    {
+        let results = join_all(self.clients.iter().map(&f)).await;
+        let mut succeeded = false;
+        let mut errors = vec![];
+        for (index, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(()) => succeeded = true,
+                Err(error) => {
+                    if !matches!(error, Error::Api(..)) {
+                        self.mark_offline(index);
+                    }
+                    let endpoint = self.clients[index].endpoint.clone();
+                    errors.push(EndpointError { endpoint, error });
+                }
+            }
+        }
+        if succeeded {
+            for err in errors {
+                warn!(%err, "endpoint failed to accept broadcast request");
+            }
+            Ok(())
+        } else {
+            Err(Error::AllEndpointsFailed(errors))
+        }
+    }
+
+    // Writes are broadcast if configured, otherwise handled like reads.
+    async fn publish<'a, F, Fut>(&'a self, f: F) -> Result<(), Error>
+    where
+        F: Fn(&'a Client<C>) -> Fut,
+        Fut: Future<Output = Result<(), Error>>,
+    {
+        if self.broadcast {
+            self.broadcast(f).await
+        } else {
+            self.request(f).await
+        }
+    }
+
+    pub async fn get<T: serde::Serialize + serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<T, Error> {
+        self.request(move |client| client.get(path)).await
+    }
+
+    pub async fn post<T: serde::Serialize + ?Sized>(
+        &self,
+        path: &str,
+        argument: &T,
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post(path, argument)).await
+    }
+
+    pub async fn get_genesis_details(&self) -> Result<GenesisDetails, Error> {
+        self.request(|client| client.get_genesis_details()).await
+    }
+
+    pub async fn get_state_root(&self, state_id: StateId) -> Result<Root, Error> {
+        self.request(move |client| client.get_state_root(state_id.clone())).await
+    }
+
+    pub async fn get_fork(&self, state_id: StateId) -> Result<Fork, Error> {
+        self.request(move |client| client.get_fork(state_id.clone())).await
+    }
+
+    pub async fn get_finality_checkpoints(
+        &self,
+        id: StateId,
+    ) -> Result<FinalityCheckpoints, Error> {
+        self.request(move |client| client.get_finality_checkpoints(id.clone())).await
+    }
+
+    pub async fn get_validators(
+        &self,
+        state_id: StateId,
+        validator_ids: &[PublicKeyOrIndex],
+        filters: &[ValidatorStatus],
+    ) -> Result<Vec<ValidatorSummary>, Error> {
+        self.request(move |client| client.get_validators(state_id.clone(), validator_ids, filters))
+            .await
+    }
+
+    pub async fn get_validator(
+        &self,
+        state_id: StateId,
+        validator_id: PublicKeyOrIndex,
+    ) -> Result<ValidatorSummary, Error> {
+        self.request(move |client| client.get_validator(state_id.clone(), validator_id.clone()))
+            .await
+    }
+
+    pub async fn get_balances(
+        &self,
+        id: StateId,
+        filters: &[PublicKeyOrIndex],
+    ) -> Result<Vec<BalanceSummary>, Error> {
+        self.request(move |client| client.get_balances(id.clone(), filters)).await
+    }
+
+    pub async fn get_all_committees(&self, id: StateId) -> Result<Vec<CommitteeSummary>, Error> {
+        self.request(move |client| client.get_all_committees(id.clone())).await
+    }
+
+    pub async fn get_committees(
+        &self,
+        id: StateId,
+        filter: CommitteeFilter,
+    ) -> Result<Vec<CommitteeSummary>, Error> {
+        self.request(move |client| client.get_committees(id.clone(), filter.clone())).await
+    }
+
+    pub async fn get_sync_committees(
+        &self,
+        id: StateId,
+        epoch: Option<Epoch>,
+    ) -> Result<SyncCommitteeSummary, Error> {
+        self.request(move |client| client.get_sync_committees(id.clone(), epoch)).await
+    }
+
+    pub async fn get_randao(&self, id: StateId, epoch: Option<Epoch>) -> Result<Bytes32, Error> {
+        self.request(move |client| client.get_randao(id.clone(), epoch)).await
+    }
+
+    pub async fn get_pending_deposits(&self, id: StateId) -> Result<Vec<PendingDeposit>, Error> {
+        self.request(move |client| client.get_pending_deposits(id.clone())).await
+    }
+
+    pub async fn get_pending_partial_withdrawals(
+        &self,
+        id: StateId,
+    ) -> Result<Vec<PendingPartialWithdrawal>, Error> {
+        self.request(move |client| client.get_pending_partial_withdrawals(id.clone())).await
+    }
+
+    pub async fn get_pending_consolidations(
+        &self,
+        id: StateId,
+    ) -> Result<Vec<PendingConsolidation>, Error> {
+        self.request(move |client| client.get_pending_consolidations(id.clone())).await
+    }
+
+    pub async fn get_beacon_header_at_head(&self) -> Result<BeaconHeaderSummary, Error> {
+        self.request(|client| client.get_beacon_header_at_head()).await
+    }
+
+    pub async fn get_beacon_header_for_slot(
+        &self,
+        slot: Slot,
+    ) -> Result<BeaconHeaderSummary, Error> {
+        self.request(move |client| client.get_beacon_header_for_slot(slot)).await
+    }
+
+    pub async fn get_beacon_header_for_parent_root(
+        &self,
+        parent_root: Root,
+    ) -> Result<BeaconHeaderSummary, Error> {
+        self.request(move |client| client.get_beacon_header_for_parent_root(parent_root)).await
+    }
+
+    pub async fn get_beacon_header(&self, id: BlockId) -> Result<BeaconHeaderSummary, Error> {
+        self.request(move |client| client.get_beacon_header(id.clone())).await
+    }
+
+    pub async fn post_signed_blinded_beacon_block(
+        &self,
+        block: &C::SignedBlindedBeaconBlock,
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post_signed_blinded_beacon_block(block)).await
+    }
+
+    pub async fn post_signed_blinded_beacon_block_v2(
+        &self,
+        block: &C::SignedBlindedBeaconBlock,
+        version: Version,
+        broadcast_validation: Option<BroadcastValidation>,
+    ) -> Result<(), Error> {
+        self.publish(move |client| {
+            client.post_signed_blinded_beacon_block_v2(block, version, broadcast_validation)
+        })
+        .await
+    }
+
+    pub async fn post_signed_beacon_block(
+        &self,
+        block: &C::SignedBeaconBlock,
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post_signed_beacon_block(block)).await
+    }
+
+    pub async fn post_signed_beacon_block_v2(
+        &self,
+        request: SubmitSignedBeaconBlock<'_, C::SignedBeaconBlock, C::Blob>,
+        version: Version,
+        broadcast_validation: Option<BroadcastValidation>,
+    ) -> Result<(), Error> {
+        self.publish(move |client| {
+            client.post_signed_beacon_block_v2(request, version, broadcast_validation)
+        })
+        .await
+    }
+
+    pub async fn get_beacon_block(&self, id: BlockId) -> Result<C::SignedBeaconBlock, Error> {
+        self.request(move |client| client.get_beacon_block(id.clone())).await
+    }
+
+    pub async fn get_beacon_block_root(&self, id: BlockId) -> Result<Root, Error> {
+        self.request(move |client| client.get_beacon_block_root(id.clone())).await
+    }
+
+    pub async fn get_attestations_from_beacon_block(
+        &self,
+        id: BlockId,
+    ) -> Result<Vec<C::Attestation>, Error> {
+        self.request(move |client| client.get_attestations_from_beacon_block(id.clone())).await
+    }
+
+    pub async fn get_blob_sidecars(
+        &self,
+        id: BlockId,
+        indices: &[BlobIndex],
+    ) -> Result<Vec<C::BlobSidecar>, Error> {
+        self.request(move |client| client.get_blob_sidecars(id.clone(), indices)).await
+    }
+
+    pub async fn get_deposit_snapshot(&self) -> Result<DepositSnapshot, Error> {
+        self.request(|client| client.get_deposit_snapshot()).await
+    }
+
+    pub async fn get_blinded_block(
+        &self,
+        id: BlockId,
+    ) -> Result<C::SignedBlindedBeaconBlock, Error> {
+        self.request(move |client| client.get_blinded_block(id.clone())).await
+    }
+
+    pub async fn get_light_client_bootstrap(
+        &self,
+        block: Root,
+    ) -> Result<C::LightClientBootstrap, Error> {
+        self.request(move |client| client.get_light_client_bootstrap(block)).await
+    }
+
+    pub async fn get_light_client_updates(
+        &self,
+        start: u64,
+        count: u64,
+    ) -> Result<Vec<C::LightClientUpdate>, Error> {
+        self.request(move |client| client.get_light_client_updates(start, count)).await
+    }
+
+    pub async fn get_light_client_finality_update(
+        &self,
+    ) -> Result<C::LightClientFinalityUpdate, Error> {
+        self.request(|client| client.get_light_client_finality_update()).await
+    }
+
+    pub async fn get_light_client_optimistic_update(
+        &self,
+    ) -> Result<C::LightClientOptimisticUpdate, Error> {
+        self.request(|client| client.get_light_client_optimistic_update()).await
+    }
+
+    pub async fn get_block_rewards(&self, block_id: BlockId) -> Result<BlockRewards, Error> {
+        self.request(move |client| client.get_block_rewards(block_id.clone())).await
+    }
+
+    pub async fn get_attestation_rewards(
+        &self,
+        epoch: Epoch,
+        validator_ids: &[PublicKeyOrIndex],
+    ) -> Result<AttestationRewards, Error> {
+        self.request(move |client| client.get_attestation_rewards(epoch, validator_ids)).await
+    }
+
+    pub async fn get_sync_committee_rewards(
+        &self,
+        block_id: BlockId,
+        validator_ids: &[PublicKeyOrIndex],
+    ) -> Result<Vec<SyncCommitteeReward>, Error> {
+        self.request(move |client| {
+            client.get_sync_committee_rewards(block_id.clone(), validator_ids)
+        })
+        .await
+    }
+
+    pub async fn get_attestations_from_pool(
+        &self,
+        slot: Option<Slot>,
+        committee_index: Option<CommitteeIndex>,
+    ) -> Result<Vec<C::Attestation>, Error> {
+        self.request(move |client| client.get_attestations_from_pool(slot, committee_index)).await
+    }
+
+    pub async fn get_attestations_from_pool_v2(
+        &self,
+        slot: Option<Slot>,
+        committee_index: Option<CommitteeIndex>,
+    ) -> Result<Vec<C::Attestation>, Error> {
+        self.request(move |client| client.get_attestations_from_pool_v2(slot, committee_index))
+            .await
+    }
+
+    pub async fn post_attestations(&self, attestations: &[C::Attestation]) -> Result<(), Error> {
+        self.publish(move |client| client.post_attestations(attestations)).await
+    }
+
+    pub async fn post_single_attestations(
+        &self,
+        version: Version,
+        attestations: &[SingleAttestation],
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post_single_attestations(version, attestations)).await
+    }
+
+    pub async fn get_attester_slashings_from_pool(
+        &self,
+    ) -> Result<Vec<C::AttesterSlashing>, Error> {
+        self.request(|client| client.get_attester_slashings_from_pool()).await
+    }
+
+    pub async fn get_attester_slashings_from_pool_v2(
+        &self,
+    ) -> Result<Vec<C::AttesterSlashing>, Error> {
+        self.request(|client| client.get_attester_slashings_from_pool_v2()).await
+    }
+
+    pub async fn post_attester_slashing(
+        &self,
+        attester_slashing: &C::AttesterSlashing,
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post_attester_slashing(attester_slashing)).await
+    }
+
+    pub async fn post_attester_slashing_v2(
+        &self,
+        version: Version,
+        attester_slashing: &C::AttesterSlashing,
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post_attester_slashing_v2(version, attester_slashing))
+            .await
+    }
+
+    pub async fn get_proposer_slashings_from_pool(&self) -> Result<Vec<ProposerSlashing>, Error> {
+        self.request(|client| client.get_proposer_slashings_from_pool()).await
+    }
+
+    pub async fn post_proposer_slashing(
+        &self,
+        proposer_slashing: &ProposerSlashing,
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post_proposer_slashing(proposer_slashing)).await
+    }
+
+    pub async fn post_sync_committee_messages(
+        &self,
+        messages: &[SyncCommitteeMessage],
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post_sync_committee_messages(messages)).await
+    }
+
+    pub async fn get_voluntary_exits_from_pool(&self) -> Result<Vec<SignedVoluntaryExit>, Error> {
+        self.request(|client| client.get_voluntary_exits_from_pool()).await
+    }
+
+    pub async fn post_signed_voluntary_exit(
+        &self,
+        exit: &SignedVoluntaryExit,
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post_signed_voluntary_exit(exit)).await
+    }
+
+    pub async fn get_bls_to_execution_changes(
+        &self,
+    ) -> Result<Vec<SignedBlsToExecutionChange>, Error> {
+        self.request(|client| client.get_bls_to_execution_changes()).await
+    }
+
+    pub async fn post_bls_to_execution_changes(
+        &self,
+        changes: &[SignedBlsToExecutionChange],
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post_bls_to_execution_changes(changes)).await
+    }
+
+    pub async fn get_expected_withdrawals(
+        &self,
+        id: StateId,
+        slot: Option<Slot>,
+    ) -> Result<Vec<Withdrawal>, Error> {
+        self.request(move |client| client.get_expected_withdrawals(id.clone(), slot)).await
+    }
+
+    pub async fn get_fork_schedule(&self) -> Result<Vec<Fork>, Error> {
+        self.request(|client| client.get_fork_schedule()).await
+    }
+
+    pub async fn get_spec(&self) -> Result<HashMap<String, String>, Error> {
+        self.request(|client| client.get_spec()).await
+    }
+
+    pub async fn get_deposit_contract_address(&self) -> Result<DepositContract, Error> {
+        self.request(|client| client.get_deposit_contract_address()).await
+    }
+
+    pub async fn get_state(&self, id: StateId) -> Result<C::BeaconState, Error> {
+        self.request(move |client| client.get_state(id.clone())).await
+    }
+
+    pub async fn get_heads(&self) -> Result<Vec<CoordinateWithMetadata>, Error> {
+        self.request(|client| client.get_heads()).await
+    }
+
+    pub async fn get_node_identity(&self) -> Result<NetworkIdentity, Error> {
+        self.request(|client| client.get_node_identity()).await
+    }
+
+    pub async fn get_node_peers(
+        &self,
+        peer_states: &[PeerState],
+        connection_orientations: &[ConnectionOrientation],
+    ) -> Result<Vec<PeerDescription>, Error> {
+        self.request(move |client| client.get_node_peers(peer_states, connection_orientations))
+            .await
+    }
+
+    pub async fn get_peer(&self, peer_id: PeerId) -> Result<PeerDescription, Error> {
+        self.request(move |client| client.get_peer(peer_id)).await
+    }
+
+    pub async fn get_peer_summary(&self) -> Result<PeerSummary, Error> {
+        self.request(|client| client.get_peer_summary()).await
+    }
+
+    pub async fn get_node_version(&self) -> Result<String, Error> {
+        self.request(|client| client.get_node_version()).await
+    }
+
+    pub async fn get_sync_status(&self) -> Result<SyncStatus, Error> {
+        self.request(|client| client.get_sync_status()).await
+    }
+
+    pub async fn get_health(&self) -> Result<HealthStatus, Error> {
+        self.request(|client| client.get_health()).await
+    }
+
+    pub async fn get_attester_duties(
+        &self,
+        epoch: Epoch,
+        indices: &[ValidatorIndex],
+    ) -> Result<(Root, Vec<AttestationDuty>), Error> {
+        self.request(move |client| client.get_attester_duties(epoch, indices)).await
+    }
+
+    pub async fn get_proposer_duties(
+        &self,
+        epoch: Epoch,
+    ) -> Result<(Root, Vec<ProposerDuty>), Error> {
+        self.request(move |client| client.get_proposer_duties(epoch)).await
+    }
+
+    pub async fn get_sync_committee_duties(
+        &self,
+        epoch: Epoch,
+        indices: &[ValidatorIndex],
+    ) -> Result<Vec<SyncCommitteeDuty>, Error> {
+        self.request(move |client| client.get_sync_committee_duties(epoch, indices)).await
+    }
+
+    pub async fn get_block_proposal(
+        &self,
+        slot: Slot,
+        randao_reveal: RandaoReveal,
+        graffiti: Option<Bytes32>,
+    ) -> Result<BlockContents<C::BeaconBlock, C::Blob>, Error> {
+        self.request(move |client| {
+            client.get_block_proposal(slot, randao_reveal.clone(), graffiti.clone())
+        })
+        .await
+    }
+
+    pub async fn get_block_proposal_v3(
+        &self,
+        slot: Slot,
+        randao_reveal: RandaoReveal,
+        graffiti: Option<Bytes32>,
+        builder_boost_factor: Option<u64>,
+    ) -> Result<BlockProposal<C::BeaconBlock, C::BlindedBeaconBlock, C::Blob>, Error> {
+        self.request(move |client| {
+            client.get_block_proposal_v3(
+                slot,
+                randao_reveal.clone(),
+                graffiti.clone(),
+                builder_boost_factor,
+            )
+        })
+        .await
+    }
+
+    pub async fn get_blinded_block_proposal(
+        &self,
+        slot: Slot,
+        randao_reveal: RandaoReveal,
+        graffiti: Option<Bytes32>,
+    ) -> Result<C::BlindedBeaconBlock, Error> {
+        self.request(move |client| {
+            client.get_blinded_block_proposal(slot, randao_reveal.clone(), graffiti.clone())
+        })
+        .await
+    }
+
+    pub async fn get_attestation_data(
+        &self,
+        slot: Slot,
+        committee_index: CommitteeIndex,
+    ) -> Result<AttestationData, Error> {
+        self.request(move |client| client.get_attestation_data(slot, committee_index)).await
+    }
+
+    pub async fn get_attestation_aggregate(
+        &self,
+        attestation_data_root: Root,
+        slot: Slot,
+    ) -> Result<C::Attestation, Error> {
+        self.request(move |client| client.get_attestation_aggregate(attestation_data_root, slot))
+            .await
+    }
+
+    pub async fn get_attestation_aggregate_v2(
+        &self,
+        attestation_data_root: Root,
+        slot: Slot,
+        committee_index: CommitteeIndex,
+    ) -> Result<C::Attestation, Error> {
+        self.request(move |client| {
+            client.get_attestation_aggregate_v2(attestation_data_root, slot, committee_index)
+        })
+        .await
+    }
+
+    pub async fn post_aggregates_with_proofs(
+        &self,
+        aggregates_with_proofs: &[C::SignedAggregateAndProof],
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.post_aggregates_with_proofs(aggregates_with_proofs)).await
+    }
+
+    pub async fn post_aggregates_with_proofs_v2(
+        &self,
+        version: Version,
+        aggregates_with_proofs: &[C::SignedAggregateAndProof],
+    ) -> Result<(), Error> {
+        self.publish(move |client| {
+            client.post_aggregates_with_proofs_v2(version, aggregates_with_proofs)
+        })
+        .await
+    }
+
+    pub async fn subscribe_subnets_for_attestation_committees(
+        &self,
+        committee_descriptors: &[CommitteeDescriptor],
+    ) -> Result<(), Error> {
+        self.publish(move |client| {
+            client.subscribe_subnets_for_attestation_committees(committee_descriptors)
+        })
+        .await
+    }
+
+    pub async fn subscribe_subnets_for_sync_committees(
+        &self,
+        sync_committee_descriptors: &[SyncCommitteeDescriptor],
+    ) -> Result<(), Error> {
+        self.publish(move |client| {
+            client.subscribe_subnets_for_sync_committees(sync_committee_descriptors)
+        })
+        .await
+    }
+
+    pub async fn get_sync_committee_contribution(
+        &self,
+        slot: Slot,
+        subcommittee_index: usize,
+        beacon_block_root: Root,
+    ) -> Result<C::SyncCommitteeContribution, Error> {
+        self.request(move |client| {
+            client.get_sync_committee_contribution(slot, subcommittee_index, beacon_block_root)
+        })
+        .await
+    }
+
+    pub async fn post_sync_committee_contributions_with_proofs(
+        &self,
+        contributions_with_proofs: &[C::SignedContributionAndProof],
+    ) -> Result<(), Error> {
+        self.publish(move |client| {
+            client.post_sync_committee_contributions_with_proofs(contributions_with_proofs)
+        })
+        .await
+    }
+
+    pub async fn prepare_proposers(
+        &self,
+        registrations: &[BeaconProposerRegistration],
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.prepare_proposers(registrations)).await
+    }
+
+    pub async fn register_validators_with_builders(
+        &self,
+        registrations: &[SignedValidatorRegistration],
+    ) -> Result<(), Error> {
+        self.publish(move |client| client.register_validators_with_builders(registrations)).await
+    }
+
+    pub async fn post_liveness(
+        &self,
+        epoch: Epoch,
+        indices: &[ValidatorIndex],
+    ) -> Result<Vec<ValidatorLiveness>, Error> {
+        self.request(move |client| client.post_liveness(epoch, indices)).await
+    }
+
+    // NOTE: event streams reconnect on their own so are opened against the preferred endpoint only
+    pub async fn get_events<T: Topic>(&self) -> Result<EventStream<T::Data>, Error>
+    where
+        T::Data: Send + 'static,
+    {
+        self.preferred_client()?.get_events::<T>().await
+    }
+
+    pub async fn get_events_for_topics(
+        &self,
+        topics: &[&str],
+    ) -> Result<EventStream<Event<C>>, Error>
+    where
+        C: 'static,
+        Event<C>: Send,
+    {
+        self.preferred_client()?.get_events_for_topics(topics).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mainnet::MainnetClientTypes;
+
+    #[test]
+    fn test_endpoint_order() {
+        let endpoints = ["http://a:5052", "http://b:5052", "http://c:5052"]
+            .into_iter()
+            .map(|endpoint| Url::parse(endpoint).unwrap());
+        let client = FallbackClient::<MainnetClientTypes>::from_endpoints(endpoints);
+        assert_eq!(client.ordered_indices(), vec![0, 1, 2]);
+
+        client.mark_offline(0);
+        client.statuses.write().unwrap()[2] = EndpointStatus::Synced;
+        assert_eq!(client.ordered_indices(), vec![2, 1, 0]);
+    }
+
+    #[cfg(feature = "server")]
+    mod server {
+        use super::*;
+        use crate::{mainnet, ApiError, BeaconNodeProvider, MockBeaconNode, MockServer};
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        // responds to every request with `code`, if set, counting submitted attestations
+        #[derive(Clone, Default)]
+        struct Provider {
+            code: Option<StatusCode>,
+            attestations: Arc<AtomicUsize>,
+        }
+
+        impl Provider {
+            fn respond<T>(&self, value: T) -> Result<T, ApiError> {
+                match self.code {
+                    Some(code) => {
+                        Err(ApiError::ErrorMessage { code, message: "mock error".to_string() })
+                    }
+                    None => Ok(value),
+                }
+            }
+        }
+
+        impl BeaconNodeProvider<MainnetClientTypes> for Provider {
+            fn node_version(&self) -> Result<String, ApiError> {
+                self.respond("mock/v1.0.0".to_string())
+            }
+
+            fn submit_attestations(
+                &self,
+                attestations: Vec<mainnet::Attestation>,
+            ) -> Result<(), ApiError> {
+                self.attestations.fetch_add(attestations.len(), Ordering::SeqCst);
+                self.respond(())
+            }
+        }
+
+        fn spawn(codes: &[Option<StatusCode>]) -> (Vec<Provider>, Vec<MockServer>) {
+            let providers = codes
+                .iter()
+                .map(|&code| Provider { code, ..Default::default() })
+                .collect::<Vec<_>>();
+            let servers = providers
+                .iter()
+                .map(|provider| {
+                    let node = MockBeaconNode::<MainnetClientTypes, _>::new(provider.clone());
+                    node.spawn(([127, 0, 0, 1], 0).into()).unwrap()
+                })
+                .collect();
+            (providers, servers)
+        }
+
+        fn fallback_client(servers: &[MockServer]) -> FallbackClient<MainnetClientTypes> {
+            FallbackClient::from_endpoints(servers.iter().map(MockServer::endpoint))
+        }
+
+        #[tokio::test]
+        async fn test_failover() {
+            let codes =
+                [Some(StatusCode::INTERNAL_SERVER_ERROR), Some(StatusCode::NOT_FOUND), None];
+            let (_, servers) = spawn(&codes);
+            let client = fallback_client(&servers);
+            assert_eq!(client.get_node_version().await.unwrap(), "mock/v1.0.0");
+            // the endpoints responded so are not marked offline
+            let statuses = client.statuses();
+            assert!(statuses.iter().all(|(_, status)| *status == EndpointStatus::Unknown));
+
+            let (_, servers) = spawn(&[Some(StatusCode::SERVICE_UNAVAILABLE)]);
+            let err = fallback_client(&servers).get_node_version().await.unwrap_err();
+            match err {
+                Error::AllEndpointsFailed(errors) => {
+                    assert_eq!(errors.len(), 1);
+                    assert!(matches!(
+                        errors[0].error,
+                        Error::Api(ApiError::ErrorMessage {
+                            code: StatusCode::SERVICE_UNAVAILABLE,
+                            ..
+                        })
+                    ));
+                }
+                err => panic!("unexpected error: {err}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_request_error_short_circuits() {
+            let (providers, servers) = spawn(&[Some(StatusCode::BAD_REQUEST), None]);
+            let client = fallback_client(&servers);
+            let err = client.get_node_version().await.unwrap_err();
+            assert!(matches!(
+                err,
+                Error::Api(ApiError::ErrorMessage { code: StatusCode::BAD_REQUEST, .. })
+            ));
+
+            let attestations = vec![mainnet::Attestation::Phase0(Default::default())];
+            let err = client.post_attestations(&attestations).await.unwrap_err();
+            assert!(matches!(err, Error::Api(..)));
+            assert_eq!(providers[0].attestations.load(Ordering::SeqCst), 1);
+            assert_eq!(providers[1].attestations.load(Ordering::SeqCst), 0);
+        }
+
+        #[tokio::test]
+        async fn test_publish_broadcast() {
+            let codes = [Some(StatusCode::INTERNAL_SERVER_ERROR), None, None];
+            let (providers, servers) = spawn(&codes);
+            let attestations = vec![mainnet::Attestation::Phase0(Default::default())];
+
+            // without broadcast, writes stop at the first endpoint to accept them
+            fallback_client(&servers).post_attestations(&attestations).await.unwrap();
+            let counts = || {
+                providers
+                    .iter()
+                    .map(|provider| provider.attestations.load(Ordering::SeqCst))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(counts(), vec![1, 1, 0]);
+
+            let client = fallback_client(&servers).with_broadcast(true);
+            client.post_attestations(&attestations).await.unwrap();
+            assert_eq!(counts(), vec![2, 2, 1]);
+
+            let (_, servers) = spawn(&[Some(StatusCode::BAD_REQUEST); 2]);
+            let client = fallback_client(&servers).with_broadcast(true);
+            let err = client.post_attestations(&attestations).await.unwrap_err();
+            assert!(matches!(err, Error::AllEndpointsFailed(errors) if errors.len() == 2));
+        }
+
+        #[tokio::test]
+        async fn test_no_endpoints() {
+            let client = fallback_client(&[]);
+            assert!(matches!(client.get_node_version().await, Err(Error::AllEndpointsFailed(_))));
+            let result = client.get_events_for_topics(&["head"]).await;
+            assert!(matches!(result, Err(Error::NoEndpoints)));
+        }
+    }
+}
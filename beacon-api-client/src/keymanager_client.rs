@@ -0,0 +1,648 @@
+//! A client for the keymanager API that validator clients serve to their operators.
+use crate::{client_builder::bearer_authorization, ApiError, Error};
+use ethereum_consensus::{
+    phase0::SignedVoluntaryExit,
+    primitives::{BlsPublicKey, Epoch, ExecutionAddress, Root, Slot},
+};
+use http::{
+    header::{HeaderValue, AUTHORIZATION},
+    Method,
+};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// The slashing protection data of a set of validators in the EIP-3076 interchange format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlashingProtectionInterchange {
+    pub metadata: InterchangeMetadata,
+    pub data: Vec<InterchangeData>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterchangeMetadata {
+    #[serde(with = "crate::serde::as_str")]
+    pub interchange_format_version: u64,
+    pub genesis_validators_root: Root,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterchangeData {
+    pub pubkey: BlsPublicKey,
+    pub signed_blocks: Vec<InterchangeBlock>,
+    pub signed_attestations: Vec<InterchangeAttestation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterchangeBlock {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_root: Option<Root>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InterchangeAttestation {
+    #[serde(with = "crate::serde::as_str")]
+    pub source_epoch: Epoch,
+    #[serde(with = "crate::serde::as_str")]
+    pub target_epoch: Epoch,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_root: Option<Root>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreSummary {
+    pub validating_pubkey: BlsPublicKey,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+    #[serde(default)]
+    pub readonly: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    Imported,
+    Duplicate,
+    Error,
+}
+
+/// The outcome of importing a single keystore or remote key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportResult {
+    pub status: ImportStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeleteKeystoreStatus {
+    Deleted,
+    /// The key was not active but slashing protection data for it was found.
+    NotActive,
+    NotFound,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteKeystoreResult {
+    pub status: DeleteKeystoreStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The outcome of deleting keystores, along with the slashing protection data of the deleted
+/// keys so they can be safely imported elsewhere.
+#[derive(Debug, Clone)]
+pub struct DeleteKeystoresResponse {
+    pub results: Vec<DeleteKeystoreResult>,
+    pub slashing_protection: SlashingProtectionInterchange,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteKeySummary {
+    pub pubkey: BlsPublicKey,
+    pub url: String,
+    #[serde(default)]
+    pub readonly: bool,
+}
+
+/// A key held by a remote signer at `url`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemoteKey {
+    pub pubkey: BlsPublicKey,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeleteRemoteKeyStatus {
+    Deleted,
+    NotFound,
+    Error,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteRemoteKeyResult {
+    pub status: DeleteRemoteKeyStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Data<T> {
+    data: T,
+}
+
+#[derive(Serialize)]
+struct ImportKeystoresRequest<'a> {
+    keystores: &'a [String],
+    passwords: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slashing_protection: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DeleteKeysRequest<'a> {
+    pubkeys: &'a [BlsPublicKey],
+}
+
+#[derive(Deserialize)]
+struct DeleteKeystoresResponseJson {
+    data: Vec<DeleteKeystoreResult>,
+    slashing_protection: String,
+}
+
+#[derive(Serialize)]
+struct ImportRemoteKeysRequest<'a> {
+    remote_keys: &'a [RemoteKey],
+}
+
+#[derive(Serialize, Deserialize)]
+struct FeeRecipient {
+    ethaddress: ExecutionAddress,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GasLimit {
+    #[serde(with = "crate::serde::as_str")]
+    gas_limit: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Graffiti {
+    graffiti: String,
+}
+
+// Errors from the keymanager API carry the status code in the response rather than in the body.
+#[derive(Deserialize)]
+struct ErrorMessage {
+    message: String,
+}
+
+async fn keymanager_error(response: reqwest::Response) -> Error {
+    let code = response.status();
+    let message = match response.json::<ErrorMessage>().await {
+        Ok(error) => error.message,
+        Err(_) => code.canonical_reason().unwrap_or_default().to_string(),
+    };
+    Error::Api(ApiError::ErrorMessage { code, message })
+}
+
+async fn error_or_value<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T, Error> {
+    if response.status().is_success() {
+        Ok(response.json().await?)
+    } else {
+        Err(keymanager_error(response).await)
+    }
+}
+
+async fn error_or_ok(response: reqwest::Response) -> Result<(), Error> {
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(keymanager_error(response).await)
+    }
+}
+
+/// A client for the keymanager API of a validator client, authenticated with the bearer token
+/// the validator client generates for its operator.
+#[derive(Clone)]
+pub struct KeymanagerClient {
+    http: reqwest::Client,
+    endpoint: Url,
+    authorization: HeaderValue,
+}
+
+impl KeymanagerClient {
+    pub fn new<U: Into<Url>>(endpoint: U, token: &str) -> Result<Self, Error> {
+        Self::new_with_client(reqwest::Client::new(), endpoint, token)
+    }
+
+    pub fn new_with_client<U: Into<Url>>(
+        client: reqwest::Client,
+        endpoint: U,
+        token: &str,
+    ) -> Result<Self, Error> {
+        let authorization = bearer_authorization(token)?;
+        Ok(Self { http: client, endpoint: endpoint.into(), authorization })
+    }
+
+    pub fn endpoint(&self) -> &Url {
+        &self.endpoint
+    }
+
+    fn request(&self, method: Method, path: &str) -> Result<reqwest::RequestBuilder, Error> {
+        let target = self.endpoint.join(path)?;
+        Ok(self.http.request(method, target).header(AUTHORIZATION, self.authorization.clone()))
+    }
+
+    pub async fn list_keystores(&self) -> Result<Vec<KeystoreSummary>, Error> {
+        let response = self.request(Method::GET, "eth/v1/keystores")?.send().await?;
+        let result: Data<_> = error_or_value(response).await?;
+        Ok(result.data)
+    }
+
+    /// Imports the JSON-encoded EIP-2335 `keystores`, decrypted with the `passwords` at the same
+    /// positions, along with any slashing protection data for them.
+    pub async fn import_keystores(
+        &self,
+        keystores: &[String],
+        passwords: &[String],
+        slashing_protection: Option<&SlashingProtectionInterchange>,
+    ) -> Result<Vec<ImportResult>, Error> {
+        let slashing_protection = slashing_protection.map(serde_json::to_string).transpose()?;
+        let request = ImportKeystoresRequest { keystores, passwords, slashing_protection };
+        let response =
+            self.request(Method::POST, "eth/v1/keystores")?.json(&request).send().await?;
+        let result: Data<_> = error_or_value(response).await?;
+        Ok(result.data)
+    }
+
+    pub async fn delete_keystores(
+        &self,
+        public_keys: &[BlsPublicKey],
+    ) -> Result<DeleteKeystoresResponse, Error> {
+        let request = DeleteKeysRequest { pubkeys: public_keys };
+        let response =
+            self.request(Method::DELETE, "eth/v1/keystores")?.json(&request).send().await?;
+        let result: DeleteKeystoresResponseJson = error_or_value(response).await?;
+        let slashing_protection = serde_json::from_str(&result.slashing_protection)?;
+        Ok(DeleteKeystoresResponse { results: result.data, slashing_protection })
+    }
+
+    pub async fn list_remote_keys(&self) -> Result<Vec<RemoteKeySummary>, Error> {
+        let response = self.request(Method::GET, "eth/v1/remotekeys")?.send().await?;
+        let result: Data<_> = error_or_value(response).await?;
+        Ok(result.data)
+    }
+
+    pub async fn import_remote_keys(
+        &self,
+        remote_keys: &[RemoteKey],
+    ) -> Result<Vec<ImportResult>, Error> {
+        let request = ImportRemoteKeysRequest { remote_keys };
+        let response =
+            self.request(Method::POST, "eth/v1/remotekeys")?.json(&request).send().await?;
+        let result: Data<_> = error_or_value(response).await?;
+        Ok(result.data)
+    }
+
+    pub async fn delete_remote_keys(
+        &self,
+        public_keys: &[BlsPublicKey],
+    ) -> Result<Vec<DeleteRemoteKeyResult>, Error> {
+        let request = DeleteKeysRequest { pubkeys: public_keys };
+        let response =
+            self.request(Method::DELETE, "eth/v1/remotekeys")?.json(&request).send().await?;
+        let result: Data<_> = error_or_value(response).await?;
+        Ok(result.data)
+    }
+
+    pub async fn get_fee_recipient(
+        &self,
+        public_key: &BlsPublicKey,
+    ) -> Result<ExecutionAddress, Error> {
+        let path = format!("eth/v1/validator/{public_key:?}/feerecipient");
+        let response = self.request(Method::GET, &path)?.send().await?;
+        let result: Data<FeeRecipient> = error_or_value(response).await?;
+        Ok(result.data.ethaddress)
+    }
+
+    pub async fn set_fee_recipient(
+        &self,
+        public_key: &BlsPublicKey,
+        fee_recipient: ExecutionAddress,
+    ) -> Result<(), Error> {
+        let path = format!("eth/v1/validator/{public_key:?}/feerecipient");
+        let request = FeeRecipient { ethaddress: fee_recipient };
+        let response = self.request(Method::POST, &path)?.json(&request).send().await?;
+        error_or_ok(response).await
+    }
+
+    /// Removes the fee recipient set for the validator so that it uses the default again.
+    pub async fn delete_fee_recipient(&self, public_key: &BlsPublicKey) -> Result<(), Error> {
+        let path = format!("eth/v1/validator/{public_key:?}/feerecipient");
+        let response = self.request(Method::DELETE, &path)?.send().await?;
+        error_or_ok(response).await
+    }
+
+    pub async fn get_gas_limit(&self, public_key: &BlsPublicKey) -> Result<u64, Error> {
+        let path = format!("eth/v1/validator/{public_key:?}/gas_limit");
+        let response = self.request(Method::GET, &path)?.send().await?;
+        let result: Data<GasLimit> = error_or_value(response).await?;
+        Ok(result.data.gas_limit)
+    }
+
+    pub async fn set_gas_limit(
+        &self,
+        public_key: &BlsPublicKey,
+        gas_limit: u64,
+    ) -> Result<(), Error> {
+        let path = format!("eth/v1/validator/{public_key:?}/gas_limit");
+        let response =
+            self.request(Method::POST, &path)?.json(&GasLimit { gas_limit }).send().await?;
+        error_or_ok(response).await
+    }
+
+    /// Removes the gas limit set for the validator so that it uses the default again.
+    pub async fn delete_gas_limit(&self, public_key: &BlsPublicKey) -> Result<(), Error> {
+        let path = format!("eth/v1/validator/{public_key:?}/gas_limit");
+        let response = self.request(Method::DELETE, &path)?.send().await?;
+        error_or_ok(response).await
+    }
+
+    pub async fn get_graffiti(&self, public_key: &BlsPublicKey) -> Result<String, Error> {
+        let path = format!("eth/v1/validator/{public_key:?}/graffiti");
+        let response = self.request(Method::GET, &path)?.send().await?;
+        let result: Data<Graffiti> = error_or_value(response).await?;
+        Ok(result.data.graffiti)
+    }
+
+    pub async fn set_graffiti(
+        &self,
+        public_key: &BlsPublicKey,
+        graffiti: &str,
+    ) -> Result<(), Error> {
+        let path = format!("eth/v1/validator/{public_key:?}/graffiti");
+        let request = Graffiti { graffiti: graffiti.to_string() };
+        let response = self.request(Method::POST, &path)?.json(&request).send().await?;
+        error_or_ok(response).await
+    }
+
+    /// Removes the graffiti set for the validator so that it uses the default again.
+    pub async fn delete_graffiti(&self, public_key: &BlsPublicKey) -> Result<(), Error> {
+        let path = format!("eth/v1/validator/{public_key:?}/graffiti");
+        let response = self.request(Method::DELETE, &path)?.send().await?;
+        error_or_ok(response).await
+    }
+
+    /// Has the validator client sign a voluntary exit for the validator, valid from `epoch` or
+    /// the current epoch if not given. The exit is returned to the caller rather than published.
+    pub async fn sign_voluntary_exit(
+        &self,
+        public_key: &BlsPublicKey,
+        epoch: Option<Epoch>,
+    ) -> Result<SignedVoluntaryExit, Error> {
+        let path = format!("eth/v1/validator/{public_key:?}/voluntary_exit");
+        let mut request = self.request(Method::POST, &path)?;
+        if let Some(epoch) = epoch {
+            request = request.query(&[("epoch", epoch)]);
+        }
+        let response = request.send().await?;
+        let result: Data<_> = error_or_value(response).await?;
+        Ok(result.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delete_keystores_response() {
+        let interchange = r#"{"metadata":{"interchange_format_version":"5","genesis_validators_root":"0x04700007fabc8282644aed6d1c7c9e21d38a03a0c4ba193f3afe428824b3a673"},"data":[{"pubkey":"0x8da04bbe26b2bbc6b042f4db18a36f1b4714123706065ed3946a3c3aeb681f98d3e67a3483b088612cb9b0c5322723a0","signed_blocks":[{"slot":"81952","signing_root":"0x4ff6f743a43f3b4f95350831aeaf0a122a1a392922c45d804280284a69eb850b"}],"signed_attestations":[{"source_epoch":"2290","target_epoch":"3007"}]}]}"#;
+        let response = serde_json::json!({
+            "data": [{"status": "deleted"}, {"status": "not_active", "message": "inactive"}],
+            "slashing_protection": interchange,
+        });
+        let response: DeleteKeystoresResponseJson = serde_json::from_value(response).unwrap();
+        assert_eq!(response.data[0].status, DeleteKeystoreStatus::Deleted);
+        assert_eq!(response.data[1].status, DeleteKeystoreStatus::NotActive);
+
+        let slashing_protection: SlashingProtectionInterchange =
+            serde_json::from_str(&response.slashing_protection).unwrap();
+        assert_eq!(slashing_protection.metadata.interchange_format_version, 5);
+        let data = &slashing_protection.data[0];
+        assert_eq!(data.signed_blocks[0].slot, 81952);
+        assert!(data.signed_attestations[0].signing_root.is_none());
+
+        let encoding = serde_json::to_string(&slashing_protection).unwrap();
+        assert_eq!(encoding, interchange);
+    }
+
+    #[cfg(feature = "server")]
+    mod server {
+        use super::*;
+        use http::StatusCode;
+        use hyper::{
+            service::{make_service_fn, service_fn},
+            Body, Request, Response, Server,
+        };
+        use serde_json::json;
+        use std::{
+            convert::Infallible,
+            sync::{Arc, Mutex},
+        };
+
+        const TOKEN: &str = "api-token-0x1234";
+
+        fn public_key() -> BlsPublicKey {
+            BlsPublicKey::try_from([0xaa; 48].as_slice()).unwrap()
+        }
+
+        fn fee_recipient() -> ExecutionAddress {
+            ExecutionAddress::try_from([0x11; 20].as_slice()).unwrap()
+        }
+
+        fn json_response(status: StatusCode, body: serde_json::Value) -> Response<Body> {
+            Response::builder()
+                .status(status)
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        }
+
+        fn empty_response(status: StatusCode) -> Response<Body> {
+            Response::builder().status(status).body(Body::empty()).unwrap()
+        }
+
+        // A keymanager with the single key `public_key()` that records the body of each
+        // authorized request and answers unknown routes with a plain-text server error.
+        #[derive(Clone, Default)]
+        struct Keymanager {
+            requests: Arc<Mutex<Vec<(Method, serde_json::Value)>>>,
+        }
+
+        impl Keymanager {
+            fn handle(
+                &self,
+                method: Method,
+                path: &str,
+                authorization: Option<&str>,
+                body: &[u8],
+            ) -> Response<Body> {
+                if authorization != Some(format!("Bearer {TOKEN}").as_str()) {
+                    return json_response(StatusCode::UNAUTHORIZED, json!({"message": "bad token"}))
+                }
+                let body = serde_json::from_slice(body).unwrap_or_default();
+                self.requests.lock().unwrap().push((method.clone(), body));
+
+                let fee_recipient_path =
+                    format!("eth/v1/validator/{:?}/feerecipient", public_key());
+                match (method, path) {
+                    (Method::POST, "eth/v1/keystores") => {
+                        let data = json!([
+                            {"status": "imported"},
+                            {"status": "error", "message": "invalid password"},
+                        ]);
+                        json_response(StatusCode::OK, json!({ "data": data }))
+                    }
+                    (Method::DELETE, "eth/v1/keystores") => {
+                        let slashing_protection = json!({
+                            "metadata": {
+                                "interchange_format_version": "5",
+                                "genesis_validators_root": Root::default(),
+                            },
+                            "data": [],
+                        });
+                        let response = json!({
+                            "data": [{"status": "deleted"}, {"status": "not_found"}],
+                            "slashing_protection": slashing_protection.to_string(),
+                        });
+                        json_response(StatusCode::OK, response)
+                    }
+                    (Method::GET, path) if path == fee_recipient_path => {
+                        let data = json!({"pubkey": public_key(), "ethaddress": fee_recipient()});
+                        json_response(StatusCode::OK, json!({ "data": data }))
+                    }
+                    (Method::POST, path) if path == fee_recipient_path => {
+                        empty_response(StatusCode::ACCEPTED)
+                    }
+                    (Method::DELETE, path) if path == fee_recipient_path => {
+                        empty_response(StatusCode::NO_CONTENT)
+                    }
+                    (_, path) if path.ends_with("/feerecipient") => {
+                        json_response(StatusCode::NOT_FOUND, json!({"message": "unknown key"}))
+                    }
+                    _ => Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::from("unexpected request"))
+                        .unwrap(),
+                }
+            }
+
+            fn take_requests(&self) -> Vec<(Method, serde_json::Value)> {
+                std::mem::take(&mut self.requests.lock().unwrap())
+            }
+        }
+
+        fn spawn(keymanager: &Keymanager) -> Url {
+            let keymanager = keymanager.clone();
+            let make_service = make_service_fn(move |_| {
+                let keymanager = keymanager.clone();
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                        let keymanager = keymanager.clone();
+                        async move {
+                            let (parts, body) = request.into_parts();
+                            let body = hyper::body::to_bytes(body).await?;
+                            let authorization = parts
+                                .headers
+                                .get(AUTHORIZATION)
+                                .and_then(|value| value.to_str().ok());
+                            let path = parts.uri.path().trim_start_matches('/');
+                            let response =
+                                keymanager.handle(parts.method.clone(), path, authorization, &body);
+                            Ok::<_, hyper::Error>(response)
+                        }
+                    }))
+                }
+            });
+            let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+            let endpoint = Url::parse(&format!("http://{}", server.local_addr())).unwrap();
+            tokio::spawn(server);
+            endpoint
+        }
+
+        fn assert_api_error(err: Error, expected_code: StatusCode, expected_message: &str) {
+            match err {
+                Error::Api(ApiError::ErrorMessage { code, message }) => {
+                    assert_eq!(code, expected_code);
+                    assert_eq!(message, expected_message);
+                }
+                err => panic!("unexpected error: {err}"),
+            }
+        }
+
+        #[tokio::test]
+        async fn test_authorization() {
+            let keymanager = Keymanager::default();
+            let endpoint = spawn(&keymanager);
+
+            // the token is read from a file, so surrounding whitespace is ignored
+            let client = KeymanagerClient::new(endpoint.clone(), &format!("{TOKEN}\n")).unwrap();
+            client.delete_fee_recipient(&public_key()).await.unwrap();
+
+            let client = KeymanagerClient::new(endpoint, "wrong-token").unwrap();
+            let err = client.delete_fee_recipient(&public_key()).await.unwrap_err();
+            assert_api_error(err, StatusCode::UNAUTHORIZED, "bad token");
+            assert_eq!(keymanager.take_requests().len(), 1);
+        }
+
+        #[tokio::test]
+        async fn test_import_and_delete_keystores() {
+            let keymanager = Keymanager::default();
+            let client = KeymanagerClient::new(spawn(&keymanager), TOKEN).unwrap();
+
+            let keystores = vec!["{}".to_string(), "{}".to_string()];
+            let passwords = vec!["first".to_string(), "second".to_string()];
+            let slashing_protection = SlashingProtectionInterchange {
+                metadata: InterchangeMetadata {
+                    interchange_format_version: 5,
+                    genesis_validators_root: Root::default(),
+                },
+                data: vec![],
+            };
+            let results = client
+                .import_keystores(&keystores, &passwords, Some(&slashing_protection))
+                .await
+                .unwrap();
+            let statuses = results.iter().map(|result| result.status).collect::<Vec<_>>();
+            assert_eq!(statuses, [ImportStatus::Imported, ImportStatus::Error]);
+            assert_eq!(results[1].message.as_deref(), Some("invalid password"));
+
+            // the slashing protection data is sent as a JSON-encoded string
+            let (method, body) = keymanager.take_requests().pop().unwrap();
+            assert_eq!(method, Method::POST);
+            assert_eq!(body["keystores"], json!(keystores));
+            assert_eq!(body["passwords"], json!(passwords));
+            let sent: SlashingProtectionInterchange =
+                serde_json::from_str(body["slashing_protection"].as_str().unwrap()).unwrap();
+            assert_eq!(sent, slashing_protection);
+
+            let response =
+                client.delete_keystores(&[public_key(), Default::default()]).await.unwrap();
+            let statuses = response.results.iter().map(|result| result.status).collect::<Vec<_>>();
+            assert_eq!(statuses, [DeleteKeystoreStatus::Deleted, DeleteKeystoreStatus::NotFound]);
+            assert_eq!(response.slashing_protection, slashing_protection);
+            let (method, body) = keymanager.take_requests().pop().unwrap();
+            assert_eq!(method, Method::DELETE);
+            assert_eq!(body, json!({"pubkeys": [public_key(), BlsPublicKey::default()]}));
+        }
+
+        #[tokio::test]
+        async fn test_fee_recipient() {
+            let keymanager = Keymanager::default();
+            let client = KeymanagerClient::new(spawn(&keymanager), TOKEN).unwrap();
+
+            assert_eq!(client.get_fee_recipient(&public_key()).await.unwrap(), fee_recipient());
+            client.set_fee_recipient(&public_key(), fee_recipient()).await.unwrap();
+            let (method, body) = keymanager.take_requests().pop().unwrap();
+            assert_eq!(method, Method::POST);
+            assert_eq!(body, json!({ "ethaddress": fee_recipient() }));
+            client.delete_fee_recipient(&public_key()).await.unwrap();
+
+            // errors carry the message of the response body, or the reason for the status
+            let err = client.get_fee_recipient(&BlsPublicKey::default()).await.unwrap_err();
+            assert_api_error(err, StatusCode::NOT_FOUND, "unknown key");
+            let err = client.set_fee_recipient(&BlsPublicKey::default(), fee_recipient()).await;
+            assert_api_error(err.unwrap_err(), StatusCode::NOT_FOUND, "unknown key");
+            let err = client.get_gas_limit(&public_key()).await.unwrap_err();
+            assert_api_error(err, StatusCode::INTERNAL_SERVER_ERROR, "Internal Server Error");
+        }
+    }
+}
@@ -1,20 +1,45 @@
 mod api_client;
 mod api_error;
+mod builder_client;
 mod cli;
+mod client_builder;
+mod doppelganger;
+mod duty_scheduler;
+mod encoding;
+mod events;
+mod fallback;
+mod keymanager_client;
 mod serde;
+#[cfg(feature = "server")]
+mod server;
 mod types;
 
 pub use api_client::*;
 pub use api_error::*;
+pub use builder_client::*;
 pub use cli::*;
+pub use client_builder::*;
+pub use doppelganger::*;
+pub use duty_scheduler::*;
+pub use encoding::*;
 pub use error::*;
+pub use events::*;
+pub use fallback::*;
+pub use keymanager_client::*;
 pub use presets::*;
+#[cfg(feature = "server")]
+pub use server::*;
 pub use types::*;
 
 pub const ETH_CONSENSUS_VERSION_HEADER: &str = "Eth-Consensus-Version";
 
 mod error {
-    use crate::ApiError;
+    use crate::{ApiError, EndpointError};
+    use ethereum_consensus::{
+        primitives::BlsPublicKey, ssz::prelude::SimpleSerializeError, Error as ConsensusError, Fork,
+    };
+    use http::header::InvalidHeaderValue;
+    use itertools::Itertools;
     use thiserror::Error;
     use url::ParseError;
 
@@ -23,13 +48,49 @@ mod error {
         #[error("could not parse URL: {0}")]
         Url(#[from] ParseError),
         #[error("could not send request: {0}")]
-        Http(#[from] reqwest::Error),
+        Http(reqwest::Error),
+        #[error("request timed out: {0}")]
+        Timeout(reqwest::Error),
+        #[error("invalid header value: {0}")]
+        InvalidHeader(#[from] InvalidHeaderValue),
         #[error("error from API: {0}")]
         Api(#[from] ApiError),
         #[error("missing expected data in response: {0}")]
         MissingExpectedData(String),
         #[error("json error: {0}")]
         Json(#[from] serde_json::Error),
+        #[error("ssz error: {0}")]
+        Ssz(#[from] SimpleSerializeError),
+        #[error("malformed ssz encoding: {0}")]
+        MalformedSsz(String),
+        #[error("unsupported fork for this type: {0}")]
+        UnsupportedVersion(Fork),
+        #[error("unknown event topic: {0}")]
+        UnknownTopic(String),
+        #[error("all endpoints failed: [{}]", .0.iter().join(", "))]
+        AllEndpointsFailed(Vec<EndpointError>),
+        #[error("no endpoints configured")]
+        NoEndpoints,
+        #[error("{0}")]
+        Consensus(#[from] ConsensusError),
+        #[error("could not parse relay public key from endpoint: {0}")]
+        InvalidRelayPublicKey(String),
+        #[error("bid from unexpected builder {provided:?} (expected {expected:?})")]
+        UnexpectedBuilder { expected: BlsPublicKey, provided: BlsPublicKey },
+        #[error("invalid bid signature: {0}")]
+        InvalidBidSignature(ConsensusError),
+        #[error("could not sign: {0}")]
+        Signer(String),
+    }
+
+    impl From<reqwest::Error> for Error {
+        fn from(err: reqwest::Error) -> Self {
+            if err.is_timeout() {
+                Self::Timeout(err)
+            } else {
+                Self::Http(err)
+            }
+        }
     }
 }
 
@@ -37,7 +98,10 @@ pub mod presets {
     pub mod mainnet {
         use ethereum_consensus::{
             altair::mainnet as altair,
+            builder::mainnet as builder,
+            capella::mainnet as capella,
             deneb::mainnet as deneb,
+            electra::mainnet as electra,
             phase0::mainnet as phase0,
             types::mainnet::{
                 BeaconBlock, BeaconState, BlindedBeaconBlock, SignedBeaconBlock,
@@ -48,31 +112,68 @@ pub mod presets {
         #[derive(Clone)]
         pub struct MainnetClientTypes;
 
+        pub type Attestation = crate::Attestation<phase0::Attestation, electra::Attestation>;
+        pub type AttesterSlashing =
+            crate::AttesterSlashing<phase0::AttesterSlashing, electra::AttesterSlashing>;
+        pub type SignedAggregateAndProof = crate::SignedAggregateAndProof<
+            phase0::SignedAggregateAndProof,
+            electra::SignedAggregateAndProof,
+        >;
+        pub type LightClientBootstrap = crate::LightClientBootstrap<
+            altair::LightClientBootstrap,
+            capella::LightClientBootstrap,
+            deneb::LightClientBootstrap,
+        >;
+        pub type LightClientUpdate = crate::LightClientUpdate<
+            altair::LightClientUpdate,
+            capella::LightClientUpdate,
+            deneb::LightClientUpdate,
+        >;
+        pub type LightClientFinalityUpdate = crate::LightClientFinalityUpdate<
+            altair::LightClientFinalityUpdate,
+            capella::LightClientFinalityUpdate,
+            deneb::LightClientFinalityUpdate,
+        >;
+        pub type LightClientOptimisticUpdate = crate::LightClientOptimisticUpdate<
+            altair::LightClientOptimisticUpdate,
+            capella::LightClientOptimisticUpdate,
+            deneb::LightClientOptimisticUpdate,
+        >;
+
         impl crate::ClientTypes for MainnetClientTypes {
             type SignedContributionAndProof = altair::SignedContributionAndProof;
             type SyncCommitteeContribution = altair::SyncCommitteeContribution;
             type BlindedBeaconBlock = BlindedBeaconBlock;
             type SignedBlindedBeaconBlock = SignedBlindedBeaconBlock;
-            type Attestation = phase0::Attestation;
-            type AttesterSlashing = phase0::AttesterSlashing;
+            type Attestation = Attestation;
+            type AttesterSlashing = AttesterSlashing;
             type BeaconBlock = BeaconBlock;
             type BeaconState = BeaconState;
-            type SignedAggregateAndProof = phase0::SignedAggregateAndProof;
+            type SignedAggregateAndProof = SignedAggregateAndProof;
             type SignedBeaconBlock = SignedBeaconBlock;
             type Blob = deneb::Blob;
             type BlobSidecar = deneb::BlobSidecar;
-            type LightClientBootstrap = altair::LightClientBootstrap;
-            type LightClientUpdate = altair::LightClientUpdate;
-            type LightClientFinalityUpdate = altair::LightClientFinalityUpdate;
-            type LightClientOptimisticUpdate = altair::LightClientOptimisticUpdate;
+            type LightClientBootstrap = LightClientBootstrap;
+            type LightClientUpdate = LightClientUpdate;
+            type LightClientFinalityUpdate = LightClientFinalityUpdate;
+            type LightClientOptimisticUpdate = LightClientOptimisticUpdate;
+        }
+
+        impl crate::BuilderTypes for MainnetClientTypes {
+            type SignedBuilderBid = builder::SignedBuilderBid;
+            type ExecutionPayloadResponse = builder::ExecutionPayloadResponse;
         }
 
         pub type Client = crate::Client<MainnetClientTypes>;
+        pub type BuilderClient = crate::BuilderClient<MainnetClientTypes>;
     }
     pub mod minimal {
         use ethereum_consensus::{
             altair::minimal as altair,
+            builder::minimal as builder,
+            capella::minimal as capella,
             deneb::minimal as deneb,
+            electra::minimal as electra,
             phase0::minimal as phase0,
             types::minimal::{
                 BeaconBlock, BeaconState, BlindedBeaconBlock, SignedBeaconBlock,
@@ -83,25 +184,59 @@ pub mod presets {
         #[derive(Clone)]
         pub struct MinimalClientTypes;
 
+        pub type Attestation = crate::Attestation<phase0::Attestation, electra::Attestation>;
+        pub type AttesterSlashing =
+            crate::AttesterSlashing<phase0::AttesterSlashing, electra::AttesterSlashing>;
+        pub type SignedAggregateAndProof = crate::SignedAggregateAndProof<
+            phase0::SignedAggregateAndProof,
+            electra::SignedAggregateAndProof,
+        >;
+        pub type LightClientBootstrap = crate::LightClientBootstrap<
+            altair::LightClientBootstrap,
+            capella::LightClientBootstrap,
+            deneb::LightClientBootstrap,
+        >;
+        pub type LightClientUpdate = crate::LightClientUpdate<
+            altair::LightClientUpdate,
+            capella::LightClientUpdate,
+            deneb::LightClientUpdate,
+        >;
+        pub type LightClientFinalityUpdate = crate::LightClientFinalityUpdate<
+            altair::LightClientFinalityUpdate,
+            capella::LightClientFinalityUpdate,
+            deneb::LightClientFinalityUpdate,
+        >;
+        pub type LightClientOptimisticUpdate = crate::LightClientOptimisticUpdate<
+            altair::LightClientOptimisticUpdate,
+            capella::LightClientOptimisticUpdate,
+            deneb::LightClientOptimisticUpdate,
+        >;
+
         impl crate::ClientTypes for MinimalClientTypes {
             type SignedContributionAndProof = altair::SignedContributionAndProof;
             type SyncCommitteeContribution = altair::SyncCommitteeContribution;
             type BlindedBeaconBlock = BlindedBeaconBlock;
             type SignedBlindedBeaconBlock = SignedBlindedBeaconBlock;
-            type Attestation = phase0::Attestation;
-            type AttesterSlashing = phase0::AttesterSlashing;
+            type Attestation = Attestation;
+            type AttesterSlashing = AttesterSlashing;
             type BeaconBlock = BeaconBlock;
             type BeaconState = BeaconState;
-            type SignedAggregateAndProof = phase0::SignedAggregateAndProof;
+            type SignedAggregateAndProof = SignedAggregateAndProof;
             type SignedBeaconBlock = SignedBeaconBlock;
             type Blob = deneb::Blob;
             type BlobSidecar = deneb::BlobSidecar;
-            type LightClientBootstrap = altair::LightClientBootstrap;
-            type LightClientUpdate = altair::LightClientUpdate;
-            type LightClientFinalityUpdate = altair::LightClientFinalityUpdate;
-            type LightClientOptimisticUpdate = altair::LightClientOptimisticUpdate;
+            type LightClientBootstrap = LightClientBootstrap;
+            type LightClientUpdate = LightClientUpdate;
+            type LightClientFinalityUpdate = LightClientFinalityUpdate;
+            type LightClientOptimisticUpdate = LightClientOptimisticUpdate;
+        }
+
+        impl crate::BuilderTypes for MinimalClientTypes {
+            type SignedBuilderBid = builder::SignedBuilderBid;
+            type ExecutionPayloadResponse = builder::ExecutionPayloadResponse;
         }
 
         pub type Client = crate::Client<MinimalClientTypes>;
+        pub type BuilderClient = crate::BuilderClient<MinimalClientTypes>;
     }
 }
@@ -1,11 +1,15 @@
-use beacon_api_client::{mainnet::Client, run_cli, CliConfig};
+use beacon_api_client::{run_cli, CliConfig};
 use clap::Parser;
-use url::Url;
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
     let args = CliConfig::parse();
-    let url = Url::parse(&args.endpoint).unwrap();
-    let client = Client::new(url);
-    run_cli(&client, &args).await;
+    match run_cli(&args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
 }
@@ -19,3 +19,29 @@ pub(crate) mod as_u16 {
         StatusCode::from_u16(value).map_err(serde::de::Error::custom)
     }
 }
+
+// Like `as_str` but for optional values which the API omits when absent.
+pub(crate) mod as_opt_str {
+    use serde::{de::Deserializer, Deserialize, Serializer};
+    use std::{fmt::Display, str::FromStr};
+
+    pub fn serialize<S, T: Display>(data: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match data {
+            Some(data) => serializer.collect_str(data),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D, T, E>(deserializer: D) -> Result<Option<T>, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr<Err = E>,
+        E: Display,
+    {
+        let s: Option<String> = Deserialize::deserialize(deserializer)?;
+        s.map(|s| T::from_str(&s).map_err(serde::de::Error::custom)).transpose()
+    }
+}
@@ -0,0 +1,763 @@
+use crate::{
+    api_client::ClientTypes,
+    server::provider::{bad_request, not_found, BeaconNodeProvider},
+    types::{
+        AttestationDuty, BalanceSummary, BeaconHeaderSummary, BlockId, FinalityCheckpoints,
+        GenesisDetails, HealthStatus, ProposerDuty, PublicKeyOrIndex, StateId, SyncCommitteeDuty,
+        SyncStatus, ValidatorStatus, ValidatorSummary,
+    },
+    ApiError,
+};
+use ethereum_consensus::{
+    phase0::{BeaconBlockHeader, Checkpoint, Fork, SignedBeaconBlockHeader, Validator},
+    primitives::{
+        BlobIndex, Epoch, Gwei, Root, Slot, ValidatorIndex, Version as ForkVersion,
+        FAR_FUTURE_EPOCH,
+    },
+    ssz::prelude::{HashTreeRoot, MerkleizationError},
+    state_transition::Context,
+    types::{BeaconState, SignedBeaconBlock},
+    Fork as Version,
+};
+use std::{
+    collections::HashMap,
+    sync::{RwLock, RwLockReadGuard},
+};
+
+/// The accessors an [`InMemoryProvider`] needs from the beacon states it serves.
+pub trait StateView: Clone + Send + Sync + 'static {
+    fn version(&self) -> Version;
+    fn slot(&self) -> Slot;
+    fn genesis_time(&self) -> u64;
+    fn genesis_validators_root(&self) -> Root;
+    fn fork(&self) -> &Fork;
+    fn finality_checkpoints(&self) -> FinalityCheckpoints;
+    fn validators(&self) -> &[Validator];
+    fn balances(&self) -> &[Gwei];
+    fn state_root(&self) -> Result<Root, MerkleizationError>;
+}
+
+/// The accessors an [`InMemoryProvider`] needs from the beacon blocks it serves.
+pub trait BlockView: Clone + Send + Sync + 'static {
+    fn version(&self) -> Version;
+    fn slot(&self) -> Slot;
+    fn block_root(&self) -> Result<Root, MerkleizationError>;
+    fn header(&self) -> Result<SignedBeaconBlockHeader, MerkleizationError>;
+}
+
+impl<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+    > StateView
+    for BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        PENDING_ATTESTATIONS_BOUND,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >
+{
+    fn version(&self) -> Version {
+        self.version()
+    }
+
+    fn slot(&self) -> Slot {
+        self.slot()
+    }
+
+    fn genesis_time(&self) -> u64 {
+        self.genesis_time()
+    }
+
+    fn genesis_validators_root(&self) -> Root {
+        self.genesis_validators_root()
+    }
+
+    fn fork(&self) -> &Fork {
+        self.fork()
+    }
+
+    fn finality_checkpoints(&self) -> FinalityCheckpoints {
+        FinalityCheckpoints {
+            previous_justified: self.previous_justified_checkpoint().clone(),
+            current_justified: self.current_justified_checkpoint().clone(),
+            finalized: self.finalized_checkpoint().clone(),
+        }
+    }
+
+    fn validators(&self) -> &[Validator] {
+        self.validators()
+    }
+
+    fn balances(&self) -> &[Gwei] {
+        self.balances()
+    }
+
+    fn state_root(&self) -> Result<Root, MerkleizationError> {
+        self.hash_tree_root()
+    }
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    > BlockView
+    for SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >
+{
+    fn version(&self) -> Version {
+        self.version()
+    }
+
+    fn slot(&self) -> Slot {
+        self.message().slot()
+    }
+
+    fn block_root(&self) -> Result<Root, MerkleizationError> {
+        self.message().hash_tree_root()
+    }
+
+    fn header(&self) -> Result<SignedBeaconBlockHeader, MerkleizationError> {
+        let block = self.message();
+        let message = BeaconBlockHeader {
+            slot: block.slot(),
+            proposer_index: block.proposer_index(),
+            parent_root: block.parent_root(),
+            state_root: block.state_root(),
+            body_root: block.body().hash_tree_root()?,
+        };
+        Ok(SignedBeaconBlockHeader { message, signature: self.signature().clone() })
+    }
+}
+
+// Computes the status of `validator` at `epoch` following the beacon API's definitions.
+fn validator_status(validator: &Validator, epoch: Epoch) -> ValidatorStatus {
+    if validator.activation_epoch > epoch {
+        if validator.activation_eligibility_epoch == FAR_FUTURE_EPOCH {
+            ValidatorStatus::PendingInitialized
+        } else {
+            ValidatorStatus::PendingQueued
+        }
+    } else if validator.exit_epoch > epoch {
+        if validator.exit_epoch == FAR_FUTURE_EPOCH {
+            ValidatorStatus::ActiveOngoing
+        } else if validator.slashed {
+            ValidatorStatus::ActiveSlashed
+        } else {
+            ValidatorStatus::ActiveExiting
+        }
+    } else if validator.withdrawable_epoch > epoch {
+        if validator.slashed {
+            ValidatorStatus::ExitedSlashed
+        } else {
+            ValidatorStatus::ExitedUnslashed
+        }
+    } else if validator.effective_balance != 0 {
+        ValidatorStatus::WithdrawalPossible
+    } else {
+        ValidatorStatus::WithdrawalDone
+    }
+}
+
+// Returns `true` if `status` is `filter` or one of the statuses `filter` groups together.
+fn status_matches(status: ValidatorStatus, filter: ValidatorStatus) -> bool {
+    use ValidatorStatus::*;
+
+    status == filter ||
+        matches!(
+            (filter, status),
+            (Pending, PendingInitialized | PendingQueued) |
+                (Active, ActiveOngoing | ActiveExiting | ActiveSlashed) |
+                (Exited, ExitedUnslashed | ExitedSlashed) |
+                (Withdrawal, WithdrawalPossible | WithdrawalDone)
+        )
+}
+
+fn id_matches(id: &PublicKeyOrIndex, index: ValidatorIndex, validator: &Validator) -> bool {
+    match id {
+        PublicKeyOrIndex::Index(id) => *id == index,
+        PublicKeyOrIndex::PublicKey(public_key) => *public_key == validator.public_key,
+    }
+}
+
+fn fork_schedule(context: &Context) -> Vec<Fork> {
+    let forks = [
+        (context.altair_fork_version, context.altair_fork_epoch),
+        (context.bellatrix_fork_version, context.bellatrix_fork_epoch),
+        (context.capella_fork_version, context.capella_fork_epoch),
+        (context.deneb_fork_version, context.deneb_fork_epoch),
+        (context.electra_fork_version, context.electra_fork_epoch),
+        (context.fulu_fork_version, context.fulu_fork_epoch),
+    ];
+    let genesis_version = context.genesis_fork_version;
+    let mut schedule = vec![Fork {
+        previous_version: genesis_version,
+        current_version: genesis_version,
+        epoch: 0,
+    }];
+    let mut previous_version = genesis_version;
+    for (current_version, epoch) in forks {
+        if epoch == FAR_FUTURE_EPOCH {
+            break
+        }
+        schedule.push(Fork { previous_version, current_version, epoch });
+        previous_version = current_version;
+    }
+    schedule
+}
+
+fn spec(context: &Context) -> HashMap<String, String> {
+    let version = |version: ForkVersion| {
+        let hex = version.iter().map(|byte| format!("{byte:02x}")).collect::<String>();
+        format!("0x{hex}")
+    };
+    HashMap::from_iter([
+        ("CONFIG_NAME".to_string(), context.name.to_string()),
+        ("SECONDS_PER_SLOT".to_string(), context.seconds_per_slot.to_string()),
+        ("SLOTS_PER_EPOCH".to_string(), context.slots_per_epoch.to_string()),
+        ("MIN_GENESIS_TIME".to_string(), context.min_genesis_time.to_string()),
+        ("GENESIS_FORK_VERSION".to_string(), version(context.genesis_fork_version)),
+        ("ALTAIR_FORK_VERSION".to_string(), version(context.altair_fork_version)),
+        ("ALTAIR_FORK_EPOCH".to_string(), context.altair_fork_epoch.to_string()),
+        ("BELLATRIX_FORK_VERSION".to_string(), version(context.bellatrix_fork_version)),
+        ("BELLATRIX_FORK_EPOCH".to_string(), context.bellatrix_fork_epoch.to_string()),
+        ("CAPELLA_FORK_VERSION".to_string(), version(context.capella_fork_version)),
+        ("CAPELLA_FORK_EPOCH".to_string(), context.capella_fork_epoch.to_string()),
+        ("DENEB_FORK_VERSION".to_string(), version(context.deneb_fork_version)),
+        ("DENEB_FORK_EPOCH".to_string(), context.deneb_fork_epoch.to_string()),
+        ("ELECTRA_FORK_VERSION".to_string(), version(context.electra_fork_version)),
+        ("ELECTRA_FORK_EPOCH".to_string(), context.electra_fork_epoch.to_string()),
+        ("FULU_FORK_VERSION".to_string(), version(context.fulu_fork_version)),
+        ("FULU_FORK_EPOCH".to_string(), context.fulu_fork_epoch.to_string()),
+    ])
+}
+
+struct Chain<C: ClientTypes> {
+    genesis_details: GenesisDetails,
+    spec: HashMap<String, String>,
+    fork_schedule: Vec<Fork>,
+    slots_per_epoch: Slot,
+    // states and blocks are kept with their roots, in the order they were inserted
+    states: Vec<(Root, C::BeaconState)>,
+    blocks: Vec<(Root, C::SignedBeaconBlock)>,
+    blob_sidecars: HashMap<Root, Vec<C::BlobSidecar>>,
+    proposer_duties: HashMap<Epoch, (Root, Vec<ProposerDuty>)>,
+    attester_duties: HashMap<Epoch, (Root, Vec<AttestationDuty>)>,
+    sync_duties: HashMap<Epoch, Vec<SyncCommitteeDuty>>,
+    sync_status: Option<SyncStatus>,
+    submitted_blocks: Vec<C::SignedBeaconBlock>,
+    submitted_attestations: Vec<C::Attestation>,
+}
+
+impl<C: ClientTypes> Chain<C>
+where
+    C::BeaconState: StateView,
+    C::SignedBeaconBlock: BlockView,
+{
+    fn head_state(&self) -> Option<&(Root, C::BeaconState)> {
+        self.states.iter().max_by_key(|(_, state)| state.slot())
+    }
+
+    fn state_for_block_root(&self, block_root: Root) -> Option<&(Root, C::BeaconState)> {
+        let (_, block) = self.blocks.iter().find(|(root, _)| *root == block_root)?;
+        let state_root = block.header().ok()?.message.state_root;
+        self.states.iter().find(|(root, _)| *root == state_root)
+    }
+
+    fn state_at_checkpoint(&self, checkpoint: &Checkpoint) -> Option<&(Root, C::BeaconState)> {
+        // the checkpoints of a state are zeroed until the chain first justifies an epoch
+        if checkpoint.epoch == 0 {
+            return self.states.iter().find(|(_, state)| state.slot() == 0)
+        }
+        self.state_for_block_root(checkpoint.root)
+    }
+
+    fn state(&self, id: &StateId) -> Result<&(Root, C::BeaconState), ApiError> {
+        let entry = match id {
+            StateId::Head => self.head_state(),
+            StateId::Genesis => self.states.iter().find(|(_, state)| state.slot() == 0),
+            StateId::Finalized => self.head_state().and_then(|(_, state)| {
+                self.state_at_checkpoint(&state.finality_checkpoints().finalized)
+            }),
+            StateId::Justified => self.head_state().and_then(|(_, state)| {
+                self.state_at_checkpoint(&state.finality_checkpoints().current_justified)
+            }),
+            StateId::Slot(slot) => self.states.iter().find(|(_, state)| state.slot() == *slot),
+            StateId::Root(root) => self.states.iter().find(|(state_root, _)| state_root == root),
+        };
+        entry.ok_or_else(|| not_found(format!("could not find state for state id {id}")))
+    }
+
+    fn block(&self, id: &BlockId) -> Result<&(Root, C::SignedBeaconBlock), ApiError> {
+        let head = || self.blocks.iter().max_by_key(|(_, block)| block.slot());
+        let genesis = || self.blocks.iter().find(|(_, block)| block.slot() == 0);
+        let entry = match id {
+            BlockId::Head => head(),
+            BlockId::Genesis => genesis(),
+            BlockId::Finalized => {
+                let finalized = self
+                    .head_state()
+                    .map(|(_, state)| state.finality_checkpoints().finalized)
+                    .unwrap_or_default();
+                if finalized.epoch == 0 {
+                    genesis()
+                } else {
+                    self.blocks.iter().find(|(root, _)| *root == finalized.root)
+                }
+            }
+            BlockId::Slot(slot) => self.blocks.iter().find(|(_, block)| block.slot() == *slot),
+            BlockId::Root(root) => self.blocks.iter().find(|(block_root, _)| block_root == root),
+        };
+        entry.ok_or_else(|| not_found(format!("could not find block for block id {id}")))
+    }
+}
+
+/// A [`BeaconNodeProvider`] serving the states and blocks it is given from memory.
+///
+/// Duties are not computed from the states; seed them with
+/// [`InMemoryProvider::set_proposer_duties`], [`InMemoryProvider::set_attester_duties`] and
+/// [`InMemoryProvider::set_sync_duties`]. Submitted blocks and attestations are recorded so tests
+/// can inspect what a client published. Of the routes a [`crate::MockBeaconNode`] serves, only
+/// attestation data and liveness are not implemented and answer `501 Not Implemented`.
+pub struct InMemoryProvider<C: ClientTypes> {
+    chain: RwLock<Chain<C>>,
+}
+
+impl<C: ClientTypes> InMemoryProvider<C>
+where
+    C::BeaconState: StateView,
+    C::SignedBeaconBlock: BlockView,
+{
+    /// Creates a provider for the chain described by `context` starting from `genesis_state`.
+    pub fn new(
+        context: &Context,
+        genesis_state: C::BeaconState,
+    ) -> Result<Self, MerkleizationError> {
+        let genesis_details = GenesisDetails {
+            genesis_time: genesis_state.genesis_time(),
+            genesis_validators_root: genesis_state.genesis_validators_root(),
+            genesis_fork_version: context.genesis_fork_version,
+        };
+        let chain = Chain {
+            genesis_details,
+            spec: spec(context),
+            fork_schedule: fork_schedule(context),
+            slots_per_epoch: context.slots_per_epoch,
+            states: vec![(genesis_state.state_root()?, genesis_state)],
+            blocks: vec![],
+            blob_sidecars: HashMap::new(),
+            proposer_duties: HashMap::new(),
+            attester_duties: HashMap::new(),
+            sync_duties: HashMap::new(),
+            sync_status: None,
+            submitted_blocks: vec![],
+            submitted_attestations: vec![],
+        };
+        Ok(Self { chain: RwLock::new(chain) })
+    }
+
+    fn chain(&self) -> RwLockReadGuard<'_, Chain<C>> {
+        // NOTE: no code panics while holding the lock so it is never poisoned
+        self.chain.read().expect("can read chain")
+    }
+
+    /// Adds `state` to the chain, returning its root.
+    pub fn insert_state(&self, state: C::BeaconState) -> Result<Root, MerkleizationError> {
+        let root = state.state_root()?;
+        self.chain.write().expect("can write chain").states.push((root, state));
+        Ok(root)
+    }
+
+    /// Adds `block` to the chain, returning its root.
+    pub fn insert_block(&self, block: C::SignedBeaconBlock) -> Result<Root, MerkleizationError> {
+        let root = block.block_root()?;
+        self.chain.write().expect("can write chain").blocks.push((root, block));
+        Ok(root)
+    }
+
+    pub fn insert_blob_sidecars(&self, block_root: Root, sidecars: Vec<C::BlobSidecar>) {
+        self.chain.write().expect("can write chain").blob_sidecars.insert(block_root, sidecars);
+    }
+
+    pub fn set_proposer_duties(
+        &self,
+        epoch: Epoch,
+        dependent_root: Root,
+        duties: Vec<ProposerDuty>,
+    ) {
+        let mut chain = self.chain.write().expect("can write chain");
+        chain.proposer_duties.insert(epoch, (dependent_root, duties));
+    }
+
+    pub fn set_attester_duties(
+        &self,
+        epoch: Epoch,
+        dependent_root: Root,
+        duties: Vec<AttestationDuty>,
+    ) {
+        let mut chain = self.chain.write().expect("can write chain");
+        chain.attester_duties.insert(epoch, (dependent_root, duties));
+    }
+
+    pub fn set_sync_duties(&self, epoch: Epoch, duties: Vec<SyncCommitteeDuty>) {
+        self.chain.write().expect("can write chain").sync_duties.insert(epoch, duties);
+    }
+
+    /// Overrides the sync status, which otherwise reports a synced node at the head state.
+    pub fn set_sync_status(&self, sync_status: SyncStatus) {
+        self.chain.write().expect("can write chain").sync_status = Some(sync_status);
+    }
+
+    pub fn submitted_blocks(&self) -> Vec<C::SignedBeaconBlock> {
+        self.chain().submitted_blocks.clone()
+    }
+
+    pub fn submitted_attestations(&self) -> Vec<C::Attestation>
+    where
+        C::Attestation: Clone,
+    {
+        self.chain().submitted_attestations.clone()
+    }
+}
+
+impl<C> BeaconNodeProvider<C> for InMemoryProvider<C>
+where
+    C: ClientTypes + Send + Sync + 'static,
+    C::BeaconState: StateView,
+    C::SignedBeaconBlock: BlockView,
+    C::BlobSidecar: Send + Sync,
+    C::Attestation: Send + Sync,
+{
+    fn genesis_details(&self) -> Result<GenesisDetails, ApiError> {
+        Ok(self.chain().genesis_details.clone())
+    }
+
+    fn state_root(&self, id: &StateId) -> Result<Root, ApiError> {
+        self.chain().state(id).map(|(root, _)| *root)
+    }
+
+    fn fork(&self, id: &StateId) -> Result<Fork, ApiError> {
+        self.chain().state(id).map(|(_, state)| state.fork().clone())
+    }
+
+    fn finality_checkpoints(&self, id: &StateId) -> Result<FinalityCheckpoints, ApiError> {
+        self.chain().state(id).map(|(_, state)| state.finality_checkpoints())
+    }
+
+    fn validators(
+        &self,
+        id: &StateId,
+        ids: &[PublicKeyOrIndex],
+        statuses: &[ValidatorStatus],
+    ) -> Result<Vec<ValidatorSummary>, ApiError> {
+        let chain = self.chain();
+        let (_, state) = chain.state(id)?;
+        let epoch = state.slot() / chain.slots_per_epoch;
+        let summaries = state
+            .validators()
+            .iter()
+            .zip(state.balances())
+            .enumerate()
+            .filter(|(index, (validator, _))| {
+                ids.is_empty() || ids.iter().any(|filter| id_matches(filter, *index, validator))
+            })
+            .map(|(index, (validator, balance))| ValidatorSummary {
+                index,
+                balance: *balance,
+                status: validator_status(validator, epoch),
+                validator: validator.clone(),
+            })
+            .filter(|summary| {
+                statuses.is_empty() ||
+                    statuses.iter().any(|filter| status_matches(summary.status, *filter))
+            })
+            .collect();
+        Ok(summaries)
+    }
+
+    fn balances(
+        &self,
+        id: &StateId,
+        ids: &[PublicKeyOrIndex],
+    ) -> Result<Vec<BalanceSummary>, ApiError> {
+        let chain = self.chain();
+        let (_, state) = chain.state(id)?;
+        let balances = state
+            .validators()
+            .iter()
+            .zip(state.balances())
+            .enumerate()
+            .filter(|(index, (validator, _))| {
+                ids.is_empty() || ids.iter().any(|filter| id_matches(filter, *index, validator))
+            })
+            .map(|(index, (_, balance))| BalanceSummary { index, balance: *balance })
+            .collect();
+        Ok(balances)
+    }
+
+    fn beacon_header(&self, id: &BlockId) -> Result<BeaconHeaderSummary, ApiError> {
+        let chain = self.chain();
+        let (root, block) = chain.block(id)?;
+        let header = block.header().map_err(|err| bad_request(err.to_string()))?;
+        Ok(BeaconHeaderSummary { root: *root, canonical: true, header })
+    }
+
+    fn beacon_block(&self, id: &BlockId) -> Result<(Version, C::SignedBeaconBlock), ApiError> {
+        self.chain().block(id).map(|(_, block)| (block.version(), block.clone()))
+    }
+
+    fn beacon_block_root(&self, id: &BlockId) -> Result<Root, ApiError> {
+        self.chain().block(id).map(|(root, _)| *root)
+    }
+
+    fn blob_sidecars(
+        &self,
+        id: &BlockId,
+        indices: &[BlobIndex],
+    ) -> Result<Vec<C::BlobSidecar>, ApiError> {
+        let chain = self.chain();
+        let (root, _) = chain.block(id)?;
+        let sidecars = chain.blob_sidecars.get(root).map(Vec::as_slice).unwrap_or_default();
+        let sidecars = sidecars
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| indices.is_empty() || indices.contains(index))
+            .map(|(_, sidecar)| sidecar.clone())
+            .collect();
+        Ok(sidecars)
+    }
+
+    fn state(&self, id: &StateId) -> Result<(Version, C::BeaconState), ApiError> {
+        self.chain().state(id).map(|(_, state)| (state.version(), state.clone()))
+    }
+
+    fn spec(&self) -> Result<HashMap<String, String>, ApiError> {
+        Ok(self.chain().spec.clone())
+    }
+
+    fn fork_schedule(&self) -> Result<Vec<Fork>, ApiError> {
+        Ok(self.chain().fork_schedule.clone())
+    }
+
+    fn sync_status(&self) -> Result<SyncStatus, ApiError> {
+        let chain = self.chain();
+        if let Some(status) = &chain.sync_status {
+            return Ok(status.clone())
+        }
+        let head_slot = chain.head_state().map(|(_, state)| state.slot()).unwrap_or_default();
+        Ok(SyncStatus { head_slot, sync_distance: 0, is_syncing: false })
+    }
+
+    fn health(&self) -> HealthStatus {
+        match &self.chain().sync_status {
+            Some(status) if status.is_syncing => HealthStatus::Syncing,
+            _ => HealthStatus::Ready,
+        }
+    }
+
+    fn proposer_duties(&self, epoch: Epoch) -> Result<(Root, Vec<ProposerDuty>), ApiError> {
+        let chain = self.chain();
+        let (dependent_root, duties) = chain
+            .proposer_duties
+            .get(&epoch)
+            .ok_or_else(|| not_found(format!("no proposer duties for epoch {epoch}")))?;
+        Ok((*dependent_root, duties.clone()))
+    }
+
+    fn attester_duties(
+        &self,
+        epoch: Epoch,
+        indices: &[ValidatorIndex],
+    ) -> Result<(Root, Vec<AttestationDuty>), ApiError> {
+        let chain = self.chain();
+        let (dependent_root, duties) = chain
+            .attester_duties
+            .get(&epoch)
+            .ok_or_else(|| not_found(format!("no attester duties for epoch {epoch}")))?;
+        let duties =
+            duties.iter().filter(|duty| indices.contains(&duty.validator_index)).cloned().collect();
+        Ok((*dependent_root, duties))
+    }
+
+    fn sync_duties(
+        &self,
+        epoch: Epoch,
+        indices: &[ValidatorIndex],
+    ) -> Result<Vec<SyncCommitteeDuty>, ApiError> {
+        let chain = self.chain();
+        let duties = chain
+            .sync_duties
+            .get(&epoch)
+            .ok_or_else(|| not_found(format!("no sync committee duties for epoch {epoch}")))?;
+        let duties =
+            duties.iter().filter(|duty| indices.contains(&duty.validator_index)).cloned().collect();
+        Ok(duties)
+    }
+
+    fn submit_block(&self, block: C::SignedBeaconBlock) -> Result<(), ApiError> {
+        self.chain.write().expect("can write chain").submitted_blocks.push(block);
+        Ok(())
+    }
+
+    fn submit_attestations(&self, attestations: Vec<C::Attestation>) -> Result<(), ApiError> {
+        let mut chain = self.chain.write().expect("can write chain");
+        chain.submitted_attestations.extend(attestations);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        presets::minimal::{self, MinimalClientTypes},
+        Error, MockBeaconNode,
+    };
+    use ethereum_consensus::{
+        phase0::minimal as phase0,
+        primitives::BlsPublicKey,
+        ssz::prelude::List,
+        types::minimal::{BeaconState, SignedBeaconBlock},
+    };
+    use http::StatusCode;
+
+    const GENESIS_TIME: u64 = 1_606_824_023;
+
+    fn validator(public_key: u8, exit_epoch: Epoch) -> Validator {
+        Validator {
+            public_key: BlsPublicKey::try_from([public_key; 48].as_slice()).unwrap(),
+            effective_balance: 32_000_000_000,
+            exit_epoch,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        }
+    }
+
+    fn state(slot: Slot) -> BeaconState {
+        let validators = vec![validator(1, FAR_FUTURE_EPOCH), validator(2, 4)];
+        let balances = vec![32_000_000_000 + slot, 31_000_000_000];
+        BeaconState::Phase0(phase0::BeaconState {
+            genesis_time: GENESIS_TIME,
+            slot,
+            validators: List::try_from(validators).unwrap(),
+            balances: List::try_from(balances).unwrap(),
+            ..Default::default()
+        })
+    }
+
+    fn assert_api_error(err: Error, expected: StatusCode) {
+        match err {
+            Error::Api(err) => assert_eq!(err.code(), expected),
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_provider() {
+        let context = Context::for_minimal();
+        let provider = InMemoryProvider::<MinimalClientTypes>::new(&context, state(0)).unwrap();
+        let genesis_root = provider.chain().states[0].0;
+        let state_root = provider.insert_state(state(1)).unwrap();
+        let block = SignedBeaconBlock::Phase0(phase0::SignedBeaconBlock {
+            message: phase0::BeaconBlock {
+                slot: 1,
+                proposer_index: 1,
+                state_root,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let block_root = provider.insert_block(block.clone()).unwrap();
+        let duty = SyncCommitteeDuty {
+            public_key: Default::default(),
+            validator_index: 1,
+            validator_sync_committee_indices: vec![3, 7],
+        };
+        provider.set_sync_duties(0, vec![duty]);
+
+        let node = MockBeaconNode::<MinimalClientTypes, _>::new(provider);
+        let server = node.spawn(([127, 0, 0, 1], 0).into()).unwrap();
+        let client = minimal::Client::new(server.endpoint());
+
+        let details = client.get_genesis_details().await.unwrap();
+        assert_eq!(details.genesis_time, GENESIS_TIME);
+        assert_eq!(details.genesis_fork_version, context.genesis_fork_version);
+        assert_eq!(client.get_state_root(StateId::Head).await.unwrap(), state_root);
+        assert_eq!(client.get_state_root(StateId::Finalized).await.unwrap(), genesis_root);
+        assert_eq!(client.get_state(StateId::Root(state_root)).await.unwrap().slot(), 1);
+
+        let statuses = [ValidatorStatus::ActiveExiting];
+        let validators = client.get_validators(StateId::Head, &[], &statuses).await.unwrap();
+        assert_eq!(validators.len(), 1);
+        assert_eq!(validators[0].index, 1);
+        let public_key = PublicKeyOrIndex::PublicKey(validators[0].validator.public_key.clone());
+        let balances = client.get_balances(StateId::Genesis, &[public_key]).await.unwrap();
+        assert_eq!(balances.len(), 1);
+        assert_eq!((balances[0].index, balances[0].balance), (1, 31_000_000_000));
+
+        let header = client.get_beacon_header(BlockId::Head).await.unwrap();
+        assert_eq!(header.root, block_root);
+        assert_eq!(header.header.message.state_root, state_root);
+        assert_eq!(client.get_beacon_block_root(BlockId::Slot(1)).await.unwrap(), block_root);
+        let fetched = client.get_beacon_block(BlockId::Root(block_root)).await.unwrap();
+        assert_eq!(fetched.message().proposer_index(), 1);
+
+        let duties = client.get_sync_committee_duties(0, &[0, 1]).await.unwrap();
+        assert_eq!(duties.len(), 1);
+        assert_eq!(duties[0].validator_sync_committee_indices, vec![3, 7]);
+        assert_eq!(client.get_sync_status().await.unwrap().head_slot, 1);
+
+        client.post_signed_beacon_block(&block).await.unwrap();
+        assert_eq!(node.provider().submitted_blocks().len(), 1);
+
+        let err = client.get_state_root(StateId::Slot(5)).await.unwrap_err();
+        assert_api_error(err, StatusCode::NOT_FOUND);
+        let err = client.get_sync_committee_duties(1, &[1]).await.unwrap_err();
+        assert_api_error(err, StatusCode::NOT_FOUND);
+        let err = client.get_attestation_data(1, 0).await.unwrap_err();
+        assert_api_error(err, StatusCode::NOT_IMPLEMENTED);
+        let err = client.get_block_rewards(BlockId::Head).await.unwrap_err();
+        assert_api_error(err, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,994 @@
+//! A local beacon node serving the routes `Client` calls from a [`BeaconNodeProvider`].
+//!
+//! The server is meant for integration tests: it binds to a local address, answers requests with
+//! the data and errors of its provider in the same shapes as a real node and forwards events
+//! given to [`MockBeaconNode::emit`] to subscribers of `/eth/v1/events`.
+//!
+//! Only part of the API is routed: the genesis, state, validator, pending queue, header, block,
+//! block root, blob sidecar, rewards and light client update reads of the `beacon` namespace
+//! along with block and attestation submission, the `debug` state, the `config` and `node`
+//! namespaces, and the duties, v3 block proposals, attestation data, aggregation and liveness of
+//! the `validator` namespace. Notably blinded blocks, reads of the operation pools, committees and
+//! the other light client data are not routed and answer `404 Not Found`.
+mod memory;
+mod provider;
+
+pub use memory::{BlockView, InMemoryProvider, StateView};
+pub use provider::{bad_request, not_found, BeaconNodeProvider};
+
+use crate::{
+    api_client::{
+        ClientTypes, CONSENSUS_BLOCK_VALUE_HEADER, EXECUTION_PAYLOAD_BLINDED_HEADER,
+        EXECUTION_PAYLOAD_VALUE_HEADER,
+    },
+    encoding::{JSON_MIME_TYPE, SSZ_MIME_TYPE},
+    events::{Event, ALL_TOPICS},
+    types::{
+        BlockContents, BlockId, BlockProposal, HealthStatus, ProduceBlockResponse,
+        PublicKeyOrIndex, RootData, StateId, SubmitSignedBeaconBlock, Value, VersionData,
+        VersionedValue,
+    },
+    ApiError, ETH_CONSENSUS_VERSION_HEADER,
+};
+use ethereum_consensus::{
+    primitives::{BlobIndex, Epoch, Root, ValidatorIndex},
+    ssz::prelude::{serialize, SimpleSerialize},
+    Fork as Version,
+};
+use futures_util::stream;
+use http::{
+    header::{ACCEPT, CACHE_CONTROL, CONTENT_TYPE},
+    HeaderMap, Method, StatusCode,
+};
+use hyper::{
+    body::Bytes,
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server,
+};
+use std::{
+    collections::HashMap, convert::Infallible, marker::PhantomData, net::SocketAddr, str::FromStr,
+    sync::Arc,
+};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::JoinHandle,
+};
+use tracing::warn;
+use url::Url;
+
+const EVENT_STREAM_MIME_TYPE: &str = "text/event-stream";
+// Events emitted while a subscriber lags further behind than this are dropped for that subscriber.
+const EVENT_BUFFER_SIZE: usize = 256;
+
+#[derive(Clone)]
+struct RawEvent {
+    topic: &'static str,
+    data: String,
+}
+
+/// A running [`MockBeaconNode`]; the server stops when this is dropped.
+pub struct MockServer {
+    address: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The URL to give to a `Client` to reach this server.
+    pub fn endpoint(&self) -> Url {
+        let endpoint = format!("http://{}", self.address);
+        Url::parse(&endpoint).expect("is valid URL")
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Serves the beacon API backed by the provider `P`.
+pub struct MockBeaconNode<C, P> {
+    provider: Arc<P>,
+    // `None` ends the response of every subscriber
+    events: broadcast::Sender<Option<RawEvent>>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C, P> Clone for MockBeaconNode<C, P> {
+    fn clone(&self) -> Self {
+        Self { provider: self.provider.clone(), events: self.events.clone(), _phantom: PhantomData }
+    }
+}
+
+fn json_response<T: serde::Serialize>(value: &T) -> Result<Response<Body>, ApiError> {
+    let body = serde_json::to_vec(value).map_err(|err| internal_error(err.to_string()))?;
+    let response = Response::builder()
+        .header(CONTENT_TYPE, JSON_MIME_TYPE)
+        .body(Body::from(body))
+        .expect("is valid response");
+    Ok(response)
+}
+
+fn value_response<T>(data: T) -> Result<Response<Body>, ApiError>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    json_response(&Value { data, meta: HashMap::new() })
+}
+
+fn value_with_dependent_root_response<T>(
+    dependent_root: Root,
+    data: T,
+) -> Result<Response<Body>, ApiError>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let dependent_root =
+        serde_json::to_value(dependent_root).map_err(|err| internal_error(err.to_string()))?;
+    let meta = HashMap::from_iter([("dependent_root".to_string(), dependent_root)]);
+    json_response(&Value { data, meta })
+}
+
+fn accepts_ssz(headers: &HeaderMap) -> bool {
+    headers
+        .get(ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.contains(SSZ_MIME_TYPE))
+        .unwrap_or_default()
+}
+
+fn ssz_response(body: Vec<u8>) -> Response<Body> {
+    Response::builder()
+        .header(CONTENT_TYPE, SSZ_MIME_TYPE)
+        .body(Body::from(body))
+        .expect("is valid response")
+}
+
+fn insert_header(response: &mut Response<Body>, name: &'static str, value: impl ToString) {
+    let value = value.to_string().parse().expect("is valid header value");
+    response.headers_mut().insert(name, value);
+}
+
+// Answers with SSZ if the client accepts it and JSON otherwise, along with the consensus version.
+fn versioned_response<T>(
+    headers: &HeaderMap,
+    version: Version,
+    data: T,
+) -> Result<Response<Body>, ApiError>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + SimpleSerialize,
+{
+    let mut response = if accepts_ssz(headers) {
+        let body = serialize(&data).map_err(|err| internal_error(err.to_string()))?;
+        ssz_response(body)
+    } else {
+        json_response(&VersionedValue { version, data, meta: HashMap::new() })?
+    };
+    insert_header(&mut response, ETH_CONSENSUS_VERSION_HEADER, version);
+    Ok(response)
+}
+
+// Answers with the block of `proposal` as `versioned_response` does, along with the headers
+// describing the proposal.
+fn block_proposal_response<BeaconBlock, BlindedBeaconBlock, Blob>(
+    headers: &HeaderMap,
+    proposal: BlockProposal<BeaconBlock, BlindedBeaconBlock, Blob>,
+) -> Result<Response<Body>, ApiError>
+where
+    BeaconBlock: serde::Serialize + serde::de::DeserializeOwned + SimpleSerialize,
+    BlindedBeaconBlock: serde::Serialize + serde::de::DeserializeOwned + SimpleSerialize,
+    Blob: serde::Serialize + serde::de::DeserializeOwned + SimpleSerialize,
+{
+    let BlockProposal { version, block, execution_payload_value, consensus_block_value } = proposal;
+    let is_blinded = block.is_blinded();
+    let mut response = match block {
+        ProduceBlockResponse::Blinded(block) => versioned_response(headers, version, block)?,
+        ProduceBlockResponse::Full(contents)
+            if BlockContents::<BeaconBlock, Blob>::is_bare_block(version) =>
+        {
+            versioned_response(headers, version, contents.block)?
+        }
+        ProduceBlockResponse::Full(contents) => {
+            let mut response = if accepts_ssz(headers) {
+                // NOTE: `BlockContents` has the same SSZ layout as the `SignedBlockContents`
+                // submitted when publishing a block
+                let body = SubmitSignedBeaconBlock {
+                    signed_block: &contents.block,
+                    kzg_proofs: Some(&contents.kzg_proofs),
+                    blobs: Some(&contents.blobs),
+                }
+                .to_ssz_bytes()
+                .map_err(|err| internal_error(err.to_string()))?;
+                ssz_response(body)
+            } else {
+                json_response(&VersionedValue { version, data: contents, meta: HashMap::new() })?
+            };
+            insert_header(&mut response, ETH_CONSENSUS_VERSION_HEADER, version);
+            response
+        }
+    };
+    insert_header(&mut response, EXECUTION_PAYLOAD_BLINDED_HEADER, is_blinded);
+    insert_header(&mut response, EXECUTION_PAYLOAD_VALUE_HEADER, execution_payload_value);
+    insert_header(&mut response, CONSENSUS_BLOCK_VALUE_HEADER, consensus_block_value);
+    Ok(response)
+}
+
+fn empty_response(status: StatusCode) -> Response<Body> {
+    Response::builder().status(status).body(Body::empty()).expect("is valid response")
+}
+
+fn error_response(error: ApiError) -> Response<Body> {
+    let code = error.code();
+    let body = serde_json::to_vec(&error).expect("can serialize error");
+    Response::builder()
+        .status(code)
+        .header(CONTENT_TYPE, JSON_MIME_TYPE)
+        .body(Body::from(body))
+        .expect("is valid response")
+}
+
+fn internal_error(message: String) -> ApiError {
+    ApiError::ErrorMessage { code: StatusCode::INTERNAL_SERVER_ERROR, message }
+}
+
+// Returns every value of `key` in `query`, splitting comma-separated lists.
+fn query_values(query: Option<&str>, key: &str) -> Vec<String> {
+    let query = query.unwrap_or_default();
+    url::form_urlencoded::parse(query.as_bytes())
+        .filter(|(name, _)| name == key)
+        .flat_map(|(_, value)| value.split(',').map(str::to_string).collect::<Vec<_>>())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
+fn parse<T: FromStr>(value: &str, name: &str) -> Result<T, ApiError> {
+    value.parse().map_err(|_| bad_request(format!("invalid {name}: {value}")))
+}
+
+fn parse_all<T: FromStr>(values: &[String], name: &str) -> Result<Vec<T>, ApiError> {
+    values.iter().map(|value| parse(value, name)).collect()
+}
+
+// Parses a hex-encoded value such as a signature from a query parameter.
+fn parse_hex<T: serde::de::DeserializeOwned>(value: &str, name: &str) -> Result<T, ApiError> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| bad_request(format!("invalid {name}: {value}")))
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(body: &[u8]) -> Result<T, ApiError> {
+    serde_json::from_slice(body).map_err(|err| bad_request(format!("invalid request body: {err}")))
+}
+
+impl<C, P> MockBeaconNode<C, P>
+where
+    C: ClientTypes + Send + Sync + 'static,
+    P: BeaconNodeProvider<C>,
+{
+    pub fn new(provider: P) -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER_SIZE);
+        Self { provider: Arc::new(provider), events, _phantom: PhantomData }
+    }
+
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Sends `event` to every current subscriber of its topic.
+    pub fn emit(&self, event: &Event<C>) -> Result<(), serde_json::Error> {
+        let data = serde_json::to_string(&event.data_to_json()?)?;
+        // NOTE: sending only fails if there are no subscribers, in which case the event is dropped
+        let _ = self.events.send(Some(RawEvent { topic: event.topic(), data }));
+        Ok(())
+    }
+
+    /// The number of open subscriptions to `/eth/v1/events`.
+    pub fn event_subscribers(&self) -> usize {
+        self.events.receiver_count()
+    }
+
+    /// Ends the response of every current subscriber of `/eth/v1/events`, as if the node dropped
+    /// the connections.
+    pub fn close_event_streams(&self) {
+        let _ = self.events.send(None);
+    }
+
+    /// Starts serving on `address` (e.g. `127.0.0.1:0` for any free port) in a new task.
+    pub fn spawn(&self, address: SocketAddr) -> Result<MockServer, hyper::Error> {
+        let node = self.clone();
+        let make_service = make_service_fn(move |_| {
+            let node = node.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let node = node.clone();
+                    async move { Ok::<_, Infallible>(node.handle(request).await) }
+                }))
+            }
+        });
+        let server = Server::try_bind(&address)?.serve(make_service);
+        let address = server.local_addr();
+        let task = tokio::spawn(async move {
+            if let Err(err) = server.await {
+                warn!(%err, "mock beacon node stopped");
+            }
+        });
+        Ok(MockServer { address, task })
+    }
+
+    async fn handle(&self, request: Request<Body>) -> Response<Body> {
+        let (parts, body) = request.into_parts();
+        let body = match hyper::body::to_bytes(body).await {
+            Ok(body) => body,
+            Err(err) => return error_response(bad_request(format!("could not read body: {err}"))),
+        };
+        let path = parts.uri.path().trim_matches('/');
+        let segments = path.split('/').collect::<Vec<_>>();
+        let query = parts.uri.query();
+        let result = match (&parts.method, segments.as_slice()) {
+            (&Method::GET, ["eth", "v1", "events"]) => self.subscribe(query),
+            (method, segments) => self.route(method, segments, query, &parts.headers, body),
+        };
+        result.unwrap_or_else(error_response)
+    }
+
+    fn subscribe(&self, query: Option<&str>) -> Result<Response<Body>, ApiError> {
+        let requested = query_values(query, "topics");
+        if requested.is_empty() {
+            return Err(bad_request("missing `topics`"))
+        }
+        let mut topics = vec![];
+        for topic in requested {
+            let topic = ALL_TOPICS
+                .iter()
+                .find(|name| **name == topic)
+                .ok_or_else(|| bad_request(format!("unknown topic: {topic}")))?;
+            topics.push(*topic);
+        }
+        let events = self.events.subscribe();
+        let stream = stream::unfold((events, topics), |(mut events, topics)| async move {
+            loop {
+                match events.recv().await {
+                    Ok(Some(event)) if topics.contains(&event.topic) => {
+                        let message = format!("event: {}\ndata: {}\n\n", event.topic, event.data);
+                        return Some((Ok::<_, Infallible>(message), (events, topics)))
+                    }
+                    Ok(Some(_)) | Err(RecvError::Lagged(_)) => continue,
+                    Ok(None) | Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+        let response = Response::builder()
+            .header(CONTENT_TYPE, EVENT_STREAM_MIME_TYPE)
+            .header(CACHE_CONTROL, "no-cache")
+            .body(Body::wrap_stream(stream))
+            .expect("is valid response");
+        Ok(response)
+    }
+
+    fn route(
+        &self,
+        method: &Method,
+        segments: &[&str],
+        query: Option<&str>,
+        headers: &HeaderMap,
+        body: Bytes,
+    ) -> Result<Response<Body>, ApiError> {
+        let provider = &self.provider;
+        match (method, segments) {
+            (&Method::GET, ["eth", "v1", "beacon", "genesis"]) => {
+                value_response(provider.genesis_details()?)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "states", id, "root"]) => {
+                let root = provider.state_root(&parse(id, "state id")?)?;
+                value_response(RootData { root })
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "states", id, "fork"]) => {
+                value_response(provider.fork(&parse(id, "state id")?)?)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "states", id, "finality_checkpoints"]) => {
+                value_response(provider.finality_checkpoints(&parse(id, "state id")?)?)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "states", id, "validators"]) => {
+                let ids = parse_all(&query_values(query, "id"), "validator id")?;
+                let statuses = query_values(query, "status")
+                    .into_iter()
+                    .map(|status| {
+                        serde_json::from_value(serde_json::Value::String(status.clone()))
+                            .map_err(|_| bad_request(format!("invalid validator status: {status}")))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                value_response(provider.validators(&parse(id, "state id")?, &ids, &statuses)?)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "states", id, "validators", validator_id]) => {
+                let validator_id: PublicKeyOrIndex = parse(validator_id, "validator id")?;
+                let validator = provider
+                    .validators(&parse(id, "state id")?, &[validator_id.clone()], &[])?
+                    .pop()
+                    .ok_or_else(|| not_found(format!("could not find validator {validator_id}")))?;
+                value_response(validator)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "states", id, "validator_balances"]) => {
+                let ids = parse_all(&query_values(query, "id"), "validator id")?;
+                value_response(provider.balances(&parse(id, "state id")?, &ids)?)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "states", id, "pending_deposits"]) => {
+                value_response(provider.pending_deposits(&parse(id, "state id")?)?)
+            }
+            (
+                &Method::GET,
+                ["eth", "v1", "beacon", "states", id, "pending_partial_withdrawals"],
+            ) => value_response(provider.pending_partial_withdrawals(&parse(id, "state id")?)?),
+            (&Method::GET, ["eth", "v1", "beacon", "states", id, "pending_consolidations"]) => {
+                value_response(provider.pending_consolidations(&parse(id, "state id")?)?)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "headers"]) => {
+                let id = match query_values(query, "slot").first() {
+                    Some(slot) => BlockId::Slot(parse(slot, "slot")?),
+                    None => BlockId::Head,
+                };
+                value_response(provider.beacon_header(&id)?)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "headers", id]) => {
+                value_response(provider.beacon_header(&parse(id, "block id")?)?)
+            }
+            (&Method::POST, ["eth", "v1" | "v2", "beacon", "blocks"]) => {
+                let is_ssz = headers
+                    .get(CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .map(|value| value.starts_with(SSZ_MIME_TYPE))
+                    .unwrap_or_default();
+                if is_ssz {
+                    let message = "only JSON request bodies are supported".to_string();
+                    return Err(ApiError::ErrorMessage {
+                        code: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                        message,
+                    })
+                }
+                // NOTE: the block contents of `deneb` wrap the block with its blobs, which are
+                // not passed on to the provider
+                let mut request: serde_json::Value = parse_json(&body)?;
+                let block = match request.get_mut("signed_block") {
+                    Some(block) => block.take(),
+                    None => request,
+                };
+                let block = serde_json::from_value(block)
+                    .map_err(|err| bad_request(format!("invalid block: {err}")))?;
+                provider.submit_block(block)?;
+                Ok(empty_response(StatusCode::OK))
+            }
+            (&Method::GET, ["eth", "v2", "beacon", "blocks", id]) => {
+                let (version, block) = provider.beacon_block(&parse(id, "block id")?)?;
+                versioned_response(headers, version, block)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "blocks", id, "root"]) => {
+                let root = provider.beacon_block_root(&parse(id, "block id")?)?;
+                value_response(RootData { root })
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "blob_sidecars", id]) => {
+                let indices: Vec<BlobIndex> =
+                    parse_all(&query_values(query, "indices"), "blob index")?;
+                value_response(provider.blob_sidecars(&parse(id, "block id")?, &indices)?)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "rewards", "blocks", id]) => {
+                value_response(provider.block_rewards(&parse(id, "block id")?)?)
+            }
+            (&Method::POST, ["eth", "v1", "beacon", "rewards", "attestations", epoch]) => {
+                let ids: Vec<String> = parse_json(&body)?;
+                let ids = parse_all(&ids, "validator id")?;
+                value_response(provider.attestation_rewards(parse(epoch, "epoch")?, &ids)?)
+            }
+            (&Method::POST, ["eth", "v1", "beacon", "rewards", "sync_committee", id]) => {
+                let ids: Vec<String> = parse_json(&body)?;
+                let ids = parse_all(&ids, "validator id")?;
+                value_response(provider.sync_committee_rewards(&parse(id, "block id")?, &ids)?)
+            }
+            (&Method::GET, ["eth", "v1", "beacon", "light_client", "updates"]) => {
+                let start_period = query_values(query, "start_period");
+                let start_period =
+                    start_period.first().ok_or_else(|| bad_request("missing `start_period`"))?;
+                let count = query_values(query, "count");
+                let count = count.first().ok_or_else(|| bad_request("missing `count`"))?;
+                let updates = provider
+                    .light_client_updates(parse(start_period, "period")?, parse(count, "count")?)?;
+                // each update carries its own `version`
+                let updates = updates
+                    .into_iter()
+                    .map(|(version, data)| VersionedValue { version, data, meta: HashMap::new() })
+                    .collect::<Vec<_>>();
+                json_response(&updates)
+            }
+            (&Method::POST, ["eth", "v1", "beacon", "pool", "attestations"]) => {
+                provider.submit_attestations(parse_json(&body)?)?;
+                Ok(empty_response(StatusCode::OK))
+            }
+            (&Method::POST, ["eth", "v2", "beacon", "pool", "attestations"]) => {
+                provider.submit_single_attestations(parse_json(&body)?)?;
+                Ok(empty_response(StatusCode::OK))
+            }
+            (&Method::GET, ["eth", "v2", "debug", "beacon", "states", id]) => {
+                let id: StateId = parse(id, "state id")?;
+                let (version, state) = provider.state(&id)?;
+                versioned_response(headers, version, state)
+            }
+            (&Method::GET, ["eth", "v1", "config", "spec"]) => value_response(provider.spec()?),
+            (&Method::GET, ["eth", "v1", "config", "fork_schedule"]) => {
+                value_response(provider.fork_schedule()?)
+            }
+            (&Method::GET, ["eth", "v1", "node", "version"]) => {
+                value_response(VersionData { version: provider.node_version()? })
+            }
+            (&Method::GET, ["eth", "v1", "node", "syncing"]) => {
+                value_response(provider.sync_status()?)
+            }
+            (&Method::GET, ["eth", "v1", "node", "health"]) => {
+                let status = match provider.health() {
+                    HealthStatus::Ready => StatusCode::OK,
+                    HealthStatus::Syncing => StatusCode::PARTIAL_CONTENT,
+                    HealthStatus::NotInitialized | HealthStatus::Unknown => {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    }
+                };
+                Ok(empty_response(status))
+            }
+            (&Method::GET, ["eth", "v1", "validator", "duties", "proposer", epoch]) => {
+                let epoch: Epoch = parse(epoch, "epoch")?;
+                let (dependent_root, duties) = provider.proposer_duties(epoch)?;
+                value_with_dependent_root_response(dependent_root, duties)
+            }
+            (&Method::POST, ["eth", "v1", "validator", "duties", "attester", epoch]) => {
+                let epoch: Epoch = parse(epoch, "epoch")?;
+                let indices: Vec<String> = parse_json(&body)?;
+                let indices: Vec<ValidatorIndex> = parse_all(&indices, "validator index")?;
+                let (dependent_root, duties) = provider.attester_duties(epoch, &indices)?;
+                value_with_dependent_root_response(dependent_root, duties)
+            }
+            (&Method::POST, ["eth", "v1", "validator", "duties", "sync", epoch]) => {
+                let epoch: Epoch = parse(epoch, "epoch")?;
+                let indices: Vec<String> = parse_json(&body)?;
+                let indices: Vec<ValidatorIndex> = parse_all(&indices, "validator index")?;
+                value_response(provider.sync_duties(epoch, &indices)?)
+            }
+            (&Method::GET, ["eth", "v3", "validator", "blocks", slot]) => {
+                let randao_reveal = query_values(query, "randao_reveal");
+                let randao_reveal =
+                    randao_reveal.first().ok_or_else(|| bad_request("missing `randao_reveal`"))?;
+                let graffiti = query_values(query, "graffiti")
+                    .first()
+                    .map(|graffiti| parse_hex(graffiti, "graffiti"))
+                    .transpose()?;
+                let proposal = provider.block_proposal(
+                    parse(slot, "slot")?,
+                    &parse_hex(randao_reveal, "randao reveal")?,
+                    graffiti,
+                )?;
+                block_proposal_response(headers, proposal)
+            }
+            (&Method::GET, ["eth", "v1", "validator", "attestation_data"]) => {
+                let slot = query_values(query, "slot");
+                let slot = slot.first().ok_or_else(|| bad_request("missing `slot`"))?;
+                let committee_index = query_values(query, "committee_index");
+                let committee_index = committee_index
+                    .first()
+                    .ok_or_else(|| bad_request("missing `committee_index`"))?;
+                let data = provider.attestation_data(
+                    parse(slot, "slot")?,
+                    parse(committee_index, "committee index")?,
+                )?;
+                value_response(data)
+            }
+            (&Method::GET, ["eth", "v1", "validator", "aggregate_attestation"]) => {
+                let root = query_values(query, "attestation_data_root");
+                let root =
+                    root.first().ok_or_else(|| bad_request("missing `attestation_data_root`"))?;
+                let slot = query_values(query, "slot");
+                let slot = slot.first().ok_or_else(|| bad_request("missing `slot`"))?;
+                let aggregate = provider.aggregate_attestation(
+                    parse_hex(root, "attestation data root")?,
+                    parse(slot, "slot")?,
+                )?;
+                value_response(aggregate)
+            }
+            (&Method::GET, ["eth", "v2", "validator", "aggregate_attestation"]) => {
+                let root = query_values(query, "attestation_data_root");
+                let root =
+                    root.first().ok_or_else(|| bad_request("missing `attestation_data_root`"))?;
+                let slot = query_values(query, "slot");
+                let slot = slot.first().ok_or_else(|| bad_request("missing `slot`"))?;
+                let committee_index = query_values(query, "committee_index");
+                let committee_index = committee_index
+                    .first()
+                    .ok_or_else(|| bad_request("missing `committee_index`"))?;
+                let (version, aggregate) = provider.aggregate_attestation_v2(
+                    parse_hex(root, "attestation data root")?,
+                    parse(slot, "slot")?,
+                    parse(committee_index, "committee index")?,
+                )?;
+                let data = VersionedValue { version, data: aggregate, meta: HashMap::new() };
+                let mut response = json_response(&data)?;
+                insert_header(&mut response, ETH_CONSENSUS_VERSION_HEADER, version);
+                Ok(response)
+            }
+            (&Method::POST, ["eth", "v1" | "v2", "validator", "aggregate_and_proofs"]) => {
+                provider.submit_aggregates(parse_json(&body)?)?;
+                Ok(empty_response(StatusCode::OK))
+            }
+            (&Method::POST, ["eth", "v1", "validator", "liveness", epoch]) => {
+                let epoch: Epoch = parse(epoch, "epoch")?;
+                let indices: Vec<String> = parse_json(&body)?;
+                let indices: Vec<ValidatorIndex> = parse_all(&indices, "validator index")?;
+                value_response(provider.liveness(epoch, &indices)?)
+            }
+            (method, segments) => {
+                let message = format!("no route for {method} /{}", segments.join("/"));
+                Err(not_found(message))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api_error::IndexedError,
+        presets::mainnet,
+        types::{
+            AttestationRewards, PendingDeposit, SyncCommitteeReward, TotalAttestationRewards,
+            ValidatorLiveness,
+        },
+        Encoding, Error,
+    };
+    use ethereum_consensus::{
+        capella::mainnet as capella,
+        deneb::mainnet as deneb,
+        electra::{PendingConsolidation, PendingPartialWithdrawal},
+        primitives::{Bytes32, RandaoReveal, Slot},
+        ssz::prelude::U256,
+        types::mainnet::{BeaconBlock, BlindedBeaconBlock},
+    };
+
+    struct Provider;
+
+    impl BeaconNodeProvider<mainnet::MainnetClientTypes> for Provider {
+        fn node_version(&self) -> Result<String, ApiError> {
+            Ok("mock/v1.0.0".to_string())
+        }
+
+        fn health(&self) -> HealthStatus {
+            HealthStatus::Syncing
+        }
+
+        fn submit_attestations(
+            &self,
+            attestations: Vec<mainnet::Attestation>,
+        ) -> Result<(), ApiError> {
+            let failures = (0..attestations.len())
+                .map(|index| IndexedError { index, message: "invalid signature".to_string() })
+                .collect();
+            Err(ApiError::IndexedError {
+                code: StatusCode::BAD_REQUEST,
+                message: "some attestations failed validation".to_string(),
+                failures,
+            })
+        }
+    }
+
+    // Proposes a blinded `deneb` block at slot 1, a full `deneb` block with a blob at slot 2 and a
+    // full `capella` block otherwise.
+    struct Proposer;
+
+    impl BeaconNodeProvider<mainnet::MainnetClientTypes> for Proposer {
+        fn block_proposal(
+            &self,
+            slot: Slot,
+            randao_reveal: &RandaoReveal,
+            graffiti: Option<Bytes32>,
+        ) -> Result<BlockProposal<BeaconBlock, BlindedBeaconBlock, deneb::Blob>, ApiError> {
+            if randao_reveal != &RandaoReveal::default() || graffiti != Some(graffiti_for(slot)) {
+                return Err(bad_request("unexpected request"))
+            }
+            let (version, block) = match slot {
+                1 => {
+                    let mut block = deneb::BlindedBeaconBlock { slot, ..Default::default() };
+                    block.body.blob_kzg_commitments.push(Default::default());
+                    let block = BlindedBeaconBlock::Deneb(block);
+                    (Version::Deneb, ProduceBlockResponse::Blinded(block))
+                }
+                2 => {
+                    let mut block = deneb::BeaconBlock { slot, ..Default::default() };
+                    block.body.blob_kzg_commitments.push(Default::default());
+                    let contents = BlockContents {
+                        block: BeaconBlock::Deneb(block),
+                        kzg_proofs: vec![Default::default()],
+                        blobs: vec![Default::default()],
+                    };
+                    (Version::Deneb, ProduceBlockResponse::Full(contents))
+                }
+                _ => {
+                    let block = capella::BeaconBlock { slot, ..Default::default() };
+                    let contents = BlockContents::from_block(BeaconBlock::Capella(block));
+                    (Version::Capella, ProduceBlockResponse::Full(contents))
+                }
+            };
+            Ok(BlockProposal {
+                version,
+                block,
+                execution_payload_value: U256::from(slot * 1_000),
+                consensus_block_value: U256::from(slot),
+            })
+        }
+    }
+
+    fn graffiti_for(slot: Slot) -> Bytes32 {
+        Bytes32::try_from([slot as u8; 32].as_ref()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_block_proposal() {
+        let node = MockBeaconNode::<mainnet::MainnetClientTypes, _>::new(Proposer);
+        let server = node.spawn(([127, 0, 0, 1], 0).into()).unwrap();
+
+        for encoding in [Encoding::Json, Encoding::Ssz] {
+            let client = mainnet::Client::new(server.endpoint()).with_encoding(encoding);
+            let propose = |slot| {
+                client.get_block_proposal_v3(
+                    slot,
+                    RandaoReveal::default(),
+                    Some(graffiti_for(slot)),
+                    None,
+                )
+            };
+
+            let proposal = propose(1).await.unwrap();
+            assert_eq!(proposal.version, Version::Deneb);
+            assert_eq!(proposal.execution_payload_value, U256::from(1_000));
+            assert_eq!(proposal.consensus_block_value, U256::from(1));
+            match proposal.block {
+                ProduceBlockResponse::Blinded(BlindedBeaconBlock::Deneb(block)) => {
+                    assert_eq!(block.slot, 1);
+                    assert_eq!(block.body.blob_kzg_commitments.len(), 1);
+                }
+                block => panic!("unexpected block: {block:?}"),
+            }
+
+            let proposal = propose(2).await.unwrap();
+            assert_eq!(proposal.version, Version::Deneb);
+            assert_eq!(proposal.execution_payload_value, U256::from(2_000));
+            assert_eq!(proposal.consensus_block_value, U256::from(2));
+            match proposal.block {
+                ProduceBlockResponse::Full(BlockContents {
+                    block: BeaconBlock::Deneb(block),
+                    kzg_proofs,
+                    blobs,
+                }) => {
+                    assert_eq!(block.slot, 2);
+                    assert_eq!(kzg_proofs.len(), 1);
+                    assert_eq!(blobs.len(), 1);
+                }
+                block => panic!("unexpected block: {block:?}"),
+            }
+
+            let proposal = propose(3).await.unwrap();
+            assert_eq!(proposal.version, Version::Capella);
+            assert!(!proposal.block.is_blinded());
+            match proposal.block {
+                ProduceBlockResponse::Full(BlockContents {
+                    block: BeaconBlock::Capella(block),
+                    blobs,
+                    ..
+                }) => {
+                    assert_eq!(block.slot, 3);
+                    assert!(blobs.is_empty());
+                }
+                block => panic!("unexpected block: {block:?}"),
+            }
+
+            let err = client
+                .get_block_proposal_v3(4, RandaoReveal::default(), None, None)
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                Error::Api(ApiError::ErrorMessage { code: StatusCode::BAD_REQUEST, .. })
+            ));
+        }
+    }
+
+    // Answers rewards and liveness requests for the validators given by index, penalizing each
+    // validator by its index.
+    struct Rewards;
+
+    impl BeaconNodeProvider<mainnet::MainnetClientTypes> for Rewards {
+        fn attestation_rewards(
+            &self,
+            _epoch: Epoch,
+            ids: &[PublicKeyOrIndex],
+        ) -> Result<AttestationRewards, ApiError> {
+            let total_rewards = indices(ids)?
+                .into_iter()
+                .map(|validator_index| TotalAttestationRewards {
+                    validator_index,
+                    head: 0,
+                    target: -(validator_index as i64),
+                    source: 0,
+                    inclusion_delay: None,
+                    inactivity: 0,
+                })
+                .collect();
+            Ok(AttestationRewards { ideal_rewards: vec![], total_rewards })
+        }
+
+        fn sync_committee_rewards(
+            &self,
+            _id: &BlockId,
+            ids: &[PublicKeyOrIndex],
+        ) -> Result<Vec<SyncCommitteeReward>, ApiError> {
+            let rewards = indices(ids)?
+                .into_iter()
+                .map(|validator_index| SyncCommitteeReward {
+                    validator_index,
+                    reward: -(validator_index as i64),
+                })
+                .collect();
+            Ok(rewards)
+        }
+
+        fn liveness(
+            &self,
+            _epoch: Epoch,
+            indices: &[ValidatorIndex],
+        ) -> Result<Vec<ValidatorLiveness>, ApiError> {
+            let liveness =
+                indices.iter().map(|&index| ValidatorLiveness { index, is_live: index % 2 == 0 });
+            Ok(liveness.collect())
+        }
+    }
+
+    fn indices(ids: &[PublicKeyOrIndex]) -> Result<Vec<ValidatorIndex>, ApiError> {
+        ids.iter()
+            .map(|id| match id {
+                PublicKeyOrIndex::Index(index) => Ok(*index),
+                id => Err(bad_request(format!("unknown validator {id}"))),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rewards_and_liveness() {
+        let node = MockBeaconNode::<mainnet::MainnetClientTypes, _>::new(Rewards);
+        let server = node.spawn(([127, 0, 0, 1], 0).into()).unwrap();
+        let client = mainnet::Client::new(server.endpoint());
+        let ids = [PublicKeyOrIndex::Index(1), PublicKeyOrIndex::Index(2)];
+
+        // the node only accepts validator ids as strings, so these succeed only if the client
+        // sends string arrays
+        let rewards = client.get_attestation_rewards(3, &ids).await.unwrap();
+        let rewards =
+            rewards.total_rewards.iter().map(|reward| (reward.validator_index, reward.target));
+        assert_eq!(rewards.collect::<Vec<_>>(), [(1, -1), (2, -2)]);
+
+        let rewards = client.get_sync_committee_rewards(BlockId::Head, &ids).await.unwrap();
+        let rewards = rewards.iter().map(|reward| (reward.validator_index, reward.reward));
+        assert_eq!(rewards.collect::<Vec<_>>(), [(1, -1), (2, -2)]);
+
+        let liveness = client.post_liveness(3, &[1, 2]).await.unwrap();
+        let liveness = liveness.iter().map(|liveness| (liveness.index, liveness.is_live));
+        assert_eq!(liveness.collect::<Vec<_>>(), [(1, false), (2, true)]);
+
+        let response = client.http_post("eth/v1/validator/liveness/3", &[1, 2]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response =
+            client.http_post("eth/v1/beacon/rewards/attestations/3", &[1, 2]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let err = client
+            .get_attestation_rewards(3, &[PublicKeyOrIndex::PublicKey(Default::default())])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Api(ApiError::ErrorMessage { code: StatusCode::BAD_REQUEST, .. })
+        ));
+    }
+
+    // Serves light client updates of successive forks and one entry in each pending queue.
+    struct Electra;
+
+    impl BeaconNodeProvider<mainnet::MainnetClientTypes> for Electra {
+        fn pending_deposits(&self, _id: &StateId) -> Result<Vec<PendingDeposit>, ApiError> {
+            let deposit = PendingDeposit {
+                public_key: Default::default(),
+                withdrawal_credentials: Default::default(),
+                amount: 32_000_000_000,
+                signature: Default::default(),
+                slot: 10,
+            };
+            Ok(vec![deposit])
+        }
+
+        fn pending_partial_withdrawals(
+            &self,
+            _id: &StateId,
+        ) -> Result<Vec<PendingPartialWithdrawal>, ApiError> {
+            Ok(vec![PendingPartialWithdrawal { index: 1, amount: 1_000, withdrawable_epoch: 2 }])
+        }
+
+        fn pending_consolidations(
+            &self,
+            _id: &StateId,
+        ) -> Result<Vec<PendingConsolidation>, ApiError> {
+            Ok(vec![PendingConsolidation { source_index: 3, target_index: 4 }])
+        }
+
+        fn light_client_updates(
+            &self,
+            start: u64,
+            count: u64,
+        ) -> Result<Vec<(Version, mainnet::LightClientUpdate)>, ApiError> {
+            let updates = [
+                (Version::Altair, mainnet::LightClientUpdate::Altair(Default::default())),
+                (Version::Capella, mainnet::LightClientUpdate::Capella(Default::default())),
+                (Version::Deneb, mainnet::LightClientUpdate::Deneb(Default::default())),
+                (Version::Electra, mainnet::LightClientUpdate::Deneb(Default::default())),
+            ];
+            Ok(updates.into_iter().skip(start as usize).take(count as usize).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_electra_state_and_light_client_updates() {
+        let node = MockBeaconNode::<mainnet::MainnetClientTypes, _>::new(Electra);
+        let server = node.spawn(([127, 0, 0, 1], 0).into()).unwrap();
+        let client = mainnet::Client::new(server.endpoint());
+
+        let deposits = client.get_pending_deposits(StateId::Head).await.unwrap();
+        assert_eq!(deposits, Electra.pending_deposits(&StateId::Head).unwrap());
+        let withdrawals = client.get_pending_partial_withdrawals(StateId::Finalized).await.unwrap();
+        assert_eq!(withdrawals, Electra.pending_partial_withdrawals(&StateId::Head).unwrap());
+        let consolidations = client.get_pending_consolidations(StateId::Slot(1)).await.unwrap();
+        assert_eq!(consolidations, Electra.pending_consolidations(&StateId::Head).unwrap());
+
+        // each update is decoded according to its own version
+        let updates = client.get_light_client_updates(0, 4).await.unwrap();
+        assert!(matches!(
+            updates.as_slice(),
+            [
+                mainnet::LightClientUpdate::Altair(..),
+                mainnet::LightClientUpdate::Capella(..),
+                mainnet::LightClientUpdate::Deneb(..),
+                mainnet::LightClientUpdate::Deneb(..),
+            ]
+        ));
+        let updates = client.get_light_client_updates(1, 1).await.unwrap();
+        assert!(matches!(updates.as_slice(), [mainnet::LightClientUpdate::Capella(..)]));
+    }
+
+    #[tokio::test]
+    async fn test_mock_beacon_node() {
+        let node = MockBeaconNode::<mainnet::MainnetClientTypes, _>::new(Provider);
+        let server = node.spawn(([127, 0, 0, 1], 0).into()).unwrap();
+        let client = mainnet::Client::new(server.endpoint());
+
+        assert_eq!(client.get_node_version().await.unwrap(), "mock/v1.0.0");
+        assert!(matches!(client.get_health().await.unwrap(), HealthStatus::Syncing));
+
+        let err = client.get_genesis_details().await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Api(ApiError::ErrorMessage { code: StatusCode::NOT_IMPLEMENTED, .. })
+        ));
+
+        let attestations = vec![mainnet::Attestation::Phase0(Default::default()); 2];
+        let err = client.post_attestations(&attestations).await.unwrap_err();
+        match err {
+            Error::Api(ApiError::IndexedError { code, failures, .. }) => {
+                assert_eq!(code, StatusCode::BAD_REQUEST);
+                assert_eq!(failures.len(), 2);
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+    }
+}
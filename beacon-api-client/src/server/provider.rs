@@ -0,0 +1,262 @@
+use crate::{
+    api_client::ClientTypes,
+    types::{
+        AttestationDuty, AttestationRewards, BalanceSummary, BeaconHeaderSummary, BlockId,
+        BlockProposal, BlockRewards, FinalityCheckpoints, GenesisDetails, HealthStatus,
+        PendingDeposit, ProposerDuty, PublicKeyOrIndex, SingleAttestation, StateId,
+        SyncCommitteeDuty, SyncCommitteeReward, SyncStatus, ValidatorLiveness, ValidatorStatus,
+        ValidatorSummary,
+    },
+    ApiError,
+};
+use ethereum_consensus::{
+    electra::{PendingConsolidation, PendingPartialWithdrawal},
+    phase0::{AttestationData, Fork},
+    primitives::{
+        BlobIndex, Bytes32, CommitteeIndex, Epoch, RandaoReveal, Root, Slot, ValidatorIndex,
+    },
+    Fork as Version,
+};
+use http::StatusCode;
+use std::collections::HashMap;
+
+pub fn not_found(message: impl Into<String>) -> ApiError {
+    ApiError::ErrorMessage { code: StatusCode::NOT_FOUND, message: message.into() }
+}
+
+pub fn bad_request(message: impl Into<String>) -> ApiError {
+    ApiError::ErrorMessage { code: StatusCode::BAD_REQUEST, message: message.into() }
+}
+
+fn not_implemented(route: &str) -> ApiError {
+    let message = format!("`{route}` is not implemented by this provider");
+    ApiError::ErrorMessage { code: StatusCode::NOT_IMPLEMENTED, message }
+}
+
+/// Supplies the data a [`crate::MockBeaconNode`] serves.
+///
+/// Every method has a default implementation answering `501 Not Implemented` so that a provider
+/// only needs to implement the routes a test exercises. Errors are returned to the client as
+/// given, so a provider can reproduce the error responses of a real node, including
+/// [`ApiError::IndexedError`] for partially failed submissions.
+pub trait BeaconNodeProvider<C: ClientTypes>: Send + Sync + 'static {
+    fn genesis_details(&self) -> Result<GenesisDetails, ApiError> {
+        Err(not_implemented("genesis"))
+    }
+
+    fn state_root(&self, _id: &StateId) -> Result<Root, ApiError> {
+        Err(not_implemented("state root"))
+    }
+
+    fn fork(&self, _id: &StateId) -> Result<Fork, ApiError> {
+        Err(not_implemented("state fork"))
+    }
+
+    fn finality_checkpoints(&self, _id: &StateId) -> Result<FinalityCheckpoints, ApiError> {
+        Err(not_implemented("finality checkpoints"))
+    }
+
+    /// Returns the validators in the state matching any of `ids` and any of `statuses`, where an
+    /// empty filter matches every validator.
+    fn validators(
+        &self,
+        _id: &StateId,
+        _ids: &[PublicKeyOrIndex],
+        _statuses: &[ValidatorStatus],
+    ) -> Result<Vec<ValidatorSummary>, ApiError> {
+        Err(not_implemented("validators"))
+    }
+
+    fn balances(
+        &self,
+        _id: &StateId,
+        _ids: &[PublicKeyOrIndex],
+    ) -> Result<Vec<BalanceSummary>, ApiError> {
+        Err(not_implemented("validator balances"))
+    }
+
+    fn pending_deposits(&self, _id: &StateId) -> Result<Vec<PendingDeposit>, ApiError> {
+        Err(not_implemented("pending deposits"))
+    }
+
+    fn pending_partial_withdrawals(
+        &self,
+        _id: &StateId,
+    ) -> Result<Vec<PendingPartialWithdrawal>, ApiError> {
+        Err(not_implemented("pending partial withdrawals"))
+    }
+
+    fn pending_consolidations(&self, _id: &StateId) -> Result<Vec<PendingConsolidation>, ApiError> {
+        Err(not_implemented("pending consolidations"))
+    }
+
+    fn beacon_header(&self, _id: &BlockId) -> Result<BeaconHeaderSummary, ApiError> {
+        Err(not_implemented("block header"))
+    }
+
+    fn beacon_block(&self, _id: &BlockId) -> Result<(Version, C::SignedBeaconBlock), ApiError> {
+        Err(not_implemented("block"))
+    }
+
+    fn beacon_block_root(&self, _id: &BlockId) -> Result<Root, ApiError> {
+        Err(not_implemented("block root"))
+    }
+
+    fn blob_sidecars(
+        &self,
+        _id: &BlockId,
+        _indices: &[BlobIndex],
+    ) -> Result<Vec<C::BlobSidecar>, ApiError> {
+        Err(not_implemented("blob sidecars"))
+    }
+
+    fn block_rewards(&self, _id: &BlockId) -> Result<BlockRewards, ApiError> {
+        Err(not_implemented("block rewards"))
+    }
+
+    /// Returns the attestation rewards of `ids` in `epoch`, or of all validators if `ids` is empty.
+    fn attestation_rewards(
+        &self,
+        _epoch: Epoch,
+        _ids: &[PublicKeyOrIndex],
+    ) -> Result<AttestationRewards, ApiError> {
+        Err(not_implemented("attestation rewards"))
+    }
+
+    /// Returns the sync committee rewards of `ids` in the block with `id`, or of the whole sync
+    /// committee if `ids` is empty.
+    fn sync_committee_rewards(
+        &self,
+        _id: &BlockId,
+        _ids: &[PublicKeyOrIndex],
+    ) -> Result<Vec<SyncCommitteeReward>, ApiError> {
+        Err(not_implemented("sync committee rewards"))
+    }
+
+    /// Returns up to `count` light client updates from the sync committee period `start`, each
+    /// with the fork it is encoded for.
+    fn light_client_updates(
+        &self,
+        _start: u64,
+        _count: u64,
+    ) -> Result<Vec<(Version, C::LightClientUpdate)>, ApiError> {
+        Err(not_implemented("light client updates"))
+    }
+
+    fn state(&self, _id: &StateId) -> Result<(Version, C::BeaconState), ApiError> {
+        Err(not_implemented("state"))
+    }
+
+    fn spec(&self) -> Result<HashMap<String, String>, ApiError> {
+        Err(not_implemented("spec"))
+    }
+
+    fn fork_schedule(&self) -> Result<Vec<Fork>, ApiError> {
+        Err(not_implemented("fork schedule"))
+    }
+
+    fn node_version(&self) -> Result<String, ApiError> {
+        Ok(concat!("beacon-api-client/v", env!("CARGO_PKG_VERSION")).to_string())
+    }
+
+    fn sync_status(&self) -> Result<SyncStatus, ApiError> {
+        Err(not_implemented("sync status"))
+    }
+
+    fn health(&self) -> HealthStatus {
+        HealthStatus::Ready
+    }
+
+    /// Returns the dependent root and the proposer duties for `epoch`.
+    fn proposer_duties(&self, _epoch: Epoch) -> Result<(Root, Vec<ProposerDuty>), ApiError> {
+        Err(not_implemented("proposer duties"))
+    }
+
+    /// Returns the dependent root and the attester duties of `indices` for `epoch`.
+    fn attester_duties(
+        &self,
+        _epoch: Epoch,
+        _indices: &[ValidatorIndex],
+    ) -> Result<(Root, Vec<AttestationDuty>), ApiError> {
+        Err(not_implemented("attester duties"))
+    }
+
+    /// Returns the sync committee duties of `indices` for `epoch`.
+    fn sync_duties(
+        &self,
+        _epoch: Epoch,
+        _indices: &[ValidatorIndex],
+    ) -> Result<Vec<SyncCommitteeDuty>, ApiError> {
+        Err(not_implemented("sync committee duties"))
+    }
+
+    fn attestation_data(
+        &self,
+        _slot: Slot,
+        _committee_index: CommitteeIndex,
+    ) -> Result<AttestationData, ApiError> {
+        Err(not_implemented("attestation data"))
+    }
+
+    /// Returns the aggregate of the attestations for `slot` with data of `attestation_data_root`.
+    fn aggregate_attestation(
+        &self,
+        _attestation_data_root: Root,
+        _slot: Slot,
+    ) -> Result<C::Attestation, ApiError> {
+        Err(not_implemented("aggregate attestation"))
+    }
+
+    /// Returns the aggregate of the attestations of the committee at `committee_index` for `slot`
+    /// with data of `attestation_data_root`, along with the fork of the aggregate.
+    fn aggregate_attestation_v2(
+        &self,
+        _attestation_data_root: Root,
+        _slot: Slot,
+        _committee_index: CommitteeIndex,
+    ) -> Result<(Version, C::Attestation), ApiError> {
+        Err(not_implemented("aggregate attestation"))
+    }
+
+    /// Returns whether each of `indices` was seen performing a duty in `epoch`.
+    fn liveness(
+        &self,
+        _epoch: Epoch,
+        _indices: &[ValidatorIndex],
+    ) -> Result<Vec<ValidatorLiveness>, ApiError> {
+        Err(not_implemented("validator liveness"))
+    }
+
+    /// Returns a block for `slot` as served by the v3 block production endpoint, along with the
+    /// values reported in its response headers.
+    fn block_proposal(
+        &self,
+        _slot: Slot,
+        _randao_reveal: &RandaoReveal,
+        _graffiti: Option<Bytes32>,
+    ) -> Result<BlockProposal<C::BeaconBlock, C::BlindedBeaconBlock, C::Blob>, ApiError> {
+        Err(not_implemented("block proposal"))
+    }
+
+    fn submit_block(&self, _block: C::SignedBeaconBlock) -> Result<(), ApiError> {
+        Err(not_implemented("block submission"))
+    }
+
+    fn submit_attestations(&self, _attestations: Vec<C::Attestation>) -> Result<(), ApiError> {
+        Err(not_implemented("attestation submission"))
+    }
+
+    fn submit_single_attestations(
+        &self,
+        _attestations: Vec<SingleAttestation>,
+    ) -> Result<(), ApiError> {
+        Err(not_implemented("attestation submission"))
+    }
+
+    fn submit_aggregates(
+        &self,
+        _aggregates: Vec<C::SignedAggregateAndProof>,
+    ) -> Result<(), ApiError> {
+        Err(not_implemented("aggregate submission"))
+    }
+}
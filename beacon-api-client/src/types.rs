@@ -2,15 +2,18 @@ use crate::ApiError;
 use ethereum_consensus::{
     altair::networking::MetaData,
     capella::Withdrawal,
+    clock::{Clock, SystemTimeProvider},
     crypto::KzgProof,
     networking::{Enr, Multiaddr, PeerId},
-    phase0::{Checkpoint, SignedBeaconBlockHeader, Validator},
+    phase0::{AttestationData, Checkpoint, SignedBeaconBlockHeader, Validator},
     primitives::{
-        BlsPublicKey, ChainId, CommitteeIndex, Coordinate, Epoch, ExecutionAddress, Gwei, Hash32,
-        Root, Slot, ValidatorIndex, Version,
+        BlsPublicKey, BlsSignature, Bytes32, ChainId, CommitteeIndex, Coordinate, Epoch,
+        ExecutionAddress, Gwei, Hash32, Root, Slot, ValidatorIndex, Version,
     },
     serde::try_bytes_from_hex_str,
-    Fork,
+    ssz::prelude::{serialize, SimpleSerialize, SimpleSerializeError, U256},
+    state_transition::Context,
+    Error as ConsensusError, Fork,
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, fmt, str::FromStr};
@@ -46,7 +49,7 @@ pub struct DepositSnapshot {
     pub execution_block_height: u64,
 }
 
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct GenesisDetails {
     #[serde(with = "crate::serde::as_str")]
     pub genesis_time: u64,
@@ -55,6 +58,27 @@ pub struct GenesisDetails {
     pub genesis_fork_version: Version,
 }
 
+impl GenesisDetails {
+    /// Returns a fork-aware clock for the network of `context` with this genesis.
+    pub fn clock(&self, context: &Context) -> Result<Clock<SystemTimeProvider>, ConsensusError> {
+        context.clock_with_genesis(self.genesis_time, self.genesis_validators_root)
+    }
+}
+
+// Parses `s` as the root form of a `kind` identifier, where `named` lists the named identifiers
+// for the error reported on input that is not a root at all.
+fn parse_root_identifier(s: &str, kind: &str, named: &str) -> Result<Root, String> {
+    let bytes = try_bytes_from_hex_str(s).map_err(|_| {
+        format!(
+            "could not parse {kind} identifier from the provided argument {s}: expected one of \
+             {named}, a slot or a hex-encoded root"
+        )
+    })?;
+    Root::try_from(bytes.as_slice()).map_err(|err| {
+        format!("could not parse {kind} identifier by root from the provided argument {s}: {err}")
+    })
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum StateId {
     Head,
@@ -90,16 +114,8 @@ impl FromStr for StateId {
             "genesis" => Ok(StateId::Genesis),
             _ => match s.parse::<Slot>() {
                 Ok(slot) => Ok(Self::Slot(slot)),
-                Err(_) => match try_bytes_from_hex_str(s) {
-                    Ok(root_data) => {
-                        let root = Root::try_from(root_data.as_slice()).map_err(|err| format!("could not parse state identifier by root from the provided argument {s}: {err}"))?;
-                        Ok(Self::Root(root))
-                    }
-                    Err(err) => {
-                        let err = format!("could not parse state identifier by root from the provided argument {s}: {err}");
-                        Err(err)
-                    }
-                },
+                Err(_) => parse_root_identifier(s, "state", "head, genesis, finalized, justified")
+                    .map(Self::Root),
             },
         }
     }
@@ -132,6 +148,24 @@ impl fmt::Display for BlockId {
     }
 }
 
+impl FromStr for BlockId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "finalized" => Ok(BlockId::Finalized),
+            "head" => Ok(BlockId::Head),
+            "genesis" => Ok(BlockId::Genesis),
+            _ => match s.parse::<Slot>() {
+                Ok(slot) => Ok(Self::Slot(slot)),
+                Err(_) => {
+                    parse_root_identifier(s, "block", "head, genesis, finalized").map(Self::Root)
+                }
+            },
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 enum ExecutionStatus {
     Default,
@@ -184,7 +218,7 @@ impl fmt::Display for ValidatorStatus {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum PublicKeyOrIndex {
     PublicKey(BlsPublicKey),
     Index(ValidatorIndex),
@@ -199,6 +233,26 @@ impl fmt::Display for PublicKeyOrIndex {
     }
 }
 
+impl FromStr for PublicKeyOrIndex {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(index) = s.parse::<ValidatorIndex>() {
+            return Ok(Self::Index(index))
+        }
+        let bytes = try_bytes_from_hex_str(s).map_err(|_| {
+            format!(
+                "could not parse validator identifier from the provided argument {s}: expected a \
+                 validator index or a hex-encoded public key"
+            )
+        })?;
+        let public_key = BlsPublicKey::try_from(bytes.as_slice()).map_err(|err| {
+            format!("could not parse validator public key from the provided argument {s}: {err}")
+        })?;
+        Ok(Self::PublicKey(public_key))
+    }
+}
+
 impl From<ValidatorIndex> for PublicKeyOrIndex {
     fn from(index: ValidatorIndex) -> Self {
         Self::Index(index)
@@ -229,7 +283,7 @@ pub struct BalanceSummary {
     pub balance: Gwei,
 }
 
-#[derive(Default)]
+#[derive(Debug, Default, Clone)]
 pub struct CommitteeFilter {
     pub epoch: Option<Epoch>,
     pub index: Option<CommitteeIndex>,
@@ -262,7 +316,7 @@ pub struct BeaconHeaderSummary {
     pub header: SignedBeaconBlockHeader,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum BroadcastValidation {
     Gossip,
@@ -331,7 +385,7 @@ pub struct NetworkIdentity {
     pub metadata: MetaData,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum PeerState {
     Disconnected,
@@ -352,7 +406,7 @@ impl fmt::Display for PeerState {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum ConnectionOrientation {
     Inbound,
@@ -396,7 +450,7 @@ pub struct PeerSummary {
     pub disconnecting: usize,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct SyncStatus {
     #[serde(with = "crate::serde::as_str")]
     pub head_slot: Slot,
@@ -413,7 +467,7 @@ pub enum HealthStatus {
     Unknown,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct AttestationDuty {
     #[serde(rename = "pubkey")]
     pub public_key: BlsPublicKey,
@@ -431,7 +485,7 @@ pub struct AttestationDuty {
     pub slot: Slot,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProposerDuty {
     #[serde(rename = "pubkey")]
     pub public_key: BlsPublicKey,
@@ -441,7 +495,7 @@ pub struct ProposerDuty {
     pub slot: Slot,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncCommitteeDuty {
     #[serde(rename = "pubkey")]
     pub public_key: BlsPublicKey,
@@ -485,6 +539,211 @@ where
     pub blobs: Option<&'a [Blob]>,
 }
 
+// NOTE: implemented by hand as the derive would require the referenced types to be `Clone`
+impl<'a, SignedBeaconBlock, Blob> Clone for SubmitSignedBeaconBlock<'a, SignedBeaconBlock, Blob>
+where
+    SignedBeaconBlock: serde::Serialize,
+    Blob: serde::Serialize,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, SignedBeaconBlock, Blob> Copy for SubmitSignedBeaconBlock<'a, SignedBeaconBlock, Blob>
+where
+    SignedBeaconBlock: serde::Serialize,
+    Blob: serde::Serialize,
+{
+}
+
+impl<'a, SignedBeaconBlock, Blob> SubmitSignedBeaconBlock<'a, SignedBeaconBlock, Blob>
+where
+    SignedBeaconBlock: serde::Serialize + SimpleSerialize,
+    Blob: serde::Serialize + SimpleSerialize,
+{
+    /// Returns the SSZ encoding of this request: the bare signed block, or the
+    /// `SignedBlockContents` container `(signed_block, kzg_proofs, blobs)` if blobs are provided.
+    pub fn to_ssz_bytes(&self) -> Result<Vec<u8>, SimpleSerializeError> {
+        let signed_block = serialize(self.signed_block)?;
+        let (kzg_proofs, blobs) = match (self.kzg_proofs, self.blobs) {
+            (Some(kzg_proofs), Some(blobs)) => (kzg_proofs, blobs),
+            _ => return Ok(signed_block),
+        };
+        let kzg_proofs = kzg_proofs.iter().flat_map(|proof| proof.as_ref().to_vec());
+        let kzg_proofs = kzg_proofs.collect::<Vec<u8>>();
+        let mut encoded_blobs = vec![];
+        for blob in blobs {
+            encoded_blobs.extend(serialize(blob)?);
+        }
+
+        // every field is variable-size so the fixed part is three 4-byte offsets
+        let fixed_size = 3 * 4;
+        let offsets = [
+            fixed_size,
+            fixed_size + signed_block.len(),
+            fixed_size + signed_block.len() + kzg_proofs.len(),
+        ];
+        let mut encoding = Vec::with_capacity(offsets[2] + encoded_blobs.len());
+        for offset in offsets {
+            encoding.extend((offset as u32).to_le_bytes());
+        }
+        encoding.extend(signed_block);
+        encoding.extend(kzg_proofs);
+        encoding.extend(encoded_blobs);
+        Ok(encoding)
+    }
+}
+
+/// The unsigned block returned when requesting a block proposal, along with the KZG proofs and
+/// blobs for its commitments.
+///
+/// Before Deneb the node only returns the bare block, in which case `kzg_proofs` and `blobs` are
+/// empty.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "BeaconBlock: serde::Serialize + serde::de::DeserializeOwned, \
+                 Blob: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct BlockContents<BeaconBlock, Blob> {
+    pub block: BeaconBlock,
+    pub kzg_proofs: Vec<KzgProof>,
+    pub blobs: Vec<Blob>,
+}
+
+impl<BeaconBlock, Blob> BlockContents<BeaconBlock, Blob> {
+    // Returns `true` if blocks of `version` are returned as a bare block rather than as a
+    // `BlockContents` container.
+    pub(crate) fn is_bare_block(version: Fork) -> bool {
+        matches!(version, Fork::Phase0 | Fork::Altair | Fork::Bellatrix | Fork::Capella)
+    }
+
+    pub fn from_block(block: BeaconBlock) -> Self {
+        Self { block, kzg_proofs: vec![], blobs: vec![] }
+    }
+}
+
+/// The block returned from the v3 block production endpoint, either with the full execution
+/// payload or blinded to the payload header if the node sourced the payload from a builder.
+#[derive(Debug)]
+pub enum ProduceBlockResponse<BeaconBlock, BlindedBeaconBlock, Blob> {
+    Full(BlockContents<BeaconBlock, Blob>),
+    Blinded(BlindedBeaconBlock),
+}
+
+impl<BeaconBlock, BlindedBeaconBlock, Blob>
+    ProduceBlockResponse<BeaconBlock, BlindedBeaconBlock, Blob>
+{
+    pub fn is_blinded(&self) -> bool {
+        matches!(self, Self::Blinded(..))
+    }
+}
+
+/// A block proposal from the v3 block production endpoint with the metadata the node returns in
+/// the response headers.
+#[derive(Debug)]
+pub struct BlockProposal<BeaconBlock, BlindedBeaconBlock, Blob> {
+    pub version: Fork,
+    pub block: ProduceBlockResponse<BeaconBlock, BlindedBeaconBlock, Blob>,
+    /// Value of the execution payload to the proposer, in Wei.
+    pub execution_payload_value: U256,
+    /// Value of the consensus rewards of the block to the proposer, in Wei.
+    pub consensus_block_value: U256,
+}
+
+// Defines an enum over the representations of a container across forks. The JSON encoding does
+// not identify the fork so `Deserialize` tries each variant from the latest fork backwards; use
+// `VersionedJson` where the response provides the fork.
+macro_rules! define_fork_variants {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident),+ }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+        #[serde(untagged)]
+        pub enum $name<$($variant),+> {
+            $($variant($variant)),+
+        }
+
+        impl<'de, $($variant),+> Deserialize<'de> for $name<$($variant),+>
+        where
+            $($variant: serde::de::DeserializeOwned),+
+        {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = serde_json::Value::deserialize(deserializer)?;
+                define_fork_variants!(@try value, $($variant),+);
+                Err(serde::de::Error::custom("no variant could be deserialized from input"))
+            }
+        }
+    };
+    (@try $value:ident, $variant:ident $(, $rest:ident)*) => {
+        define_fork_variants!(@try $value $(, $rest)*);
+        if let Ok(inner) = serde_json::from_value(serde_json::Value::clone(&$value)) {
+            return Ok(Self::$variant(inner))
+        }
+    };
+    (@try $value:ident) => {};
+}
+
+define_fork_variants!(
+    /// An `Attestation` in the format of `phase0` through `deneb`, or of `electra` onwards.
+    Attestation { Phase0, Electra }
+);
+
+define_fork_variants!(
+    /// An `AttesterSlashing` in the format of `phase0` through `deneb`, or of `electra` onwards.
+    AttesterSlashing { Phase0, Electra }
+);
+
+define_fork_variants!(
+    /// A `SignedAggregateAndProof` in the format of `phase0` through `deneb`, or of `electra`
+    /// onwards.
+    SignedAggregateAndProof { Phase0, Electra }
+);
+
+define_fork_variants!(
+    /// A `LightClientBootstrap` in the format of `altair`, `capella` or `deneb`.
+    LightClientBootstrap { Altair, Capella, Deneb }
+);
+
+define_fork_variants!(
+    /// A `LightClientUpdate` in the format of `altair`, `capella` or `deneb`.
+    LightClientUpdate { Altair, Capella, Deneb }
+);
+
+define_fork_variants!(
+    /// A `LightClientFinalityUpdate` in the format of `altair`, `capella` or `deneb`.
+    LightClientFinalityUpdate { Altair, Capella, Deneb }
+);
+
+define_fork_variants!(
+    /// A `LightClientOptimisticUpdate` in the format of `altair`, `capella` or `deneb`.
+    LightClientOptimisticUpdate { Altair, Capella, Deneb }
+);
+
+/// An attestation from a single validator, as submitted to the node from `electra` onwards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SingleAttestation {
+    #[serde(with = "crate::serde::as_str")]
+    pub committee_index: CommitteeIndex,
+    #[serde(with = "crate::serde::as_str")]
+    pub attester_index: ValidatorIndex,
+    pub data: AttestationData,
+    pub signature: BlsSignature,
+}
+
+/// A deposit waiting to be applied to the state in `electra` onwards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PendingDeposit {
+    #[serde(rename = "pubkey")]
+    pub public_key: BlsPublicKey,
+    pub withdrawal_credentials: Bytes32,
+    #[serde(with = "crate::serde::as_str")]
+    pub amount: Gwei,
+    pub signature: BlsSignature,
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BeaconProposerRegistration {
     #[serde(with = "crate::serde::as_str")]
@@ -492,11 +751,83 @@ pub struct BeaconProposerRegistration {
     pub fee_recipient: ExecutionAddress,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorLiveness {
     #[serde(with = "crate::serde::as_str")]
-    index: ValidatorIndex,
-    is_live: bool,
+    pub index: ValidatorIndex,
+    pub is_live: bool,
+}
+
+/// Rewards, in Gwei, the proposer of a block receives for its contents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockRewards {
+    #[serde(with = "crate::serde::as_str")]
+    pub proposer_index: ValidatorIndex,
+    #[serde(with = "crate::serde::as_str")]
+    pub total: Gwei,
+    #[serde(with = "crate::serde::as_str")]
+    pub attestations: Gwei,
+    #[serde(with = "crate::serde::as_str")]
+    pub sync_aggregate: Gwei,
+    #[serde(with = "crate::serde::as_str")]
+    pub proposer_slashings: Gwei,
+    #[serde(with = "crate::serde::as_str")]
+    pub attester_slashings: Gwei,
+}
+
+/// Rewards, in Gwei, a validator with the given effective balance would receive for perfect
+/// attestation performance in an epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdealAttestationRewards {
+    #[serde(with = "crate::serde::as_str")]
+    pub effective_balance: Gwei,
+    #[serde(with = "crate::serde::as_str")]
+    pub head: i64,
+    #[serde(with = "crate::serde::as_str")]
+    pub target: i64,
+    #[serde(with = "crate::serde::as_str")]
+    pub source: i64,
+    // only present before `altair`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(with = "crate::serde::as_opt_str")]
+    pub inclusion_delay: Option<u64>,
+    #[serde(with = "crate::serde::as_str")]
+    pub inactivity: i64,
+}
+
+/// Rewards, in Gwei, a validator received for its attestation in an epoch; penalties are negative.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotalAttestationRewards {
+    #[serde(with = "crate::serde::as_str")]
+    pub validator_index: ValidatorIndex,
+    #[serde(with = "crate::serde::as_str")]
+    pub head: i64,
+    #[serde(with = "crate::serde::as_str")]
+    pub target: i64,
+    #[serde(with = "crate::serde::as_str")]
+    pub source: i64,
+    // only present before `altair`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(with = "crate::serde::as_opt_str")]
+    pub inclusion_delay: Option<u64>,
+    #[serde(with = "crate::serde::as_str")]
+    pub inactivity: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttestationRewards {
+    pub ideal_rewards: Vec<IdealAttestationRewards>,
+    pub total_rewards: Vec<TotalAttestationRewards>,
+}
+
+/// Reward, in Gwei, a validator received for its participation in the sync aggregate of a block;
+/// penalties are negative.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncCommitteeReward {
+    #[serde(with = "crate::serde::as_str")]
+    pub validator_index: ValidatorIndex,
+    #[serde(with = "crate::serde::as_str")]
+    pub reward: i64,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -524,3 +855,135 @@ pub enum ApiResult<T> {
     Ok(T),
     Err(ApiError),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_id_from_str() {
+        assert!(matches!("head".parse(), Ok(BlockId::Head)));
+        assert!(matches!("genesis".parse(), Ok(BlockId::Genesis)));
+        assert!(matches!("finalized".parse(), Ok(BlockId::Finalized)));
+        assert!(matches!("1234".parse(), Ok(BlockId::Slot(1234))));
+
+        let root = Root::try_from([0xab; 32].as_slice()).unwrap();
+        let id = format!("0x{}", "ab".repeat(32));
+        assert!(matches!(id.parse(), Ok(BlockId::Root(r)) if r == root));
+        assert_eq!(id.parse::<BlockId>().unwrap().to_string(), id);
+
+        let err = "latest".parse::<BlockId>().unwrap_err();
+        assert!(err.contains("expected one of head, genesis, finalized"), "{err}");
+        assert!(!err.contains("by root"), "{err}");
+        // `justified` is only a state identifier
+        assert!("justified".parse::<BlockId>().is_err());
+        let err = "0xabcd".parse::<BlockId>().unwrap_err();
+        assert!(err.contains("by root"), "{err}");
+    }
+
+    #[test]
+    fn test_public_key_or_index_from_str() {
+        assert!(matches!("42".parse(), Ok(PublicKeyOrIndex::Index(42))));
+
+        let public_key = BlsPublicKey::try_from([0xaa; 48].as_slice()).unwrap();
+        let id = format!("0x{}", "aa".repeat(48));
+        assert!(matches!(id.parse(), Ok(PublicKeyOrIndex::PublicKey(pk)) if pk == public_key));
+
+        let err = "validator".parse::<PublicKeyOrIndex>().unwrap_err();
+        assert!(err.contains("expected a validator index or a hex-encoded public key"), "{err}");
+        let err = "0xabcd".parse::<PublicKeyOrIndex>().unwrap_err();
+        assert!(err.contains("could not parse validator public key"), "{err}");
+        assert!("-1".parse::<PublicKeyOrIndex>().is_err());
+    }
+
+    #[test]
+    fn test_rewards_from_json() {
+        let response = serde_json::json!({
+            "execution_optimistic": false,
+            "finalized": true,
+            "data": {
+                "proposer_index": "123",
+                "total": "1000",
+                "attestations": "700",
+                "sync_aggregate": "200",
+                "proposer_slashings": "100",
+                "attester_slashings": "0"
+            }
+        });
+        let rewards: Value<BlockRewards> = serde_json::from_value(response).unwrap();
+        assert_eq!(rewards.data.proposer_index, 123);
+        assert_eq!(rewards.data.total, 1000);
+        assert_eq!(rewards.meta["finalized"], true);
+
+        // `inclusion_delay` is only present before `altair`
+        let response = serde_json::json!({
+            "execution_optimistic": false,
+            "finalized": false,
+            "data": {
+                "ideal_rewards": [
+                    {
+                        "effective_balance": "1000000000",
+                        "head": "2500",
+                        "target": "5000",
+                        "source": "5000",
+                        "inclusion_delay": "5000",
+                        "inactivity": "5000"
+                    },
+                    {
+                        "effective_balance": "32000000000",
+                        "head": "80000",
+                        "target": "160000",
+                        "source": "160000",
+                        "inactivity": "0"
+                    }
+                ],
+                "total_rewards": [
+                    {
+                        "validator_index": "0",
+                        "head": "2000",
+                        "target": "2000",
+                        "source": "4000",
+                        "inclusion_delay": "2000",
+                        "inactivity": "2000"
+                    },
+                    {
+                        "validator_index": "1",
+                        "head": "0",
+                        "target": "-160000",
+                        "source": "-160000",
+                        "inactivity": "-2048"
+                    }
+                ]
+            }
+        });
+        let rewards: Value<AttestationRewards> = serde_json::from_value(response).unwrap();
+        let ideal = &rewards.data.ideal_rewards;
+        assert_eq!(ideal[0].inclusion_delay, Some(5000));
+        assert_eq!(ideal[1].effective_balance, 32_000_000_000);
+        assert_eq!(ideal[1].inclusion_delay, None);
+        let total = &rewards.data.total_rewards;
+        assert_eq!(total[0].inclusion_delay, Some(2000));
+        assert_eq!(total[1].validator_index, 1);
+        assert_eq!(
+            (total[1].target, total[1].source, total[1].inactivity),
+            (-160000, -160000, -2048)
+        );
+        assert_eq!(total[1].inclusion_delay, None);
+        let encoding = serde_json::to_value(&total[1]).unwrap();
+        assert!(encoding.get("inclusion_delay").is_none());
+        assert_eq!(encoding["target"], "-160000");
+
+        let response = serde_json::json!({
+            "execution_optimistic": false,
+            "finalized": false,
+            "data": [
+                {"validator_index": "1", "reward": "2000"},
+                {"validator_index": "2", "reward": "-2000"}
+            ]
+        });
+        let rewards: Value<Vec<SyncCommitteeReward>> = serde_json::from_value(response).unwrap();
+        assert_eq!(rewards.data[0].reward, 2000);
+        assert_eq!(rewards.data[1].validator_index, 2);
+        assert_eq!(rewards.data[1].reward, -2000);
+    }
+}
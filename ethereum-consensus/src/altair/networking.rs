@@ -1,9 +1,11 @@
 use crate::{
     altair::constants::SYNC_COMMITTEE_SUBNET_COUNT, phase0::networking::ATTESTATION_SUBNET_COUNT,
-    ssz::prelude::Bitvector,
+    ssz::prelude::*,
 };
 
-#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, SimpleSerialize, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct MetaData {
     #[serde(with = "crate::serde::as_str")]
     pub seq_number: u64,
@@ -34,8 +34,8 @@ pub use crate::{
         },
         sync::{SyncAggregate, SyncCommittee},
         validator::{
-            ContributionAndProof, SignedContributionAndProof, SyncAggregatorSelectionData,
-            SyncCommitteeContribution, SyncCommitteeMessage,
+            is_sync_committee_aggregator, ContributionAndProof, SignedContributionAndProof,
+            SyncAggregatorSelectionData, SyncCommitteeContribution, SyncCommitteeMessage,
         },
     },
     error::*,
@@ -59,7 +59,9 @@ pub use crate::{
             DepositMessage, Eth1Data, IndexedAttestation, PendingAttestation, ProposerSlashing,
             SignedVoluntaryExit, VoluntaryExit,
         },
-        validator::{AggregateAndProof, Eth1Block, SignedAggregateAndProof, Validator},
+        validator::{
+            is_aggregator, AggregateAndProof, Eth1Block, SignedAggregateAndProof, Validator,
+        },
     },
     primitives::*,
     signing::*,
@@ -1,6 +1,9 @@
 use crate::{
+    altair::constants::SYNC_COMMITTEE_SUBNET_COUNT,
+    phase0::validator::is_selected_aggregator,
     primitives::{BlsSignature, Root, Slot, ValidatorIndex},
     ssz::prelude::*,
+    state_transition::Context,
 };
 
 #[derive(Debug, Default, Clone, SimpleSerialize, serde::Serialize, serde::Deserialize)]
@@ -43,3 +46,12 @@ pub struct SyncAggregatorSelectionData {
     pub slot: Slot,
     pub subcommittee_index: u64,
 }
+
+/// Returns whether the `selection_proof` of a member of a sync subcommittee selects it to aggregate
+/// the subcommittee's messages.
+pub fn is_sync_committee_aggregator(selection_proof: &BlsSignature, context: &Context) -> bool {
+    let modulo = context.sync_committee_size /
+        SYNC_COMMITTEE_SUBNET_COUNT /
+        context.target_aggregators_per_sync_subcommittee;
+    is_selected_aggregator(selection_proof, modulo as u64)
+}
@@ -1,5 +1,12 @@
-use crate::blobs::{bundler, decode, encode, framing::Mode as Framing};
 use clap::{Args, Subcommand, ValueEnum};
+use ethereum_consensus::{
+    blobs::{self, decode, encode, Compression, Framing},
+    crypto::kzg_settings_from_json,
+    deneb::{
+        mainnet::{Blob, BlobsBundle},
+        presets::TRUSTED_SETUP_JSON,
+    },
+};
 use std::io;
 
 #[derive(Debug, Subcommand)]
@@ -7,12 +14,15 @@ enum Commands {
     Encode {
         #[arg(value_enum, default_value_t)]
         framing: FramingArg,
+        #[arg(long, help = "compress the payload with snappy (only for `segmented` framing)")]
+        compress: bool,
     },
     Decode {
         #[arg(value_enum, default_value_t)]
         framing: FramingArg,
     },
     Bundle,
+    Verify,
 }
 
 #[derive(Debug, Clone, Default, ValueEnum)]
@@ -20,13 +30,16 @@ enum FramingArg {
     Raw,
     #[default]
     Sized,
+    Segmented,
 }
 
-impl From<FramingArg> for Framing {
-    fn from(value: FramingArg) -> Self {
-        match value {
-            FramingArg::Raw => Framing::Raw,
-            FramingArg::Sized => Framing::Sized,
+impl FramingArg {
+    fn to_framing(&self, compress: bool) -> Framing {
+        match self {
+            Self::Raw => Framing::Raw,
+            Self::Sized => Framing::Sized,
+            Self::Segmented if compress => Framing::Segmented(Compression::Snappy),
+            Self::Segmented => Framing::Segmented(Compression::None),
         }
     }
 }
@@ -41,9 +54,9 @@ pub struct Command {
 impl Command {
     pub fn execute(self) -> eyre::Result<()> {
         match self.command {
-            Commands::Encode { framing } => {
+            Commands::Encode { framing, compress } => {
                 let stdin = io::stdin().lock();
-                let blobs = encode::from_reader(stdin, framing.into())?;
+                let blobs: Vec<Blob> = encode::from_reader(stdin, framing.to_framing(compress))?;
                 let result = serde_json::to_string_pretty(&blobs)?;
                 println!("{}", result);
                 Ok(())
@@ -51,16 +64,28 @@ impl Command {
             Commands::Decode { framing } => {
                 let stdin = io::stdin().lock();
                 let stdout = io::stdout().lock();
-                decode::to_writer_from_json(stdin, stdout, framing.into())?;
+                let blobs: Vec<Blob> = serde_json::from_reader(stdin)?;
+                // NOTE: compression is read from the encoded framing
+                decode::to_writer(&blobs, stdout, framing.to_framing(false))?;
                 Ok(())
             }
             Commands::Bundle => {
                 let stdin = io::stdin().lock();
-                let blobs_bundle = bundler::from_reader(stdin)?;
+                let kzg_settings = kzg_settings_from_json(TRUSTED_SETUP_JSON)?;
+                let blobs: Vec<Blob> = serde_json::from_reader(stdin)?;
+                let blobs_bundle = blobs::bundle(blobs, &kzg_settings)?;
                 let result = serde_json::to_string_pretty(&blobs_bundle)?;
                 println!("{}", result);
                 Ok(())
             }
+            Commands::Verify => {
+                let stdin = io::stdin().lock();
+                let kzg_settings = kzg_settings_from_json(TRUSTED_SETUP_JSON)?;
+                let blobs_bundle: BlobsBundle = serde_json::from_reader(stdin)?;
+                blobs::verify(&blobs_bundle, &kzg_settings)?;
+                println!("blobs bundle is valid");
+                Ok(())
+            }
         }
     }
 }
@@ -1,22 +1,509 @@
-use clap::Args;
-use ethereum_consensus::crypto::SecretKey;
+use clap::{Args, Subcommand, ValueEnum};
+use ethereum_consensus::{
+    altair::SyncAggregatorSelectionData,
+    builder::ValidatorRegistration,
+    capella::BlsToExecutionChange,
+    crypto::{
+        aggregate, eth_aggregate_public_keys, fast_aggregate_verify, verify_possession,
+        verify_signature, PublicKey, SecretKey, Signature,
+    },
+    domains::DomainType,
+    electra::Consolidation,
+    networks::Network,
+    phase0::{compute_domain, AttestationData, BeaconBlockHeader, DepositMessage, VoluntaryExit},
+    primitives::{Domain, Epoch, Root, Slot, Version},
+    serde::try_bytes_from_hex_str,
+    signing::SigningData,
+    ssz::prelude::*,
+    state_transition::Context,
+};
 use rand::prelude::*;
-use ruint::{aliases::U256, uint};
 
-pub(crate) const MODULUS: U256 =
-    uint!(52435875175126190479447740508185965837690552500527637822603658699938581184513_U256);
+#[derive(Debug, Clone, ValueEnum)]
+enum DomainTypeArg {
+    BeaconProposer,
+    BeaconAttester,
+    Randao,
+    Deposit,
+    VoluntaryExit,
+    SelectionProof,
+    AggregateAndProof,
+    SyncCommittee,
+    SyncCommitteeSelectionProof,
+    ContributionAndProof,
+    BlsToExecutionChange,
+    Consolidation,
+    ApplicationBuilder,
+}
+
+impl From<DomainTypeArg> for DomainType {
+    fn from(value: DomainTypeArg) -> Self {
+        match value {
+            DomainTypeArg::BeaconProposer => Self::BeaconProposer,
+            DomainTypeArg::BeaconAttester => Self::BeaconAttester,
+            DomainTypeArg::Randao => Self::Randao,
+            DomainTypeArg::Deposit => Self::Deposit,
+            DomainTypeArg::VoluntaryExit => Self::VoluntaryExit,
+            DomainTypeArg::SelectionProof => Self::SelectionProof,
+            DomainTypeArg::AggregateAndProof => Self::AggregateAndProof,
+            DomainTypeArg::SyncCommittee => Self::SyncCommittee,
+            DomainTypeArg::SyncCommitteeSelectionProof => Self::SyncCommitteeSelectionProof,
+            DomainTypeArg::ContributionAndProof => Self::ContributionAndProof,
+            DomainTypeArg::BlsToExecutionChange => Self::BlsToExecutionChange,
+            DomainTypeArg::Consolidation => Self::Consolidation,
+            DomainTypeArg::ApplicationBuilder => Self::ApplicationBuilder,
+        }
+    }
+}
+
+// The types of SSZ objects whose signing root can be computed.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ObjectType {
+    BeaconBlockHeader,
+    AttestationData,
+    Epoch,
+    Slot,
+    Root,
+    DepositMessage,
+    VoluntaryExit,
+    SyncAggregatorSelectionData,
+    BlsToExecutionChange,
+    Consolidation,
+    ValidatorRegistration,
+}
+
+fn decode_root<T: SimpleSerialize>(encoding: &[u8]) -> eyre::Result<Root> {
+    Ok(T::deserialize(encoding)?.hash_tree_root()?)
+}
+
+impl ObjectType {
+    // Decodes the SSZ `encoding` of an object of this type and returns its `hash_tree_root`.
+    fn hash_tree_root(&self, encoding: &[u8]) -> eyre::Result<Root> {
+        match self {
+            Self::BeaconBlockHeader => decode_root::<BeaconBlockHeader>(encoding),
+            Self::AttestationData => decode_root::<AttestationData>(encoding),
+            Self::Epoch => decode_root::<Epoch>(encoding),
+            Self::Slot => decode_root::<Slot>(encoding),
+            Self::Root => decode_root::<Root>(encoding),
+            Self::DepositMessage => decode_root::<DepositMessage>(encoding),
+            Self::VoluntaryExit => decode_root::<VoluntaryExit>(encoding),
+            Self::SyncAggregatorSelectionData => {
+                decode_root::<SyncAggregatorSelectionData>(encoding)
+            }
+            Self::BlsToExecutionChange => decode_root::<BlsToExecutionChange>(encoding),
+            Self::Consolidation => decode_root::<Consolidation>(encoding),
+            Self::ValidatorRegistration => decode_root::<ValidatorRegistration>(encoding),
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+struct Message {
+    #[clap(long, help = "hex-encoded message to sign directly")]
+    message: Option<String>,
+    #[clap(
+        long,
+        requires = "object_type",
+        help = "hex-encoded SSZ object; the signing root is computed under the given domain"
+    )]
+    object: Option<String>,
+}
+
+#[derive(Debug, Args)]
+struct DomainArgs {
+    #[clap(
+        long,
+        default_value = "mainnet",
+        help = "network (or directory with a `config.yaml`) for the default fork version"
+    )]
+    network: String,
+    #[clap(long, value_enum, help = "domain type used to compute the signing root")]
+    domain_type: Option<DomainTypeArg>,
+    #[clap(
+        long,
+        help = "hex-encoded fork version for the domain; the network's genesis version if absent"
+    )]
+    fork_version: Option<String>,
+    #[clap(long, help = "hex-encoded genesis validators root used to compute the domain")]
+    genesis_validators_root: Option<String>,
+    #[clap(
+        long,
+        conflicts_with_all = ["domain_type", "fork_version", "genesis_validators_root"],
+        help = "hex-encoded domain, instead of computing it"
+    )]
+    domain: Option<String>,
+}
+
+#[derive(Debug, Args)]
+struct MessageArgs {
+    #[clap(flatten)]
+    message: Message,
+    #[clap(
+        long,
+        value_enum,
+        requires = "object",
+        help = "type of the SSZ object given by `--object`"
+    )]
+    object_type: Option<ObjectType>,
+    #[clap(long, requires = "object", help = "print the signing root of `--object` on STDERR")]
+    show_root: bool,
+    #[clap(flatten)]
+    domain: DomainArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    #[clap(about = "generate a random BLS12-381 keypair")]
+    Random,
+    #[clap(about = "derive the public key for a secret key")]
+    PublicKey { secret_key: String },
+    #[clap(about = "sign a message or the signing root of an SSZ object")]
+    Sign {
+        #[clap(long)]
+        secret_key: String,
+        #[clap(flatten)]
+        args: MessageArgs,
+    },
+    #[clap(about = "verify a signature over a message or the signing root of an SSZ object")]
+    Verify {
+        #[clap(long)]
+        public_key: String,
+        #[clap(long)]
+        signature: String,
+        #[clap(flatten)]
+        args: MessageArgs,
+    },
+    #[clap(about = "aggregate signatures")]
+    Aggregate {
+        #[clap(required = true)]
+        signatures: Vec<String>,
+    },
+    #[clap(about = "aggregate public keys")]
+    AggregatePublicKeys {
+        #[clap(required = true)]
+        public_keys: Vec<String>,
+    },
+    #[clap(about = "verify an aggregate signature from several public keys over the same message")]
+    FastAggregateVerify {
+        #[clap(long = "public-key", required = true)]
+        public_keys: Vec<String>,
+        #[clap(long)]
+        signature: String,
+        #[clap(flatten)]
+        args: MessageArgs,
+    },
+    #[clap(about = "produce a proof of possession for a secret key")]
+    ProvePossession { secret_key: String },
+    #[clap(about = "verify a proof of possession for a public key")]
+    VerifyPossession {
+        #[clap(long)]
+        public_key: String,
+        #[clap(long)]
+        proof: String,
+    },
+}
 
 #[derive(Debug, Args)]
-#[clap(about = "generate a random BLS12-381 keypair")]
-pub struct Command;
+#[clap(
+    about = "utilities for BLS12-381 keys and signatures; generates a random keypair by default"
+)]
+pub struct Command {
+    #[clap(subcommand)]
+    command: Option<Commands>,
+}
+
+fn parse_secret_key(data: &str) -> eyre::Result<SecretKey> {
+    Ok(SecretKey::try_from(try_bytes_from_hex_str(data)?.as_ref())?)
+}
+
+fn parse_public_key(data: &str) -> eyre::Result<PublicKey> {
+    Ok(PublicKey::try_from(try_bytes_from_hex_str(data)?.as_ref())?)
+}
+
+fn parse_signature(data: &str) -> eyre::Result<Signature> {
+    Ok(Signature::try_from(try_bytes_from_hex_str(data)?.as_ref())?)
+}
+
+fn parse_root(data: &str) -> eyre::Result<Root> {
+    Ok(Root::try_from(try_bytes_from_hex_str(data)?.as_ref())?)
+}
+
+fn parse_fixed<const N: usize>(data: &str) -> eyre::Result<[u8; N]> {
+    let bytes = try_bytes_from_hex_str(data)?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| {
+        eyre::eyre!("expected {N} bytes but input `{data}` has {} bytes", bytes.len())
+    })
+}
+
+impl DomainArgs {
+    fn domain(&self) -> eyre::Result<Domain> {
+        if let Some(domain) = &self.domain {
+            return parse_fixed(domain)
+        }
+        let domain_type: DomainType = self
+            .domain_type
+            .clone()
+            .ok_or_else(|| eyre::eyre!("one of `--domain` or `--domain-type` is required"))?
+            .into();
+        let fork_version: Option<Version> =
+            self.fork_version.as_deref().map(parse_fixed).transpose()?;
+        let genesis_validators_root =
+            self.genesis_validators_root.as_deref().map(parse_root).transpose()?;
+        let context = Context::try_from(Network::from(self.network.clone()))?;
+        Ok(compute_domain(domain_type, fork_version, genesis_validators_root, &context)?)
+    }
+}
+
+impl MessageArgs {
+    // Returns the bytes to sign: either the raw message, or the signing root of an SSZ object.
+    fn signing_message(&self) -> eyre::Result<Vec<u8>> {
+        match (&self.message.message, &self.message.object) {
+            (Some(message), _) => Ok(try_bytes_from_hex_str(message)?),
+            (None, Some(object)) => {
+                let object_type =
+                    self.object_type.expect("clap requires `--object-type` with `--object`");
+                let object_root = object_type.hash_tree_root(&try_bytes_from_hex_str(object)?)?;
+                let domain = self.domain.domain()?;
+                let signing_root = SigningData { object_root, domain }.hash_tree_root()?;
+                if self.show_root {
+                    eprintln!("signing root: {signing_root}");
+                }
+                Ok(signing_root.as_ref().to_vec())
+            }
+            (None, None) => unreachable!("clap requires one of the message arguments"),
+        }
+    }
+}
 
 impl Command {
     pub fn execute(self) -> eyre::Result<()> {
-        let mut rng = thread_rng();
-        let secret_key = SecretKey::random(&mut rng).unwrap();
-        let public_key = secret_key.public_key();
-        println!("secret key: {secret_key:?}");
-        println!("public key: {public_key:?}");
+        match self.command.unwrap_or(Commands::Random) {
+            Commands::Random => {
+                let mut rng = thread_rng();
+                let secret_key = SecretKey::random(&mut rng).unwrap();
+                let public_key = secret_key.public_key();
+                println!("secret key: {secret_key:?}");
+                println!("public key: {public_key:?}");
+            }
+            Commands::PublicKey { secret_key } => {
+                let secret_key = parse_secret_key(&secret_key)?;
+                println!("{:?}", secret_key.public_key());
+            }
+            Commands::Sign { secret_key, args } => {
+                let secret_key = parse_secret_key(&secret_key)?;
+                let message = args.signing_message()?;
+                println!("{:?}", secret_key.sign(&message));
+            }
+            Commands::Verify { public_key, signature, args } => {
+                let public_key = parse_public_key(&public_key)?;
+                let signature = parse_signature(&signature)?;
+                let message = args.signing_message()?;
+                verify_signature(&public_key, &message, &signature)?;
+                println!("signature is valid");
+            }
+            Commands::Aggregate { signatures } => {
+                let signatures =
+                    signatures.iter().map(|s| parse_signature(s)).collect::<Result<Vec<_>, _>>()?;
+                println!("{:?}", aggregate(&signatures)?);
+            }
+            Commands::AggregatePublicKeys { public_keys } => {
+                let public_keys = public_keys
+                    .iter()
+                    .map(|s| parse_public_key(s))
+                    .collect::<Result<Vec<_>, _>>()?;
+                println!("{:?}", eth_aggregate_public_keys(&public_keys)?);
+            }
+            Commands::FastAggregateVerify { public_keys, signature, args } => {
+                let public_keys = public_keys
+                    .iter()
+                    .map(|s| parse_public_key(s))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let public_keys = public_keys.iter().collect::<Vec<_>>();
+                let signature = parse_signature(&signature)?;
+                let message = args.signing_message()?;
+                fast_aggregate_verify(&public_keys, &message, &signature)?;
+                println!("signature is valid");
+            }
+            Commands::ProvePossession { secret_key } => {
+                let secret_key = parse_secret_key(&secret_key)?;
+                println!("{:?}", secret_key.prove_possession());
+            }
+            Commands::VerifyPossession { public_key, proof } => {
+                let public_key = parse_public_key(&public_key)?;
+                let proof = parse_signature(&proof)?;
+                verify_possession(&public_key, &proof)?;
+                println!("proof of possession is valid");
+            }
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use ethereum_consensus::{primitives::BlsPublicKey, signing::compute_signing_root};
+
+    #[derive(Debug, Parser)]
+    struct Cli {
+        #[clap(flatten)]
+        command: Command,
+    }
+
+    fn parse(args: &[&str]) -> Result<Commands, clap::Error> {
+        let cli = Cli::try_parse_from(std::iter::once("bls").chain(args.iter().copied()))?;
+        Ok(cli.command.command.expect("has subcommand"))
+    }
+
+    fn secret_key() -> SecretKey {
+        SecretKey::try_from([1u8; 32].as_ref()).unwrap()
+    }
+
+    fn sign_args(args: &[&str]) -> MessageArgs {
+        let secret_key = hex::encode(secret_key().to_bytes());
+        let mut argv = vec!["sign", "--secret-key", &secret_key];
+        argv.extend_from_slice(args);
+        match parse(&argv).unwrap() {
+            Commands::Sign { args, .. } => args,
+            other => panic!("unexpected command {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_domain() {
+        let context = Context::for_mainnet();
+        let genesis_validators_root = Root::try_from([2u8; 32].as_ref()).unwrap();
+        let args = sign_args(&[
+            "--message",
+            "0x01",
+            "--domain-type",
+            "beacon-proposer",
+            "--fork-version",
+            "0x04000000",
+            "--genesis-validators-root",
+            &hex::encode(genesis_validators_root.as_ref()),
+        ]);
+        let expected = compute_domain(
+            DomainType::BeaconProposer,
+            Some([4, 0, 0, 0]),
+            Some(genesis_validators_root),
+            &context,
+        )
+        .unwrap();
+        assert_eq!(args.domain.domain().unwrap(), expected);
+
+        // defaults to the genesis fork version of the network
+        let args = sign_args(&["--message", "0x01", "--domain-type", "deposit"]);
+        let expected = compute_domain(DomainType::Deposit, None, None, &context).unwrap();
+        assert_eq!(args.domain.domain().unwrap(), expected);
+        let args =
+            sign_args(&["--message", "0x01", "--domain-type", "deposit", "--network", "sepolia"]);
+        let expected =
+            compute_domain(DomainType::Deposit, None, None, &Context::for_sepolia()).unwrap();
+        assert_eq!(args.domain.domain().unwrap(), expected);
+
+        let domain = format!("0x{}", "ab".repeat(32));
+        let args = sign_args(&["--message", "0x01", "--domain", &domain]);
+        assert_eq!(args.domain.domain().unwrap(), parse_fixed::<32>(&domain).unwrap());
+
+        let args = sign_args(&["--message", "0x01", "--domain", "0xabab"]);
+        assert!(args.domain.domain().is_err());
+        let args = sign_args(&["--message", "0x01"]);
+        assert!(args.domain.domain().is_err());
+    }
+
+    #[test]
+    fn test_signing_message() {
+        let args = sign_args(&["--message", "0xdeadbeef"]);
+        assert_eq!(args.signing_message().unwrap(), vec![0xde, 0xad, 0xbe, 0xef]);
+
+        let exit = VoluntaryExit { epoch: 10, validator_index: 42 };
+        let object = format!("0x{}", hex::encode(serialize(&exit).unwrap()));
+        let args = sign_args(&[
+            "--object",
+            &object,
+            "--object-type",
+            "voluntary-exit",
+            "--domain-type",
+            "voluntary-exit",
+        ]);
+        let domain = args.domain.domain().unwrap();
+        let signing_root = compute_signing_root(&exit, domain).unwrap();
+        assert_eq!(args.signing_message().unwrap(), signing_root.as_ref());
+
+        // the object must decode as the given type
+        let args = sign_args(&[
+            "--object",
+            &object,
+            "--object-type",
+            "attestation-data",
+            "--domain-type",
+            "beacon-attester",
+        ]);
+        assert!(args.signing_message().is_err());
+    }
+
+    #[test]
+    fn test_message_arguments() {
+        // exactly one of `--message` and `--object` is required
+        assert!(parse(&["verify", "--public-key", "0x", "--signature", "0x"]).is_err());
+        assert!(parse(&[
+            "verify",
+            "--public-key",
+            "0x",
+            "--signature",
+            "0x",
+            "--message",
+            "0x01",
+            "--object",
+            "0x01",
+            "--object-type",
+            "epoch",
+        ])
+        .is_err());
+        // `--object` and `--object-type` go together
+        assert!(parse(&["sign", "--secret-key", "0x", "--object", "0x01"]).is_err());
+        assert!(parse(&[
+            "sign",
+            "--secret-key",
+            "0x",
+            "--message",
+            "0x01",
+            "--object-type",
+            "slot"
+        ])
+        .is_err());
+        // an explicit domain conflicts with the arguments used to compute one
+        assert!(parse(&[
+            "sign",
+            "--secret-key",
+            "0x",
+            "--message",
+            "0x01",
+            "--domain",
+            "0x01",
+            "--domain-type",
+            "randao",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_sign_and_verify_object() {
+        let secret_key = secret_key();
+        let public_key: BlsPublicKey = secret_key.public_key();
+        let epoch = format!("0x{}", hex::encode(serialize(&7u64).unwrap()));
+        let args =
+            sign_args(&["--object", &epoch, "--object-type", "epoch", "--domain-type", "randao"]);
+        let message = args.signing_message().unwrap();
+        let signature = secret_key.sign(&message);
+        verify_signature(&public_key, &message, &signature).unwrap();
+
+        let domain = args.domain.domain().unwrap();
+        let signing_root = compute_signing_root(&7u64, domain).unwrap();
+        assert_eq!(message, signing_root.as_ref());
+        assert!(verify_signature(&public_key, &[0u8; 32], &signature).is_err());
+    }
+}
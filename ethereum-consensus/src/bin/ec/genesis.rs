@@ -0,0 +1,231 @@
+use clap::{Args, ValueEnum};
+use ethereum_consensus::{
+    crypto::SecretKey,
+    deneb, electra,
+    genesis::{
+        self, bls_withdrawal_credentials, compounding_withdrawal_credentials,
+        eth1_withdrawal_credentials, interop_secret_keys, GenesisBuilder, GenesisValidator,
+    },
+    keys,
+    networks::Network,
+    primitives::{ExecutionAddress, Gwei},
+    ssz::prelude::*,
+    state_transition::Context,
+};
+use serde::de::DeserializeOwned;
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug, Clone, Default, ValueEnum)]
+enum PresetArg {
+    #[default]
+    Mainnet,
+    Minimal,
+}
+
+#[derive(Debug, Clone, Default, ValueEnum)]
+enum ForkArg {
+    #[default]
+    Deneb,
+    Electra,
+}
+
+#[derive(Debug, Args)]
+#[clap(about = "build a genesis state for a devnet starting at a post-merge fork")]
+pub struct Command {
+    #[clap(long, value_enum, default_value_t, help = "preset to use for the genesis state")]
+    preset: PresetArg,
+    #[clap(
+        long,
+        help = "directory containing a `config.yaml` to use instead of the preset's config"
+    )]
+    config_dir: Option<String>,
+    #[clap(long, value_enum, default_value_t, help = "fork of the genesis state")]
+    fork: ForkArg,
+    #[clap(
+        long,
+        help = "BIP-39 mnemonic to derive validator keys from; uses interop keys if absent"
+    )]
+    mnemonic: Option<String>,
+    #[clap(long, default_value_t = 64, help = "number of genesis validators")]
+    validator_count: u32,
+    #[clap(long, default_value_t = 32_000_000_000, help = "balance of each validator, in Gwei")]
+    balance: Gwei,
+    #[clap(
+        long,
+        help = "execution address (hex) for withdrawal credentials; uses BLS credentials if absent"
+    )]
+    withdrawal_address: Option<String>,
+    #[clap(
+        long,
+        requires = "withdrawal_address",
+        help = "use compounding (0x02) withdrawal credentials"
+    )]
+    compounding: bool,
+    #[clap(long, help = "genesis time; defaults to the execution header's timestamp, or now")]
+    genesis_time: Option<u64>,
+    #[clap(long, help = "path to the execution layer genesis header, as JSON")]
+    execution_payload_header: Option<PathBuf>,
+    #[clap(long, default_value = "genesis.ssz", help = "path to write the SSZ genesis state")]
+    output: PathBuf,
+}
+
+fn now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).expect("after unix epoch").as_secs()
+}
+
+impl Command {
+    fn execution_payload_header<T: DeserializeOwned>(
+        &self,
+        default: impl FnOnce(u64) -> T,
+    ) -> eyre::Result<T> {
+        let header = match &self.execution_payload_header {
+            Some(path) => serde_json::from_slice(&fs::read(path)?)?,
+            None => default(self.genesis_time.unwrap_or_else(now)),
+        };
+        Ok(header)
+    }
+
+    fn context(&self) -> eyre::Result<Context> {
+        let context = match (&self.config_dir, &self.preset) {
+            (Some(dir), _) => Context::try_from(Network::Custom(dir.clone()))?,
+            (None, PresetArg::Mainnet) => Context::for_mainnet(),
+            (None, PresetArg::Minimal) => Context::for_minimal(),
+        };
+        Ok(context)
+    }
+
+    fn secret_keys(&self) -> eyre::Result<Vec<(SecretKey, Option<SecretKey>)>> {
+        let keys = match &self.mnemonic {
+            Some(phrase) => {
+                keys::derive_validator_keys_from_mnemonic(phrase, None, 0, self.validator_count)?
+                    .into_iter()
+                    .map(|keys| (keys.signing.private_key, Some(keys.withdrawal.private_key)))
+                    .collect()
+            }
+            None => interop_secret_keys(self.validator_count as usize)?
+                .into_iter()
+                .map(|key| (key, None))
+                .collect(),
+        };
+        Ok(keys)
+    }
+
+    fn validators(&self, context: &Context) -> eyre::Result<Vec<GenesisValidator>> {
+        if self.compounding && matches!(self.fork, ForkArg::Deneb) {
+            eyre::bail!("compounding withdrawal credentials require `--fork electra`")
+        }
+        let address = self
+            .withdrawal_address
+            .as_ref()
+            .map(|address| {
+                let bytes = hex::decode(address.trim_start_matches("0x"))?;
+                let address = ExecutionAddress::try_from(bytes.as_ref())
+                    .map_err(|_| eyre::eyre!("withdrawal address must be 20 bytes"))?;
+                Ok::<_, eyre::Report>(address)
+            })
+            .transpose()?;
+
+        self.secret_keys()?
+            .iter()
+            .map(|(signing_key, withdrawal_key)| {
+                let withdrawal_credentials = match &address {
+                    Some(address) if self.compounding => {
+                        compounding_withdrawal_credentials(address)
+                    }
+                    Some(address) => eth1_withdrawal_credentials(address),
+                    None => {
+                        let withdrawal_key = withdrawal_key.as_ref().unwrap_or(signing_key);
+                        bls_withdrawal_credentials(&withdrawal_key.public_key())
+                    }
+                };
+                let validator = GenesisValidator::from_secret_key(
+                    signing_key,
+                    withdrawal_credentials,
+                    self.balance,
+                    context,
+                )?;
+                Ok(validator)
+            })
+            .collect()
+    }
+
+    pub fn execute(self) -> eyre::Result<()> {
+        let context = self.context()?;
+        let validators = self.validators(&context)?;
+        let mut builder = GenesisBuilder::new(&context).with_validators(validators);
+        if let Some(genesis_time) = self.genesis_time {
+            builder = builder.with_genesis_time(genesis_time);
+        }
+
+        let (encoding, genesis_validators_root) = match (&self.fork, &self.preset) {
+            (ForkArg::Deneb, PresetArg::Mainnet) => {
+                let header = self.execution_payload_header(|timestamp| {
+                    deneb::mainnet::ExecutionPayloadHeader { timestamp, ..Default::default() }
+                })?;
+                let state = genesis::mainnet::build_deneb(&builder, header)?;
+                (serialize(&state)?, state.genesis_validators_root)
+            }
+            (ForkArg::Deneb, PresetArg::Minimal) => {
+                let header = self.execution_payload_header(|timestamp| {
+                    deneb::minimal::ExecutionPayloadHeader { timestamp, ..Default::default() }
+                })?;
+                let state = genesis::minimal::build_deneb(&builder, header)?;
+                (serialize(&state)?, state.genesis_validators_root)
+            }
+            (ForkArg::Electra, PresetArg::Mainnet) => {
+                let header = self.execution_payload_header(|timestamp| {
+                    electra::mainnet::ExecutionPayloadHeader { timestamp, ..Default::default() }
+                })?;
+                let state = genesis::mainnet::build_electra(&builder, header)?;
+                (serialize(&state)?, state.genesis_validators_root)
+            }
+            (ForkArg::Electra, PresetArg::Minimal) => {
+                let header = self.execution_payload_header(|timestamp| {
+                    electra::minimal::ExecutionPayloadHeader { timestamp, ..Default::default() }
+                })?;
+                let state = genesis::minimal::build_electra(&builder, header)?;
+                (serialize(&state)?, state.genesis_validators_root)
+            }
+        };
+
+        fs::write(&self.output, encoding)?;
+        println!("{genesis_validators_root}");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(fork: ForkArg, compounding: bool) -> Command {
+        Command {
+            preset: PresetArg::Minimal,
+            config_dir: None,
+            fork,
+            mnemonic: None,
+            validator_count: 4,
+            balance: 32_000_000_000,
+            withdrawal_address: Some(format!("0x{}", "11".repeat(20))),
+            compounding,
+            genesis_time: None,
+            execution_payload_header: None,
+            output: PathBuf::from("genesis.ssz"),
+        }
+    }
+
+    #[test]
+    fn test_compounding_requires_electra() {
+        let context = Context::for_minimal();
+        assert!(command(ForkArg::Deneb, true).validators(&context).is_err());
+        assert!(command(ForkArg::Deneb, false).validators(&context).is_ok());
+
+        let validators = command(ForkArg::Electra, true).validators(&context).unwrap();
+        assert_eq!(validators.len(), 4);
+        assert!(validators.iter().all(|v| v.withdrawal_credentials[0] == 0x02));
+    }
+}
@@ -1,5 +1,6 @@
 mod blobs;
 mod bls;
+mod genesis;
 mod validator;
 
 use clap::{Parser, Subcommand};
@@ -9,6 +10,7 @@ pub enum Commands {
     Validator(validator::Command),
     Bls(bls::Command),
     Blobs(blobs::Command),
+    Genesis(genesis::Command),
 }
 
 #[derive(Debug, Parser)]
@@ -25,5 +27,6 @@ fn main() -> eyre::Result<()> {
         Commands::Validator(cmd) => cmd.execute(),
         Commands::Bls(cmd) => cmd.execute(),
         Commands::Blobs(cmd) => cmd.execute(),
+        Commands::Genesis(cmd) => cmd.execute(),
     }
 }
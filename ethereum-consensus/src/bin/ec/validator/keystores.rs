@@ -1,6 +1,9 @@
-use crate::validator::keys::{KeyPair, Path};
 use aes::cipher::{KeyIvInit, StreamCipher};
-use ethereum_consensus::crypto::{hash, PublicKey as BlsPublicKey, SecretKey as BlsSecretKey};
+use ethereum_consensus::{
+    crypto::{hash, PublicKey as BlsPublicKey, SecretKey as BlsSecretKey},
+    keys::{KeyPair, Path},
+};
+use pbkdf2::pbkdf2_hmac;
 use rayon::prelude::*;
 use scrypt::{
     password_hash::{
@@ -10,6 +13,7 @@ use scrypt::{
     Params as ScryptParams, Scrypt,
 };
 use serde::{Deserialize, Serialize, Serializer};
+use sha2::Sha256;
 use unicode_normalization::UnicodeNormalization;
 use uuid::Uuid;
 
@@ -41,23 +45,69 @@ const PASSPHRASE_LEN: usize = 32;
 
 const VERSION: usize = 4;
 
-const KDF_FN: &str = "scrypt";
+const SCRYPT_KDF_FN: &str = "scrypt";
 const SCRYPT_DKLEN: usize = 32;
 
+const PBKDF2_KDF_FN: &str = "pbkdf2";
+const PBKDF2_PRF: &str = "hmac-sha256";
+const PBKDF2_DKLEN: usize = 32;
+const PBKDF2_SALT_LEN: usize = 32;
+// Iteration count used in the EIP-2335 test vectors.
+pub const PBKDF2_DEFAULT_ROUNDS: u32 = 262144;
+
 const CIPHER_FN: &str = "aes-128-ctr";
 type CtrCipher = ctr::Ctr64BE<aes::Aes128>;
 const AES_SIZE: usize = 16;
 
 const CHECKSUM_FN: &str = "sha256";
 
+/// The key derivation function (and its cost parameters) used to encrypt a keystore.
+#[derive(Debug, Clone, Copy)]
+pub enum KdfConfig {
+    Scrypt(ScryptParams),
+    Pbkdf2 { rounds: u32 },
+}
+
+impl KdfConfig {
+    pub fn scrypt(log_n: u8, r: u32, p: u32) -> eyre::Result<Self> {
+        let params = ScryptParams::new(log_n, r, p, SCRYPT_DKLEN)
+            .map_err(|err| eyre::eyre!("invalid scrypt parameters: {err}"))?;
+        Ok(Self::Scrypt(params))
+    }
+
+    pub fn pbkdf2(rounds: u32) -> eyre::Result<Self> {
+        if rounds == 0 {
+            eyre::bail!("the number of PBKDF2 rounds must be positive")
+        }
+        Ok(Self::Pbkdf2 { rounds })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
-struct KdfParams {
-    n: usize,
-    r: u32,
-    p: u32,
-    dklen: usize,
-    #[serde(serialize_with = "as_hex")]
-    salt: Vec<u8>,
+#[serde(untagged)]
+enum KdfParams {
+    Scrypt {
+        n: usize,
+        r: u32,
+        p: u32,
+        dklen: usize,
+        #[serde(serialize_with = "as_hex")]
+        salt: Vec<u8>,
+    },
+    Pbkdf2 {
+        c: u32,
+        prf: String,
+        dklen: usize,
+        #[serde(serialize_with = "as_hex")]
+        salt: Vec<u8>,
+    },
+}
+
+// Applies the EIP-2335 password processing: NFKD normalization and removal of control codes.
+fn normalize_passphrase(passphrase: &Passphrase) -> String {
+    let mut passphrase = passphrase.nfkd().collect::<String>();
+    passphrase.retain(|c| !c.is_control());
+    passphrase
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -74,8 +124,7 @@ impl Kdf {
         salt: SaltString,
         params: ScryptParams,
     ) -> Self {
-        let mut passphrase = passphrase.nfkd().collect::<String>();
-        passphrase.retain(|c| !c.is_control());
+        let passphrase = normalize_passphrase(passphrase);
         let mut buf = vec![0u8; salt.len()];
         let salt_bytes = salt.decode_b64(&mut buf).unwrap().to_vec();
 
@@ -84,18 +133,40 @@ impl Kdf {
             .unwrap();
 
         let n = 2_usize.pow(params.log_n() as u32);
-        let params =
-            KdfParams { n, r: params.r(), p: params.p(), dklen: SCRYPT_DKLEN, salt: salt_bytes };
+        let params = KdfParams::Scrypt {
+            n,
+            r: params.r(),
+            p: params.p(),
+            dklen: SCRYPT_DKLEN,
+            salt: salt_bytes,
+        };
         let message = password_hash.hash.unwrap().as_bytes().to_vec();
-        Self { function: KDF_FN.to_string(), params, message }
+        Self { function: SCRYPT_KDF_FN.to_string(), params, message }
     }
 
-    fn from(passphrase: &Passphrase) -> Self {
-        let salt = SaltString::generate(OsRng);
-        // NOTE: `SCRYPT_DKLEN` used in `new_with_salt_and_params` matches the `recommended` params
-        let params = ScryptParams::recommended();
+    fn new_pbkdf2_with_salt(passphrase: &Passphrase, salt: Vec<u8>, rounds: u32) -> Self {
+        let passphrase = normalize_passphrase(passphrase);
+        let mut message = vec![0u8; PBKDF2_DKLEN];
+        pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), &salt, rounds, &mut message);
 
-        Self::new_with_salt_and_params(passphrase, salt, params)
+        let params =
+            KdfParams::Pbkdf2 { c: rounds, prf: PBKDF2_PRF.to_string(), dklen: PBKDF2_DKLEN, salt };
+        Self { function: PBKDF2_KDF_FN.to_string(), params, message }
+    }
+
+    fn new(passphrase: &Passphrase, config: &KdfConfig) -> Self {
+        match config {
+            KdfConfig::Scrypt(params) => {
+                let salt = SaltString::generate(OsRng);
+                // NOTE: `params` are built with `SCRYPT_DKLEN`, see `KdfConfig::scrypt`
+                Self::new_with_salt_and_params(passphrase, salt, *params)
+            }
+            KdfConfig::Pbkdf2 { rounds } => {
+                let mut salt = vec![0u8; PBKDF2_SALT_LEN];
+                OsRng.fill_bytes(&mut salt);
+                Self::new_pbkdf2_with_salt(passphrase, salt, *rounds)
+            }
+        }
     }
 
     fn encryption_key(&self) -> &[u8] {
@@ -170,8 +241,8 @@ pub struct Crypto {
 }
 
 impl Crypto {
-    fn from(passphrase: &Passphrase, key: BlsSecretKey) -> Self {
-        let kdf = Kdf::from(passphrase);
+    fn new(passphrase: &Passphrase, key: BlsSecretKey, config: &KdfConfig) -> Self {
+        let kdf = Kdf::new(passphrase, config);
         let cipher = Cipher::new(key, kdf.encryption_key());
         let checksum = Checksum::new(cipher.cipher_text(), kdf.checksum_salt());
         Crypto { kdf, checksum, cipher }
@@ -188,23 +259,39 @@ pub struct Keystore {
     version: usize,
 }
 
+pub fn generate_passphrase() -> Passphrase {
+    let mut passphrase = [0u8; PASSPHRASE_LEN];
+    OsRng.fill_bytes(&mut passphrase);
+    hex::encode(passphrase)
+}
+
 impl Keystore {
-    fn new_with_generated_passphrase(key_pair: KeyPair) -> (Self, Passphrase) {
-        let mut passphrase = [0u8; PASSPHRASE_LEN];
-        OsRng.fill_bytes(&mut passphrase);
-        let passphrase = hex::encode(passphrase);
-        (Self::new(key_pair, &passphrase), passphrase)
+    fn new_with_generated_passphrase(key_pair: KeyPair, config: &KdfConfig) -> (Self, Passphrase) {
+        let passphrase = generate_passphrase();
+        (Self::new(key_pair, &passphrase, config), passphrase)
     }
 
-    fn new(KeyPair { private_key, public_key, path }: KeyPair, passphrase: &Passphrase) -> Self {
+    pub fn new(
+        KeyPair { private_key, public_key, path }: KeyPair,
+        passphrase: &Passphrase,
+        config: &KdfConfig,
+    ) -> Self {
         Keystore {
-            crypto: Crypto::from(passphrase, private_key),
+            crypto: Crypto::new(passphrase, private_key, config),
             public_key,
             path,
             uuid: Uuid::new_v4(),
             version: VERSION,
         }
     }
+
+    pub fn public_key(&self) -> &BlsPublicKey {
+        &self.public_key
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -218,10 +305,10 @@ pub struct KeystoreWithPassphrase {
     passphrase: Passphrase,
 }
 
-pub fn generate(keys: Vec<KeyPair>) -> Vec<KeystoreWithPassphrase> {
+pub fn generate(keys: Vec<KeyPair>, config: &KdfConfig) -> Vec<KeystoreWithPassphrase> {
     keys.into_par_iter()
         .map(|key_pair| {
-            let (keystore, passphrase) = Keystore::new_with_generated_passphrase(key_pair);
+            let (keystore, passphrase) = Keystore::new_with_generated_passphrase(key_pair, config);
             KeystoreWithPassphrase { keystore, passphrase }
         })
         .collect()
@@ -268,9 +355,10 @@ mod tests {
 
         let checksum = Checksum::new(cipher.cipher_text(), kdf.checksum_salt());
 
-        assert_eq!(kdf.params.n, 262144);
+        let KdfParams::Scrypt { n, salt, .. } = &kdf.params else { panic!("expected scrypt") };
+        assert_eq!(*n, 262144);
         assert_eq!(
-            kdf.params.salt,
+            *salt,
             hex::decode("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")
                 .unwrap()
         );
@@ -286,4 +374,33 @@ mod tests {
                 .unwrap()
         );
     }
+
+    // Test case from EIP-2335:
+    #[test]
+    fn test_keystore_crypto_pbkdf2() {
+        let passphrase = "𝔱𝔢𝔰𝔱𝔭𝔞𝔰𝔰𝔴𝔬𝔯𝔡🔑".to_string();
+        let secret = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+        let secret_key = BlsSecretKey::try_from(hex::decode(secret).unwrap().as_ref()).unwrap();
+
+        let salt = hex::decode("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3")
+            .unwrap();
+        let kdf = Kdf::new_pbkdf2_with_salt(&passphrase, salt, PBKDF2_DEFAULT_ROUNDS);
+
+        let iv = hex::decode("264daa3f303d7259501c93d997d84fe6").unwrap();
+        let cipher = Cipher::new_with_iv(secret_key, kdf.encryption_key(), iv.try_into().unwrap());
+
+        let checksum = Checksum::new(cipher.cipher_text(), kdf.checksum_salt());
+
+        assert_eq!(kdf.function, "pbkdf2");
+        assert_eq!(
+            checksum.message,
+            hex::decode("8a9f5d9912ed7e75ea794bc5a89bca5f193721d30868ade6f73043c6ea6febf1")
+                .unwrap()
+        );
+        assert_eq!(
+            cipher.message,
+            hex::decode("cee03fde2af33149775b7223e7845e4fb2c8ae1792e5f99fe9ecf474cc8c16ad")
+                .unwrap()
+        );
+    }
 }
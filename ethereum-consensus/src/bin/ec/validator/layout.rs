@@ -0,0 +1,274 @@
+use crate::validator::keystores::{Keystore, Passphrase};
+use clap::ValueEnum;
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+const SHARED_PASSWORD_FILE: &str = "password.txt";
+
+/// On-disk layouts of keystores and their passwords, ready to be imported by each client.
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+pub enum Layout {
+    /// `keystore-m_12381_3600_<i>_0_0-<timestamp>.json` files, as written by
+    /// `staking-deposit-cli`.
+    #[default]
+    Eip2335,
+    /// `keys/<keystore>.json` with the matching `passwords/<keystore>.txt`,
+    /// for `teku --validator-keys=keys:passwords`.
+    Teku,
+    /// Keystores alongside a single `password.txt`,
+    /// for `prysm validator accounts import --keys-dir`.
+    Prysm,
+    /// `validators/<pubkey>/keystore.json` and `secrets/<pubkey>`, as in the nimbus data
+    /// directory.
+    Nimbus,
+    /// `keystores/<pubkey>/voting-keystore.json` and `secrets/<pubkey>`, as in the lodestar
+    /// data directory.
+    Lodestar,
+}
+
+/// Passwords protecting a set of keystores.
+#[derive(Debug)]
+pub enum Passwords {
+    /// The same password for every keystore.
+    Shared(Passphrase),
+    /// One password per keystore, in the same order as the keystores.
+    PerKey(Vec<Passphrase>),
+}
+
+impl Passwords {
+    pub fn get(&self, index: usize) -> &Passphrase {
+        match self {
+            Self::Shared(password) => password,
+            Self::PerKey(passwords) => &passwords[index],
+        }
+    }
+}
+
+// Name (without extension) of the keystore file following the EIP-2335 convention
+// used by `staking-deposit-cli`, e.g. `keystore-m_12381_3600_0_0_0-1700000000`.
+fn keystore_name(keystore: &Keystore, timestamp: u64) -> String {
+    let path = keystore.path().to_string().replace('/', "_");
+    format!("keystore-{path}-{timestamp}")
+}
+
+fn public_key_name(keystore: &Keystore) -> String {
+    format!("0x{}", hex::encode(keystore.public_key().as_ref()))
+}
+
+// Writes `contents` to a new file at `path`, refusing to overwrite existing files.
+// As the contents are secret, the file is only readable by its owner where supported.
+fn write_file(path: &Path, contents: &[u8]) -> eyre::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|err| eyre::eyre!("could not create `{}`: {err}", path.display()))?;
+    file.write_all(contents)?;
+    Ok(())
+}
+
+fn write_keystore(path: &Path, keystore: &Keystore) -> eyre::Result<()> {
+    let encoding = serde_json::to_vec_pretty(keystore)?;
+    write_file(path, &encoding)
+}
+
+impl Layout {
+    /// Writes `keystores` protected by `passwords` under `output_dir`.
+    /// `timestamp` is used in keystore file names for the layouts that include one.
+    pub fn write(
+        &self,
+        output_dir: &Path,
+        keystores: &[Keystore],
+        passwords: &Passwords,
+        timestamp: u64,
+    ) -> eyre::Result<Vec<PathBuf>> {
+        let mut written = Vec::with_capacity(keystores.len());
+        match self {
+            Self::Eip2335 | Self::Prysm => {
+                if let (Self::Prysm, Passwords::PerKey(..)) = (self, passwords) {
+                    eyre::bail!("the prysm layout only supports a shared password")
+                }
+                for (i, keystore) in keystores.iter().enumerate() {
+                    let name = keystore_name(keystore, timestamp);
+                    let path = output_dir.join(format!("{name}.json"));
+                    write_keystore(&path, keystore)?;
+                    if matches!(passwords, Passwords::PerKey(..)) {
+                        let password_path = output_dir.join(format!("{name}.txt"));
+                        write_file(&password_path, passwords.get(i).as_bytes())?;
+                    }
+                    written.push(path);
+                }
+                if let Passwords::Shared(password) = passwords {
+                    write_file(&output_dir.join(SHARED_PASSWORD_FILE), password.as_bytes())?;
+                }
+            }
+            Self::Teku => {
+                for (i, keystore) in keystores.iter().enumerate() {
+                    let name = keystore_name(keystore, timestamp);
+                    let path = output_dir.join("keys").join(format!("{name}.json"));
+                    write_keystore(&path, keystore)?;
+                    let password_path = output_dir.join("passwords").join(format!("{name}.txt"));
+                    write_file(&password_path, passwords.get(i).as_bytes())?;
+                    written.push(path);
+                }
+            }
+            Self::Nimbus | Self::Lodestar => {
+                let (keystores_dir, keystore_file) = match self {
+                    Self::Nimbus => ("validators", "keystore.json"),
+                    _ => ("keystores", "voting-keystore.json"),
+                };
+                for (i, keystore) in keystores.iter().enumerate() {
+                    let name = public_key_name(keystore);
+                    let path = output_dir.join(keystores_dir).join(&name).join(keystore_file);
+                    write_keystore(&path, keystore)?;
+                    let password_path = output_dir.join("secrets").join(&name);
+                    write_file(&password_path, passwords.get(i).as_bytes())?;
+                    written.push(path);
+                }
+            }
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validator::keystores::KdfConfig;
+    use ethereum_consensus::keys::{derive_key_pair, Path as KeyPath};
+
+    const TIMESTAMP: u64 = 1_700_000_000;
+
+    // NOTE: a single PBKDF2 round keeps the encryption of the test keystores fast
+    fn keystores(count: u32, password: &Passphrase) -> Vec<Keystore> {
+        let config = KdfConfig::pbkdf2(1).unwrap();
+        (0..count)
+            .map(|i| {
+                let key_pair = derive_key_pair(&[i as u8; 32], KeyPath::signing_key(i)).unwrap();
+                Keystore::new(key_pair, password, &config)
+            })
+            .collect()
+    }
+
+    fn read(path: &Path) -> String {
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[cfg(unix)]
+    fn assert_owner_only(path: &Path) {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600, "{}", path.display());
+    }
+
+    #[cfg(not(unix))]
+    fn assert_owner_only(_: &Path) {}
+
+    #[test]
+    fn test_eip2335_layout() {
+        let dir = tempfile::tempdir().unwrap();
+        let password = "password".to_string();
+        let keystores = keystores(2, &password);
+        let passwords = Passwords::Shared(password.clone());
+        let written = Layout::Eip2335.write(dir.path(), &keystores, &passwords, TIMESTAMP).unwrap();
+
+        let expected = (0..2)
+            .map(|i| dir.path().join(format!("keystore-m_12381_3600_{i}_0_0-{TIMESTAMP}.json")))
+            .collect::<Vec<_>>();
+        assert_eq!(written, expected);
+        for path in &written {
+            let keystore: serde_json::Value = serde_json::from_str(&read(path)).unwrap();
+            assert_eq!(keystore["version"], 4);
+            assert_owner_only(path);
+        }
+        let password_path = dir.path().join(SHARED_PASSWORD_FILE);
+        assert_eq!(read(&password_path), password);
+        assert_owner_only(&password_path);
+
+        // per-key passwords are written next to each keystore instead of a shared file
+        let dir = tempfile::tempdir().unwrap();
+        let passwords = Passwords::PerKey(vec!["a".to_string(), "b".to_string()]);
+        let written = Layout::Eip2335.write(dir.path(), &keystores, &passwords, TIMESTAMP).unwrap();
+        assert_eq!(read(&written[0].with_extension("txt")), "a");
+        assert_eq!(read(&written[1].with_extension("txt")), "b");
+        assert!(!dir.path().join(SHARED_PASSWORD_FILE).exists());
+    }
+
+    #[test]
+    fn test_client_layouts() {
+        let password = "password".to_string();
+        let keystores = keystores(2, &password);
+        let names = keystores.iter().map(public_key_name).collect::<Vec<_>>();
+        let passwords = Passwords::PerKey(vec!["a".to_string(), "b".to_string()]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let written = Layout::Teku.write(dir.path(), &keystores, &passwords, TIMESTAMP).unwrap();
+        for (i, path) in written.iter().enumerate() {
+            let name = format!("keystore-m_12381_3600_{i}_0_0-{TIMESTAMP}");
+            assert_eq!(path, &dir.path().join("keys").join(format!("{name}.json")));
+            let password_path = dir.path().join("passwords").join(format!("{name}.txt"));
+            assert_eq!(read(&password_path), passwords.get(i).as_str());
+            assert_owner_only(path);
+            assert_owner_only(&password_path);
+        }
+
+        let layouts = [
+            (Layout::Nimbus, "validators", "keystore.json"),
+            (Layout::Lodestar, "keystores", "voting-keystore.json"),
+        ];
+        for (layout, keystores_dir, keystore_file) in layouts {
+            let dir = tempfile::tempdir().unwrap();
+            let written = layout.write(dir.path(), &keystores, &passwords, TIMESTAMP).unwrap();
+            for (i, path) in written.iter().enumerate() {
+                let expected = dir.path().join(keystores_dir).join(&names[i]).join(keystore_file);
+                assert_eq!(path, &expected);
+                let password_path = dir.path().join("secrets").join(&names[i]);
+                assert_eq!(read(&password_path), passwords.get(i).as_str());
+                assert_owner_only(path);
+                assert_owner_only(&password_path);
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let shared = Passwords::Shared(password.clone());
+        let written = Layout::Prysm.write(dir.path(), &keystores, &shared, TIMESTAMP).unwrap();
+        assert_eq!(written.len(), 2);
+        assert_eq!(read(&dir.path().join(SHARED_PASSWORD_FILE)), password);
+    }
+
+    #[test]
+    fn test_prysm_rejects_per_key_passwords() {
+        let dir = tempfile::tempdir().unwrap();
+        let keystores = keystores(1, &"password".to_string());
+        let passwords = Passwords::PerKey(vec!["a".to_string()]);
+        let result = Layout::Prysm.write(dir.path(), &keystores, &passwords, TIMESTAMP);
+        assert!(result.is_err());
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_existing_files_are_not_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let keystores = keystores(1, &"password".to_string());
+        let passwords = Passwords::Shared("password".to_string());
+        let written = Layout::Eip2335.write(dir.path(), &keystores, &passwords, TIMESTAMP).unwrap();
+        let contents = read(&written[0]);
+
+        let passwords = Passwords::Shared("other".to_string());
+        let err = Layout::Eip2335.write(dir.path(), &keystores, &passwords, TIMESTAMP).unwrap_err();
+        assert!(err.to_string().contains("could not create"));
+        assert_eq!(read(&written[0]), contents);
+        assert_eq!(read(&dir.path().join(SHARED_PASSWORD_FILE)), "password");
+    }
+}
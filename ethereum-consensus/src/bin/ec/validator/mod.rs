@@ -1,8 +1,49 @@
-mod keys;
 mod keystores;
-mod mnemonic;
+mod layout;
 
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
+use ethereum_consensus::keys::{self, mnemonic};
+use keystores::{KdfConfig, Keystore, PBKDF2_DEFAULT_ROUNDS};
+use layout::{Layout, Passwords};
+use rayon::prelude::*;
+use std::{path::PathBuf, time::SystemTime};
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+enum KdfFunction {
+    #[default]
+    Scrypt,
+    Pbkdf2,
+}
+
+#[derive(Debug, Args)]
+pub struct KdfArgs {
+    #[clap(
+        long,
+        value_enum,
+        default_value_t,
+        help = "key derivation function used to encrypt keystores"
+    )]
+    kdf: KdfFunction,
+    #[clap(long, default_value_t = 18, help = "scrypt cost parameter, as log2(n)")]
+    scrypt_log_n: u8,
+    #[clap(long, default_value_t = 8, help = "scrypt block size parameter")]
+    scrypt_r: u32,
+    #[clap(long, default_value_t = 1, help = "scrypt parallelization parameter")]
+    scrypt_p: u32,
+    #[clap(long, default_value_t = PBKDF2_DEFAULT_ROUNDS, help = "number of PBKDF2 rounds")]
+    pbkdf2_rounds: u32,
+}
+
+impl KdfArgs {
+    fn config(&self) -> eyre::Result<KdfConfig> {
+        match self.kdf {
+            KdfFunction::Scrypt => {
+                KdfConfig::scrypt(self.scrypt_log_n, self.scrypt_r, self.scrypt_p)
+            }
+            KdfFunction::Pbkdf2 => KdfConfig::pbkdf2(self.pbkdf2_rounds),
+        }
+    }
+}
 
 #[derive(Debug, Subcommand)]
 pub enum Commands {
@@ -17,6 +58,37 @@ pub enum Commands {
         start: u32,
         #[clap(help = "EIP-2334 index to stop key generation (exclusive)")]
         end: u32,
+        #[clap(long, help = "optional BIP-39 passphrase")]
+        passphrase: Option<String>,
+        #[clap(flatten)]
+        kdf: KdfArgs,
+    },
+    #[clap(
+        about = "Writes EIP-2335 keystores and their passwords to a directory, in a layout ready to import into a given client."
+    )]
+    GenerateKeystores {
+        #[clap(help = "BIP-39 mnemonic to use following EIP-2334")]
+        phrase: String,
+        #[clap(help = "EIP-2334 index to start key generation (inclusive)")]
+        start: u32,
+        #[clap(help = "EIP-2334 index to stop key generation (exclusive)")]
+        end: u32,
+        #[clap(long, help = "optional BIP-39 passphrase")]
+        passphrase: Option<String>,
+        #[clap(long, help = "directory to write keystores into")]
+        output_dir: PathBuf,
+        #[clap(long, value_enum, default_value_t, help = "directory layout to write")]
+        layout: Layout,
+        #[clap(
+            long,
+            conflicts_with = "per_key_passwords",
+            help = "password shared by all keystores; a random one is generated if missing"
+        )]
+        password: Option<String>,
+        #[clap(long, help = "generate a random password for each keystore")]
+        per_key_passwords: bool,
+        #[clap(flatten)]
+        kdf: KdfArgs,
     },
 }
 
@@ -35,14 +107,55 @@ impl Command {
                 println!("{}", mnemonic);
                 Ok(())
             }
-            Commands::GenerateLighthouseKeystores { phrase, start, end } => {
-                let mnemonic = mnemonic::recover_from_phrase(&phrase)?;
-                let seed = mnemonic::to_seed(mnemonic, None);
-                let (signing_keys, _withdrawal_keys) = keys::generate(&seed, start, end);
-                let keystores_with_passphrases = keystores::generate(signing_keys);
+            Commands::GenerateLighthouseKeystores { phrase, start, end, passphrase, kdf } => {
+                let config = kdf.config()?;
+                let keys = keys::derive_validator_keys_from_mnemonic(
+                    &phrase,
+                    passphrase.as_deref(),
+                    start,
+                    end,
+                )?;
+                let signing_keys = keys.into_iter().map(|keys| keys.signing).collect();
+                let keystores_with_passphrases = keystores::generate(signing_keys, &config);
                 println!("{}", serde_json::to_string_pretty(&keystores_with_passphrases).unwrap());
                 Ok(())
             }
+            Commands::GenerateKeystores {
+                phrase,
+                start,
+                end,
+                passphrase,
+                output_dir,
+                layout,
+                password,
+                per_key_passwords,
+                kdf,
+            } => {
+                let config = kdf.config()?;
+                let keys = keys::derive_validator_keys_from_mnemonic(
+                    &phrase,
+                    passphrase.as_deref(),
+                    start,
+                    end,
+                )?;
+                let passwords = if per_key_passwords {
+                    Passwords::PerKey(
+                        keys.iter().map(|_| keystores::generate_passphrase()).collect(),
+                    )
+                } else {
+                    Passwords::Shared(password.unwrap_or_else(keystores::generate_passphrase))
+                };
+                let keystores = keys
+                    .into_par_iter()
+                    .enumerate()
+                    .map(|(i, keys)| Keystore::new(keys.signing, passwords.get(i), &config))
+                    .collect::<Vec<_>>();
+                let timestamp = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH)?.as_secs();
+                for path in layout.write(&output_dir, &keystores, &passwords, timestamp)? {
+                    println!("{}", path.display());
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -0,0 +1,54 @@
+use crate::{
+    blobs::Error,
+    crypto::kzg as spec,
+    deneb::{Blob, BlobsBundle},
+    Error as ConsensusError,
+};
+
+type Commitment = spec::KzgCommitment;
+type Proof = spec::KzgProof;
+type CommitmentAndProof = (Commitment, Proof);
+
+pub fn commit_and_prove_blob<const BYTES_PER_BLOB: usize>(
+    blob: &Blob<BYTES_PER_BLOB>,
+    kzg_settings: &spec::KzgSettings,
+) -> Result<CommitmentAndProof, ConsensusError> {
+    let commitment = spec::blob_to_kzg_commitment(blob, kzg_settings)?;
+    let proof = spec::compute_blob_kzg_proof(blob, &commitment, kzg_settings)?;
+    Ok((commitment, proof))
+}
+
+// Computes the commitments and proofs for `blobs`, returning the (verified) `BlobsBundle`.
+pub fn bundle<const BYTES_PER_BLOB: usize>(
+    blobs: Vec<Blob<BYTES_PER_BLOB>>,
+    kzg_settings: &spec::KzgSettings,
+) -> Result<BlobsBundle<BYTES_PER_BLOB>, Error> {
+    let commitments_and_proofs = blobs
+        .iter()
+        .map(|blob| commit_and_prove_blob(blob, kzg_settings))
+        .collect::<Result<Vec<CommitmentAndProof>, ConsensusError>>()?;
+    let (commitments, proofs) = commitments_and_proofs.into_iter().unzip();
+    let blobs_bundle = BlobsBundle { commitments, proofs, blobs };
+
+    verify(&blobs_bundle, kzg_settings)?;
+
+    Ok(blobs_bundle)
+}
+
+// Verifies the commitments and proofs in `blobs_bundle` match the contained blobs.
+pub fn verify<const BYTES_PER_BLOB: usize>(
+    blobs_bundle: &BlobsBundle<BYTES_PER_BLOB>,
+    kzg_settings: &spec::KzgSettings,
+) -> Result<(), Error> {
+    let BlobsBundle { commitments, proofs, blobs } = blobs_bundle;
+    if commitments.len() != blobs.len() || proofs.len() != blobs.len() {
+        return Err(Error::BundleLengthMismatch {
+            blobs: blobs.len(),
+            commitments: commitments.len(),
+            proofs: proofs.len(),
+        })
+    }
+
+    spec::verify_blob_kzg_proof_batch(blobs, commitments, proofs, kzg_settings)?;
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use crate::{
+    blobs::{
+        framing::{self, Mode as Framing},
+        usable_bytes_per_blob, Error, BITS_PER_FIELD_ELEMENT,
+    },
+    crypto::kzg::BYTES_PER_FIELD_ELEMENT,
+    deneb::Blob,
+};
+use bitvec::prelude::*;
+use std::io::Write;
+
+const BITS_PER_SERIALIZED_FIELD_ELEMENT: usize = 8 * BYTES_PER_FIELD_ELEMENT;
+
+// Recovers the byte stream packed into `blobs`.
+// The stream includes any padding added when packing into the final blob.
+pub fn unpack_from_blobs<const BYTES_PER_BLOB: usize>(blobs: &[Blob<BYTES_PER_BLOB>]) -> Vec<u8> {
+    let mut stream = vec![0u8; blobs.len() * usable_bytes_per_blob::<BYTES_PER_BLOB>()];
+    let stream_bits = stream.view_bits_mut::<Msb0>();
+
+    let mut i = 0;
+    for blob in blobs {
+        let blob_bits = blob.as_ref().view_bits::<Msb0>();
+        // chunks of serialized field element bits
+        let mut chunks = blob_bits.chunks_exact(BITS_PER_SERIALIZED_FIELD_ELEMENT);
+        for chunk in chunks.by_ref() {
+            // first two-bits are unusable via the big-endian field element encoding
+            let src = &chunk[2..];
+            stream_bits[i * BITS_PER_FIELD_ELEMENT..(i + 1) * BITS_PER_FIELD_ELEMENT]
+                .copy_from_bitslice(src);
+            i += 1;
+        }
+
+        let remainder = chunks.remainder();
+        debug_assert!(remainder.is_empty());
+    }
+
+    stream
+}
+
+// Writes the byte stream recovered from `blobs` to `writer`, removing any `framing`.
+// Segmented framing writes each segment back-to-back.
+pub fn to_writer<const BYTES_PER_BLOB: usize>(
+    blobs: &[Blob<BYTES_PER_BLOB>],
+    mut writer: impl Write,
+    framing: Framing,
+) -> Result<(), Error> {
+    let stream = unpack_from_blobs(blobs);
+    match framing {
+        Framing::Raw => writer.write_all(&stream)?,
+        Framing::Sized => writer.write_all(framing::payload_from_sized(&stream)?)?,
+        Framing::Segmented(_) => {
+            for segment in framing::segments_from_stream(&stream)? {
+                writer.write_all(&segment)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Recovers the individual segments written with `encode::from_segments`.
+pub fn segments_from_blobs<const BYTES_PER_BLOB: usize>(
+    blobs: &[Blob<BYTES_PER_BLOB>],
+) -> Result<Vec<Vec<u8>>, Error> {
+    let stream = unpack_from_blobs(blobs);
+    framing::segments_from_stream(&stream)
+}
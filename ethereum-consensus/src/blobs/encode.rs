@@ -0,0 +1,79 @@
+use crate::{
+    blobs::{
+        framing::{self, Compression, Mode as Framing},
+        Error, BITS_PER_FIELD_ELEMENT,
+    },
+    crypto::kzg::BYTES_PER_FIELD_ELEMENT,
+    deneb::Blob,
+};
+use bitvec::prelude::*;
+use std::io::Read;
+
+type BitSlice = bitvec::slice::BitSlice<u8, Msb0>;
+
+fn field_element_from_bits(src: &BitSlice) -> [u8; BYTES_PER_FIELD_ELEMENT] {
+    let mut field_element = [0u8; BYTES_PER_FIELD_ELEMENT];
+    // first two-bits are unusable via the big-endian field element encoding;
+    // leaving them unset also ensures the element is less than the BLS modulus
+    let dst = &mut field_element.view_bits_mut::<Msb0>()[2..2 + src.len()];
+    dst.copy_from_bitslice(src);
+    field_element
+}
+
+// Pack a buffer of an arbitrary number of bytes into a series of `Blob`s.
+// An empty buffer yields a single (zero) blob.
+pub fn pack_into_blobs<const BYTES_PER_BLOB: usize>(buffer: &[u8]) -> Vec<Blob<BYTES_PER_BLOB>> {
+    let mut blobs = vec![];
+    let bits = BitSlice::from_slice(buffer);
+    let mut blob_buffer = Vec::with_capacity(BYTES_PER_BLOB);
+    let mut chunks = bits.chunks_exact(BITS_PER_FIELD_ELEMENT);
+    for src in chunks.by_ref() {
+        if blob_buffer.len() == BYTES_PER_BLOB {
+            let blob = Blob::try_from(blob_buffer.as_ref()).expect("is the right size");
+            blobs.push(blob);
+            blob_buffer.clear();
+        }
+        blob_buffer.extend_from_slice(&field_element_from_bits(src));
+    }
+
+    // ensure we have only packed complete field elements so far
+    assert!(blob_buffer.len() % BYTES_PER_FIELD_ELEMENT == 0);
+
+    let remainder = chunks.remainder();
+    if !remainder.is_empty() {
+        if blob_buffer.len() == BYTES_PER_BLOB {
+            let blob = Blob::try_from(blob_buffer.as_ref()).expect("is the right size");
+            blobs.push(blob);
+            blob_buffer.clear();
+        }
+        blob_buffer.extend_from_slice(&field_element_from_bits(remainder));
+    }
+
+    blob_buffer.resize(BYTES_PER_BLOB, 0);
+    let blob = Blob::try_from(blob_buffer.as_ref()).expect("is the right size");
+    blobs.push(blob);
+
+    blobs
+}
+
+// Writes bytes read from `reader` according to `framing` to a sequence of `Blob`s.
+// Data is tightly packed into field elements.
+pub fn from_reader<const BYTES_PER_BLOB: usize>(
+    mut reader: impl Read,
+    framing: Framing,
+) -> Result<Vec<Blob<BYTES_PER_BLOB>>, Error> {
+    let mut buffer = Vec::with_capacity(BYTES_PER_BLOB);
+    reader.read_to_end(&mut buffer)?;
+    let prepared_buffer = framing::frame(buffer, framing)?;
+    Ok(pack_into_blobs(&prepared_buffer))
+}
+
+// Writes each of the `segments` into a sequence of `Blob`s using the segmented framing so that
+// they can be recovered individually with `decode::segments_from_blobs`.
+pub fn from_segments<const BYTES_PER_BLOB: usize>(
+    segments: &[impl AsRef<[u8]>],
+    compression: Compression,
+) -> Result<Vec<Blob<BYTES_PER_BLOB>>, Error> {
+    let prepared_buffer = framing::frame_segments(segments, compression)?;
+    Ok(pack_into_blobs(&prepared_buffer))
+}
@@ -0,0 +1,186 @@
+use crate::blobs::Error;
+
+pub const SIZED_FRAMING_VERSION: u8 = 0;
+pub const SEGMENTED_FRAMING_VERSION: u8 = 1;
+pub const HEADER_SIZE: usize = 5;
+pub const SEGMENTED_HEADER_SIZE: usize = 6;
+pub const SEGMENT_HEADER_SIZE: usize = 4;
+/// Maximum total size of the (decompressed) segments in the `Segmented` framing mode.
+/// Bounds the memory a (possibly adversarial) stream of compressed segments can expand to.
+pub const MAX_PAYLOAD_SIZE: usize = 10 * 2usize.pow(20);
+
+/// A `Mode` to indicate how the target data should be packed into blob data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// No framing, data is written/read directly from the blob data
+    Raw,
+    /// The size of a "payload" is written in-band to the blob data.
+    /// Supports "lossless" {de,}serialization if the payload data is not
+    /// a multiple of the blob size.
+    Sized,
+    /// Any number of payloads ("segments") are written in-band, each with their size,
+    /// and optionally compressed.
+    Segmented(Compression),
+}
+
+/// Compression applied to each segment in the `Segmented` framing mode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Compression {
+    #[default]
+    None = 0,
+    /// Snappy compression, using the "raw" (unframed) format.
+    Snappy = 1,
+}
+
+impl TryFrom<u8> for Compression {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Snappy),
+            other => Err(Error::UnsupportedCompression(other)),
+        }
+    }
+}
+
+impl Compression {
+    fn compress(&self, data: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            Self::None => Ok(data.to_vec()),
+            Self::Snappy => Ok(snap::raw::Encoder::new().compress_vec(data)?),
+        }
+    }
+
+    // Decompresses `data`, failing if the result would be larger than `max_size` bytes.
+    fn decompress(&self, data: &[u8], max_size: usize) -> Result<Vec<u8>, Error> {
+        let size = match self {
+            Self::None => data.len(),
+            Self::Snappy => snap::raw::decompress_len(data)?,
+        };
+        if size > max_size {
+            return Err(Error::InvalidPayloadSize)
+        }
+        match self {
+            Self::None => Ok(data.to_vec()),
+            Self::Snappy => Ok(snap::raw::Decoder::new().decompress_vec(data)?),
+        }
+    }
+}
+
+fn encode_size(data_byte_length: usize) -> Result<[u8; 4], Error> {
+    let size = u32::try_from(data_byte_length).map_err(|_| Error::InvalidPayloadSize)?;
+    Ok(size.to_be_bytes())
+}
+
+fn decode_size(bytes: &[u8]) -> usize {
+    u32::from_be_bytes(bytes.try_into().expect("correct size bytes")) as usize
+}
+
+// Returns the header bytes that should prepend the target data in `Sized` framing mode.
+// The header consists of one version byte, then a `u32` integer in big-endian encoding containing
+// the size of the trailing data.
+pub fn sized_header(data_byte_length: usize) -> Result<[u8; HEADER_SIZE], Error> {
+    let mut header = [0u8; HEADER_SIZE];
+    header[0] = SIZED_FRAMING_VERSION;
+    header[1..].copy_from_slice(&encode_size(data_byte_length)?);
+    Ok(header)
+}
+
+// Attempts to parse a `stream` of bytes assuming they were written to blobs with the `Sized`
+// framing mode.
+pub fn payload_from_sized(stream: &[u8]) -> Result<&[u8], Error> {
+    if stream.len() < HEADER_SIZE {
+        return Err(Error::ExpectedHeaderForFraming)
+    }
+
+    let (header, payload) = stream.split_at(HEADER_SIZE);
+
+    if header[0] != SIZED_FRAMING_VERSION {
+        return Err(Error::UnsupportedFramingVersion(header[0]))
+    }
+    let size = decode_size(&header[1..5]);
+    if size > payload.len() {
+        return Err(Error::InvalidPayloadSize)
+    }
+
+    Ok(&payload[..size])
+}
+
+// Returns the bytes for `segments` in the `Segmented` framing mode.
+// The header consists of one version byte, one byte for the `Compression` scheme, then a `u32`
+// integer in big-endian encoding containing the number of segments. Each segment follows
+// in order as a `u32` integer in big-endian encoding containing the size of the (possibly
+// compressed) segment data, then the segment data.
+pub fn frame_segments(
+    segments: &[impl AsRef<[u8]>],
+    compression: Compression,
+) -> Result<Vec<u8>, Error> {
+    let mut buffer = Vec::with_capacity(SEGMENTED_HEADER_SIZE);
+    buffer.push(SEGMENTED_FRAMING_VERSION);
+    buffer.push(compression as u8);
+    buffer.extend_from_slice(&encode_size(segments.len())?);
+    let total_size = segments.iter().map(|segment| segment.as_ref().len()).sum::<usize>();
+    if total_size > MAX_PAYLOAD_SIZE {
+        return Err(Error::InvalidPayloadSize)
+    }
+    for segment in segments {
+        let data = compression.compress(segment.as_ref())?;
+        buffer.extend_from_slice(&encode_size(data.len())?);
+        buffer.extend_from_slice(&data);
+    }
+    Ok(buffer)
+}
+
+// Attempts to parse a `stream` of bytes assuming they were written to blobs with the `Segmented`
+// framing mode. Fails if the segments decompress to more than `MAX_PAYLOAD_SIZE` bytes in total.
+pub fn segments_from_stream(stream: &[u8]) -> Result<Vec<Vec<u8>>, Error> {
+    if stream.len() < SEGMENTED_HEADER_SIZE {
+        return Err(Error::ExpectedHeaderForFraming)
+    }
+
+    let (header, mut cursor) = stream.split_at(SEGMENTED_HEADER_SIZE);
+
+    if header[0] != SEGMENTED_FRAMING_VERSION {
+        return Err(Error::UnsupportedFramingVersion(header[0]))
+    }
+    let compression = Compression::try_from(header[1])?;
+    let count = decode_size(&header[2..6]);
+
+    // NOTE: do not trust `count` when reserving capacity
+    let mut segments = vec![];
+    let mut remaining = MAX_PAYLOAD_SIZE;
+    for _ in 0..count {
+        if cursor.len() < SEGMENT_HEADER_SIZE {
+            return Err(Error::InvalidPayloadSize)
+        }
+        let (size, rest) = cursor.split_at(SEGMENT_HEADER_SIZE);
+        let size = decode_size(size);
+        if size > rest.len() {
+            return Err(Error::InvalidPayloadSize)
+        }
+        let (data, rest) = rest.split_at(size);
+        let segment = compression.decompress(data, remaining)?;
+        remaining -= segment.len();
+        segments.push(segment);
+        cursor = rest;
+    }
+    Ok(segments)
+}
+
+// Prepares `payload` for packing into blobs according to `mode`.
+// `Segmented` framing writes the `payload` as a single segment.
+pub fn frame(mut payload: Vec<u8>, mode: Mode) -> Result<Vec<u8>, Error> {
+    match mode {
+        Mode::Raw => Ok(payload),
+        Mode::Sized => {
+            let header = sized_header(payload.len())?;
+            let mut framed_buffer = Vec::with_capacity(header.len() + payload.len());
+            framed_buffer.extend_from_slice(&header);
+            framed_buffer.append(&mut payload);
+            Ok(framed_buffer)
+        }
+        Mode::Segmented(compression) => frame_segments(&[payload], compression),
+    }
+}
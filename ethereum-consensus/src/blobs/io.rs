@@ -0,0 +1,87 @@
+//! Streaming adapters between byte streams and blobs.
+//!
+//! Both adapters rely on the data in a blob being aligned to a whole number of bytes,
+//! which holds for any blob with a multiple of 4 field elements (e.g. all presets).
+use crate::{
+    blobs::{decode::unpack_from_blobs, encode::pack_into_blobs, usable_bytes_per_blob},
+    deneb::Blob,
+};
+use std::io::{self, Read, Write};
+
+/// A `Write` adapter that (raw) packs written bytes into blobs as data arrives.
+#[derive(Debug)]
+pub struct BlobWriter<const BYTES_PER_BLOB: usize> {
+    buffer: Vec<u8>,
+    blobs: Vec<Blob<BYTES_PER_BLOB>>,
+}
+
+impl<const BYTES_PER_BLOB: usize> Default for BlobWriter<BYTES_PER_BLOB> {
+    fn default() -> Self {
+        Self {
+            buffer: Vec::with_capacity(usable_bytes_per_blob::<BYTES_PER_BLOB>()),
+            blobs: vec![],
+        }
+    }
+}
+
+impl<const BYTES_PER_BLOB: usize> BlobWriter<BYTES_PER_BLOB> {
+    /// Pack any remaining data into a final (zero-padded) blob and return all blobs.
+    pub fn finish(mut self) -> Vec<Blob<BYTES_PER_BLOB>> {
+        if !self.buffer.is_empty() || self.blobs.is_empty() {
+            self.blobs.extend(pack_into_blobs(&self.buffer));
+        }
+        self.blobs
+    }
+}
+
+impl<const BYTES_PER_BLOB: usize> Write for BlobWriter<BYTES_PER_BLOB> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let usable_bytes = usable_bytes_per_blob::<BYTES_PER_BLOB>();
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= usable_bytes {
+            let mut chunks = self.buffer.chunks_exact(usable_bytes);
+            for chunk in chunks.by_ref() {
+                self.blobs.extend(pack_into_blobs(chunk));
+            }
+            self.buffer = chunks.remainder().to_vec();
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A `Read` adapter that unpacks the (raw) data in a sequence of blobs, one blob at a time.
+#[derive(Debug)]
+pub struct BlobReader<'a, const BYTES_PER_BLOB: usize> {
+    blobs: std::slice::Iter<'a, Blob<BYTES_PER_BLOB>>,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl<'a, const BYTES_PER_BLOB: usize> BlobReader<'a, BYTES_PER_BLOB> {
+    pub fn new(blobs: &'a [Blob<BYTES_PER_BLOB>]) -> Self {
+        Self { blobs: blobs.iter(), buffer: vec![], position: 0 }
+    }
+}
+
+impl<const BYTES_PER_BLOB: usize> Read for BlobReader<'_, BYTES_PER_BLOB> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.position == self.buffer.len() {
+            match self.blobs.next() {
+                Some(blob) => {
+                    self.buffer = unpack_from_blobs(std::slice::from_ref(blob));
+                    self.position = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+        let available = &self.buffer[self.position..];
+        let count = available.len().min(buf.len());
+        buf[..count].copy_from_slice(&available[..count]);
+        self.position += count;
+        Ok(count)
+    }
+}
@@ -0,0 +1,138 @@
+//! Facilities for mapping an arbitrary stream of data into blobs and back.
+//!
+//! Data is tightly packed into the low 254 bits of each field element of a blob so that
+//! any byte stream yields valid blobs. All routines are generic over the blob size
+//! (`BYTES_PER_BLOB`) so they can be used with any preset.
+pub mod bundler;
+pub mod decode;
+pub mod encode;
+pub mod framing;
+pub mod io;
+
+pub use bundler::{bundle, verify};
+pub use decode::unpack_from_blobs;
+pub use encode::pack_into_blobs;
+pub use framing::{Compression, Mode as Framing};
+pub use io::{BlobReader, BlobWriter};
+
+use crate::{
+    crypto::{kzg::BYTES_PER_FIELD_ELEMENT, KzgError},
+    Error as ConsensusError,
+};
+use thiserror::Error;
+
+// Number of bits in a valid field element.
+pub const BITS_PER_FIELD_ELEMENT: usize = 254;
+
+/// Number of bytes of data that can be packed into a single blob of `BYTES_PER_BLOB` bytes.
+pub const fn usable_bytes_per_blob<const BYTES_PER_BLOB: usize>() -> usize {
+    BYTES_PER_BLOB / BYTES_PER_FIELD_ELEMENT * BITS_PER_FIELD_ELEMENT / 8
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("framing was requested but decoded stream is not large enough for frame header")]
+    ExpectedHeaderForFraming,
+    #[error("the encoded framing version {0} is not supported")]
+    UnsupportedFramingVersion(u8),
+    #[error("the encoded compression scheme {0} is not supported")]
+    UnsupportedCompression(u8),
+    #[error("framing was requested but the encoded size exceeds the maximum possible size")]
+    InvalidPayloadSize,
+    #[error("blobs bundle has {blobs} blobs but {commitments} commitments and {proofs} proofs")]
+    BundleLengthMismatch { blobs: usize, commitments: usize, proofs: usize },
+    #[error(transparent)]
+    Compression(#[from] snap::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Kzg(#[from] KzgError),
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deneb::mainnet::{Blob, BYTES_PER_BLOB};
+    use std::io::{Read, Write};
+
+    fn data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    #[test]
+    fn test_roundtrip_raw() {
+        let len = usable_bytes_per_blob::<BYTES_PER_BLOB>() + 17;
+        let data = data(len);
+        let blobs = pack_into_blobs::<BYTES_PER_BLOB>(&data);
+        assert_eq!(blobs.len(), 2);
+        let recovered = unpack_from_blobs(&blobs);
+        assert_eq!(&recovered[..len], &data[..]);
+        assert!(recovered[len..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_roundtrip_io() {
+        let data = data(3 * usable_bytes_per_blob::<BYTES_PER_BLOB>() / 2);
+        let mut writer = BlobWriter::<BYTES_PER_BLOB>::default();
+        for chunk in data.chunks(1000) {
+            writer.write_all(chunk).unwrap();
+        }
+        let blobs = writer.finish();
+        assert_eq!(blobs, pack_into_blobs::<BYTES_PER_BLOB>(&data));
+
+        let mut recovered = vec![];
+        BlobReader::new(&blobs).read_to_end(&mut recovered).unwrap();
+        assert_eq!(&recovered[..data.len()], &data[..]);
+    }
+
+    #[test]
+    fn test_roundtrip_framing() {
+        let data = data(1234);
+        for mode in [Framing::Sized, Framing::Segmented(Compression::Snappy)] {
+            let blobs: Vec<Blob> = encode::from_reader(&data[..], mode).unwrap();
+            let mut recovered = vec![];
+            decode::to_writer(&blobs, &mut recovered, mode).unwrap();
+            assert_eq!(recovered, data);
+        }
+    }
+
+    #[test]
+    fn test_multiple_segments() {
+        let segments = [data(10), vec![], data(100_000)];
+        for compression in [Compression::None, Compression::Snappy] {
+            let blobs: Vec<Blob> = encode::from_segments(&segments, compression).unwrap();
+            let recovered = decode::segments_from_blobs(&blobs).unwrap();
+            assert_eq!(recovered, segments);
+        }
+    }
+
+    #[test]
+    fn test_segments_exceeding_max_payload_size() {
+        let segment = vec![0u8; framing::MAX_PAYLOAD_SIZE + 1];
+        let result = framing::frame_segments(&[&segment], Compression::Snappy);
+        assert!(matches!(result, Err(Error::InvalidPayloadSize)));
+
+        // a small compressed segment that claims a decompressed size beyond the limit
+        let compressed = snap::raw::Encoder::new().compress_vec(&segment).unwrap();
+        let mut stream = vec![framing::SEGMENTED_FRAMING_VERSION, Compression::Snappy as u8];
+        stream.extend_from_slice(&1u32.to_be_bytes());
+        stream.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+        stream.extend_from_slice(&compressed);
+        let result = framing::segments_from_stream(&stream);
+        assert!(matches!(result, Err(Error::InvalidPayloadSize)));
+
+        // the limit applies to the total size of all segments
+        let half = vec![0u8; framing::MAX_PAYLOAD_SIZE / 2 + 1];
+        let compressed = snap::raw::Encoder::new().compress_vec(&half).unwrap();
+        let mut stream = vec![framing::SEGMENTED_FRAMING_VERSION, Compression::Snappy as u8];
+        stream.extend_from_slice(&2u32.to_be_bytes());
+        for _ in 0..2 {
+            stream.extend_from_slice(&(compressed.len() as u32).to_be_bytes());
+            stream.extend_from_slice(&compressed);
+        }
+        let result = framing::segments_from_stream(&stream);
+        assert!(matches!(result, Err(Error::InvalidPayloadSize)));
+    }
+}
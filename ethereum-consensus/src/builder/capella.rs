@@ -0,0 +1,24 @@
+use crate::{
+    capella::ExecutionPayloadHeader,
+    primitives::{BlsPublicKey, BlsSignature},
+    ssz::prelude::*,
+};
+
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
+)]
+pub struct BuilderBid<const BYTES_PER_LOGS_BLOOM: usize, const MAX_EXTRA_DATA_BYTES: usize> {
+    pub header: ExecutionPayloadHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    #[serde(with = "crate::serde::as_str")]
+    pub value: U256,
+    #[serde(rename = "pubkey")]
+    pub public_key: BlsPublicKey,
+}
+
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
+)]
+pub struct SignedBuilderBid<const BYTES_PER_LOGS_BLOOM: usize, const MAX_EXTRA_DATA_BYTES: usize> {
+    pub message: BuilderBid<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub signature: BlsSignature,
+}
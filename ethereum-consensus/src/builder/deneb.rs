@@ -0,0 +1,55 @@
+use crate::{
+    crypto::KzgCommitment,
+    deneb::{BlobsBundle, ExecutionPayload, ExecutionPayloadHeader},
+    primitives::{BlsPublicKey, BlsSignature},
+    ssz::prelude::*,
+};
+
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
+)]
+pub struct BuilderBid<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+> {
+    pub header: ExecutionPayloadHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>,
+    pub blob_kzg_commitments: List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    #[serde(with = "crate::serde::as_str")]
+    pub value: U256,
+    #[serde(rename = "pubkey")]
+    pub public_key: BlsPublicKey,
+}
+
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
+)]
+pub struct SignedBuilderBid<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+> {
+    pub message:
+        BuilderBid<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    pub signature: BlsSignature,
+}
+
+/// The payload a relay returns for a `deneb` blinded block, along with the blobs it commits to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ExecutionPayloadAndBlobsBundle<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const BYTES_PER_BLOB: usize,
+> {
+    pub execution_payload: ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >,
+    pub blobs_bundle: BlobsBundle<BYTES_PER_BLOB>,
+}
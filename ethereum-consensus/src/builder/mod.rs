@@ -1,10 +1,23 @@
+//! Types of the builder API (<https://github.com/ethereum/builder-specs>), which proposers use to
+//! source execution payloads from relays.
+pub mod bellatrix;
+pub mod capella;
+pub mod deneb;
+pub mod electra;
+pub mod presets;
+pub mod relay;
+
+pub use presets::{mainnet, minimal};
+
 use crate::{
     phase0::compute_domain,
-    primitives::{BlsPublicKey, BlsSignature, Domain, DomainType, ExecutionAddress},
+    primitives::{BlsPublicKey, BlsSignature, Domain, DomainType, ExecutionAddress, Hash32},
+    signing::verify_signed_data,
     ssz::prelude::*,
     state_transition::Context,
-    Error,
+    Error, Fork,
 };
+use serde::de::Error as _;
 
 #[derive(Debug, Clone, Default, SimpleSerialize, serde::Serialize, serde::Deserialize)]
 pub struct ValidatorRegistration {
@@ -27,3 +40,259 @@ pub fn compute_builder_domain(context: &Context) -> Result<Domain, Error> {
     let domain_type = DomainType::ApplicationBuilder;
     compute_domain(domain_type, None, None, context)
 }
+
+/// A bid from a builder for the fork it was made in.
+///
+/// The JSON encodings of the bids of each fork cannot be told apart, so use
+/// [`SignedBuilderBid::deserialize_versioned`] with the version given alongside the bid.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(untagged)]
+pub enum SignedBuilderBid<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+> {
+    Bellatrix(bellatrix::SignedBuilderBid<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>),
+    Capella(capella::SignedBuilderBid<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>),
+    Deneb(
+        deneb::SignedBuilderBid<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+    ),
+    Electra(
+        electra::SignedBuilderBid<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+    ),
+}
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    > SignedBuilderBid<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES, MAX_BLOB_COMMITMENTS_PER_BLOCK>
+{
+    pub fn deserialize_versioned<'de, D: serde::Deserializer<'de>>(
+        version: Fork,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let bid = match version {
+            Fork::Bellatrix => Self::Bellatrix(serde::Deserialize::deserialize(deserializer)?),
+            Fork::Capella => Self::Capella(serde::Deserialize::deserialize(deserializer)?),
+            Fork::Deneb => Self::Deneb(serde::Deserialize::deserialize(deserializer)?),
+            Fork::Electra => Self::Electra(serde::Deserialize::deserialize(deserializer)?),
+            version => return Err(D::Error::custom(format!("no builder bid for fork {version}"))),
+        };
+        Ok(bid)
+    }
+
+    pub fn version(&self) -> Fork {
+        match self {
+            Self::Bellatrix(_) => Fork::Bellatrix,
+            Self::Capella(_) => Fork::Capella,
+            Self::Deneb(_) => Fork::Deneb,
+            Self::Electra(_) => Fork::Electra,
+        }
+    }
+
+    pub fn value(&self) -> U256 {
+        match self {
+            Self::Bellatrix(bid) => bid.message.value,
+            Self::Capella(bid) => bid.message.value,
+            Self::Deneb(bid) => bid.message.value,
+            Self::Electra(bid) => bid.message.value,
+        }
+    }
+
+    pub fn public_key(&self) -> &BlsPublicKey {
+        match self {
+            Self::Bellatrix(bid) => &bid.message.public_key,
+            Self::Capella(bid) => &bid.message.public_key,
+            Self::Deneb(bid) => &bid.message.public_key,
+            Self::Electra(bid) => &bid.message.public_key,
+        }
+    }
+
+    pub fn parent_hash(&self) -> &Hash32 {
+        match self {
+            Self::Bellatrix(bid) => &bid.message.header.parent_hash,
+            Self::Capella(bid) => &bid.message.header.parent_hash,
+            Self::Deneb(bid) => &bid.message.header.parent_hash,
+            Self::Electra(bid) => &bid.message.header.parent_hash,
+        }
+    }
+
+    pub fn block_hash(&self) -> &Hash32 {
+        match self {
+            Self::Bellatrix(bid) => &bid.message.header.block_hash,
+            Self::Capella(bid) => &bid.message.header.block_hash,
+            Self::Deneb(bid) => &bid.message.header.block_hash,
+            Self::Electra(bid) => &bid.message.header.block_hash,
+        }
+    }
+
+    /// Verifies the signature of the bid by its builder under the builder `domain`
+    /// (see [`compute_builder_domain`]).
+    pub fn verify_signature(&self, domain: Domain) -> Result<(), Error> {
+        match self {
+            Self::Bellatrix(bid) => {
+                verify_signed_data(&bid.message, &bid.signature, &bid.message.public_key, domain)
+            }
+            Self::Capella(bid) => {
+                verify_signed_data(&bid.message, &bid.signature, &bid.message.public_key, domain)
+            }
+            Self::Deneb(bid) => {
+                verify_signed_data(&bid.message, &bid.signature, &bid.message.public_key, domain)
+            }
+            Self::Electra(bid) => {
+                verify_signed_data(&bid.message, &bid.signature, &bid.message.public_key, domain)
+            }
+        }
+    }
+}
+
+/// The execution payload a relay reveals for a signed blinded block.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(untagged)]
+pub enum ExecutionPayloadResponse<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const BYTES_PER_BLOB: usize,
+> {
+    Bellatrix(
+        crate::bellatrix::ExecutionPayload<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+        >,
+    ),
+    Capella(
+        crate::capella::ExecutionPayload<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+        >,
+    ),
+    Deneb(
+        deneb::ExecutionPayloadAndBlobsBundle<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            BYTES_PER_BLOB,
+        >,
+    ),
+}
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const BYTES_PER_BLOB: usize,
+    >
+    ExecutionPayloadResponse<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        BYTES_PER_BLOB,
+    >
+{
+    pub fn deserialize_versioned<'de, D: serde::Deserializer<'de>>(
+        version: Fork,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let response = match version {
+            Fork::Bellatrix => Self::Bellatrix(serde::Deserialize::deserialize(deserializer)?),
+            Fork::Capella => Self::Capella(serde::Deserialize::deserialize(deserializer)?),
+            Fork::Deneb => Self::Deneb(serde::Deserialize::deserialize(deserializer)?),
+            version => {
+                return Err(D::Error::custom(format!("no execution payload for fork {version}")))
+            }
+        };
+        Ok(response)
+    }
+
+    pub fn version(&self) -> Fork {
+        match self {
+            Self::Bellatrix(_) => Fork::Bellatrix,
+            Self::Capella(_) => Fork::Capella,
+            Self::Deneb(_) => Fork::Deneb,
+        }
+    }
+
+    pub fn block_hash(&self) -> &Hash32 {
+        match self {
+            Self::Bellatrix(payload) => &payload.block_hash,
+            Self::Capella(payload) => &payload.block_hash,
+            Self::Deneb(contents) => &contents.execution_payload.block_hash,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{builder::mainnet, crypto::SecretKey, signing::sign_with_domain};
+
+    #[test]
+    fn test_verify_signature() {
+        let context = Context::for_mainnet();
+        let domain = compute_builder_domain(&context).unwrap();
+        let secret_key = SecretKey::random(&mut rand::thread_rng()).unwrap();
+
+        let mut bid = mainnet::ElectraSignedBuilderBid::default();
+        bid.message.value = U256::from(1_000_000_000u64);
+        bid.message.public_key = secret_key.public_key();
+        bid.signature = sign_with_domain(&bid.message, &secret_key, domain).unwrap();
+        let mut bid = mainnet::SignedBuilderBid::Electra(bid);
+        assert_eq!(bid.version(), Fork::Electra);
+        bid.verify_signature(domain).unwrap();
+
+        // the signature must be made under the builder domain...
+        let domain = compute_domain(DomainType::BeaconProposer, None, None, &context).unwrap();
+        assert!(bid.verify_signature(domain).is_err());
+        // ...and cover the whole bid
+        let domain = compute_builder_domain(&context).unwrap();
+        if let mainnet::SignedBuilderBid::Electra(bid) = &mut bid {
+            bid.message.value = U256::from(2_000_000_000u64);
+        }
+        assert!(bid.verify_signature(domain).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_versioned() {
+        let bids = [
+            mainnet::SignedBuilderBid::Bellatrix(Default::default()),
+            mainnet::SignedBuilderBid::Capella(Default::default()),
+            mainnet::SignedBuilderBid::Deneb(Default::default()),
+            mainnet::SignedBuilderBid::Electra(Default::default()),
+        ];
+        for bid in bids {
+            let json = serde_json::to_value(&bid).unwrap();
+            let decoded = mainnet::SignedBuilderBid::deserialize_versioned(bid.version(), json);
+            assert_eq!(decoded.unwrap(), bid);
+        }
+
+        // an `electra` header has fields a `deneb` header does not
+        let json = serde_json::to_value(mainnet::DenebSignedBuilderBid::default()).unwrap();
+        let result = mainnet::SignedBuilderBid::deserialize_versioned(Fork::Electra, json.clone());
+        assert!(result.is_err());
+        let result = mainnet::SignedBuilderBid::deserialize_versioned(Fork::Phase0, json);
+        assert!(result.unwrap_err().to_string().contains("no builder bid for fork phase0"));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{
+    builder::{self, bellatrix, capella, deneb, electra, relay},
+    deneb::mainnet::{
+        BYTES_PER_BLOB, BYTES_PER_LOGS_BLOOM, MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_BYTES_PER_TRANSACTION, MAX_EXTRA_DATA_BYTES, MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    },
+    electra::mainnet::{MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD, MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD},
+};
+
+pub type BellatrixSignedBuilderBid =
+    bellatrix::SignedBuilderBid<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>;
+pub type CapellaSignedBuilderBid =
+    capella::SignedBuilderBid<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>;
+pub type DenebSignedBuilderBid = deneb::SignedBuilderBid<
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+>;
+
+pub type ElectraSignedBuilderBid = electra::SignedBuilderBid<
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+>;
+
+pub type SignedBuilderBid = builder::SignedBuilderBid<
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+>;
+
+pub type ExecutionPayloadAndBlobsBundle = deneb::ExecutionPayloadAndBlobsBundle<
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+    MAX_WITHDRAWALS_PER_PAYLOAD,
+    BYTES_PER_BLOB,
+>;
+
+pub type ExecutionPayloadResponse = builder::ExecutionPayloadResponse<
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+    MAX_WITHDRAWALS_PER_PAYLOAD,
+    BYTES_PER_BLOB,
+>;
+
+pub type CapellaSignedBidSubmission = relay::capella::SignedBidSubmission<
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+    MAX_WITHDRAWALS_PER_PAYLOAD,
+>;
+
+pub type DenebBlobsBundle =
+    relay::deneb::BlobsBundle<MAX_BLOB_COMMITMENTS_PER_BLOCK, BYTES_PER_BLOB>;
+
+pub type DenebSignedBidSubmission = relay::deneb::SignedBidSubmission<
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+    MAX_WITHDRAWALS_PER_PAYLOAD,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    BYTES_PER_BLOB,
+>;
+
+pub type ElectraSignedBidSubmission = relay::electra::SignedBidSubmission<
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+    MAX_WITHDRAWALS_PER_PAYLOAD,
+    MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+    MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    BYTES_PER_BLOB,
+>;
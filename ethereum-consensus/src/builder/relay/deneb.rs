@@ -0,0 +1,340 @@
+use crate::{
+    builder::relay::{
+        invalid_bid_submission_error, BidSubmission, BidTrace, ExpectedPayload,
+        InvalidBidSubmission,
+    },
+    crypto::{kzg::verify_blob_kzg_proof_batch, KzgCommitment, KzgProof},
+    deneb::{
+        compute_timestamp_at_slot, get_current_epoch, get_expected_withdrawals, get_randao_mix,
+        BeaconState, Blob, ExecutionPayload, Withdrawal,
+    },
+    primitives::{BlsSignature, Bytes32, Hash32, Root},
+    ssz::prelude::*,
+    state_transition::{Context, Result},
+};
+
+/// The blobs of a submission along with their commitments and proofs.
+///
+/// Unlike `deneb::BlobsBundle`, the lists are bounded so that submissions have an SSZ encoding.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
+)]
+pub struct BlobsBundle<const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize, const BYTES_PER_BLOB: usize> {
+    pub commitments: List<KzgCommitment, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    pub proofs: List<KzgProof, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+    pub blobs: List<Blob<BYTES_PER_BLOB>, MAX_BLOB_COMMITMENTS_PER_BLOCK>,
+}
+
+/// Checks `bundle` has a commitment and proof for each blob, has at most `limit` blobs and that
+/// the proofs verify, as a batch, against the blobs and commitments.
+pub(crate) fn verify_blobs_bundle<
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const BYTES_PER_BLOB: usize,
+>(
+    bundle: &BlobsBundle<MAX_BLOB_COMMITMENTS_PER_BLOCK, BYTES_PER_BLOB>,
+    limit: usize,
+    context: &Context,
+) -> Result<()> {
+    let commitments = bundle.commitments.len();
+    if commitments > limit ||
+        bundle.proofs.len() != commitments ||
+        bundle.blobs.len() != commitments
+    {
+        return Err(invalid_bid_submission_error(InvalidBidSubmission::InvalidBlobsBundle {
+            commitments,
+            proofs: bundle.proofs.len(),
+            blobs: bundle.blobs.len(),
+            limit,
+        }))
+    }
+    let blobs: &[Blob<BYTES_PER_BLOB>] = bundle.blobs.as_ref();
+    let commitments: &[KzgCommitment] = bundle.commitments.as_ref();
+    let proofs: &[KzgProof] = bundle.proofs.as_ref();
+    verify_blob_kzg_proof_batch(blobs, commitments, proofs, &context.kzg_settings)
+        .map_err(|err| invalid_bid_submission_error(InvalidBidSubmission::InvalidBlobProofs(err)))
+}
+
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
+)]
+pub struct SignedBidSubmission<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const BYTES_PER_BLOB: usize,
+> {
+    pub message: BidTrace,
+    pub execution_payload: ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+    >,
+    pub blobs_bundle: BlobsBundle<MAX_BLOB_COMMITMENTS_PER_BLOCK, BYTES_PER_BLOB>,
+    pub signature: BlsSignature,
+}
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const BYTES_PER_BLOB: usize,
+    > BidSubmission
+    for SignedBidSubmission<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        BYTES_PER_BLOB,
+    >
+{
+    fn trace(&self) -> &BidTrace {
+        &self.message
+    }
+
+    fn signature(&self) -> &BlsSignature {
+        &self.signature
+    }
+
+    fn parent_hash(&self) -> &Hash32 {
+        &self.execution_payload.parent_hash
+    }
+
+    fn block_hash(&self) -> &Hash32 {
+        &self.execution_payload.block_hash
+    }
+
+    fn prev_randao(&self) -> &Bytes32 {
+        &self.execution_payload.prev_randao
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.execution_payload.gas_limit
+    }
+
+    fn gas_used(&self) -> u64 {
+        self.execution_payload.gas_used
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.execution_payload.timestamp
+    }
+
+    fn withdrawals_root(&self) -> Result<Root> {
+        Ok(self.execution_payload.withdrawals.hash_tree_root()?)
+    }
+
+    fn compute_withdrawals_root(&self, withdrawals: Vec<Withdrawal>) -> Result<Root> {
+        let withdrawals = List::<Withdrawal, MAX_WITHDRAWALS_PER_PAYLOAD>::try_from(withdrawals)
+            .map_err(|(_, err)| err)?;
+        Ok(withdrawals.hash_tree_root()?)
+    }
+
+    fn verify_contents(&self, context: &Context) -> Result<()> {
+        verify_blobs_bundle(&self.blobs_bundle, context.max_blobs_per_block, context)
+    }
+}
+
+/// Computes the payload a proposal must build from `state`, the head state advanced to the slot
+/// of the proposal.
+pub fn expected_payload<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    context: &Context,
+) -> Result<ExpectedPayload> {
+    Ok(ExpectedPayload {
+        slot: state.slot,
+        parent_hash: state.latest_execution_payload_header.block_hash.clone(),
+        prev_randao: get_randao_mix(state, get_current_epoch(state, context)).clone(),
+        timestamp: compute_timestamp_at_slot(state, state.slot, context)?,
+        withdrawals: get_expected_withdrawals(state, context),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        builder::{
+            compute_builder_domain, minimal, relay::validate_bid_submission, ValidatorRegistration,
+        },
+        crypto::{
+            kzg::{blob_to_kzg_commitment, compute_blob_kzg_proof},
+            SecretKey,
+        },
+        deneb::minimal as spec,
+        phase0::Validator,
+        primitives::ETH1_ADDRESS_WITHDRAWAL_PREFIX,
+        signing::sign_with_domain,
+    };
+
+    const GENESIS_TIME: u64 = 1_606_824_023;
+    const SLOT: u64 = 17;
+
+    fn bytes<T: for<'a> TryFrom<&'a [u8]>>(byte: u8, len: usize) -> T {
+        T::try_from(vec![byte; len].as_slice()).ok().unwrap()
+    }
+
+    fn blobs_bundle(count: usize, context: &Context) -> minimal::DenebBlobsBundle {
+        let mut bundle = minimal::DenebBlobsBundle::default();
+        for i in 0..count {
+            let mut blob = spec::Blob::default();
+            // NOTE: leave the first byte of each field element empty so it stays canonical
+            blob[31] = i as u8 + 1;
+            let commitment = blob_to_kzg_commitment(&blob, &context.kzg_settings).unwrap();
+            let proof = compute_blob_kzg_proof(&blob, &commitment, &context.kzg_settings).unwrap();
+            bundle.commitments.push(commitment);
+            bundle.proofs.push(proof);
+            bundle.blobs.push(blob);
+        }
+        bundle
+    }
+
+    fn rejection(
+        submission: &minimal::DenebSignedBidSubmission,
+        context: &Context,
+    ) -> InvalidBidSubmission {
+        match submission.verify_contents(context) {
+            Err(crate::Error::InvalidBidSubmission(err)) => *err,
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_verify_contents() {
+        let context = Context::for_minimal();
+        let submission = minimal::DenebSignedBidSubmission {
+            blobs_bundle: blobs_bundle(2, &context),
+            ..Default::default()
+        };
+        submission.verify_contents(&context).unwrap();
+        let encoding = serialize(&submission).unwrap();
+        let recovered = minimal::DenebSignedBidSubmission::deserialize(&encoding).unwrap();
+        assert_eq!(recovered, submission);
+
+        let mut invalid = submission.clone();
+        let proofs = submission.blobs_bundle.proofs.iter().rev().cloned().collect::<Vec<_>>();
+        invalid.blobs_bundle.proofs = List::try_from(proofs).unwrap();
+        assert!(matches!(
+            rejection(&invalid, &context),
+            InvalidBidSubmission::InvalidBlobProofs(..)
+        ));
+
+        let mut invalid = submission.clone();
+        invalid.blobs_bundle.proofs =
+            List::try_from(vec![submission.blobs_bundle.proofs[0].clone()]).unwrap();
+        assert!(matches!(
+            rejection(&invalid, &context),
+            InvalidBidSubmission::InvalidBlobsBundle { commitments: 2, proofs: 1, .. }
+        ));
+
+        let limit = context.max_blobs_per_block;
+        let invalid = minimal::DenebSignedBidSubmission {
+            blobs_bundle: blobs_bundle(limit + 1, &context),
+            ..Default::default()
+        };
+        let err = rejection(&invalid, &context);
+        assert!(
+            matches!(err, InvalidBidSubmission::InvalidBlobsBundle { limit: l, .. } if l == limit)
+        );
+    }
+
+    #[test]
+    fn test_expected_payload() {
+        let context = Context::for_minimal();
+
+        // a fully withdrawable validator, so the payload has a withdrawal
+        let mut withdrawal_credentials = Bytes32::default();
+        withdrawal_credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
+        let validator = Validator { withdrawal_credentials, ..Default::default() };
+        let mut state = spec::BeaconState {
+            genesis_time: GENESIS_TIME,
+            slot: SLOT,
+            validators: List::try_from(vec![validator]).unwrap(),
+            balances: List::try_from(vec![32_000_000_000]).unwrap(),
+            ..Default::default()
+        };
+        state.latest_execution_payload_header.block_hash = bytes(0x22, 32);
+        let epoch = get_current_epoch(&state, &context) as usize;
+        state.randao_mixes[epoch] = bytes(0x33, 32);
+
+        let expected = expected_payload(&state, &context).unwrap();
+        assert_eq!(expected.slot, SLOT);
+        assert_eq!(expected.parent_hash, bytes(0x22, 32));
+        assert_eq!(expected.prev_randao, bytes(0x33, 32));
+        assert_eq!(expected.timestamp, GENESIS_TIME + SLOT * context.seconds_per_slot);
+        let withdrawal = Withdrawal { amount: 32_000_000_000, ..Default::default() };
+        assert_eq!(expected.withdrawals, vec![withdrawal]);
+
+        // a submission building the expected payload is valid
+        let mut rng = rand::thread_rng();
+        let builder_key = SecretKey::random(&mut rng).unwrap();
+        let registration = ValidatorRegistration {
+            fee_recipient: bytes(0x11, 20),
+            gas_limit: 30_000_000,
+            timestamp: GENESIS_TIME,
+            public_key: SecretKey::random(&mut rng).unwrap().public_key(),
+        };
+        let execution_payload = spec::ExecutionPayload {
+            parent_hash: expected.parent_hash.clone(),
+            fee_recipient: registration.fee_recipient.clone(),
+            prev_randao: expected.prev_randao.clone(),
+            gas_limit: 30_000_000,
+            gas_used: 15_000_000,
+            timestamp: expected.timestamp,
+            block_hash: bytes(0x44, 32),
+            withdrawals: List::try_from(expected.withdrawals.clone()).unwrap(),
+            ..Default::default()
+        };
+        let message = BidTrace {
+            slot: SLOT,
+            parent_hash: execution_payload.parent_hash.clone(),
+            block_hash: execution_payload.block_hash.clone(),
+            builder_public_key: builder_key.public_key(),
+            proposer_public_key: registration.public_key.clone(),
+            proposer_fee_recipient: registration.fee_recipient.clone(),
+            gas_limit: execution_payload.gas_limit,
+            gas_used: execution_payload.gas_used,
+            value: U256::from(1_000_000_000u64),
+        };
+        let domain = compute_builder_domain(&context).unwrap();
+        let signature = sign_with_domain(&message, &builder_key, domain).unwrap();
+        let submission = minimal::DenebSignedBidSubmission {
+            message,
+            execution_payload,
+            blobs_bundle: blobs_bundle(1, &context),
+            signature,
+        };
+        validate_bid_submission(&submission, &registration, &expected, &context).unwrap();
+    }
+}
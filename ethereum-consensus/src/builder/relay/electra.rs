@@ -0,0 +1,160 @@
+use crate::{
+    builder::relay::{
+        deneb::{verify_blobs_bundle, BlobsBundle},
+        BidSubmission, BidTrace, ExpectedPayload,
+    },
+    electra::{
+        compute_timestamp_at_slot, get_current_epoch, get_expected_withdrawals, get_randao_mix,
+        BeaconState, ExecutionPayload, Withdrawal,
+    },
+    primitives::{BlsSignature, Bytes32, Hash32, Root},
+    ssz::prelude::*,
+    state_transition::{Context, Result},
+};
+
+/// A builder's block submission from `electra` onwards.
+///
+/// The execution requests of the block (its deposit receipts and withdrawal requests) are part of
+/// `execution_payload` in `electra`, so the submission carries them to the relay with the payload.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
+)]
+pub struct SignedBidSubmission<
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    const BYTES_PER_BLOB: usize,
+> {
+    pub message: BidTrace,
+    pub execution_payload: ExecutionPayload<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    >,
+    pub blobs_bundle: BlobsBundle<MAX_BLOB_COMMITMENTS_PER_BLOCK, BYTES_PER_BLOB>,
+    pub signature: BlsSignature,
+}
+
+impl<
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const BYTES_PER_BLOB: usize,
+    > BidSubmission
+    for SignedBidSubmission<
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        BYTES_PER_BLOB,
+    >
+{
+    fn trace(&self) -> &BidTrace {
+        &self.message
+    }
+
+    fn signature(&self) -> &BlsSignature {
+        &self.signature
+    }
+
+    fn parent_hash(&self) -> &Hash32 {
+        &self.execution_payload.parent_hash
+    }
+
+    fn block_hash(&self) -> &Hash32 {
+        &self.execution_payload.block_hash
+    }
+
+    fn prev_randao(&self) -> &Bytes32 {
+        &self.execution_payload.prev_randao
+    }
+
+    fn gas_limit(&self) -> u64 {
+        self.execution_payload.gas_limit
+    }
+
+    fn gas_used(&self) -> u64 {
+        self.execution_payload.gas_used
+    }
+
+    fn timestamp(&self) -> u64 {
+        self.execution_payload.timestamp
+    }
+
+    fn withdrawals_root(&self) -> Result<Root> {
+        Ok(self.execution_payload.withdrawals.hash_tree_root()?)
+    }
+
+    fn compute_withdrawals_root(&self, withdrawals: Vec<Withdrawal>) -> Result<Root> {
+        let withdrawals = List::<Withdrawal, MAX_WITHDRAWALS_PER_PAYLOAD>::try_from(withdrawals)
+            .map_err(|(_, err)| err)?;
+        Ok(withdrawals.hash_tree_root()?)
+    }
+
+    fn verify_contents(&self, context: &Context) -> Result<()> {
+        let limit = context.max_blobs_per_block_electra as usize;
+        verify_blobs_bundle(&self.blobs_bundle, limit, context)
+    }
+}
+
+/// Computes the payload a proposal must build from `state`, the head state advanced to the slot
+/// of the proposal.
+pub fn expected_payload<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+    const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+    const PENDING_CONSOLIDATIONS_LIMIT: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        PENDING_BALANCE_DEPOSITS_LIMIT,
+        PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+        PENDING_CONSOLIDATIONS_LIMIT,
+    >,
+    context: &Context,
+) -> Result<ExpectedPayload> {
+    let (withdrawals, _) = get_expected_withdrawals(state, context);
+    Ok(ExpectedPayload {
+        slot: state.slot,
+        parent_hash: state.latest_execution_payload_header.block_hash.clone(),
+        prev_randao: get_randao_mix(state, get_current_epoch(state, context)).clone(),
+        timestamp: compute_timestamp_at_slot(state, state.slot, context)?,
+        withdrawals,
+    })
+}
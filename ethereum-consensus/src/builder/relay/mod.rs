@@ -0,0 +1,551 @@
+//! Checks a relay makes on the registrations it receives from proposers and on the blocks
+//! builders submit to it.
+pub mod capella;
+pub mod deneb;
+pub mod electra;
+
+use crate::{
+    builder::{compute_builder_domain, SignedValidatorRegistration, ValidatorRegistration},
+    capella::Withdrawal,
+    crypto::{self, verify_signature_sets, KzgError},
+    primitives::{BlsPublicKey, BlsSignature, Bytes32, ExecutionAddress, Hash32, Root, Slot},
+    signing::{compute_signing_root, verify_signed_data},
+    ssz::prelude::*,
+    state_transition::{Context, Result},
+};
+use thiserror::Error;
+
+/// The number of seconds a registration's timestamp may be ahead of the relay's clock.
+pub const MAX_REGISTRATION_CLOCK_DRIFT: u64 = 10;
+/// The lowest gas limit an execution block can have.
+pub const MIN_GAS_LIMIT: u64 = 5000;
+
+/// The summary of a block a builder signs when submitting it to a relay.
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, SimpleSerialize, serde::Serialize, serde::Deserialize,
+)]
+pub struct BidTrace {
+    #[serde(with = "crate::serde::as_str")]
+    pub slot: Slot,
+    pub parent_hash: Hash32,
+    pub block_hash: Hash32,
+    #[serde(rename = "builder_pubkey")]
+    pub builder_public_key: BlsPublicKey,
+    #[serde(rename = "proposer_pubkey")]
+    pub proposer_public_key: BlsPublicKey,
+    pub proposer_fee_recipient: ExecutionAddress,
+    #[serde(with = "crate::serde::as_str")]
+    pub gas_limit: u64,
+    #[serde(with = "crate::serde::as_str")]
+    pub gas_used: u64,
+    #[serde(with = "crate::serde::as_str")]
+    pub value: U256,
+}
+
+#[derive(Debug, Error)]
+pub enum InvalidValidatorRegistration {
+    #[error("timestamp {timestamp} is before genesis at {genesis_time}")]
+    TimestampBeforeGenesis { timestamp: u64, genesis_time: u64 },
+    #[error("timestamp {timestamp} is later than the latest allowed timestamp {limit}")]
+    TimestampInFuture { timestamp: u64, limit: u64 },
+    #[error("gas limit {0} is below the minimum gas limit {MIN_GAS_LIMIT}")]
+    GasLimitTooLow(u64),
+    #[error("invalid signature for registration: {0:?}")]
+    InvalidSignature(BlsSignature),
+}
+
+#[derive(Debug, Error)]
+pub enum InvalidBidSubmission {
+    #[error("invalid signature for bid trace: {0:?}")]
+    InvalidSignature(BlsSignature),
+    #[error("bid is for slot {provided} but the proposal is at slot {expected}")]
+    SlotMismatch { provided: Slot, expected: Slot },
+    #[error("bid is for proposer {provided:?} but the registered proposer is {expected:?}")]
+    ProposerMismatch { provided: BlsPublicKey, expected: BlsPublicKey },
+    #[error("bid pays fee recipient {provided:?} but the proposer registered {expected:?}")]
+    FeeRecipientMismatch { provided: ExecutionAddress, expected: ExecutionAddress },
+    #[error("bid trace has parent hash {trace:?} but the payload has parent hash {payload:?}")]
+    ParentHashMismatch { trace: Hash32, payload: Hash32 },
+    #[error("bid trace has block hash {trace:?} but the payload has block hash {payload:?}")]
+    BlockHashMismatch { trace: Hash32, payload: Hash32 },
+    #[error("bid trace has gas limit {trace} but the payload has gas limit {payload}")]
+    GasLimitMismatch { trace: u64, payload: u64 },
+    #[error("bid trace has gas used {trace} but the payload has gas used {payload}")]
+    GasUsedMismatch { trace: u64, payload: u64 },
+    #[error("payload builds on {provided:?} instead of the head of the chain {expected:?}")]
+    UnknownParentHash { provided: Hash32, expected: Hash32 },
+    #[error("expected randao value {expected:?} but payload has randao value {provided:?}")]
+    InvalidPrevRandao { provided: Bytes32, expected: Bytes32 },
+    #[error("expected timestamp {expected} but payload has timestamp {provided}")]
+    InvalidTimestamp { provided: u64, expected: u64 },
+    #[error(
+        "expected withdrawals root {expected:?} but payload has withdrawals root {provided:?}"
+    )]
+    InvalidWithdrawalsRoot { provided: Root, expected: Root },
+    #[error(
+        "bundle of {commitments} commitments, {proofs} proofs and {blobs} blobs (limit {limit})"
+    )]
+    InvalidBlobsBundle { commitments: usize, proofs: usize, blobs: usize, limit: usize },
+    #[error("blob proofs do not verify against the blobs and commitments: {0}")]
+    InvalidBlobProofs(KzgError),
+}
+
+fn invalid_bid_submission_error(error: InvalidBidSubmission) -> crate::Error {
+    crate::Error::InvalidBidSubmission(Box::new(error))
+}
+
+fn verify_registration_fields(
+    registration: &ValidatorRegistration,
+    genesis_time: u64,
+    current_time: u64,
+) -> std::result::Result<(), InvalidValidatorRegistration> {
+    let timestamp = registration.timestamp;
+    if timestamp < genesis_time {
+        return Err(InvalidValidatorRegistration::TimestampBeforeGenesis { timestamp, genesis_time })
+    }
+    let limit = current_time + MAX_REGISTRATION_CLOCK_DRIFT;
+    if timestamp > limit {
+        return Err(InvalidValidatorRegistration::TimestampInFuture { timestamp, limit })
+    }
+    if registration.gas_limit < MIN_GAS_LIMIT {
+        return Err(InvalidValidatorRegistration::GasLimitTooLow(registration.gas_limit))
+    }
+    Ok(())
+}
+
+/// Verifies a batch of `registrations` received at `current_time` (in seconds since the Unix
+/// epoch), returning the outcome for each registration in the order given.
+///
+/// The signatures of all registrations passing the other checks are verified together as a batch
+/// (scaling each by a random factor so one invalid signature cannot offset another) and only
+/// verified one by one if the batch does not verify.
+pub fn verify_validator_registrations(
+    registrations: &[SignedValidatorRegistration],
+    genesis_time: u64,
+    current_time: u64,
+    context: &Context,
+) -> Result<Vec<std::result::Result<(), InvalidValidatorRegistration>>> {
+    let domain = compute_builder_domain(context)?;
+    let mut outcomes = Vec::with_capacity(registrations.len());
+    let mut batch = vec![];
+    for (index, registration) in registrations.iter().enumerate() {
+        let outcome = verify_registration_fields(&registration.message, genesis_time, current_time);
+        if outcome.is_ok() {
+            let signing_root = compute_signing_root(&registration.message, domain)?;
+            batch.push((index, signing_root));
+        }
+        outcomes.push(outcome);
+    }
+    if batch.is_empty() {
+        return Ok(outcomes)
+    }
+
+    let public_keys =
+        batch.iter().map(|(index, _)| registrations[*index].message.public_key.clone());
+    let public_keys = public_keys.collect::<Vec<_>>();
+    let signatures =
+        batch.iter().map(|(index, _)| registrations[*index].signature.clone()).collect::<Vec<_>>();
+    let messages = batch.iter().map(|(_, root)| root.as_ref()).collect::<Vec<&[u8]>>();
+    if verify_signature_sets(&public_keys, &messages, &signatures).is_ok() {
+        return Ok(outcomes)
+    }
+
+    for (index, signing_root) in &batch {
+        let registration = &registrations[*index];
+        let public_key = &registration.message.public_key;
+        if crypto::verify_signature(public_key, signing_root.as_ref(), &registration.signature)
+            .is_err()
+        {
+            outcomes[*index] =
+                Err(InvalidValidatorRegistration::InvalidSignature(registration.signature.clone()));
+        }
+    }
+    Ok(outcomes)
+}
+
+/// Checks `trace` is signed by its builder and pays the proposer that made `registration`.
+fn verify_bid_trace(
+    trace: &BidTrace,
+    signature: &BlsSignature,
+    registration: &ValidatorRegistration,
+    context: &Context,
+) -> Result<()> {
+    if trace.proposer_public_key != registration.public_key {
+        return Err(invalid_bid_submission_error(InvalidBidSubmission::ProposerMismatch {
+            provided: trace.proposer_public_key.clone(),
+            expected: registration.public_key.clone(),
+        }))
+    }
+    if trace.proposer_fee_recipient != registration.fee_recipient {
+        return Err(invalid_bid_submission_error(InvalidBidSubmission::FeeRecipientMismatch {
+            provided: trace.proposer_fee_recipient.clone(),
+            expected: registration.fee_recipient.clone(),
+        }))
+    }
+    let domain = compute_builder_domain(context)?;
+    if verify_signed_data(trace, signature, &trace.builder_public_key, domain).is_err() {
+        return Err(invalid_bid_submission_error(InvalidBidSubmission::InvalidSignature(
+            signature.clone(),
+        )))
+    }
+    Ok(())
+}
+
+/// Checks `trace` describes the execution payload with the given fields.
+fn verify_bid_trace_matches_payload(
+    trace: &BidTrace,
+    parent_hash: &Hash32,
+    block_hash: &Hash32,
+    gas_limit: u64,
+    gas_used: u64,
+) -> Result<()> {
+    let error = if &trace.parent_hash != parent_hash {
+        InvalidBidSubmission::ParentHashMismatch {
+            trace: trace.parent_hash.clone(),
+            payload: parent_hash.clone(),
+        }
+    } else if &trace.block_hash != block_hash {
+        InvalidBidSubmission::BlockHashMismatch {
+            trace: trace.block_hash.clone(),
+            payload: block_hash.clone(),
+        }
+    } else if trace.gas_limit != gas_limit {
+        InvalidBidSubmission::GasLimitMismatch { trace: trace.gas_limit, payload: gas_limit }
+    } else if trace.gas_used != gas_used {
+        InvalidBidSubmission::GasUsedMismatch { trace: trace.gas_used, payload: gas_used }
+    } else {
+        return Ok(())
+    };
+    Err(invalid_bid_submission_error(error))
+}
+
+/// A builder's block submission to a relay, for each fork relays accept submissions in.
+pub trait BidSubmission {
+    fn trace(&self) -> &BidTrace;
+    fn signature(&self) -> &BlsSignature;
+    fn parent_hash(&self) -> &Hash32;
+    fn block_hash(&self) -> &Hash32;
+    fn prev_randao(&self) -> &Bytes32;
+    fn gas_limit(&self) -> u64;
+    fn gas_used(&self) -> u64;
+    fn timestamp(&self) -> u64;
+    fn withdrawals_root(&self) -> Result<Root>;
+    /// Computes the root `withdrawals` would have as the withdrawals of the submitted payload.
+    fn compute_withdrawals_root(&self, withdrawals: Vec<Withdrawal>) -> Result<Root>;
+
+    /// Checks the parts of the submission specific to its fork, e.g. its blobs.
+    fn verify_contents(&self, _context: &Context) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The payload a proposal at `slot` must build, computed from the head state advanced to `slot`
+/// (see `capella::expected_payload`, `deneb::expected_payload` and `electra::expected_payload`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedPayload {
+    pub slot: Slot,
+    pub parent_hash: Hash32,
+    pub prev_randao: Bytes32,
+    pub timestamp: u64,
+    pub withdrawals: Vec<Withdrawal>,
+}
+
+/// Validates a builder's `submission` for the proposer that made `registration` against the
+/// `expected` payload of the proposal.
+pub fn validate_bid_submission<S: BidSubmission>(
+    submission: &S,
+    registration: &ValidatorRegistration,
+    expected: &ExpectedPayload,
+    context: &Context,
+) -> Result<()> {
+    let trace = submission.trace();
+
+    if trace.slot != expected.slot {
+        return Err(invalid_bid_submission_error(InvalidBidSubmission::SlotMismatch {
+            provided: trace.slot,
+            expected: expected.slot,
+        }))
+    }
+    verify_bid_trace_matches_payload(
+        trace,
+        submission.parent_hash(),
+        submission.block_hash(),
+        submission.gas_limit(),
+        submission.gas_used(),
+    )?;
+    verify_bid_trace(trace, submission.signature(), registration, context)?;
+
+    if submission.parent_hash() != &expected.parent_hash {
+        return Err(invalid_bid_submission_error(InvalidBidSubmission::UnknownParentHash {
+            provided: submission.parent_hash().clone(),
+            expected: expected.parent_hash.clone(),
+        }))
+    }
+
+    if submission.prev_randao() != &expected.prev_randao {
+        return Err(invalid_bid_submission_error(InvalidBidSubmission::InvalidPrevRandao {
+            provided: submission.prev_randao().clone(),
+            expected: expected.prev_randao.clone(),
+        }))
+    }
+
+    if submission.timestamp() != expected.timestamp {
+        return Err(invalid_bid_submission_error(InvalidBidSubmission::InvalidTimestamp {
+            provided: submission.timestamp(),
+            expected: expected.timestamp,
+        }))
+    }
+
+    let withdrawals_root = submission.withdrawals_root()?;
+    let expected_withdrawals_root =
+        submission.compute_withdrawals_root(expected.withdrawals.clone())?;
+    if withdrawals_root != expected_withdrawals_root {
+        return Err(invalid_bid_submission_error(InvalidBidSubmission::InvalidWithdrawalsRoot {
+            provided: withdrawals_root,
+            expected: expected_withdrawals_root,
+        }))
+    }
+
+    submission.verify_contents(context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        builder::minimal,
+        capella::{self as spec, minimal::BeaconState},
+        crypto::SecretKey,
+        phase0::Validator,
+        primitives::ETH1_ADDRESS_WITHDRAWAL_PREFIX,
+        signing::sign_with_domain,
+    };
+
+    const GENESIS_TIME: u64 = 1_606_824_023;
+    const SLOT: Slot = 17;
+
+    fn signed_registration(
+        secret_key: &SecretKey,
+        timestamp: u64,
+        context: &Context,
+    ) -> SignedValidatorRegistration {
+        let message = ValidatorRegistration {
+            gas_limit: 30_000_000,
+            timestamp,
+            public_key: secret_key.public_key(),
+            ..Default::default()
+        };
+        let domain = compute_builder_domain(context).unwrap();
+        let signature = sign_with_domain(&message, secret_key, domain).unwrap();
+        SignedValidatorRegistration { message, signature }
+    }
+
+    #[test]
+    fn test_verify_validator_registrations() {
+        let context = Context::for_mainnet();
+        let mut rng = rand::thread_rng();
+        let genesis_time = 1_606_824_023;
+        let current_time = genesis_time + 1_000;
+
+        let keys = (0..4).map(|_| SecretKey::random(&mut rng).unwrap()).collect::<Vec<_>>();
+        let mut registrations = keys
+            .iter()
+            .map(|key| signed_registration(key, current_time, &context))
+            .collect::<Vec<_>>();
+        let outcomes =
+            verify_validator_registrations(&registrations, genesis_time, current_time, &context)
+                .unwrap();
+        assert!(outcomes.iter().all(|outcome| outcome.is_ok()));
+
+        registrations[1] = signed_registration(&keys[1], genesis_time - 1, &context);
+        registrations[2].signature = registrations[3].signature.clone();
+        let outcomes =
+            verify_validator_registrations(&registrations, genesis_time, current_time, &context)
+                .unwrap();
+        assert!(outcomes[0].is_ok());
+        assert!(matches!(
+            outcomes[1],
+            Err(InvalidValidatorRegistration::TimestampBeforeGenesis { .. })
+        ));
+        assert!(matches!(outcomes[2], Err(InvalidValidatorRegistration::InvalidSignature(..))));
+        assert!(outcomes[3].is_ok());
+    }
+
+    fn bytes<T: for<'a> TryFrom<&'a [u8]>>(byte: u8, len: usize) -> T {
+        T::try_from(vec![byte; len].as_slice()).ok().unwrap()
+    }
+
+    // A valid submission from `builder_key` for the proposer that made `registration`.
+    struct Harness {
+        context: Context,
+        builder_key: SecretKey,
+        registration: ValidatorRegistration,
+        expected: ExpectedPayload,
+        submission: minimal::CapellaSignedBidSubmission,
+    }
+
+    impl Harness {
+        fn new() -> Self {
+            let context = Context::for_minimal();
+            let mut rng = rand::thread_rng();
+            let builder_key = SecretKey::random(&mut rng).unwrap();
+            let registration = ValidatorRegistration {
+                fee_recipient: bytes(0x11, 20),
+                gas_limit: 30_000_000,
+                timestamp: GENESIS_TIME,
+                public_key: SecretKey::random(&mut rng).unwrap().public_key(),
+            };
+
+            // a fully withdrawable validator, so the payload has a withdrawal
+            let mut withdrawal_credentials = Bytes32::default();
+            withdrawal_credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
+            let validator = Validator { withdrawal_credentials, ..Default::default() };
+            let mut state = BeaconState {
+                genesis_time: GENESIS_TIME,
+                slot: SLOT,
+                validators: List::try_from(vec![validator]).unwrap(),
+                balances: List::try_from(vec![32_000_000_000]).unwrap(),
+                ..Default::default()
+            };
+            state.latest_execution_payload_header.block_hash = bytes(0x22, 32);
+            let epoch = spec::get_current_epoch(&state, &context) as usize;
+            state.randao_mixes[epoch] = bytes(0x33, 32);
+            let expected = capella::expected_payload(&state, &context).unwrap();
+            assert_eq!(expected.withdrawals.len(), 1);
+
+            let execution_payload = spec::ExecutionPayload {
+                parent_hash: expected.parent_hash.clone(),
+                fee_recipient: registration.fee_recipient.clone(),
+                prev_randao: expected.prev_randao.clone(),
+                gas_limit: 30_000_000,
+                gas_used: 15_000_000,
+                timestamp: expected.timestamp,
+                block_hash: bytes(0x44, 32),
+                withdrawals: List::try_from(expected.withdrawals.clone()).unwrap(),
+                ..Default::default()
+            };
+            let message = BidTrace {
+                slot: SLOT,
+                parent_hash: execution_payload.parent_hash.clone(),
+                block_hash: execution_payload.block_hash.clone(),
+                builder_public_key: builder_key.public_key(),
+                proposer_public_key: registration.public_key.clone(),
+                proposer_fee_recipient: registration.fee_recipient.clone(),
+                gas_limit: execution_payload.gas_limit,
+                gas_used: execution_payload.gas_used,
+                value: U256::from(1_000_000_000u64),
+            };
+            let submission = minimal::CapellaSignedBidSubmission {
+                message,
+                execution_payload,
+                signature: Default::default(),
+            };
+            let mut harness = Self { context, builder_key, registration, expected, submission };
+            harness.sign();
+            harness
+        }
+
+        fn sign(&mut self) {
+            let domain = compute_builder_domain(&self.context).unwrap();
+            let message = &self.submission.message;
+            self.submission.signature =
+                sign_with_domain(message, &self.builder_key, domain).unwrap();
+        }
+
+        fn rejection(&self) -> InvalidBidSubmission {
+            let result = validate_bid_submission(
+                &self.submission,
+                &self.registration,
+                &self.expected,
+                &self.context,
+            );
+            match result {
+                Err(crate::Error::InvalidBidSubmission(err)) => *err,
+                result => panic!("unexpected result: {result:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_bid_submission() {
+        let harness = Harness::new();
+        validate_bid_submission(
+            &harness.submission,
+            &harness.registration,
+            &harness.expected,
+            &harness.context,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_reject_unknown_parent() {
+        let mut harness = Harness::new();
+        let parent_hash: Hash32 = bytes(0x55, 32);
+        harness.submission.execution_payload.parent_hash = parent_hash.clone();
+        harness.submission.message.parent_hash = parent_hash;
+        harness.sign();
+        assert!(matches!(harness.rejection(), InvalidBidSubmission::UnknownParentHash { .. }));
+
+        // the trace must describe the payload
+        let mut harness = Harness::new();
+        harness.submission.message.parent_hash = bytes(0x55, 32);
+        harness.sign();
+        assert!(matches!(harness.rejection(), InvalidBidSubmission::ParentHashMismatch { .. }));
+    }
+
+    #[test]
+    fn test_reject_invalid_timestamp() {
+        let mut harness = Harness::new();
+        harness.submission.execution_payload.timestamp += 1;
+        assert!(matches!(harness.rejection(), InvalidBidSubmission::InvalidTimestamp { .. }));
+    }
+
+    #[test]
+    fn test_reject_invalid_prev_randao() {
+        let mut harness = Harness::new();
+        harness.submission.execution_payload.prev_randao = bytes(0x66, 32);
+        assert!(matches!(harness.rejection(), InvalidBidSubmission::InvalidPrevRandao { .. }));
+    }
+
+    #[test]
+    fn test_reject_invalid_withdrawals() {
+        let mut harness = Harness::new();
+        harness.submission.execution_payload.withdrawals = Default::default();
+        assert!(matches!(harness.rejection(), InvalidBidSubmission::InvalidWithdrawalsRoot { .. }));
+    }
+
+    #[test]
+    fn test_reject_fee_recipient_mismatch() {
+        let mut harness = Harness::new();
+        harness.submission.message.proposer_fee_recipient = bytes(0x77, 20);
+        harness.sign();
+        assert!(matches!(harness.rejection(), InvalidBidSubmission::FeeRecipientMismatch { .. }));
+    }
+
+    #[test]
+    fn test_reject_value_mismatch() {
+        // the value is only covered by the builder's signature
+        let mut harness = Harness::new();
+        harness.submission.message.value = U256::from(2_000_000_000u64);
+        assert!(matches!(harness.rejection(), InvalidBidSubmission::InvalidSignature(..)));
+
+        let mut harness = Harness::new();
+        harness.submission.message.gas_used += 1;
+        harness.sign();
+        assert!(matches!(harness.rejection(), InvalidBidSubmission::GasUsedMismatch { .. }));
+    }
+
+    #[test]
+    fn test_reject_invalid_signature() {
+        let mut harness = Harness::new();
+        harness.builder_key = SecretKey::random(&mut rand::thread_rng()).unwrap();
+        harness.sign();
+        assert!(matches!(harness.rejection(), InvalidBidSubmission::InvalidSignature(..)));
+
+        let mut harness = Harness::new();
+        harness.submission.message.slot += 1;
+        harness.sign();
+        assert!(matches!(harness.rejection(), InvalidBidSubmission::SlotMismatch { .. }));
+    }
+}
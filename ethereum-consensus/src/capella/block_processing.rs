@@ -77,7 +77,11 @@ pub fn process_bls_to_execution_change<
         Some(state.genesis_validators_root),
         context,
     )?;
-    verify_signed_data(address_change, signature, public_key, domain)?;
+    verify_signed_data(address_change, signature, public_key, domain).map_err(|_| {
+        invalid_operation_error(InvalidOperation::BlsToExecutionChange(
+            InvalidBlsToExecutionChange::InvalidSignature(signature.clone()),
+        ))
+    })?;
 
     withdrawal_credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
     withdrawal_credentials[1..12].fill(0);
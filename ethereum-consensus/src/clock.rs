@@ -1,11 +1,16 @@
 //! A consensus clock
 use crate::{
-    configs, phase0 as presets,
-    primitives::{Epoch, Slot},
+    configs,
+    networking::{ForkDigestSchedule, ScheduledFork},
+    phase0 as presets,
+    primitives::{Epoch, ForkDigest, Root, Slot},
+    state_transition::Context,
+    types::BeaconState,
+    Error,
 };
 use std::{
     ops::Deref,
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -14,6 +19,10 @@ pub const SEPOLIA_GENESIS_TIME: u64 = 1655733600;
 pub const GOERLI_GENESIS_TIME: u64 = 1616508000;
 pub const HOLESKY_GENESIS_TIME: u64 = 1695902400;
 
+/// The number of intervals a slot is divided into, e.g. attestations are due one interval into
+/// the slot and aggregates two intervals into the slot.
+pub const INTERVALS_PER_SLOT: u64 = 3;
+
 pub fn duration_since_unix_epoch() -> Duration {
     SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default()
 }
@@ -79,6 +88,32 @@ impl TimeProvider for SystemTimeProvider {
     }
 }
 
+/// A `TimeProvider` reporting a time set by its owner, e.g. to step a `Clock` through a test.
+/// Clones share the same time.
+#[derive(Clone, Default)]
+pub struct ManualTimeProvider(Arc<Mutex<u128>>);
+
+impl ManualTimeProvider {
+    /// Start at `time` since the `UNIX_EPOCH`.
+    pub fn new(time: Duration) -> Self {
+        Self(Arc::new(Mutex::new(time.as_nanos())))
+    }
+
+    pub fn set(&self, time: Duration) {
+        *self.0.lock().expect("can lock time") = time.as_nanos();
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        *self.0.lock().expect("can lock time") += duration.as_nanos();
+    }
+}
+
+impl TimeProvider for ManualTimeProvider {
+    fn get_current_time(&self) -> u128 {
+        *self.0.lock().expect("can lock time")
+    }
+}
+
 #[derive(Clone)]
 pub struct Clock<T: TimeProvider + Send + Sync>(Arc<Inner<T>>);
 
@@ -95,6 +130,7 @@ pub struct Inner<T: TimeProvider> {
     seconds_per_slot: u128,
     slots_per_epoch: Slot,
     time_provider: T,
+    fork_schedule: Option<ForkDigestSchedule>,
 }
 
 pub fn from_system_time(
@@ -140,10 +176,76 @@ impl<T: TimeProvider + Send + Sync> Clock<T> {
         seconds_per_slot: u64,
         slots_per_epoch: Slot,
         time_provider: T,
+    ) -> Self {
+        Self::from_parts(genesis_time, seconds_per_slot, slots_per_epoch, time_provider, None)
+    }
+
+    /// Build a clock for the network described by `context` from its genesis, e.g. as read from
+    /// the genesis state or from a beacon node. Unlike [`Clock::new`], the clock can also answer
+    /// fork-aware queries like [`Clock::current_fork`].
+    pub fn from_context(
+        context: &Context,
+        genesis_time: u64,
+        genesis_validators_root: Root,
+        time_provider: T,
+    ) -> Result<Self, Error> {
+        let fork_schedule = ForkDigestSchedule::new(genesis_validators_root, context)?;
+        Ok(Self::from_parts(
+            genesis_time,
+            context.seconds_per_slot,
+            context.slots_per_epoch,
+            time_provider,
+            Some(fork_schedule),
+        ))
+    }
+
+    /// Build a fork-aware clock for the network described by `context` from the genesis recorded
+    /// in `state`; see [`Clock::from_context`].
+    pub fn from_state<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const PENDING_ATTESTATIONS_BOUND: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+    >(
+        context: &Context,
+        state: &BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            PENDING_ATTESTATIONS_BOUND,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+        time_provider: T,
+    ) -> Result<Self, Error> {
+        let genesis_time = state.genesis_time();
+        let genesis_validators_root = state.genesis_validators_root();
+        Self::from_context(context, genesis_time, genesis_validators_root, time_provider)
+    }
+
+    fn from_parts(
+        genesis_time: u64,
+        seconds_per_slot: u64,
+        slots_per_epoch: Slot,
+        time_provider: T,
+        fork_schedule: Option<ForkDigestSchedule>,
     ) -> Self {
         let genesis_time = Duration::from_secs(genesis_time).as_nanos();
         let seconds_per_slot = Duration::from_secs(seconds_per_slot).as_nanos();
-        let inner = Inner { genesis_time, seconds_per_slot, slots_per_epoch, time_provider };
+        let inner =
+            Inner { genesis_time, seconds_per_slot, slots_per_epoch, time_provider, fork_schedule };
         Self(Arc::new(inner))
     }
 
@@ -212,6 +314,73 @@ impl<T: TimeProvider + Send + Sync> Clock<T> {
             Duration::from_nanos(u128_to_u64(target_slot_in_nanos - current_time))
         }
     }
+
+    #[inline]
+    fn slot_interval_to_nanos(&self, slot: Slot, interval: u64) -> u128 {
+        let slot_in_nanos = slot_to_nanos(slot, self.seconds_per_slot, self.genesis_time);
+        let interval_in_nanos = self.seconds_per_slot / u128::from(INTERVALS_PER_SLOT);
+        slot_in_nanos + u128::from(interval) * interval_in_nanos
+    }
+
+    /// Return a `Duration` until `interval` (out of `INTERVALS_PER_SLOT`) into the provided
+    /// `slot`. If that time is in the past, return a `Duration` of 0.
+    pub fn duration_until_slot_interval(&self, slot: Slot, interval: u64) -> Duration {
+        let current_time = self.get_current_time();
+        self.slot_interval_to_nanos(slot, interval)
+            .checked_sub(current_time)
+            .map(|t| Duration::from_nanos(u128_to_u64(t)))
+            .unwrap_or_default()
+    }
+
+    /// Return a `Duration` until attestations for `slot` are due.
+    pub fn duration_until_attestation_deadline(&self, slot: Slot) -> Duration {
+        self.duration_until_slot_interval(slot, 1)
+    }
+
+    /// Return a `Duration` until aggregates of the attestations for `slot` are due.
+    pub fn duration_until_aggregation(&self, slot: Slot) -> Duration {
+        self.duration_until_slot_interval(slot, 2)
+    }
+
+    /// Return a `Duration` until sync committee messages for `slot` are due.
+    pub fn duration_until_sync_committee_message_deadline(&self, slot: Slot) -> Duration {
+        self.duration_until_slot_interval(slot, 1)
+    }
+
+    /// Return a `Duration` until a block for `slot` no longer receives the proposer score boost
+    /// in fork choice.
+    pub fn duration_until_proposer_boost_cutoff(&self, slot: Slot) -> Duration {
+        self.duration_until_slot_interval(slot, 1)
+    }
+
+    /// Return `true` if a block for `slot` arriving now would receive the proposer score boost.
+    pub fn is_before_proposer_boost_cutoff(&self, slot: Slot) -> bool {
+        let current_time = self.get_current_time();
+        self.slot_at_time(current_time) == Some(slot) &&
+            current_time < self.slot_interval_to_nanos(slot, 1)
+    }
+
+    /// Return the fork current at `time`, with **nanosecond** precision, or `None` if `time` is
+    /// before genesis or the clock was not built with [`Clock::from_context`].
+    pub fn fork_at_time(&self, time: u128) -> Option<ScheduledFork> {
+        let fork_schedule = self.fork_schedule.as_ref()?;
+        let slot = self.slot_at_time(time)?;
+        Some(*fork_schedule.fork_at_epoch(self.epoch_for(slot)))
+    }
+
+    pub fn current_fork(&self) -> Option<ScheduledFork> {
+        self.fork_at_time(self.get_current_time())
+    }
+
+    /// Return the fork digest current at `time`, with **nanosecond** precision; see
+    /// [`Clock::fork_at_time`].
+    pub fn fork_digest_at_time(&self, time: u128) -> Option<ForkDigest> {
+        self.fork_at_time(time).map(|fork| fork.digest)
+    }
+
+    pub fn current_fork_digest(&self) -> Option<ForkDigest> {
+        self.current_fork().map(|fork| fork.digest)
+    }
 }
 
 pub type SystemClock = Clock<SystemTimeProvider>;
@@ -223,10 +392,36 @@ use std::{
     task::{self, Poll},
 };
 #[cfg(feature = "async")]
-use tokio::time::{sleep, Sleep};
+use tokio::time::{sleep, Instant, Sleep};
 #[cfg(feature = "async")]
 use tokio_stream::Stream;
 
+#[cfg(feature = "async")]
+/// A `TimeProvider` following the `tokio` clock from a given start time.
+///
+/// `SlotStream` waits on the same clock, so a test can pause time with `tokio::time::pause` and
+/// drive a `Clock` and its `SlotStream` deterministically, e.g. with `tokio::time::advance`.
+#[derive(Clone)]
+pub struct TokioTimeProvider {
+    start: u128,
+    origin: Instant,
+}
+
+#[cfg(feature = "async")]
+impl TokioTimeProvider {
+    /// Start at `time` since the `UNIX_EPOCH`.
+    pub fn new(time: Duration) -> Self {
+        Self { start: time.as_nanos(), origin: Instant::now() }
+    }
+}
+
+#[cfg(feature = "async")]
+impl TimeProvider for TokioTimeProvider {
+    fn get_current_time(&self) -> u128 {
+        self.start + self.origin.elapsed().as_nanos()
+    }
+}
+
 #[cfg(feature = "async")]
 /// Implements `futures_core::Stream` yielding the slots tracked by the `clock`.
 /// NOTE: the first poll will return the current slot, even if it is not aligned to the slot start.
@@ -269,7 +464,7 @@ impl<T: TimeProvider + Send + Sync> Stream for SlotStream<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::Mutex;
+    use crate::{phase0::compute_fork_digest, primitives::FAR_FUTURE_EPOCH, Fork};
 
     struct Ticker {
         tick: Mutex<u128>,
@@ -341,6 +536,89 @@ mod tests {
         assert!(clock.current_slot().is_none());
     }
 
+    #[test]
+    fn test_slot_intervals() {
+        let seconds_per_slot: u64 = 12;
+        let time_provider = ManualTimeProvider::new(Duration::from_secs(100));
+        let clock = Clock::new(100, seconds_per_slot, 32, time_provider.clone());
+        assert_eq!(clock.duration_until_attestation_deadline(0), Duration::from_secs(4));
+        assert_eq!(clock.duration_until_sync_committee_message_deadline(0), Duration::from_secs(4));
+        assert_eq!(clock.duration_until_aggregation(0), Duration::from_secs(8));
+        assert_eq!(clock.duration_until_attestation_deadline(1), Duration::from_secs(16));
+        assert!(clock.is_before_proposer_boost_cutoff(0));
+        assert!(!clock.is_before_proposer_boost_cutoff(1));
+
+        time_provider.advance(Duration::from_secs(4));
+        assert_eq!(clock.duration_until_proposer_boost_cutoff(0), Duration::default());
+        assert!(!clock.is_before_proposer_boost_cutoff(0));
+        assert_eq!(clock.duration_until_aggregation(0), Duration::from_secs(4));
+    }
+
+    #[test]
+    fn test_fork_aware_clock() {
+        let mut context = Context::for_minimal();
+        context.altair_fork_epoch = 0;
+        context.bellatrix_fork_epoch = 2;
+        context.capella_fork_epoch = FAR_FUTURE_EPOCH;
+        context.deneb_fork_epoch = FAR_FUTURE_EPOCH;
+        context.electra_fork_epoch = FAR_FUTURE_EPOCH;
+        let genesis_time = 100;
+        let time_provider = ManualTimeProvider::new(Duration::from_secs(genesis_time - 1));
+        let clock =
+            Clock::from_context(&context, genesis_time, Root::default(), time_provider.clone())
+                .unwrap();
+        assert!(clock.current_fork().is_none());
+
+        time_provider.set(Duration::from_secs(genesis_time));
+        assert_eq!(clock.current_fork().unwrap().fork, Fork::Altair);
+
+        let seconds_per_epoch = context.seconds_per_slot * context.slots_per_epoch;
+        let bellatrix_time = Duration::from_secs(genesis_time + 2 * seconds_per_epoch);
+        let bellatrix = clock.fork_at_time(bellatrix_time.as_nanos()).unwrap();
+        assert_eq!(bellatrix.fork, Fork::Bellatrix);
+        let expected =
+            compute_fork_digest(context.bellatrix_fork_version, Root::default()).unwrap();
+        assert_eq!(clock.fork_digest_at_time(bellatrix_time.as_nanos()), Some(expected));
+
+        let clock = Clock::new(genesis_time, 6, 8, time_provider);
+        assert!(clock.current_fork_digest().is_none());
+    }
+
+    #[test]
+    fn test_clock_from_state() {
+        let context = Context::for_mainnet();
+        let mut state = crate::types::mainnet::BeaconState::Phase0(Default::default());
+        *state.genesis_time_mut() = MAINNET_GENESIS_TIME;
+        *state.genesis_validators_root_mut() = Root::try_from([1u8; 32].as_ref()).unwrap();
+        let now = Duration::from_secs(MAINNET_GENESIS_TIME + 12 * 32 + 6);
+        let clock = Clock::from_state(&context, &state, ManualTimeProvider::new(now)).unwrap();
+        assert_eq!(clock.current_slot(), Some(32));
+        let genesis_validators_root = state.genesis_validators_root();
+        let expected = compute_fork_digest(context.genesis_fork_version, genesis_validators_root);
+        assert_eq!(clock.current_fork_digest(), Some(expected.unwrap()));
+
+        // a custom network has a clock once its genesis time is known
+        let mut context = Context::for_minimal();
+        assert!(context.clock().is_none());
+        context = context.with_genesis_time(state.genesis_time());
+        let clock = context.clock().unwrap();
+        assert_eq!(clock.timestamp_at_slot(0), MAINNET_GENESIS_TIME);
+        assert_eq!(context.genesis_time().unwrap(), MAINNET_GENESIS_TIME);
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test(start_paused = true)]
+    async fn test_slot_stream_with_paused_time() {
+        use tokio_stream::StreamExt;
+
+        // NOTE: with paused time `tokio` skips ahead to each sleep of the stream
+        let genesis_time = Duration::from_secs(1_000);
+        let time_provider = TokioTimeProvider::new(genesis_time + Duration::from_secs(30));
+        let clock = Clock::new(genesis_time.as_secs(), 12, 32, time_provider);
+        let slots = clock.into_stream().take(4).collect::<Vec<_>>().await;
+        assert_eq!(slots, vec![2, 3, 4, 5]);
+    }
+
     #[cfg(feature = "async")]
     #[tokio::test]
     async fn test_slot_stream() {
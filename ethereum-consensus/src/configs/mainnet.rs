@@ -19,6 +19,7 @@ pub const MAX_PER_EPOCH_ACTIVATION_CHURN_LIMIT: u64 = 8;
 pub const CHURN_LIMIT_QUOTIENT: u64 = 65536;
 pub const MIN_PER_EPOCH_CHURN_LIMIT_ELECTRA: u64 = 128 * 10u64.pow(9);
 pub const MAX_PER_EPOCH_ACTIVATION_EXIT_CHURN_LIMIT: u64 = 256 * 10u64.pow(9);
+pub const MAX_BLOBS_PER_BLOCK_ELECTRA: u64 = 9;
 pub const TERMINAL_BLOCK_HASH_ACTIVATION_EPOCH: Epoch = FAR_FUTURE_EPOCH;
 pub const ALTAIR_FORK_VERSION: Version = [1, 0, 0, 0];
 pub const ALTAIR_FORK_EPOCH: Epoch = 74240;
@@ -30,6 +31,8 @@ pub const DENEB_FORK_VERSION: Version = [4, 0, 0, 0];
 pub const DENEB_FORK_EPOCH: Epoch = 269568;
 pub const ELECTRA_FORK_VERSION: Version = [5, 0, 0, 0];
 pub const ELECTRA_FORK_EPOCH: Epoch = FAR_FUTURE_EPOCH;
+pub const FULU_FORK_VERSION: Version = [6, 0, 0, 0];
+pub const FULU_FORK_EPOCH: Epoch = FAR_FUTURE_EPOCH;
 pub const INACTIVITY_SCORE_BIAS: u64 = 4;
 pub const INACTIVITY_SCORE_RECOVERY_RATE: u64 = 16;
 pub const PROPOSER_SCORE_BOOST: u64 = 40;
@@ -71,6 +74,8 @@ pub fn config() -> Config {
         deneb_fork_epoch: DENEB_FORK_EPOCH,
         electra_fork_version: ELECTRA_FORK_VERSION,
         electra_fork_epoch: ELECTRA_FORK_EPOCH,
+        fulu_fork_version: FULU_FORK_VERSION,
+        fulu_fork_epoch: FULU_FORK_EPOCH,
         seconds_per_slot: SECONDS_PER_SLOT,
         seconds_per_eth1_block: SECONDS_PER_ETH1_BLOCK,
         min_validator_withdrawability_delay: MIN_VALIDATOR_WITHDRAWABILITY_DELAY,
@@ -83,10 +88,12 @@ pub fn config() -> Config {
         max_per_epoch_activation_churn_limit: MAX_PER_EPOCH_ACTIVATION_CHURN_LIMIT,
         min_per_epoch_churn_limit_electra: MIN_PER_EPOCH_CHURN_LIMIT_ELECTRA,
         max_per_epoch_activation_exit_churn_limit: MAX_PER_EPOCH_ACTIVATION_EXIT_CHURN_LIMIT,
+        max_blobs_per_block_electra: MAX_BLOBS_PER_BLOCK_ELECTRA,
         churn_limit_quotient: CHURN_LIMIT_QUOTIENT,
         proposer_score_boost: PROPOSER_SCORE_BOOST,
         deposit_chain_id: DEPOSIT_CHAIN_ID,
         deposit_network_id: DEPOSIT_NETWORK_ID,
         deposit_contract_address,
+        blob_schedule: vec![],
     }
 }
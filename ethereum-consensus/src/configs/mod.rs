@@ -6,7 +6,7 @@ pub mod sepolia;
 
 use crate::{
     networks::Network,
-    primitives::{Epoch, ExecutionAddress, Gwei, Hash32, Version, U256},
+    primitives::{Epoch, ExecutionAddress, Gwei, Hash32, Version, FAR_FUTURE_EPOCH, U256},
 };
 
 #[derive(Debug, serde::Deserialize)]
@@ -41,6 +41,11 @@ pub struct Config {
     #[serde(with = "crate::serde::as_hex")]
     pub electra_fork_version: Version,
     pub electra_fork_epoch: Epoch,
+    // NOTE: defaulted so that configs predating Fulu can still be loaded
+    #[serde(with = "crate::serde::as_hex", default)]
+    pub fulu_fork_version: Version,
+    #[serde(default = "far_future_epoch")]
+    pub fulu_fork_epoch: Epoch,
 
     pub seconds_per_slot: u64,
     pub seconds_per_eth1_block: u64,
@@ -56,10 +61,31 @@ pub struct Config {
     pub churn_limit_quotient: u64,
     pub min_per_epoch_churn_limit_electra: u64,
     pub max_per_epoch_activation_exit_churn_limit: u64,
+    #[serde(default = "default_max_blobs_per_block_electra")]
+    pub max_blobs_per_block_electra: u64,
 
     pub proposer_score_boost: u64,
 
     pub deposit_chain_id: usize,
     pub deposit_network_id: usize,
     pub deposit_contract_address: ExecutionAddress,
+
+    #[serde(default)]
+    pub blob_schedule: Vec<BlobParameters>,
+}
+
+/// An entry of the blob schedule: the maximum number of blobs per block from `epoch` onwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub struct BlobParameters {
+    pub epoch: Epoch,
+    pub max_blobs_per_block: u64,
+}
+
+fn far_future_epoch() -> Epoch {
+    FAR_FUTURE_EPOCH
+}
+
+fn default_max_blobs_per_block_electra() -> u64 {
+    9
 }
@@ -2,6 +2,7 @@
 use crate::serde::try_bytes_from_hex_str;
 use crate::{primitives::Bytes32, ssz::prelude::*};
 use blst::{min_pk as bls_impl, BLST_ERROR};
+use rand::Rng;
 use sha2::{Digest, Sha256};
 use std::{
     fmt,
@@ -20,9 +21,12 @@ pub fn hash<D: AsRef<[u8]>>(data: D) -> Bytes32 {
 }
 
 const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const BLS_POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
 const BLS_PUBLIC_KEY_BYTES_LEN: usize = 48;
 const BLS_SECRET_KEY_BYTES_LEN: usize = 32;
 const BLS_SIGNATURE_BYTES_LEN: usize = 96;
+// The number of random bits used to scale each set in `verify_signature_sets`
+const RANDOM_SCALAR_BITS: usize = 64;
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -76,6 +80,18 @@ pub fn verify_signature(
     }
 }
 
+// Verify a proof of possession (`PopVerify` in the IETF BLS signature draft) for `public_key`
+pub fn verify_possession(public_key: &PublicKey, proof: &Signature) -> Result<(), Error> {
+    let inner: bls_impl::PublicKey = public_key.try_into()?;
+    let proof: bls_impl::Signature = proof.try_into()?;
+    let res = proof.verify(true, public_key.as_ref(), BLS_POP_DST, &[], &inner, true);
+    if res == BLST_ERROR::BLST_SUCCESS {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
 pub fn aggregate(signatures: &[Signature]) -> Result<Signature, Error> {
     if signatures.is_empty() {
         return Err(Error::EmptyAggregate)
@@ -111,6 +127,58 @@ pub fn aggregate_verify(
     }
 }
 
+// Verify each signature in `signatures` over the message at the same index in `msgs` under the
+// public key at that index, scaling each set by a random scalar so that invalid signatures cannot
+// cancel each other out in the batch
+pub fn verify_signature_sets(
+    public_keys: &[PublicKey],
+    msgs: &[&[u8]],
+    signatures: &[Signature],
+) -> Result<(), Error> {
+    if signatures.is_empty() {
+        return Err(Error::EmptyAggregate)
+    }
+    if public_keys.len() != signatures.len() || msgs.len() != signatures.len() {
+        return Err(Error::InvalidSignature)
+    }
+
+    let public_keys = public_keys
+        .iter()
+        .map(bls_impl::PublicKey::try_from)
+        .collect::<Result<Vec<bls_impl::PublicKey>, Error>>()?;
+    let public_keys: Vec<&bls_impl::PublicKey> = public_keys.iter().collect();
+    let signatures = signatures
+        .iter()
+        .map(bls_impl::Signature::try_from)
+        .collect::<Result<Vec<bls_impl::Signature>, Error>>()?;
+    let signatures: Vec<&bls_impl::Signature> = signatures.iter().collect();
+
+    let mut rng = rand::thread_rng();
+    let scalars = (0..signatures.len())
+        .map(|_| {
+            let mut scalar = blst::blst_scalar::default();
+            let value = rng.gen_range(1..=u64::MAX);
+            scalar.b[..8].copy_from_slice(&value.to_le_bytes());
+            scalar
+        })
+        .collect::<Vec<_>>();
+    let res = bls_impl::Signature::verify_multiple_aggregate_signatures(
+        msgs,
+        BLS_DST,
+        &public_keys,
+        true,
+        &signatures,
+        true,
+        &scalars,
+        RANDOM_SCALAR_BITS,
+    );
+    if res == BLST_ERROR::BLST_SUCCESS {
+        Ok(())
+    } else {
+        Err(Error::InvalidSignature)
+    }
+}
+
 pub fn fast_aggregate_verify(
     public_keys: &[&PublicKey],
     msg: &[u8],
@@ -219,6 +287,13 @@ impl SecretKey {
         Signature::try_from(inner.to_bytes().as_ref()).unwrap()
     }
 
+    // Produce a proof of possession (`PopProve` in the IETF BLS signature draft) for this key
+    pub fn prove_possession(&self) -> Signature {
+        let public_key = self.public_key();
+        let inner = self.0.sign(public_key.as_ref(), BLS_POP_DST, &[]);
+        Signature::try_from(inner.to_bytes().as_ref()).unwrap()
+    }
+
     pub fn to_bytes(self) -> [u8; 32] {
         self.0.to_bytes()
     }
@@ -505,6 +580,30 @@ mod tests {
         assert!(v.is_ok());
     }
 
+    #[test]
+    fn test_verify_signature_sets() {
+        let n = 20;
+        let mut rng = thread_rng();
+        let sks: Vec<_> = (0..n).map(|_| SecretKey::random(&mut rng).unwrap()).collect();
+        let pks: Vec<_> = sks.iter().map(|sk| sk.public_key()).collect();
+        let msgs: Vec<Vec<u8>> =
+            (0..n).map(|_| (0..64).map(|_| rand::thread_rng().gen()).collect()).collect();
+
+        let mut signatures: Vec<_> = msgs.iter().zip(&sks).map(|(msg, sk)| sk.sign(msg)).collect();
+
+        let msgs = msgs.iter().map(|r| &r[..]).collect::<Vec<_>>();
+
+        assert!(verify_signature_sets(&pks, &msgs, &signatures).is_ok());
+        assert!(verify_signature_sets(&pks[1..], &msgs, &signatures).is_err());
+        assert!(verify_signature_sets(&[], &[], &[]).is_err());
+
+        // swapping signatures leaves their aggregate unchanged
+        signatures.swap(0, 1);
+        let sig = aggregate(&signatures).unwrap();
+        assert!(aggregate_verify(&pks, &msgs, &sig).is_ok());
+        assert!(verify_signature_sets(&pks, &msgs, &signatures).is_err());
+    }
+
     #[test]
     fn test_fast_aggregated_verify() {
         let n = 20;
@@ -562,6 +661,20 @@ mod tests {
         assert!(verify_signature(&public_key, msg, &signature).is_ok());
     }
 
+    #[test]
+    fn test_proof_of_possession() {
+        let mut rng = thread_rng();
+        let secret_key = SecretKey::random(&mut rng).unwrap();
+        let public_key = secret_key.public_key();
+
+        let proof = secret_key.prove_possession();
+        assert!(verify_possession(&public_key, &proof).is_ok());
+
+        // a plain signature over the public key uses a different domain separation tag
+        let signature = secret_key.sign(public_key.as_ref());
+        assert!(verify_possession(&public_key, &signature).is_err());
+    }
+
     #[test]
     fn test_serde() {
         let mut rng = thread_rng();
@@ -3,7 +3,7 @@ pub mod kzg;
 
 pub use bls::{
     aggregate, aggregate_verify, eth_aggregate_public_keys, eth_fast_aggregate_verify,
-    fast_aggregate_verify, hash, verify_signature, Error as BlsError, PublicKey, SecretKey,
-    Signature,
+    fast_aggregate_verify, hash, verify_possession, verify_signature, verify_signature_sets,
+    Error as BlsError, PublicKey, SecretKey, Signature,
 };
 pub use kzg::{kzg_settings_from_json, Error as KzgError, KzgCommitment, KzgProof, KzgSettings};
@@ -15,8 +15,8 @@ pub use crate::{
         },
         sync::{SyncAggregate, SyncCommittee},
         validator::{
-            ContributionAndProof, SignedContributionAndProof, SyncAggregatorSelectionData,
-            SyncCommitteeContribution, SyncCommitteeMessage,
+            is_sync_committee_aggregator, ContributionAndProof, SignedContributionAndProof,
+            SyncAggregatorSelectionData, SyncCommitteeContribution, SyncCommitteeMessage,
         },
     },
     bellatrix::{execution_payload::Transaction, fork_choice::PowBlock},
@@ -77,7 +77,9 @@ pub use crate::{
             DepositMessage, Eth1Data, IndexedAttestation, PendingAttestation, ProposerSlashing,
             SignedVoluntaryExit, VoluntaryExit,
         },
-        validator::{AggregateAndProof, Eth1Block, SignedAggregateAndProof, Validator},
+        validator::{
+            is_aggregator, AggregateAndProof, Eth1Block, SignedAggregateAndProof, Validator,
+        },
     },
     primitives::*,
     signing::*,
@@ -146,7 +148,11 @@ pub fn process_bls_to_execution_change<
         Some(state.genesis_validators_root),
         context,
     )?;
-    verify_signed_data(address_change, signature, public_key, domain)?;
+    verify_signed_data(address_change, signature, public_key, domain).map_err(|_| {
+        invalid_operation_error(InvalidOperation::BlsToExecutionChange(
+            InvalidBlsToExecutionChange::InvalidSignature(signature.clone()),
+        ))
+    })?;
     withdrawal_credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
     withdrawal_credentials[1..12].fill(0);
     withdrawal_credentials[12..].copy_from_slice(address_change.to_execution_address.as_ref());
@@ -1,5 +1,18 @@
-pub use crate::electra::presets::Preset;
-use crate::electra::spec;
+pub use crate::{
+    deneb::presets::mainnet::{
+        ContributionAndProof, HistoricalBatch, SignedContributionAndProof, SyncAggregate,
+        SyncCommittee, SyncCommitteeContribution, BYTES_PER_BLOB, BYTES_PER_LOGS_BLOOM,
+        EPOCHS_PER_HISTORICAL_VECTOR, EPOCHS_PER_SLASHINGS_VECTOR, ETH1_DATA_VOTES_BOUND,
+        FIELD_ELEMENTS_PER_BLOB, HISTORICAL_ROOTS_LIMIT, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH,
+        MAX_BLOBS_PER_BLOCK, MAX_BLOB_COMMITMENTS_PER_BLOCK, MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BYTES_PER_TRANSACTION, MAX_DEPOSITS, MAX_EXTRA_DATA_BYTES, MAX_PROPOSER_SLASHINGS,
+        MAX_TRANSACTIONS_PER_PAYLOAD, MAX_VALIDATORS_PER_COMMITTEE, MAX_VOLUNTARY_EXITS,
+        MAX_WITHDRAWALS_PER_PAYLOAD, SLOTS_PER_HISTORICAL_ROOT, SYNC_COMMITTEE_SIZE,
+        VALIDATOR_REGISTRY_LIMIT,
+    },
+    electra::presets::Preset,
+};
+use crate::{electra::spec, phase0::presets::mainnet as phase0};
 
 pub use spec::*;
 
@@ -17,6 +30,9 @@ pub const MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD: usize = 8192;
 pub const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize = 16;
 pub const MAX_PENDING_PARTIALS_PER_WITHDRAWALS_SWEEP: usize = 8;
 
+pub const MAX_COMMITTEES_PER_SLOT: usize = phase0::MAX_COMMITTEES_PER_SLOT as usize;
+pub const MAX_VALIDATORS_PER_SLOT: usize = MAX_VALIDATORS_PER_COMMITTEE * MAX_COMMITTEES_PER_SLOT;
+
 pub const PRESET: Preset = Preset {
     min_activation_balance: MIN_ACTIVATION_BALANCE,
     max_effective_balance_electra: MAX_EFFECTIVE_BALANCE_ELECTRA,
@@ -32,3 +48,154 @@ pub const PRESET: Preset = Preset {
     max_withdrawal_requests_per_payload: MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
     max_pending_partials_per_withdrawals_sweep: MAX_PENDING_PARTIALS_PER_WITHDRAWALS_SWEEP,
 };
+
+pub type IndexedAttestation = spec::IndexedAttestation<MAX_VALIDATORS_PER_SLOT>;
+pub type Attestation = spec::Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>;
+pub type AttesterSlashing = spec::AttesterSlashing<MAX_VALIDATORS_PER_SLOT>;
+pub type AggregateAndProof =
+    spec::AggregateAndProof<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>;
+pub type SignedAggregateAndProof =
+    spec::SignedAggregateAndProof<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>;
+
+pub type ExecutionPayload = spec::ExecutionPayload<
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+    MAX_WITHDRAWALS_PER_PAYLOAD,
+    MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+    MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+>;
+
+pub type ExecutionPayloadHeader =
+    spec::ExecutionPayloadHeader<BYTES_PER_LOGS_BLOOM, MAX_EXTRA_DATA_BYTES>;
+
+pub type BeaconState = spec::BeaconState<
+    SLOTS_PER_HISTORICAL_ROOT,
+    HISTORICAL_ROOTS_LIMIT,
+    ETH1_DATA_VOTES_BOUND,
+    VALIDATOR_REGISTRY_LIMIT,
+    EPOCHS_PER_HISTORICAL_VECTOR,
+    EPOCHS_PER_SLASHINGS_VECTOR,
+    MAX_VALIDATORS_PER_COMMITTEE,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    PENDING_BALANCE_DEPOSITS_LIMIT,
+    PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+    PENDING_CONSOLIDATIONS_LIMIT,
+>;
+
+pub type BeaconBlockBody = spec::BeaconBlockBody<
+    MAX_PROPOSER_SLASHINGS,
+    MAX_VALIDATORS_PER_SLOT,
+    MAX_COMMITTEES_PER_SLOT,
+    MAX_ATTESTER_SLASHINGS_ELECTRA,
+    MAX_ATTESTATIONS_ELECTRA,
+    MAX_DEPOSITS,
+    MAX_VOLUNTARY_EXITS,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+    MAX_WITHDRAWALS_PER_PAYLOAD,
+    MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+    MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    MAX_BLS_TO_EXECUTION_CHANGES,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    MAX_CONSOLIDATIONS,
+>;
+
+pub type BeaconBlock = spec::BeaconBlock<
+    MAX_PROPOSER_SLASHINGS,
+    MAX_VALIDATORS_PER_SLOT,
+    MAX_COMMITTEES_PER_SLOT,
+    MAX_ATTESTER_SLASHINGS_ELECTRA,
+    MAX_ATTESTATIONS_ELECTRA,
+    MAX_DEPOSITS,
+    MAX_VOLUNTARY_EXITS,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+    MAX_WITHDRAWALS_PER_PAYLOAD,
+    MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+    MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    MAX_BLS_TO_EXECUTION_CHANGES,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    MAX_CONSOLIDATIONS,
+>;
+
+pub type SignedBeaconBlock = spec::SignedBeaconBlock<
+    MAX_PROPOSER_SLASHINGS,
+    MAX_VALIDATORS_PER_SLOT,
+    MAX_COMMITTEES_PER_SLOT,
+    MAX_ATTESTER_SLASHINGS_ELECTRA,
+    MAX_ATTESTATIONS_ELECTRA,
+    MAX_DEPOSITS,
+    MAX_VOLUNTARY_EXITS,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BYTES_PER_TRANSACTION,
+    MAX_TRANSACTIONS_PER_PAYLOAD,
+    MAX_WITHDRAWALS_PER_PAYLOAD,
+    MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+    MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+    MAX_BLS_TO_EXECUTION_CHANGES,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    MAX_CONSOLIDATIONS,
+>;
+
+pub type BlindedBeaconBlockBody = spec::BlindedBeaconBlockBody<
+    MAX_PROPOSER_SLASHINGS,
+    MAX_VALIDATORS_PER_SLOT,
+    MAX_COMMITTEES_PER_SLOT,
+    MAX_ATTESTER_SLASHINGS_ELECTRA,
+    MAX_ATTESTATIONS_ELECTRA,
+    MAX_DEPOSITS,
+    MAX_VOLUNTARY_EXITS,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BLS_TO_EXECUTION_CHANGES,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    MAX_CONSOLIDATIONS,
+>;
+
+pub type BlindedBeaconBlock = spec::BlindedBeaconBlock<
+    MAX_PROPOSER_SLASHINGS,
+    MAX_VALIDATORS_PER_SLOT,
+    MAX_COMMITTEES_PER_SLOT,
+    MAX_ATTESTER_SLASHINGS_ELECTRA,
+    MAX_ATTESTATIONS_ELECTRA,
+    MAX_DEPOSITS,
+    MAX_VOLUNTARY_EXITS,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BLS_TO_EXECUTION_CHANGES,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    MAX_CONSOLIDATIONS,
+>;
+
+pub type SignedBlindedBeaconBlock = spec::SignedBlindedBeaconBlock<
+    MAX_PROPOSER_SLASHINGS,
+    MAX_VALIDATORS_PER_SLOT,
+    MAX_COMMITTEES_PER_SLOT,
+    MAX_ATTESTER_SLASHINGS_ELECTRA,
+    MAX_ATTESTATIONS_ELECTRA,
+    MAX_DEPOSITS,
+    MAX_VOLUNTARY_EXITS,
+    SYNC_COMMITTEE_SIZE,
+    BYTES_PER_LOGS_BLOOM,
+    MAX_EXTRA_DATA_BYTES,
+    MAX_BLS_TO_EXECUTION_CHANGES,
+    MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    MAX_CONSOLIDATIONS,
+>;
+
+pub type Blob = spec::Blob<BYTES_PER_BLOB>;
+pub type BlobsBundle = spec::BlobsBundle<BYTES_PER_BLOB>;
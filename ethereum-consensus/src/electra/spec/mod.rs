@@ -15,8 +15,8 @@ pub use crate::{
         },
         sync::{SyncAggregate, SyncCommittee},
         validator::{
-            ContributionAndProof, SignedContributionAndProof, SyncAggregatorSelectionData,
-            SyncCommitteeContribution, SyncCommitteeMessage,
+            is_sync_committee_aggregator, ContributionAndProof, SignedContributionAndProof,
+            SyncAggregatorSelectionData, SyncCommitteeContribution, SyncCommitteeMessage,
         },
     },
     bellatrix::{execution_payload::Transaction, fork_choice::PowBlock},
@@ -27,9 +27,6 @@ pub use crate::{
         withdrawal::Withdrawal,
     },
     deneb::{
-        blinded_beacon_block::{
-            BlindedBeaconBlock, BlindedBeaconBlockBody, SignedBlindedBeaconBlock,
-        },
         blob_sidecar::{
             verify_blob_sidecar_inclusion_proof, Blob, BlobIdentifier, BlobSidecar, BlobsBundle,
             VersionedHash, VERSIONED_HASH_VERSION_KZG,
@@ -46,6 +43,9 @@ pub use crate::{
             BeaconState, DepositReceipt, ExecutionLayerWithdrawalRequest, PendingBalanceDeposit,
             PendingConsolidation, PendingPartialWithdrawal,
         },
+        blinded_beacon_block::{
+            BlindedBeaconBlock, BlindedBeaconBlockBody, SignedBlindedBeaconBlock,
+        },
         block_processing::{
             add_validator_to_registry, apply_deposit, get_expected_withdrawals,
             get_validator_from_deposit, is_valid_deposit_signature, process_attestation,
@@ -77,6 +77,7 @@ pub use crate::{
         operations::{
             Attestation, AttesterSlashing, Consolidation, IndexedAttestation, SignedConsolidation,
         },
+        validator::{AggregateAndProof, SignedAggregateAndProof},
     },
     error::*,
     phase0::{
@@ -97,7 +98,7 @@ pub use crate::{
             AttestationData, Checkpoint, Deposit, DepositData, DepositMessage, Eth1Data,
             PendingAttestation, ProposerSlashing, SignedVoluntaryExit, VoluntaryExit,
         },
-        validator::{AggregateAndProof, Eth1Block, SignedAggregateAndProof, Validator},
+        validator::{is_aggregator, Eth1Block, Validator},
     },
     primitives::*,
     signing::*,
@@ -248,7 +249,11 @@ pub fn process_bls_to_execution_change<
         Some(state.genesis_validators_root),
         context,
     )?;
-    verify_signed_data(address_change, signature, public_key, domain)?;
+    verify_signed_data(address_change, signature, public_key, domain).map_err(|_| {
+        invalid_operation_error(InvalidOperation::BlsToExecutionChange(
+            InvalidBlsToExecutionChange::InvalidSignature(signature.clone()),
+        ))
+    })?;
     withdrawal_credentials[0] = ETH1_ADDRESS_WITHDRAWAL_PREFIX;
     withdrawal_credentials[1..12].fill(0);
     withdrawal_credentials[12..].copy_from_slice(address_change.to_execution_address.as_ref());
@@ -0,0 +1,25 @@
+use crate::{
+    electra::operations::Attestation,
+    primitives::{BlsSignature, ValidatorIndex},
+    ssz::prelude::*,
+};
+
+#[derive(Default, Debug, SimpleSerialize, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AggregateAndProof<
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+> {
+    #[serde(with = "crate::serde::as_str")]
+    pub aggregator_index: ValidatorIndex,
+    pub aggregate: Attestation<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>,
+    pub selection_proof: BlsSignature,
+}
+
+#[derive(Default, Debug, SimpleSerialize, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SignedAggregateAndProof<
+    const MAX_VALIDATORS_PER_SLOT: usize,
+    const MAX_COMMITTEES_PER_SLOT: usize,
+> {
+    pub message: AggregateAndProof<MAX_VALIDATORS_PER_SLOT, MAX_COMMITTEES_PER_SLOT>,
+    pub signature: BlsSignature,
+}
@@ -1,4 +1,5 @@
 use crate::{
+    builder::relay::InvalidBidSubmission,
     capella::Withdrawal,
     crypto::{BlsError, KzgError},
     phase0::{AttestationData, BeaconBlockHeader, Checkpoint},
@@ -44,10 +45,20 @@ pub enum Error {
     Underflow,
     #[error("{0}")]
     InvalidBlock(#[from] Box<InvalidBlock>),
+    #[error("invalid bid submission: {0}")]
+    InvalidBidSubmission(#[from] Box<InvalidBidSubmission>),
     #[error("an invalid transition to a past slot {requested} from slot {current}")]
     TransitionToPreviousSlot { current: Slot, requested: Slot },
     #[error("invalid state root")]
     InvalidStateRoot,
+    #[error("execution payload with header root {provided:?} does not match the header root {expected:?} of the blinded block")]
+    ExecutionPayloadHeaderMismatch { expected: Root, provided: Root },
+    #[error("blobs bundle does not match the blob commitments of the block")]
+    BlobsBundleMismatch,
+    #[error("blocks from fork {0:?} have no execution payload")]
+    MissingExecutionPayload(Fork),
+    #[error("expected a value from fork {expected:?} but it is from fork {provided:?}")]
+    IncorrectFork { expected: Fork, provided: Fork },
     #[error(
     "the requested epoch {requested} is not in the required current epoch {current} or previous epoch {previous}"
     )]
@@ -211,6 +222,8 @@ pub enum InvalidBlsToExecutionChange {
     WithdrawalCredentialsPrefix(u8),
     #[error("operation's public key did not match the registered key: {0:?}")]
     PublicKeyMismatch(BlsPublicKey),
+    #[error("BLS to execution change has invalid signature: {0:?}")]
+    InvalidSignature(BlsSignature),
 }
 
 #[derive(Debug, Error)]
@@ -1,7 +1,7 @@
 use std::fmt;
 
 // Identifies the fork of the protocol the associated object belongs to.
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Fork {
     Phase0,
@@ -10,6 +10,7 @@ pub enum Fork {
     Capella,
     Deneb,
     Electra,
+    Fulu,
 }
 
 impl fmt::Display for Fork {
@@ -21,6 +22,7 @@ impl fmt::Display for Fork {
             Self::Capella => write!(f, "capella"),
             Self::Deneb => write!(f, "deneb"),
             Self::Electra => write!(f, "electra"),
+            Self::Fulu => write!(f, "fulu"),
         }
     }
 }
@@ -0,0 +1,82 @@
+//! Deterministic "interop" validator keys, as used across client test networks.
+//!
+//! See: https://github.com/ethereum/eth2.0-pm/tree/master/interop/mocked_start
+use crate::{
+    crypto::{hash, SecretKey},
+    Error,
+};
+
+// The BLS12-381 curve order `r` as little-endian 64-bit limbs.
+const CURVE_ORDER: [u64; 4] =
+    [0xffffffff00000001, 0x53bda402fffe5bfe, 0x3339d80809a1d805, 0x73eda753299d7d48];
+
+fn is_at_least_curve_order(limbs: &[u64; 4]) -> bool {
+    for (limb, modulus) in limbs.iter().rev().zip(CURVE_ORDER.iter().rev()) {
+        if limb != modulus {
+            return limb > modulus
+        }
+    }
+    true
+}
+
+fn subtract_curve_order(limbs: &mut [u64; 4]) {
+    let mut borrow = false;
+    for (limb, modulus) in limbs.iter_mut().zip(CURVE_ORDER.iter()) {
+        let (result, underflow_a) = limb.overflowing_sub(*modulus);
+        let (result, underflow_b) = result.overflowing_sub(borrow as u64);
+        *limb = result;
+        borrow = underflow_a || underflow_b;
+    }
+}
+
+/// Returns the interop secret key for the validator at `index`:
+/// `int.from_bytes(sha256(index.to_bytes(32, "little")), "little") % r`.
+pub fn interop_secret_key(index: u64) -> Result<SecretKey, Error> {
+    let mut preimage = [0u8; 32];
+    preimage[..8].copy_from_slice(&index.to_le_bytes());
+    let digest = hash(preimage);
+
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(digest.as_ref().chunks_exact(8)) {
+        *limb = u64::from_le_bytes(chunk.try_into().expect("chunk has 8 bytes"));
+    }
+    while is_at_least_curve_order(&limbs) {
+        subtract_curve_order(&mut limbs);
+    }
+
+    let mut key = [0u8; 32];
+    for (chunk, limb) in key.chunks_exact_mut(8).zip(limbs.iter().rev()) {
+        chunk.copy_from_slice(&limb.to_be_bytes());
+    }
+    SecretKey::try_from(key.as_ref()).map_err(Into::into)
+}
+
+/// Returns the interop secret keys for validators in `0..count`.
+pub fn interop_secret_keys(count: usize) -> Result<Vec<SecretKey>, Error> {
+    (0..count as u64).map(interop_secret_key).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interop_keys() {
+        let expected = [
+            (
+                "25295f0d1d592a90b333e26e85149708208e9f8e8bc18f6c77bd62f8ad7a6866",
+                "a99a76ed7796f7be22d5b7e85deeb7c5677e88e511e0b337618f8c4eb61349b4bf2d153f649f7b53359fe8b94a38e44c",
+            ),
+            (
+                "51d0b65185db6989ab0b560d6deed19c7ead0e24b9b6372cbecb1f26bdfad000",
+                "b89bebc699769726a318c8e9971bd3171297c61aea4a6578a7a4f94b547dcba5bac16a89108b6b6a1fe3695d1a874a0b",
+            ),
+        ];
+        for (index, (secret_key, public_key)) in expected.iter().enumerate() {
+            let key = interop_secret_key(index as u64).unwrap();
+            let expected_public_key = hex::decode(public_key).unwrap();
+            assert_eq!(key.public_key().as_ref(), expected_public_key.as_slice());
+            assert_eq!(hex::encode(key.to_bytes()), *secret_key);
+        }
+    }
+}
@@ -0,0 +1,654 @@
+//! Construct genesis states for devnets that start directly at a post-merge fork.
+//!
+//! Unlike `initialize_beacon_state_from_eth1`, the builder here does not replay deposits
+//! (and so does not require deposit proofs): the validator set is written directly into
+//! the genesis state, along with the matching `Eth1Data`.
+pub mod interop;
+
+use crate::{
+    crypto::{hash, SecretKey},
+    deneb, electra,
+    phase0::{
+        compute_domain, BeaconBlockHeader, DepositData, DepositMessage, Eth1Data, Fork, Validator,
+        DEPOSIT_DATA_LIST_BOUND,
+    },
+    primitives::{
+        BlsPublicKey, BlsSignature, Bytes32, DomainType, ExecutionAddress, Gwei, Hash32,
+        BLS_WITHDRAWAL_PREFIX, COMPOUNDING_WITHDRAWAL_PREFIX, ETH1_ADDRESS_WITHDRAWAL_PREFIX,
+        FAR_FUTURE_EPOCH, GENESIS_EPOCH,
+    },
+    signing::sign_with_domain,
+    ssz::prelude::*,
+    state_transition::{Context, Result},
+};
+
+pub use interop::{interop_secret_key, interop_secret_keys};
+
+/// Withdrawal credentials committing to the BLS withdrawal key `public_key`.
+pub fn bls_withdrawal_credentials(public_key: &BlsPublicKey) -> Bytes32 {
+    let mut credentials = hash(public_key.as_ref());
+    credentials[0] = BLS_WITHDRAWAL_PREFIX;
+    credentials
+}
+
+/// Withdrawal credentials committing to the execution layer `address`.
+pub fn eth1_withdrawal_credentials(address: &ExecutionAddress) -> Bytes32 {
+    execution_withdrawal_credentials(ETH1_ADDRESS_WITHDRAWAL_PREFIX, address)
+}
+
+/// Compounding (EIP-7251) withdrawal credentials for the execution layer `address`.
+pub fn compounding_withdrawal_credentials(address: &ExecutionAddress) -> Bytes32 {
+    execution_withdrawal_credentials(COMPOUNDING_WITHDRAWAL_PREFIX, address)
+}
+
+fn execution_withdrawal_credentials(prefix: u8, address: &ExecutionAddress) -> Bytes32 {
+    let mut credentials = Bytes32::default();
+    credentials[0] = prefix;
+    credentials[12..].copy_from_slice(address.as_ref());
+    credentials
+}
+
+/// A validator to include in the genesis state.
+#[derive(Debug, Clone)]
+pub struct GenesisValidator {
+    pub public_key: BlsPublicKey,
+    pub withdrawal_credentials: Bytes32,
+    pub balance: Gwei,
+    /// Signature over the corresponding `DepositMessage`; only used to
+    /// populate the deposit data committed to in the genesis `Eth1Data`.
+    pub signature: BlsSignature,
+}
+
+impl GenesisValidator {
+    pub fn new(public_key: BlsPublicKey, withdrawal_credentials: Bytes32, balance: Gwei) -> Self {
+        Self { public_key, withdrawal_credentials, balance, signature: Default::default() }
+    }
+
+    /// Construct a validator with a valid deposit signature from `secret_key`.
+    pub fn from_secret_key(
+        secret_key: &SecretKey,
+        withdrawal_credentials: Bytes32,
+        balance: Gwei,
+        context: &Context,
+    ) -> Result<Self> {
+        let public_key = secret_key.public_key();
+        let message = DepositMessage {
+            public_key: public_key.clone(),
+            withdrawal_credentials: withdrawal_credentials.clone(),
+            amount: balance,
+        };
+        let domain = compute_domain(DomainType::Deposit, None, None, context)?;
+        let signature = sign_with_domain(&message, secret_key, domain)?;
+        Ok(Self { public_key, withdrawal_credentials, balance, signature })
+    }
+
+    fn deposit_data(&self) -> DepositData {
+        DepositData {
+            public_key: self.public_key.clone(),
+            withdrawal_credentials: self.withdrawal_credentials.clone(),
+            amount: self.balance,
+            signature: self.signature.clone(),
+        }
+    }
+
+    fn to_validator(&self) -> Validator {
+        Validator {
+            public_key: self.public_key.clone(),
+            withdrawal_credentials: self.withdrawal_credentials.clone(),
+            activation_eligibility_epoch: FAR_FUTURE_EPOCH,
+            activation_epoch: FAR_FUTURE_EPOCH,
+            exit_epoch: FAR_FUTURE_EPOCH,
+            withdrawable_epoch: FAR_FUTURE_EPOCH,
+            ..Default::default()
+        }
+    }
+}
+
+/// Builds a genesis `BeaconState` for a devnet starting at Deneb or Electra.
+///
+/// By default, the genesis time is taken from the execution payload header's timestamp
+/// and the eth1 block hash from the execution payload header's block hash.
+#[derive(Clone)]
+pub struct GenesisBuilder<'a> {
+    context: &'a Context,
+    validators: Vec<GenesisValidator>,
+    genesis_time: Option<u64>,
+    eth1_block_hash: Option<Hash32>,
+}
+
+impl<'a> GenesisBuilder<'a> {
+    pub fn new(context: &'a Context) -> Self {
+        Self { context, validators: vec![], genesis_time: None, eth1_block_hash: None }
+    }
+
+    pub fn with_validators(
+        mut self,
+        validators: impl IntoIterator<Item = GenesisValidator>,
+    ) -> Self {
+        self.validators.extend(validators);
+        self
+    }
+
+    pub fn with_genesis_time(mut self, genesis_time: u64) -> Self {
+        self.genesis_time = Some(genesis_time);
+        self
+    }
+
+    pub fn with_eth1_block_hash(mut self, eth1_block_hash: Hash32) -> Self {
+        self.eth1_block_hash = Some(eth1_block_hash);
+        self
+    }
+
+    fn eth1_data(&self, block_hash: Hash32) -> Result<Eth1Data> {
+        let mut deposits = List::<DepositData, DEPOSIT_DATA_LIST_BOUND>::default();
+        for validator in &self.validators {
+            deposits.push(validator.deposit_data());
+        }
+        Ok(Eth1Data {
+            deposit_root: deposits.hash_tree_root()?,
+            deposit_count: self.validators.len() as u64,
+            block_hash,
+        })
+    }
+
+    fn randao_mixes<const EPOCHS_PER_HISTORICAL_VECTOR: usize>(
+        &self,
+        block_hash: &Hash32,
+    ) -> Result<Vector<Bytes32, EPOCHS_PER_HISTORICAL_VECTOR>> {
+        let mixes = Vector::try_from(
+            std::iter::repeat(block_hash.clone())
+                .take(self.context.epochs_per_historical_vector as usize)
+                .collect::<Vec<_>>(),
+        )
+        .map_err(|(_, err)| err)?;
+        Ok(mixes)
+    }
+
+    /// Returns the genesis validator registry and balances, with effective balances capped by
+    /// `max_effective_balance` and validators satisfying `is_active` activated at genesis.
+    fn registry(
+        &self,
+        max_effective_balance: impl Fn(&Validator) -> Gwei,
+        is_active: impl Fn(&Validator) -> bool,
+    ) -> (Vec<Validator>, Vec<Gwei>) {
+        let increment = self.context.effective_balance_increment;
+        self.validators
+            .iter()
+            .map(|genesis_validator| {
+                let balance = genesis_validator.balance;
+                let mut validator = genesis_validator.to_validator();
+                validator.effective_balance =
+                    Gwei::min(balance - balance % increment, max_effective_balance(&validator));
+                if is_active(&validator) {
+                    validator.activation_eligibility_epoch = GENESIS_EPOCH;
+                    validator.activation_epoch = GENESIS_EPOCH;
+                }
+                (validator, balance)
+            })
+            .unzip()
+    }
+
+    pub fn build_deneb<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    >(
+        &self,
+        execution_payload_header: deneb::ExecutionPayloadHeader<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+    ) -> Result<
+        deneb::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+    > {
+        let context = self.context;
+        let block_hash = self
+            .eth1_block_hash
+            .clone()
+            .unwrap_or_else(|| execution_payload_header.block_hash.clone());
+        let genesis_time = self.genesis_time.unwrap_or(execution_payload_header.timestamp);
+
+        let latest_block_body = deneb::BeaconBlockBody::<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >::default();
+        let body_root = latest_block_body.hash_tree_root()?;
+
+        let (validators, balances) = self.registry(
+            |_| context.max_effective_balance,
+            |validator| validator.effective_balance == context.max_effective_balance,
+        );
+
+        let mut state = deneb::BeaconState {
+            genesis_time,
+            fork: Fork {
+                previous_version: context.deneb_fork_version,
+                current_version: context.deneb_fork_version,
+                epoch: GENESIS_EPOCH,
+            },
+            latest_block_header: BeaconBlockHeader { body_root, ..Default::default() },
+            eth1_data: self.eth1_data(block_hash.clone())?,
+            eth1_deposit_index: self.validators.len() as u64,
+            validators: List::try_from(validators).map_err(|(_, err)| err)?,
+            balances: List::try_from(balances).map_err(|(_, err)| err)?,
+            randao_mixes: self.randao_mixes(&block_hash)?,
+            latest_execution_payload_header: execution_payload_header,
+            ..Default::default()
+        };
+
+        state.genesis_validators_root = state.validators.hash_tree_root()?;
+
+        let sync_committee = deneb::get_next_sync_committee(&state, context)?;
+        state.current_sync_committee = sync_committee.clone();
+        state.next_sync_committee = sync_committee;
+
+        Ok(state)
+    }
+
+    pub fn build_electra<
+        const SLOTS_PER_HISTORICAL_ROOT: usize,
+        const HISTORICAL_ROOTS_LIMIT: usize,
+        const ETH1_DATA_VOTES_BOUND: usize,
+        const VALIDATOR_REGISTRY_LIMIT: usize,
+        const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+        const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const PENDING_BALANCE_DEPOSITS_LIMIT: usize,
+        const PENDING_PARTIAL_WITHDRAWALS_LIMIT: usize,
+        const PENDING_CONSOLIDATIONS_LIMIT: usize,
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_CONSOLIDATIONS: usize,
+    >(
+        &self,
+        execution_payload_header: electra::ExecutionPayloadHeader<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+        >,
+    ) -> Result<
+        electra::BeaconState<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            PENDING_BALANCE_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+        >,
+    > {
+        let context = self.context;
+        let block_hash = self
+            .eth1_block_hash
+            .clone()
+            .unwrap_or_else(|| execution_payload_header.block_hash.clone());
+        let genesis_time = self.genesis_time.unwrap_or(execution_payload_header.timestamp);
+
+        let latest_block_body = electra::BeaconBlockBody::<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_CONSOLIDATIONS,
+        >::default();
+        let body_root = latest_block_body.hash_tree_root()?;
+
+        let (validators, balances) = self.registry(
+            |validator| electra::get_validator_max_effective_balance(validator, context),
+            |validator| validator.effective_balance >= context.min_activation_balance,
+        );
+
+        let mut state = electra::BeaconState {
+            genesis_time,
+            fork: Fork {
+                previous_version: context.electra_fork_version,
+                current_version: context.electra_fork_version,
+                epoch: GENESIS_EPOCH,
+            },
+            latest_block_header: BeaconBlockHeader { body_root, ..Default::default() },
+            eth1_data: self.eth1_data(block_hash.clone())?,
+            eth1_deposit_index: self.validators.len() as u64,
+            validators: List::try_from(validators).map_err(|(_, err)| err)?,
+            balances: List::try_from(balances).map_err(|(_, err)| err)?,
+            randao_mixes: self.randao_mixes(&block_hash)?,
+            latest_execution_payload_header: execution_payload_header,
+            deposit_receipts_start_index: electra::UNSET_DEPOSIT_RECEIPTS_START_INDEX,
+            deposit_balance_to_consume: 0,
+            // NOTE: no validator has exited at genesis, matching `upgrade_to_electra`
+            earliest_exit_epoch: GENESIS_EPOCH + 1,
+            earliest_consolidation_epoch: electra::compute_activation_exit_epoch(
+                GENESIS_EPOCH,
+                context,
+            ),
+            ..Default::default()
+        };
+
+        state.genesis_validators_root = state.validators.hash_tree_root()?;
+        state.exit_balance_to_consume = electra::get_activation_exit_churn_limit(&state, context)?;
+        state.consolidation_balance_to_consume =
+            electra::get_consolidation_churn_limit(&state, context)?;
+
+        let sync_committee = electra::get_next_sync_committee(&state, context)?;
+        state.current_sync_committee = sync_committee.clone();
+        state.next_sync_committee = sync_committee;
+
+        Ok(state)
+    }
+}
+
+pub mod mainnet {
+    use super::GenesisBuilder;
+    use crate::{deneb, electra, state_transition::Result};
+
+    pub fn build_deneb(
+        builder: &GenesisBuilder<'_>,
+        execution_payload_header: deneb::mainnet::ExecutionPayloadHeader,
+    ) -> Result<deneb::mainnet::BeaconState> {
+        use deneb::mainnet::*;
+        builder.build_deneb::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >(execution_payload_header)
+    }
+
+    pub fn build_electra(
+        builder: &GenesisBuilder<'_>,
+        execution_payload_header: electra::mainnet::ExecutionPayloadHeader,
+    ) -> Result<electra::mainnet::BeaconState> {
+        use electra::mainnet::*;
+        builder.build_electra::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            PENDING_BALANCE_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_CONSOLIDATIONS,
+        >(execution_payload_header)
+    }
+}
+
+pub mod minimal {
+    use super::GenesisBuilder;
+    use crate::{deneb, electra, state_transition::Result};
+
+    pub fn build_deneb(
+        builder: &GenesisBuilder<'_>,
+        execution_payload_header: deneb::minimal::ExecutionPayloadHeader,
+    ) -> Result<deneb::minimal::BeaconState> {
+        use deneb::minimal::*;
+        builder.build_deneb::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >(execution_payload_header)
+    }
+
+    pub fn build_electra(
+        builder: &GenesisBuilder<'_>,
+        execution_payload_header: electra::minimal::ExecutionPayloadHeader,
+    ) -> Result<electra::minimal::BeaconState> {
+        use electra::minimal::*;
+        builder.build_electra::<
+            SLOTS_PER_HISTORICAL_ROOT,
+            HISTORICAL_ROOTS_LIMIT,
+            ETH1_DATA_VOTES_BOUND,
+            VALIDATOR_REGISTRY_LIMIT,
+            EPOCHS_PER_HISTORICAL_VECTOR,
+            EPOCHS_PER_SLASHINGS_VECTOR,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            SYNC_COMMITTEE_SIZE,
+            PENDING_BALANCE_DEPOSITS_LIMIT,
+            PENDING_PARTIAL_WITHDRAWALS_LIMIT,
+            PENDING_CONSOLIDATIONS_LIMIT,
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_SLOT,
+            MAX_COMMITTEES_PER_SLOT,
+            MAX_ATTESTER_SLASHINGS_ELECTRA,
+            MAX_ATTESTATIONS_ELECTRA,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+            MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+            MAX_CONSOLIDATIONS,
+        >(execution_payload_header)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimal_electra_genesis() {
+        let context = Context::for_minimal();
+        let validators = interop_secret_keys(64)
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(i, secret_key)| {
+                let address = ExecutionAddress::try_from([i as u8; 20].as_ref()).unwrap();
+                let (credentials, balance) = if i % 2 == 0 {
+                    (eth1_withdrawal_credentials(&address), context.min_activation_balance)
+                } else {
+                    (compounding_withdrawal_credentials(&address), 64 * 10u64.pow(9))
+                };
+                GenesisValidator::from_secret_key(secret_key, credentials, balance, &context)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let builder = GenesisBuilder::new(&context).with_validators(validators);
+        let header = electra::minimal::ExecutionPayloadHeader {
+            timestamp: 1_700_000_000,
+            ..Default::default()
+        };
+        let state = minimal::build_electra(&builder, header).unwrap();
+
+        assert_eq!(state.genesis_time, 1_700_000_000);
+        assert_eq!(state.validators.len(), 64);
+        assert_eq!(state.eth1_deposit_index, 64);
+        assert_eq!(state.validators[1].effective_balance, 64 * 10u64.pow(9));
+        assert!(state.validators.iter().all(|v| v.activation_epoch == GENESIS_EPOCH));
+        assert_eq!(state.genesis_validators_root, state.validators.hash_tree_root().unwrap());
+        assert_eq!(state.deposit_balance_to_consume, 0);
+        assert_eq!(state.earliest_exit_epoch, GENESIS_EPOCH + 1);
+        assert_eq!(
+            state.exit_balance_to_consume,
+            electra::get_activation_exit_churn_limit(&state, &context).unwrap()
+        );
+        assert!(state.exit_balance_to_consume > 0);
+        assert_eq!(
+            state.consolidation_balance_to_consume,
+            electra::get_consolidation_churn_limit(&state, &context).unwrap()
+        );
+        assert_eq!(
+            state.earliest_consolidation_epoch,
+            electra::compute_activation_exit_epoch(GENESIS_EPOCH, &context)
+        );
+    }
+
+    #[test]
+    fn test_minimal_deneb_genesis() {
+        let context = Context::for_minimal();
+        let validators = interop_secret_keys(64)
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(i, secret_key)| {
+                let credentials = if i % 2 == 0 {
+                    bls_withdrawal_credentials(&secret_key.public_key())
+                } else {
+                    let address = ExecutionAddress::try_from([i as u8; 20].as_ref()).unwrap();
+                    eth1_withdrawal_credentials(&address)
+                };
+                // NOTE: balances above the maximum are capped, those below it stay inactive
+                let balance = match i {
+                    0 => context.max_effective_balance - context.effective_balance_increment,
+                    1 => 2 * context.max_effective_balance,
+                    _ => context.max_effective_balance,
+                };
+                GenesisValidator::from_secret_key(secret_key, credentials, balance, &context)
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let block_hash = Hash32::try_from([1u8; 32].as_ref()).unwrap();
+        let builder = GenesisBuilder::new(&context)
+            .with_validators(validators)
+            .with_genesis_time(1_600_000_000)
+            .with_eth1_block_hash(block_hash.clone());
+        let header = deneb::minimal::ExecutionPayloadHeader {
+            timestamp: 1_700_000_000,
+            ..Default::default()
+        };
+        let state = minimal::build_deneb(&builder, header).unwrap();
+
+        assert_eq!(state.genesis_time, 1_600_000_000);
+        assert_eq!(state.fork.current_version, context.deneb_fork_version);
+        assert_eq!(state.eth1_data.block_hash, block_hash);
+        assert_eq!(state.eth1_data.deposit_count, 64);
+        assert!(state.randao_mixes.iter().all(|mix| *mix == block_hash));
+        assert_eq!(state.validators.len(), 64);
+        assert_eq!(state.balances[1], 2 * context.max_effective_balance);
+        assert_eq!(state.validators[1].effective_balance, context.max_effective_balance);
+        assert_eq!(state.validators[0].activation_epoch, FAR_FUTURE_EPOCH);
+        assert!(state.validators.iter().skip(1).all(|v| v.activation_epoch == GENESIS_EPOCH));
+        assert_eq!(state.genesis_validators_root, state.validators.hash_tree_root().unwrap());
+        assert_eq!(state.current_sync_committee, state.next_sync_committee);
+    }
+}
@@ -1,4 +1,5 @@
-use bip39::{Error, Mnemonic};
+use bip39::Error;
+pub use bip39::Mnemonic;
 use rand_core::{OsRng, RngCore};
 use std::str::FromStr;
 
@@ -17,6 +18,7 @@ pub fn recover_from_phrase(phrase: &str) -> Result<Mnemonic, Error> {
     Mnemonic::from_str(phrase)
 }
 
+// Derives the BIP-39 seed from `mnemonic` with an optional `passphrase`.
 pub fn to_seed(mnemonic: Mnemonic, passphrase: Option<&str>) -> Seed {
     mnemonic.to_seed(passphrase.unwrap_or(""))
 }
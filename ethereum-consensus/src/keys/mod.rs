@@ -0,0 +1,287 @@
+//! Hierarchical key derivation for BLS12-381 keys following EIP-2333, with paths following
+//! EIP-2334 and seeds from BIP-39 mnemonics.
+pub mod mnemonic;
+pub mod path;
+
+pub use mnemonic::{Mnemonic, Seed};
+pub use path::Path;
+
+use crate::crypto::{hash, BlsError, PublicKey as BlsPublicKey, SecretKey as BlsSecretKey};
+use hkdf::Hkdf;
+use ruint::{aliases::U256, uint, Uint};
+use sha2::Sha256;
+use thiserror::Error;
+
+// Order of the BLS12-381 curve.
+const MODULUS: U256 =
+    uint!(52435875175126190479447740508185965837690552500527637822603658699938581184513_U256);
+
+const SALT: &[u8; 20] = b"BLS-SIG-KEYGEN-SALT-";
+const L: usize = 48;
+const K: usize = 32;
+const LAMPORT_COUNT: usize = 255;
+const LAMPORT_L: usize = K * LAMPORT_COUNT;
+const MIN_SEED_LEN: usize = 32;
+
+type U384 = Uint<384, 6>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("invalid derivation path `{0}`")]
+    InvalidPath(String),
+    #[error("seed must be at least {MIN_SEED_LEN} bytes but was {0} bytes")]
+    SeedTooShort(usize),
+    #[error(transparent)]
+    Mnemonic(#[from] bip39::Error),
+    #[error(transparent)]
+    Bls(#[from] BlsError),
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyPair {
+    pub private_key: BlsSecretKey,
+    pub public_key: BlsPublicKey,
+    pub path: Path,
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidatorKeys {
+    pub signing: KeyPair,
+    pub withdrawal: KeyPair,
+}
+
+#[derive(Debug, Default, Clone)]
+struct Key(U256);
+
+fn bytes_split<const M: usize, const N: usize>(input: &[u8]) -> Vec<&[u8]> {
+    debug_assert!(M % N == 0);
+    debug_assert!(input.len() == M);
+
+    input.chunks_exact(N).collect()
+}
+
+fn ikm_to_lamport_secret_key<'a>(ikm: &[u8], salt: &[u8], output: &'a mut [u8]) -> Vec<&'a [u8]> {
+    let hk = Hkdf::<Sha256>::new(Some(salt), ikm);
+    hk.expand(&[], output).expect("length L is valid");
+    bytes_split::<LAMPORT_L, K>(output)
+}
+
+fn flip_bits(input: [u8; 32]) -> Vec<u8> {
+    input.into_iter().map(|i| !i).collect()
+}
+
+fn parent_key_to_lamport_public_key(key: &Key, index: u32) -> [u8; 32] {
+    let salt = index.to_be_bytes();
+    let ikm: [u8; 32] = key.0.to_be_bytes();
+    let mut output_0 = [0u8; LAMPORT_L];
+    let lamport_0 = ikm_to_lamport_secret_key(&ikm, &salt, &mut output_0);
+    let not_ikm = flip_bits(ikm);
+    let mut output_1 = [0u8; LAMPORT_L];
+    let lamport_1 = ikm_to_lamport_secret_key(&not_ikm, &salt, &mut output_1);
+    let mut lamport_public_key = Vec::with_capacity(LAMPORT_L);
+    lamport_0.into_iter().map(hash).for_each(|data| {
+        lamport_public_key.extend_from_slice(data.as_ref());
+    });
+    lamport_1.into_iter().map(hash).for_each(|data| {
+        lamport_public_key.extend_from_slice(data.as_ref());
+    });
+    debug_assert_eq!(lamport_public_key.len(), 16320);
+    let compressed_lamport_public_key = hash(&lamport_public_key);
+    compressed_lamport_public_key.as_ref().try_into().unwrap()
+}
+
+fn hkdf_mod_r(input: &[u8]) -> Key {
+    let mut key = U384::ZERO;
+    let mut salt = hash(SALT);
+    // safety: `L` fits in type by definition
+    let l = L as u8;
+    let key_info = [0, l];
+    let mut ikm = input.to_vec();
+    ikm.push(0);
+
+    let r = U384::from(MODULUS);
+    while key == U384::ZERO {
+        let hk = Hkdf::<Sha256>::new(Some(salt.as_ref()), &ikm);
+        let mut okm = [0u8; L];
+        hk.expand(&key_info, &mut okm).expect("length L is valid");
+        let inner = U384::from_be_bytes(okm);
+        key = inner % r;
+
+        salt = hash(salt.as_ref());
+    }
+
+    // ensure we are in the field
+    debug_assert_eq!(key % r, key);
+
+    let key_bytes: [u8; L] = key.to_be_bytes();
+    let inner: [u8; 32] = key_bytes[16..].try_into().unwrap();
+    Key(U256::from_be_bytes(inner))
+}
+
+fn derive_child_key(parent_key: &Key, index: u32) -> Key {
+    let compressed_lamport_public_key = parent_key_to_lamport_public_key(parent_key, index);
+    hkdf_mod_r(&compressed_lamport_public_key)
+}
+
+fn derive_master_key(seed: &[u8]) -> Result<Key, Error> {
+    if seed.len() < MIN_SEED_LEN {
+        return Err(Error::SeedTooShort(seed.len()))
+    }
+    Ok(hkdf_mod_r(seed))
+}
+
+fn derive_key_from_path(root_key: &Key, path: &Path) -> Key {
+    path.indices().iter().fold(root_key.clone(), |key, &index| derive_child_key(&key, index))
+}
+
+fn to_bls_secret_key(key: &Key) -> BlsSecretKey {
+    let key: [u8; 32] = key.0.to_be_bytes();
+    // NOTE: keys are non-zero and reduced modulo the curve order by construction
+    BlsSecretKey::try_from(key.as_ref()).expect("valid secret key")
+}
+
+fn from_bls_secret_key(secret_key: &BlsSecretKey) -> Key {
+    Key(U256::from_be_bytes(secret_key.clone().to_bytes()))
+}
+
+fn to_key_pair(key: &Key, path: Path) -> KeyPair {
+    let private_key = to_bls_secret_key(key);
+    let public_key = private_key.public_key();
+    KeyPair { private_key, public_key, path }
+}
+
+/// Derive the master secret key from `seed` (`derive_master_SK` in EIP-2333).
+pub fn derive_master_sk(seed: &[u8]) -> Result<BlsSecretKey, Error> {
+    derive_master_key(seed).map(|key| to_bls_secret_key(&key))
+}
+
+/// Derive the child secret key at `index` from `parent_sk` (`derive_child_SK` in EIP-2333).
+pub fn derive_child_sk(parent_sk: &BlsSecretKey, index: u32) -> BlsSecretKey {
+    let key = derive_child_key(&from_bls_secret_key(parent_sk), index);
+    to_bls_secret_key(&key)
+}
+
+/// Derive the secret key at `path` starting from `master_sk`.
+pub fn derive_sk_from_path(master_sk: &BlsSecretKey, path: &Path) -> BlsSecretKey {
+    let key = derive_key_from_path(&from_bls_secret_key(master_sk), path);
+    to_bls_secret_key(&key)
+}
+
+/// Derive the key pair at `path` from `seed`.
+pub fn derive_key_pair(seed: &[u8], path: Path) -> Result<KeyPair, Error> {
+    let root_key = derive_master_key(seed)?;
+    let key = derive_key_from_path(&root_key, &path);
+    Ok(to_key_pair(&key, path))
+}
+
+/// Derive the EIP-2334 signing and withdrawal keys for the validator at `index`.
+pub fn derive_validator_keys(master_sk: &BlsSecretKey, index: u32) -> ValidatorKeys {
+    let root_key = from_bls_secret_key(master_sk);
+    let withdrawal_path = Path::withdrawal_key(index);
+    let withdrawal_key = derive_key_from_path(&root_key, &withdrawal_path);
+    // NOTE: the signing key is a child of the withdrawal key
+    let signing_key = derive_child_key(&withdrawal_key, 0);
+
+    let signing = to_key_pair(&signing_key, Path::signing_key(index));
+    let withdrawal = to_key_pair(&withdrawal_key, withdrawal_path);
+    ValidatorKeys { signing, withdrawal }
+}
+
+/// Derive the EIP-2334 validator keys for indices in `start..end` from a BIP-39 `phrase`
+/// and optional `passphrase`.
+pub fn derive_validator_keys_from_mnemonic(
+    phrase: &str,
+    passphrase: Option<&str>,
+    start: u32,
+    end: u32,
+) -> Result<Vec<ValidatorKeys>, Error> {
+    let mnemonic = mnemonic::recover_from_phrase(phrase)?;
+    let seed = mnemonic::to_seed(mnemonic, passphrase);
+    let master_sk = derive_master_sk(&seed)?;
+    Ok((start..end).map(|index| derive_validator_keys(&master_sk, index)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_key_derive() {
+        let mnemonic = mnemonic::recover_from_phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about").unwrap();
+        let passphrase = "TREZOR";
+        let seed = mnemonic::to_seed(mnemonic, Some(passphrase));
+        let expected_seed = [
+            197, 82, 87, 195, 96, 192, 124, 114, 2, 154, 235, 193, 181, 60, 5, 237, 3, 98, 173,
+            163, 142, 173, 62, 62, 158, 250, 55, 8, 229, 52, 149, 83, 31, 9, 166, 152, 117, 153,
+            209, 130, 100, 193, 225, 201, 47, 44, 241, 65, 99, 12, 122, 60, 74, 183, 200, 27, 47,
+            0, 22, 152, 231, 70, 59, 4,
+        ];
+        assert_eq!(seed, expected_seed);
+
+        let root_key = derive_master_key(&seed).unwrap();
+        let expected_root_key = uint!(
+            6083874454709270928345386274498605044986640685124978867557563392430687146096_U256
+        );
+        assert_eq!(root_key.0, expected_root_key);
+
+        let child_index = 0;
+        let child_key = derive_child_key(&root_key, child_index);
+        let expected_child_key = uint!(
+            20397789859736650942317412262472558107875392172444076792671091975210932703118_U256
+        );
+        assert_eq!(child_key.0, expected_child_key);
+    }
+
+    // Test cases from EIP-2333:
+    #[test]
+    fn test_eip2333_vectors() {
+        let test_cases = [
+            (
+                "3141592653589793238462643383279502884197169399375105820974944592",
+                uint!(29757020647961307431480504535336562678282505419141012933316116377660817309383_U256),
+                3141592653,
+                uint!(25457201688850691947727629385191704516744796114925897962676248250929345014287_U256),
+            ),
+            (
+                "0099FF991111002299DD7744EE3355BBDD8844115566CC55663355668888CC00",
+                uint!(27580842291869792442942448775674722299803720648445448686099262467207037398656_U256),
+                4294967295,
+                uint!(29358610794459428860402234341874281240803786294062035874021252734817515685787_U256),
+            ),
+            (
+                "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+                uint!(19022158461524446591288038168518313374041767046816487870552872741050760015818_U256),
+                42,
+                uint!(31372231650479070279774297061823572166496564838472787488249775572789064611981_U256),
+            ),
+        ];
+        for (seed, expected_master_key, child_index, expected_child_key) in test_cases {
+            let seed = hex::decode(seed).unwrap();
+            let master_sk = derive_master_sk(&seed).unwrap();
+            assert_eq!(from_bls_secret_key(&master_sk).0, expected_master_key);
+            let child_sk = derive_child_sk(&master_sk, child_index);
+            assert_eq!(from_bls_secret_key(&child_sk).0, expected_child_key);
+        }
+    }
+
+    #[test]
+    fn test_validator_keys_match_paths() {
+        let seed = [7u8; 64];
+        let master_sk = derive_master_sk(&seed).unwrap();
+        let ValidatorKeys { signing, withdrawal } = derive_validator_keys(&master_sk, 3);
+        assert_eq!(signing.path.to_string(), "m/12381/3600/3/0/0");
+        assert_eq!(withdrawal.path.to_string(), "m/12381/3600/3/0");
+
+        let expected = derive_key_pair(&seed, "m/12381/3600/3/0/0".parse().unwrap()).unwrap();
+        assert_eq!(signing.public_key, expected.public_key);
+        assert_eq!(
+            derive_sk_from_path(&master_sk, &withdrawal.path).public_key(),
+            withdrawal.public_key
+        );
+    }
+
+    #[test]
+    fn test_short_seed() {
+        assert!(matches!(derive_master_sk(&[0u8; 31]), Err(Error::SeedTooShort(31))));
+    }
+}
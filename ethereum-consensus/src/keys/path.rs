@@ -0,0 +1,104 @@
+use crate::keys::Error;
+use std::{fmt, str::FromStr};
+
+// Purpose for BLS12-381 keys following EIP-2334.
+pub const PURPOSE: u32 = 12381;
+// Coin type for Ethereum following EIP-2334.
+pub const COIN_TYPE: u32 = 3600;
+
+const ROOT: &str = "m";
+const SEPARATOR: char = '/';
+
+/// A key derivation path, as in EIP-2334: `m / purpose / coin_type / account / use`.
+///
+/// Any sequence of (non-hardened) indices is accepted; see `is_eip2334` to check
+/// the path uses the purpose and coin type for Ethereum validators.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Path(Vec<u32>);
+
+impl Path {
+    pub fn new(indices: Vec<u32>) -> Self {
+        Self(indices)
+    }
+
+    /// Path of the withdrawal key for the validator at `index`: `m/12381/3600/<index>/0`.
+    pub fn withdrawal_key(index: u32) -> Self {
+        Self(vec![PURPOSE, COIN_TYPE, index, 0])
+    }
+
+    /// Path of the signing key for the validator at `index`: `m/12381/3600/<index>/0/0`.
+    pub fn signing_key(index: u32) -> Self {
+        Self(vec![PURPOSE, COIN_TYPE, index, 0, 0])
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+
+    pub fn is_eip2334(&self) -> bool {
+        matches!(self.0.as_slice(), [PURPOSE, COIN_TYPE, _, ..])
+    }
+
+    /// Returns a copy of this path extended with `index`.
+    pub fn child(&self, index: u32) -> Self {
+        let mut indices = self.0.clone();
+        indices.push(index);
+        Self(indices)
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{ROOT}")?;
+        for index in &self.0 {
+            write!(f, "{SEPARATOR}{index}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Path {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(SEPARATOR);
+        if parts.next() != Some(ROOT) {
+            return Err(Error::InvalidPath(s.to_string()))
+        }
+        let indices = parts
+            .map(|part| part.parse::<u32>().map_err(|_| Error::InvalidPath(s.to_string())))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self(indices))
+    }
+}
+
+impl serde::Serialize for Path {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Path {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <String>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_roundtrip() {
+        let path: Path = "m/12381/3600/7/0/0".parse().unwrap();
+        assert_eq!(path, Path::signing_key(7));
+        assert!(path.is_eip2334());
+        assert_eq!(path.to_string(), "m/12381/3600/7/0/0");
+        assert_eq!("m".parse::<Path>().unwrap(), Path::default());
+
+        for invalid in ["", "12381/3600", "m/", "m/12381/-1", "m/4294967296", "m/0'"] {
+            assert!(invalid.parse::<Path>().is_err(), "{invalid}");
+        }
+    }
+}
@@ -1,5 +1,7 @@
 pub mod altair;
 pub mod bellatrix;
+#[cfg(feature = "blobs")]
+pub mod blobs;
 pub mod builder;
 pub mod capella;
 pub mod clock;
@@ -11,6 +13,9 @@ pub mod electra;
 pub mod error;
 pub mod execution_engine;
 mod fork;
+pub mod genesis;
+#[cfg(feature = "keys")]
+pub mod keys;
 pub mod networking;
 pub mod networks;
 pub mod phase0;
@@ -0,0 +1,351 @@
+//! Consensus-specific fields of node records (ENRs) and the subnets a node is expected to join.
+use crate::{
+    altair::constants::SYNC_COMMITTEE_SUBNET_COUNT,
+    crypto::hash,
+    networking::{
+        gossip::{compute_subnet_for_data_column_sidecar, DATA_COLUMN_SIDECAR_SUBNET_COUNT},
+        Enr,
+    },
+    phase0::{
+        compute_shuffled_index,
+        networking::{
+            ATTESTATION_SUBNET_COUNT, ATTESTATION_SUBNET_PREFIX_BITS,
+            EPOCHS_PER_SUBNET_SUBSCRIPTION, NODE_ID_BITS, SUBNETS_PER_NODE,
+        },
+    },
+    primitives::{Epoch, ForkDigest, Version},
+    ssz::prelude::*,
+    state_transition::Context,
+    Error,
+};
+use enr::k256::ecdsa::SigningKey;
+use thiserror::Error;
+
+pub const ETH2_ENR_KEY: &str = "eth2";
+pub const ATTESTATION_BITFIELD_ENR_KEY: &str = "attnets";
+pub const SYNC_COMMITTEE_BITFIELD_ENR_KEY: &str = "syncnets";
+pub const CUSTODY_GROUP_COUNT_ENR_KEY: &str = "cgc";
+
+// PeerDAS (Fulu) parameters
+pub const NUMBER_OF_COLUMNS: u64 = 128;
+pub const NUMBER_OF_CUSTODY_GROUPS: u64 = 128;
+
+pub type NodeId = [u8; 32];
+
+/// The value of the `eth2` key of a node record.
+#[derive(
+    Default, Debug, SimpleSerialize, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct EnrForkId {
+    pub fork_digest: ForkDigest,
+    pub next_fork_version: Version,
+    #[serde(with = "crate::serde::as_str")]
+    pub next_fork_epoch: Epoch,
+}
+
+#[derive(Debug, Error)]
+pub enum EnrFieldError {
+    #[error("node record has no `{0}` field")]
+    Missing(&'static str),
+    #[error("could not decode node record field `{key}`: {source}")]
+    Ssz { key: &'static str, source: SimpleSerializeError },
+    #[error("custody group count is not a valid integer")]
+    InvalidCustodyGroupCount,
+    #[error(transparent)]
+    Enr(#[from] enr::EnrError),
+}
+
+fn decode_field<T: SimpleSerialize>(enr: &Enr, key: &'static str) -> Result<T, EnrFieldError> {
+    let data = enr.get(key).ok_or(EnrFieldError::Missing(key))?;
+    T::deserialize(data).map_err(|err| EnrFieldError::Ssz { key, source: err.into() })
+}
+
+fn encode_field<T: SimpleSerialize>(
+    enr: &mut Enr,
+    key: &'static str,
+    value: &T,
+    signing_key: &SigningKey,
+) -> Result<(), EnrFieldError> {
+    let data = serialize(value).map_err(|err| EnrFieldError::Ssz { key, source: err.into() })?;
+    enr.insert(key, &data, signing_key)?;
+    Ok(())
+}
+
+/// Accessors for the consensus-specific fields of a node record.
+///
+/// Setters update the sequence number and signature of the record with `signing_key`.
+pub trait Eth2Enr {
+    fn eth2(&self) -> Result<EnrForkId, EnrFieldError>;
+
+    fn attnets(&self) -> Result<Bitvector<ATTESTATION_SUBNET_COUNT>, EnrFieldError>;
+
+    fn syncnets(&self) -> Result<Bitvector<SYNC_COMMITTEE_SUBNET_COUNT>, EnrFieldError>;
+
+    fn custody_group_count(&self) -> Result<u64, EnrFieldError>;
+
+    fn set_eth2(
+        &mut self,
+        fork_id: &EnrForkId,
+        signing_key: &SigningKey,
+    ) -> Result<(), EnrFieldError>;
+
+    fn set_attnets(
+        &mut self,
+        attnets: &Bitvector<ATTESTATION_SUBNET_COUNT>,
+        signing_key: &SigningKey,
+    ) -> Result<(), EnrFieldError>;
+
+    fn set_syncnets(
+        &mut self,
+        syncnets: &Bitvector<SYNC_COMMITTEE_SUBNET_COUNT>,
+        signing_key: &SigningKey,
+    ) -> Result<(), EnrFieldError>;
+
+    fn set_custody_group_count(
+        &mut self,
+        count: u64,
+        signing_key: &SigningKey,
+    ) -> Result<(), EnrFieldError>;
+}
+
+impl Eth2Enr for Enr {
+    fn eth2(&self) -> Result<EnrForkId, EnrFieldError> {
+        decode_field(self, ETH2_ENR_KEY)
+    }
+
+    fn attnets(&self) -> Result<Bitvector<ATTESTATION_SUBNET_COUNT>, EnrFieldError> {
+        decode_field(self, ATTESTATION_BITFIELD_ENR_KEY)
+    }
+
+    fn syncnets(&self) -> Result<Bitvector<SYNC_COMMITTEE_SUBNET_COUNT>, EnrFieldError> {
+        decode_field(self, SYNC_COMMITTEE_BITFIELD_ENR_KEY)
+    }
+
+    fn custody_group_count(&self) -> Result<u64, EnrFieldError> {
+        let key = CUSTODY_GROUP_COUNT_ENR_KEY;
+        let data = self.get(key).ok_or(EnrFieldError::Missing(key))?;
+        // NOTE: encoded as a big-endian RLP integer, without leading zeros
+        if data.len() > 8 || data.first() == Some(&0) {
+            return Err(EnrFieldError::InvalidCustodyGroupCount)
+        }
+        Ok(data.iter().fold(0u64, |count, &byte| (count << 8) | byte as u64))
+    }
+
+    fn set_eth2(
+        &mut self,
+        fork_id: &EnrForkId,
+        signing_key: &SigningKey,
+    ) -> Result<(), EnrFieldError> {
+        encode_field(self, ETH2_ENR_KEY, fork_id, signing_key)
+    }
+
+    fn set_attnets(
+        &mut self,
+        attnets: &Bitvector<ATTESTATION_SUBNET_COUNT>,
+        signing_key: &SigningKey,
+    ) -> Result<(), EnrFieldError> {
+        encode_field(self, ATTESTATION_BITFIELD_ENR_KEY, attnets, signing_key)
+    }
+
+    fn set_syncnets(
+        &mut self,
+        syncnets: &Bitvector<SYNC_COMMITTEE_SUBNET_COUNT>,
+        signing_key: &SigningKey,
+    ) -> Result<(), EnrFieldError> {
+        encode_field(self, SYNC_COMMITTEE_BITFIELD_ENR_KEY, syncnets, signing_key)
+    }
+
+    fn set_custody_group_count(
+        &mut self,
+        count: u64,
+        signing_key: &SigningKey,
+    ) -> Result<(), EnrFieldError> {
+        // NOTE: RLP integers are big-endian without leading zeros
+        let data =
+            count.to_be_bytes().into_iter().skip_while(|&byte| byte == 0).collect::<Vec<_>>();
+        self.insert(CUSTODY_GROUP_COUNT_ENR_KEY, &data, signing_key)?;
+        Ok(())
+    }
+}
+
+/// The node id of `enr`, as used to compute its subnet subscriptions.
+pub fn node_id(enr: &Enr) -> NodeId {
+    enr.node_id().raw()
+}
+
+fn compute_subscribed_subnet(
+    node_id: &NodeId,
+    epoch: Epoch,
+    index: usize,
+    context: &Context,
+) -> Result<u64, Error> {
+    // NOTE: `node_id` is a big-endian `uint256`
+    let prefix_bits = ATTESTATION_SUBNET_PREFIX_BITS;
+    debug_assert!(prefix_bits <= 8);
+    let node_id_prefix = (node_id[0] >> (8 - prefix_bits)) as usize;
+    debug_assert_eq!(NODE_ID_BITS, 8 * node_id.len());
+    // NOTE: `EPOCHS_PER_SUBNET_SUBSCRIPTION` is 256 so the offset is the low byte of `node_id`
+    let node_offset = node_id[31] as Epoch % EPOCHS_PER_SUBNET_SUBSCRIPTION;
+    let period = (epoch + node_offset) / EPOCHS_PER_SUBNET_SUBSCRIPTION;
+    let permutation_seed = hash(period.to_le_bytes());
+    let permutated_prefix =
+        compute_shuffled_index(node_id_prefix, 1 << prefix_bits, &permutation_seed, context)?;
+    Ok(((permutated_prefix + index) % ATTESTATION_SUBNET_COUNT) as u64)
+}
+
+/// The long-lived attestation subnets the node with `node_id` must subscribe to at `epoch`.
+pub fn compute_subscribed_subnets(
+    node_id: &NodeId,
+    epoch: Epoch,
+    context: &Context,
+) -> Result<Vec<u64>, Error> {
+    (0..SUBNETS_PER_NODE)
+        .map(|index| compute_subscribed_subnet(node_id, epoch, index, context))
+        .collect()
+}
+
+/// The custody groups of the node with `node_id` when it custodies `custody_group_count` groups,
+/// in ascending order.
+pub fn get_custody_groups(node_id: &NodeId, custody_group_count: u64) -> Result<Vec<u64>, Error> {
+    if custody_group_count > NUMBER_OF_CUSTODY_GROUPS {
+        return Err(Error::OutOfBounds {
+            requested: custody_group_count as usize,
+            bound: NUMBER_OF_CUSTODY_GROUPS as usize,
+        })
+    }
+    if custody_group_count == NUMBER_OF_CUSTODY_GROUPS {
+        return Ok((0..NUMBER_OF_CUSTODY_GROUPS).collect())
+    }
+    // NOTE: `node_id` is a big-endian `uint256` that is hashed in little-endian order
+    let mut current_id = *node_id;
+    current_id.reverse();
+    let mut custody_groups = Vec::with_capacity(custody_group_count as usize);
+    while (custody_groups.len() as u64) < custody_group_count {
+        let digest = hash(current_id);
+        let value = u64::from_le_bytes(digest[..8].try_into().expect("correct size"));
+        let custody_group = value % NUMBER_OF_CUSTODY_GROUPS;
+        if !custody_groups.contains(&custody_group) {
+            custody_groups.push(custody_group);
+        }
+        // NOTE: wraps around to zero after the maximum `uint256`
+        for byte in current_id.iter_mut() {
+            let (next, overflow) = byte.overflowing_add(1);
+            *byte = next;
+            if !overflow {
+                break
+            }
+        }
+    }
+    custody_groups.sort_unstable();
+    Ok(custody_groups)
+}
+
+/// The columns that belong to `custody_group`.
+pub fn compute_columns_for_custody_group(custody_group: u64) -> Result<Vec<u64>, Error> {
+    if custody_group >= NUMBER_OF_CUSTODY_GROUPS {
+        return Err(Error::OutOfBounds {
+            requested: custody_group as usize,
+            bound: NUMBER_OF_CUSTODY_GROUPS as usize,
+        })
+    }
+    let columns_per_group = NUMBER_OF_COLUMNS / NUMBER_OF_CUSTODY_GROUPS;
+    Ok((0..columns_per_group).map(|i| NUMBER_OF_CUSTODY_GROUPS * i + custody_group).collect())
+}
+
+/// The data column subnets the node with `node_id` must subscribe to when it custodies
+/// `custody_group_count` groups, in ascending order.
+pub fn compute_data_column_subnets(
+    node_id: &NodeId,
+    custody_group_count: u64,
+) -> Result<Vec<u64>, Error> {
+    let mut subnets = vec![];
+    for custody_group in get_custody_groups(node_id, custody_group_count)? {
+        for column_index in compute_columns_for_custody_group(custody_group)? {
+            subnets.push(compute_subnet_for_data_column_sidecar(column_index));
+        }
+    }
+    subnets.sort_unstable();
+    subnets.dedup();
+    Ok(subnets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_enr_fields() {
+        let signing_key = SigningKey::from_bytes(&[1u8; 32]).unwrap();
+        let mut enr = enr::EnrBuilder::new("v4").build(&signing_key).unwrap();
+        assert!(matches!(enr.eth2(), Err(EnrFieldError::Missing(ETH2_ENR_KEY))));
+
+        let fork_id = EnrForkId {
+            fork_digest: [1, 2, 3, 4],
+            next_fork_version: [5, 6, 7, 8],
+            next_fork_epoch: 1234,
+        };
+        enr.set_eth2(&fork_id, &signing_key).unwrap();
+        assert_eq!(enr.eth2().unwrap(), fork_id);
+
+        let mut attnets = Bitvector::<ATTESTATION_SUBNET_COUNT>::default();
+        attnets.set(3, true);
+        attnets.set(63, true);
+        enr.set_attnets(&attnets, &signing_key).unwrap();
+        assert_eq!(enr.attnets().unwrap(), attnets);
+        assert!(matches!(enr.syncnets(), Err(EnrFieldError::Missing(_))));
+
+        enr.set_custody_group_count(128, &signing_key).unwrap();
+        assert_eq!(enr.custody_group_count().unwrap(), 128);
+    }
+
+    #[test]
+    fn test_subscribed_subnets() {
+        let context = Context::for_mainnet();
+        let node_id = [0xab; 32];
+        let subnets = compute_subscribed_subnets(&node_id, 0, &context).unwrap();
+        assert_eq!(subnets.len(), SUBNETS_PER_NODE);
+        assert_eq!(subnets[1], (subnets[0] + 1) % ATTESTATION_SUBNET_COUNT as u64);
+        assert!(subnets.iter().all(|&subnet| subnet < ATTESTATION_SUBNET_COUNT as u64));
+
+        // subscriptions only change at the node's subscription period boundary
+        let offset = 0xab % EPOCHS_PER_SUBNET_SUBSCRIPTION;
+        let boundary = EPOCHS_PER_SUBNET_SUBSCRIPTION - offset;
+        assert_eq!(compute_subscribed_subnets(&node_id, boundary - 1, &context).unwrap(), subnets);
+        let period_subnets = compute_subscribed_subnets(&node_id, boundary, &context).unwrap();
+        let next_period_subnets = compute_subscribed_subnets(
+            &node_id,
+            boundary + EPOCHS_PER_SUBNET_SUBSCRIPTION - 1,
+            &context,
+        )
+        .unwrap();
+        assert_eq!(period_subnets, next_period_subnets);
+    }
+
+    #[test]
+    fn test_data_column_subnets() {
+        assert_eq!(compute_columns_for_custody_group(7).unwrap(), [7]);
+        assert!(compute_columns_for_custody_group(NUMBER_OF_CUSTODY_GROUPS).is_err());
+
+        let node_id = [0xab; 32];
+        let groups = get_custody_groups(&node_id, 4).unwrap();
+        assert_eq!(groups.len(), 4);
+        assert!(groups.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(groups.iter().all(|&group| group < NUMBER_OF_CUSTODY_GROUPS));
+        // custodying more groups only adds to the smaller set
+        let more_groups = get_custody_groups(&node_id, 8).unwrap();
+        assert!(groups.iter().all(|group| more_groups.contains(group)));
+        assert_eq!(compute_data_column_subnets(&node_id, 4).unwrap(), groups);
+
+        // the first group is derived from the hash of the little-endian node id
+        let expected = u64::from_le_bytes(hash([0xab; 32])[..8].try_into().unwrap()) %
+            NUMBER_OF_CUSTODY_GROUPS;
+        assert_eq!(get_custody_groups(&node_id, 1).unwrap(), [expected]);
+
+        // the node id wraps around after the maximum value
+        assert_eq!(get_custody_groups(&[0xff; 32], 2).unwrap().len(), 2);
+
+        let all = compute_data_column_subnets(&node_id, NUMBER_OF_CUSTODY_GROUPS).unwrap();
+        assert_eq!(all, (0..DATA_COLUMN_SIDECAR_SUBNET_COUNT).collect::<Vec<_>>());
+        assert!(get_custody_groups(&node_id, NUMBER_OF_CUSTODY_GROUPS + 1).is_err());
+    }
+}
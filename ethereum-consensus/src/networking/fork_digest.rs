@@ -0,0 +1,201 @@
+use crate::{
+    crypto::hash,
+    phase0::{compute_fork_data_root, compute_fork_digest},
+    primitives::{Epoch, ForkDigest, Root, Slot, Version, FAR_FUTURE_EPOCH, GENESIS_EPOCH},
+    state_transition::Context,
+    Error, Fork,
+};
+
+/// Computes the fork digest in use at `epoch`.
+///
+/// From Fulu, the digest also commits to the blob parameters in effect, so that it changes at
+/// every blob parameter only ("BPO") fork in the blob schedule.
+pub fn compute_fork_digest_at_epoch(
+    epoch: Epoch,
+    genesis_validators_root: Root,
+    context: &Context,
+) -> Result<ForkDigest, Error> {
+    let fork = context.fork_for(epoch.saturating_mul(context.slots_per_epoch));
+    let version = context.fork_version_for(fork);
+    if fork != Fork::Fulu {
+        return compute_fork_digest(version, genesis_validators_root)
+    }
+    let base_digest = compute_fork_data_root(version, genesis_validators_root)?;
+    let blob_parameters = context.blob_parameters(epoch);
+    let mut data = [0u8; 16];
+    data[..8].copy_from_slice(&blob_parameters.epoch.to_le_bytes());
+    data[8..].copy_from_slice(&blob_parameters.max_blobs_per_block.to_le_bytes());
+    let mask = hash(data);
+    let mut digest = ForkDigest::default();
+    for (i, byte) in digest.iter_mut().enumerate() {
+        *byte = base_digest[i] ^ mask[i];
+    }
+    Ok(digest)
+}
+
+/// A fork activated on the network, along with the fork digest peers use while it is current.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScheduledFork {
+    pub fork: Fork,
+    pub epoch: Epoch,
+    pub version: Version,
+    pub digest: ForkDigest,
+}
+
+/// The fork digests in use over the lifetime of a network, in order of activation.
+///
+/// Only forks with a scheduled activation epoch (i.e. not `FAR_FUTURE_EPOCH`) are included.
+/// The blob parameter only forks scheduled after Fulu are included as further Fulu entries,
+/// each with its own digest.
+#[derive(Debug, Clone)]
+pub struct ForkDigestSchedule {
+    forks: Vec<ScheduledFork>,
+}
+
+impl ForkDigestSchedule {
+    pub fn new(genesis_validators_root: Root, context: &Context) -> Result<Self, Error> {
+        let activations = [
+            (Fork::Phase0, GENESIS_EPOCH),
+            (Fork::Altair, context.altair_fork_epoch),
+            (Fork::Bellatrix, context.bellatrix_fork_epoch),
+            (Fork::Capella, context.capella_fork_epoch),
+            (Fork::Deneb, context.deneb_fork_epoch),
+            (Fork::Electra, context.electra_fork_epoch),
+            (Fork::Fulu, context.fulu_fork_epoch),
+        ];
+        let mut activations = activations
+            .into_iter()
+            .filter(|(_, epoch)| *epoch != FAR_FUTURE_EPOCH)
+            .collect::<Vec<_>>();
+        if context.fulu_fork_epoch != FAR_FUTURE_EPOCH {
+            let mut blob_forks = context
+                .blob_schedule
+                .iter()
+                .map(|parameters| parameters.epoch)
+                .filter(|epoch| *epoch > context.fulu_fork_epoch && *epoch != FAR_FUTURE_EPOCH)
+                .collect::<Vec<_>>();
+            blob_forks.sort_unstable();
+            blob_forks.dedup();
+            activations.extend(blob_forks.into_iter().map(|epoch| (Fork::Fulu, epoch)));
+        }
+        let forks = activations
+            .into_iter()
+            .map(|(fork, epoch)| {
+                let version = context.fork_version_for(fork);
+                let digest = compute_fork_digest_at_epoch(epoch, genesis_validators_root, context)?;
+                Ok(ScheduledFork { fork, epoch, version, digest })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Self { forks })
+    }
+
+    /// All scheduled forks, in order of activation.
+    pub fn forks(&self) -> &[ScheduledFork] {
+        &self.forks
+    }
+
+    /// The fork current at `epoch`.
+    pub fn fork_at_epoch(&self, epoch: Epoch) -> &ScheduledFork {
+        // NOTE: phase0 is always scheduled at genesis so there is always a match
+        self.forks.iter().rev().find(|fork| fork.epoch <= epoch).expect("phase0 is scheduled")
+    }
+
+    pub fn fork_at_slot(&self, slot: Slot, context: &Context) -> &ScheduledFork {
+        self.fork_at_epoch(slot / context.slots_per_epoch)
+    }
+
+    pub fn digest_at_epoch(&self, epoch: Epoch) -> ForkDigest {
+        self.fork_at_epoch(epoch).digest
+    }
+
+    /// The next fork to activate after `epoch`, if any is scheduled.
+    pub fn next_fork(&self, epoch: Epoch) -> Option<&ScheduledFork> {
+        self.forks.iter().find(|fork| fork.epoch > epoch)
+    }
+
+    /// Finds the fork with the given `digest`, e.g. to interpret a gossip topic.
+    ///
+    /// If several forks activate at the same epoch and share a digest, the latest is returned.
+    pub fn fork_for_digest(&self, digest: &ForkDigest) -> Option<&ScheduledFork> {
+        self.forks.iter().rev().find(|fork| &fork.digest == digest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configs::BlobParameters;
+
+    #[test]
+    fn test_schedule() {
+        let mut context = Context::for_minimal();
+        context.altair_fork_epoch = 0;
+        context.bellatrix_fork_epoch = 2;
+        context.capella_fork_epoch = 4;
+        context.deneb_fork_epoch = FAR_FUTURE_EPOCH;
+        context.electra_fork_epoch = FAR_FUTURE_EPOCH;
+        context.fulu_fork_epoch = FAR_FUTURE_EPOCH;
+
+        let schedule = ForkDigestSchedule::new(Root::default(), &context).unwrap();
+        let forks = schedule.forks().iter().map(|fork| fork.fork).collect::<Vec<_>>();
+        assert_eq!(forks, [Fork::Phase0, Fork::Altair, Fork::Bellatrix, Fork::Capella]);
+
+        assert_eq!(schedule.fork_at_epoch(0).fork, Fork::Altair);
+        assert_eq!(schedule.fork_at_epoch(3).fork, Fork::Bellatrix);
+        assert_eq!(schedule.fork_at_epoch(1000).fork, Fork::Capella);
+        assert_eq!(schedule.next_fork(2).map(|fork| fork.fork), Some(Fork::Capella));
+        assert!(schedule.next_fork(4).is_none());
+
+        let capella = schedule.fork_at_epoch(4);
+        let expected = compute_fork_digest(context.capella_fork_version, Root::default()).unwrap();
+        assert_eq!(capella.digest, expected);
+        assert_eq!(schedule.fork_for_digest(&expected), Some(capella));
+    }
+
+    #[test]
+    fn test_blob_parameter_forks() {
+        let mut context = Context::for_mainnet();
+        context.electra_fork_epoch = 10;
+        context.fulu_fork_epoch = 20;
+        context.blob_schedule = vec![
+            BlobParameters { epoch: 30, max_blobs_per_block: 15 },
+            BlobParameters { epoch: 10, max_blobs_per_block: 9 },
+            BlobParameters { epoch: 40, max_blobs_per_block: 21 },
+        ];
+        let genesis_validators_root = Root::try_from([1u8; 32].as_ref()).unwrap();
+        let schedule = ForkDigestSchedule::new(genesis_validators_root, &context).unwrap();
+
+        let fulu = schedule.forks().iter().filter(|fork| fork.fork == Fork::Fulu);
+        assert_eq!(fulu.map(|fork| fork.epoch).collect::<Vec<_>>(), [20, 30, 40]);
+        assert_eq!(schedule.next_fork(20).map(|fork| fork.epoch), Some(30));
+
+        // digests before Fulu do not commit to the blob parameters
+        let electra = schedule.fork_at_epoch(19);
+        let expected =
+            compute_fork_digest(context.electra_fork_version, genesis_validators_root).unwrap();
+        assert_eq!(electra.digest, expected);
+
+        // the Fulu digest is masked with the hash of the blob parameters in effect
+        let base_digest =
+            compute_fork_data_root(context.fulu_fork_version, genesis_validators_root).unwrap();
+        let mut data = [0u8; 16];
+        data[..8].copy_from_slice(&10u64.to_le_bytes());
+        data[8..].copy_from_slice(&9u64.to_le_bytes());
+        let mask = hash(data);
+        let expected = [0, 1, 2, 3].map(|i| base_digest[i] ^ mask[i]);
+        assert_eq!(schedule.digest_at_epoch(20), expected);
+        assert_eq!(schedule.digest_at_epoch(29), expected);
+
+        let digests = [20, 30, 40].map(|epoch| schedule.digest_at_epoch(epoch));
+        assert_ne!(digests[0], digests[1]);
+        assert_ne!(digests[1], digests[2]);
+        for (epoch, digest) in [20, 30, 40].into_iter().zip(digests) {
+            let fork = schedule.fork_for_digest(&digest).unwrap();
+            assert_eq!((fork.fork, fork.epoch), (Fork::Fulu, epoch));
+            assert_eq!(
+                compute_fork_digest_at_epoch(epoch, genesis_validators_root, &context).unwrap(),
+                digest
+            );
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Names of the gossipsub topics used on the consensus p2p network.
+use crate::{primitives::ForkDigest, Fork};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+const TOPIC_PREFIX: &str = "eth2";
+const SSZ_SNAPPY_ENCODING: &str = "ssz_snappy";
+
+const BEACON_BLOCK: &str = "beacon_block";
+const BEACON_AGGREGATE_AND_PROOF: &str = "beacon_aggregate_and_proof";
+const VOLUNTARY_EXIT: &str = "voluntary_exit";
+const PROPOSER_SLASHING: &str = "proposer_slashing";
+const ATTESTER_SLASHING: &str = "attester_slashing";
+const BEACON_ATTESTATION_PREFIX: &str = "beacon_attestation_";
+const SYNC_COMMITTEE_CONTRIBUTION_AND_PROOF: &str = "sync_committee_contribution_and_proof";
+const SYNC_COMMITTEE_PREFIX: &str = "sync_committee_";
+const LIGHT_CLIENT_FINALITY_UPDATE: &str = "light_client_finality_update";
+const LIGHT_CLIENT_OPTIMISTIC_UPDATE: &str = "light_client_optimistic_update";
+const BLS_TO_EXECUTION_CHANGE: &str = "bls_to_execution_change";
+const BLOB_SIDECAR_PREFIX: &str = "blob_sidecar_";
+const DATA_COLUMN_SIDECAR_PREFIX: &str = "data_column_sidecar_";
+
+pub const DATA_COLUMN_SIDECAR_SUBNET_COUNT: u64 = 128;
+
+/// The subnet a data column sidecar for column `column_index` is gossiped on.
+pub fn compute_subnet_for_data_column_sidecar(column_index: u64) -> u64 {
+    column_index % DATA_COLUMN_SIDECAR_SUBNET_COUNT
+}
+
+#[derive(Debug, Error)]
+pub enum TopicError {
+    #[error("topic `{0}` is not of the form `/eth2/<fork-digest>/<name>/<encoding>`")]
+    InvalidFormat(String),
+    #[error("invalid fork digest `{0}` in topic")]
+    InvalidForkDigest(String),
+    #[error("unknown topic name `{0}`")]
+    UnknownName(String),
+    #[error("unsupported topic encoding `{0}`")]
+    UnsupportedEncoding(String),
+}
+
+/// The kind of message carried on a gossip topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GossipKind {
+    BeaconBlock,
+    BeaconAggregateAndProof,
+    VoluntaryExit,
+    ProposerSlashing,
+    AttesterSlashing,
+    BeaconAttestation { subnet_id: u64 },
+    SyncCommitteeContributionAndProof,
+    SyncCommittee { subnet_id: u64 },
+    LightClientFinalityUpdate,
+    LightClientOptimisticUpdate,
+    BlsToExecutionChange,
+    BlobSidecar { subnet_id: u64 },
+    // NOTE: PeerDAS topic, introduced after the forks implemented in this crate
+    DataColumnSidecar { subnet_id: u64 },
+}
+
+impl GossipKind {
+    /// The topics without a subnet that a node subscribes to while `fork` is current.
+    pub fn global_topics(fork: Fork) -> Vec<Self> {
+        let mut kinds = vec![
+            Self::BeaconBlock,
+            Self::BeaconAggregateAndProof,
+            Self::VoluntaryExit,
+            Self::ProposerSlashing,
+            Self::AttesterSlashing,
+        ];
+        if !matches!(fork, Fork::Phase0) {
+            kinds.push(Self::SyncCommitteeContributionAndProof);
+        }
+        if matches!(fork, Fork::Capella | Fork::Deneb | Fork::Electra | Fork::Fulu) {
+            kinds.push(Self::BlsToExecutionChange);
+        }
+        kinds
+    }
+
+    pub fn subnet_id(&self) -> Option<u64> {
+        match self {
+            Self::BeaconAttestation { subnet_id } |
+            Self::SyncCommittee { subnet_id } |
+            Self::BlobSidecar { subnet_id } |
+            Self::DataColumnSidecar { subnet_id } => Some(*subnet_id),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GossipKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BeaconBlock => write!(f, "{BEACON_BLOCK}"),
+            Self::BeaconAggregateAndProof => write!(f, "{BEACON_AGGREGATE_AND_PROOF}"),
+            Self::VoluntaryExit => write!(f, "{VOLUNTARY_EXIT}"),
+            Self::ProposerSlashing => write!(f, "{PROPOSER_SLASHING}"),
+            Self::AttesterSlashing => write!(f, "{ATTESTER_SLASHING}"),
+            Self::BeaconAttestation { subnet_id } => {
+                write!(f, "{BEACON_ATTESTATION_PREFIX}{subnet_id}")
+            }
+            Self::SyncCommitteeContributionAndProof => {
+                write!(f, "{SYNC_COMMITTEE_CONTRIBUTION_AND_PROOF}")
+            }
+            Self::SyncCommittee { subnet_id } => write!(f, "{SYNC_COMMITTEE_PREFIX}{subnet_id}"),
+            Self::LightClientFinalityUpdate => write!(f, "{LIGHT_CLIENT_FINALITY_UPDATE}"),
+            Self::LightClientOptimisticUpdate => write!(f, "{LIGHT_CLIENT_OPTIMISTIC_UPDATE}"),
+            Self::BlsToExecutionChange => write!(f, "{BLS_TO_EXECUTION_CHANGE}"),
+            Self::BlobSidecar { subnet_id } => write!(f, "{BLOB_SIDECAR_PREFIX}{subnet_id}"),
+            Self::DataColumnSidecar { subnet_id } => {
+                write!(f, "{DATA_COLUMN_SIDECAR_PREFIX}{subnet_id}")
+            }
+        }
+    }
+}
+
+fn parse_subnet_id(name: &str, prefix: &str) -> Option<u64> {
+    let subnet_id = name.strip_prefix(prefix)?;
+    // NOTE: disallow non-canonical encodings like `+1` or `01`
+    if subnet_id.is_empty() ||
+        !subnet_id.bytes().all(|b| b.is_ascii_digit()) ||
+        (subnet_id.len() > 1 && subnet_id.starts_with('0'))
+    {
+        return None
+    }
+    subnet_id.parse().ok()
+}
+
+impl FromStr for GossipKind {
+    type Err = TopicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let kind = match s {
+            BEACON_BLOCK => Self::BeaconBlock,
+            BEACON_AGGREGATE_AND_PROOF => Self::BeaconAggregateAndProof,
+            VOLUNTARY_EXIT => Self::VoluntaryExit,
+            PROPOSER_SLASHING => Self::ProposerSlashing,
+            ATTESTER_SLASHING => Self::AttesterSlashing,
+            SYNC_COMMITTEE_CONTRIBUTION_AND_PROOF => Self::SyncCommitteeContributionAndProof,
+            LIGHT_CLIENT_FINALITY_UPDATE => Self::LightClientFinalityUpdate,
+            LIGHT_CLIENT_OPTIMISTIC_UPDATE => Self::LightClientOptimisticUpdate,
+            BLS_TO_EXECUTION_CHANGE => Self::BlsToExecutionChange,
+            name => {
+                if let Some(subnet_id) = parse_subnet_id(name, BEACON_ATTESTATION_PREFIX) {
+                    Self::BeaconAttestation { subnet_id }
+                } else if let Some(subnet_id) = parse_subnet_id(name, SYNC_COMMITTEE_PREFIX) {
+                    Self::SyncCommittee { subnet_id }
+                } else if let Some(subnet_id) = parse_subnet_id(name, BLOB_SIDECAR_PREFIX) {
+                    Self::BlobSidecar { subnet_id }
+                } else if let Some(subnet_id) = parse_subnet_id(name, DATA_COLUMN_SIDECAR_PREFIX) {
+                    Self::DataColumnSidecar { subnet_id }
+                } else {
+                    return Err(TopicError::UnknownName(name.to_string()))
+                }
+            }
+        };
+        Ok(kind)
+    }
+}
+
+/// A gossipsub topic: `/eth2/<fork-digest>/<name>/ssz_snappy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GossipTopic {
+    pub fork_digest: ForkDigest,
+    pub kind: GossipKind,
+}
+
+impl GossipTopic {
+    pub fn new(fork_digest: ForkDigest, kind: GossipKind) -> Self {
+        Self { fork_digest, kind }
+    }
+}
+
+impl fmt::Display for GossipTopic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{TOPIC_PREFIX}/")?;
+        for byte in self.fork_digest {
+            write!(f, "{byte:02x}")?;
+        }
+        write!(f, "/{}/{SSZ_SNAPPY_ENCODING}", self.kind)
+    }
+}
+
+fn parse_fork_digest(s: &str) -> Option<ForkDigest> {
+    if s.len() != 2 * std::mem::size_of::<ForkDigest>() || !s.is_ascii() {
+        return None
+    }
+    let mut digest = ForkDigest::default();
+    for (i, byte) in digest.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).ok()?;
+    }
+    Some(digest)
+}
+
+impl FromStr for GossipTopic {
+    type Err = TopicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split('/').collect::<Vec<_>>();
+        let ["", TOPIC_PREFIX, fork_digest, name, encoding] = parts.as_slice() else {
+            return Err(TopicError::InvalidFormat(s.to_string()))
+        };
+        let fork_digest = parse_fork_digest(fork_digest)
+            .ok_or_else(|| TopicError::InvalidForkDigest(fork_digest.to_string()))?;
+        if *encoding != SSZ_SNAPPY_ENCODING {
+            return Err(TopicError::UnsupportedEncoding(encoding.to_string()))
+        }
+        let kind = name.parse()?;
+        Ok(Self { fork_digest, kind })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topic_roundtrip() {
+        let fork_digest = [0x6a, 0x95, 0xa1, 0xa9];
+        let cases = [
+            (GossipKind::BeaconBlock, "/eth2/6a95a1a9/beacon_block/ssz_snappy"),
+            (
+                GossipKind::BeaconAttestation { subnet_id: 63 },
+                "/eth2/6a95a1a9/beacon_attestation_63/ssz_snappy",
+            ),
+            (
+                GossipKind::SyncCommitteeContributionAndProof,
+                "/eth2/6a95a1a9/sync_committee_contribution_and_proof/ssz_snappy",
+            ),
+            (
+                GossipKind::SyncCommittee { subnet_id: 0 },
+                "/eth2/6a95a1a9/sync_committee_0/ssz_snappy",
+            ),
+            (GossipKind::BlobSidecar { subnet_id: 5 }, "/eth2/6a95a1a9/blob_sidecar_5/ssz_snappy"),
+            (
+                GossipKind::DataColumnSidecar { subnet_id: 127 },
+                "/eth2/6a95a1a9/data_column_sidecar_127/ssz_snappy",
+            ),
+        ];
+        for (kind, expected) in cases {
+            let topic = GossipTopic::new(fork_digest, kind);
+            assert_eq!(topic.to_string(), expected);
+            assert_eq!(expected.parse::<GossipTopic>().unwrap(), topic);
+        }
+    }
+
+    #[test]
+    fn test_data_column_subnets() {
+        assert_eq!(compute_subnet_for_data_column_sidecar(5), 5);
+        assert_eq!(compute_subnet_for_data_column_sidecar(DATA_COLUMN_SIDECAR_SUBNET_COUNT + 5), 5);
+        let kind = GossipKind::DataColumnSidecar {
+            subnet_id: compute_subnet_for_data_column_sidecar(255),
+        };
+        assert_eq!(kind.to_string(), "data_column_sidecar_127");
+    }
+
+    #[test]
+    fn test_invalid_topics() {
+        for topic in [
+            "/eth2/6a95a1a9/beacon_block/ssz",
+            "/eth2/6a95a1a9/beacon_block",
+            "eth2/6a95a1a9/beacon_block/ssz_snappy",
+            "/eth2/6a95a1/beacon_block/ssz_snappy",
+            "/eth2/6a95a1zz/beacon_block/ssz_snappy",
+            "/eth2/6a95a1a9/beacon_attestation_/ssz_snappy",
+            "/eth2/6a95a1a9/beacon_attestation_01/ssz_snappy",
+            "/eth2/6a95a1a9/beacon_attestation_+1/ssz_snappy",
+            "/eth2/6a95a1a9/unknown/ssz_snappy",
+        ] {
+            assert!(topic.parse::<GossipTopic>().is_err(), "{topic}");
+        }
+    }
+}
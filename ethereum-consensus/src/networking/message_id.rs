@@ -0,0 +1,81 @@
+//! Computation of gossipsub message ids, as specified in the consensus p2p spec.
+use crate::{
+    bellatrix::networking::GOSSIP_MAX_SIZE_BELLATRIX, crypto::hash, networking::MessageDomain,
+    phase0::networking::GOSSIP_MAX_SIZE, Fork,
+};
+
+pub const MESSAGE_ID_LEN: usize = 20;
+
+pub type MessageId = [u8; MESSAGE_ID_LEN];
+
+/// Maximum size of an uncompressed gossip payload while `fork` is current.
+pub fn gossip_max_size(fork: Fork) -> usize {
+    match fork {
+        Fork::Phase0 | Fork::Altair => GOSSIP_MAX_SIZE,
+        _ => GOSSIP_MAX_SIZE_BELLATRIX,
+    }
+}
+
+// Decompresses `data` with the raw snappy format, as used for gossip payloads.
+// Payloads that would decompress beyond `max_size` are considered invalid.
+fn snappy_decompress(data: &[u8], max_size: usize) -> Option<Vec<u8>> {
+    let len = snap::raw::decompress_len(data).ok()?;
+    if len > max_size {
+        return None
+    }
+    snap::raw::Decoder::new().decompress_vec(data).ok()
+}
+
+/// Computes the id of a gossip message with the given (compressed) `data` published on
+/// `topic` while `fork` is current.
+///
+/// From Altair, the topic is part of the pre-image so that the same payload on different topics
+/// has distinct ids.
+pub fn compute_message_id(topic: &str, data: &[u8], fork: Fork) -> MessageId {
+    let (domain, payload) = match snappy_decompress(data, gossip_max_size(fork)) {
+        Some(decompressed) => (MessageDomain::ValidSnappy, decompressed),
+        None => (MessageDomain::InvalidSnappy, data.to_vec()),
+    };
+
+    let mut pre_image = domain.as_bytes().to_vec();
+    if !matches!(fork, Fork::Phase0) {
+        let topic_len = topic.len() as u64;
+        pre_image.extend_from_slice(&topic_len.to_le_bytes());
+        pre_image.extend_from_slice(topic.as_bytes());
+    }
+    pre_image.extend_from_slice(&payload);
+
+    let digest = hash(pre_image);
+    digest.as_ref()[..MESSAGE_ID_LEN].try_into().expect("digest is longer than the message id")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_id() {
+        let payload = b"some gossip payload".to_vec();
+        let compressed = snap::raw::Encoder::new().compress_vec(&payload).unwrap();
+        let topic = "/eth2/6a95a1a9/beacon_block/ssz_snappy";
+
+        let mut pre_image = vec![1, 0, 0, 0];
+        pre_image.extend_from_slice(&payload);
+        let expected = hash(&pre_image);
+        assert_eq!(compute_message_id(topic, &compressed, Fork::Phase0), expected.as_ref()[..20]);
+
+        let mut pre_image = vec![1, 0, 0, 0];
+        pre_image.extend_from_slice(&(topic.len() as u64).to_le_bytes());
+        pre_image.extend_from_slice(topic.as_bytes());
+        pre_image.extend_from_slice(&payload);
+        let expected = hash(&pre_image);
+        assert_eq!(compute_message_id(topic, &compressed, Fork::Deneb), expected.as_ref()[..20]);
+
+        // invalid snappy data is hashed as-is under the other domain
+        let invalid = [0xff; 8];
+        let mut pre_image = vec![0, 0, 0, 0];
+        pre_image.extend_from_slice(&invalid);
+        let expected = hash(&pre_image);
+        assert_eq!(compute_message_id(topic, &invalid, Fork::Phase0), expected.as_ref()[..20]);
+    }
+}
@@ -0,0 +1,41 @@
+//! Versions of the `MetaData` a node returns over req/resp.
+pub use crate::{
+    altair::networking::MetaData as MetaDataV2, phase0::networking::MetaData as MetaDataV1,
+};
+
+use crate::{
+    altair::constants::SYNC_COMMITTEE_SUBNET_COUNT, phase0::networking::ATTESTATION_SUBNET_COUNT,
+    ssz::prelude::*,
+};
+
+/// `MetaData` from PeerDAS, which adds the number of custody groups of the node.
+#[derive(
+    Default, Debug, SimpleSerialize, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct MetaDataV3 {
+    #[serde(with = "crate::serde::as_str")]
+    pub seq_number: u64,
+    pub attnets: Bitvector<ATTESTATION_SUBNET_COUNT>,
+    pub syncnets: Bitvector<SYNC_COMMITTEE_SUBNET_COUNT>,
+    #[serde(with = "crate::serde::as_str")]
+    pub custody_group_count: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_encoding() {
+        let mut metadata =
+            MetaDataV3 { seq_number: 7, custody_group_count: 4, ..Default::default() };
+        metadata.attnets.set(1, true);
+        let encoding = serialize(&metadata).unwrap();
+        assert_eq!(encoding.len(), 8 + 8 + 1 + 8);
+        assert_eq!(MetaDataV3::deserialize(&encoding).unwrap(), metadata);
+
+        let v2 =
+            MetaDataV2 { seq_number: 7, attnets: metadata.attnets.clone(), ..Default::default() };
+        assert_eq!(serialize(&v2).unwrap(), encoding[..17]);
+    }
+}
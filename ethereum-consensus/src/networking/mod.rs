@@ -1,9 +1,23 @@
+pub mod discovery;
+pub mod fork_digest;
+pub mod gossip;
+pub mod message_id;
+pub mod metadata;
+pub mod req_resp;
+pub mod validation;
+
+pub use discovery::{compute_data_column_subnets, compute_subscribed_subnets, EnrForkId, Eth2Enr};
+pub use fork_digest::{compute_fork_digest_at_epoch, ForkDigestSchedule, ScheduledFork};
+pub use gossip::{compute_subnet_for_data_column_sidecar, GossipKind, GossipTopic, TopicError};
+pub use message_id::{compute_message_id, MessageId};
+pub use metadata::{MetaDataV1, MetaDataV2, MetaDataV3};
 pub use multiaddr::Multiaddr;
 use multihash::{Code, Error, Multihash};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::{fmt, str::FromStr};
 use thiserror::Error;
+pub use validation::{IgnoreReason, RejectReason, SeenCache, ValidationResult};
 
 pub const MAX_INLINE_KEY_LENGTH: usize = 42;
 
@@ -119,11 +133,21 @@ impl FromStr for PeerId {
 
 pub type Enr = enr::Enr<enr::k256::ecdsa::SigningKey>;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MessageDomain {
     InvalidSnappy,
     ValidSnappy,
 }
 
+impl MessageDomain {
+    pub fn as_bytes(&self) -> [u8; 4] {
+        match self {
+            Self::InvalidSnappy => [0, 0, 0, 0],
+            Self::ValidSnappy => [1, 0, 0, 0],
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
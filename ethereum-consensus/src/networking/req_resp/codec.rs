@@ -0,0 +1,611 @@
+//! The `ssz_snappy` encoding of req/resp messages.
+//!
+//! Each payload is the SSZ encoding of a message, compressed with the snappy frame format
+//! and prefixed with its uncompressed length as an unsigned LEB128 varint. Response chunks
+//! are further prefixed with a result byte and, for some protocols, the fork digest of the
+//! payload (the "context bytes").
+use crate::{
+    altair::light_client as altair,
+    bellatrix::networking::MAX_CHUNK_SIZE_BELLATRIX,
+    capella::light_client as capella,
+    deneb::{light_client as deneb, BlobSidecar},
+    electra,
+    networking::{
+        req_resp::{
+            messages::{ErrorMessage, MAX_ERROR_MESSAGE_LENGTH},
+            Protocol,
+        },
+        ForkDigestSchedule,
+    },
+    phase0::networking::MAX_CHUNK_SIZE,
+    primitives::ForkDigest,
+    ssz::prelude::*,
+    types::SignedBeaconBlock,
+    Fork,
+};
+use snap::{read::FrameDecoder, write::FrameEncoder};
+use std::io::{self, Read, Write};
+use thiserror::Error;
+
+// Maximum number of bytes in the varint encoding of a `u64`.
+const MAX_VARINT_LEN: usize = 10;
+
+#[derive(Debug, Error)]
+pub enum CodecError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("{0}")]
+    SimpleSerialize(#[from] SimpleSerializeError),
+    #[error("invalid varint length prefix")]
+    InvalidVarint,
+    #[error("payload of {size} bytes exceeds the maximum of {max} bytes")]
+    PayloadTooLarge { size: u64, max: usize },
+    #[error("response has more than the maximum of {0} chunks")]
+    TooManyChunks(usize),
+    #[error("unknown protocol `{0}`")]
+    UnknownProtocol(String),
+    #[error("error response {code:?}: {message}")]
+    ErrorResponse { code: ResponseCode, message: String },
+    #[error("response chunk has no context bytes")]
+    MissingContext,
+    #[error("unknown fork digest {0:?} in response context")]
+    UnknownForkDigest(ForkDigest),
+    #[error("payload type is not defined for fork {0}")]
+    UnsupportedFork(Fork),
+}
+
+/// Maximum size of an uncompressed payload while `fork` is current.
+pub fn max_chunk_size(fork: Fork) -> usize {
+    match fork {
+        Fork::Phase0 | Fork::Altair => MAX_CHUNK_SIZE,
+        _ => MAX_CHUNK_SIZE_BELLATRIX,
+    }
+}
+
+/// Worst-case size of the snappy-framed encoding of `len` bytes.
+pub fn max_compressed_len(len: usize) -> usize {
+    32 + len + len / 6
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> io::Result<()> {
+    let mut buffer = [0u8; MAX_VARINT_LEN];
+    let mut i = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buffer[i] = byte;
+            i += 1;
+            break
+        }
+        buffer[i] = byte | 0x80;
+        i += 1;
+    }
+    writer.write_all(&buffer[..i])
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> Result<u64, CodecError> {
+    let mut value = 0u64;
+    for i in 0..MAX_VARINT_LEN {
+        let mut byte = [0u8];
+        reader.read_exact(&mut byte)?;
+        let byte = byte[0];
+        let bits = (byte & 0x7f) as u64;
+        // NOTE: the 10th byte can only hold the most significant bit of a `u64`
+        if i == MAX_VARINT_LEN - 1 && bits > 1 {
+            return Err(CodecError::InvalidVarint)
+        }
+        value |= bits << (7 * i);
+        if byte & 0x80 == 0 {
+            // NOTE: reject non-minimal encodings (with trailing zero groups)
+            if i > 0 && byte == 0 {
+                return Err(CodecError::InvalidVarint)
+            }
+            return Ok(value)
+        }
+    }
+    Err(CodecError::InvalidVarint)
+}
+
+/// Writes the `ssz_snappy` encoding of `encoding` (an SSZ-encoded message) to `writer`.
+pub fn write_payload<W: Write>(writer: &mut W, encoding: &[u8]) -> Result<(), CodecError> {
+    write_varint(writer, encoding.len() as u64)?;
+    let mut encoder = FrameEncoder::new(writer);
+    encoder.write_all(encoding)?;
+    encoder.flush()?;
+    Ok(())
+}
+
+/// Reads an `ssz_snappy` payload from `reader`, returning the SSZ encoding of the message.
+///
+/// Payloads larger than `max_size` bytes once decompressed are rejected before decompression.
+pub fn read_payload<R: Read>(reader: &mut R, max_size: usize) -> Result<Vec<u8>, CodecError> {
+    let size = read_varint(reader)?;
+    if size > max_size as u64 {
+        return Err(CodecError::PayloadTooLarge { size, max: max_size })
+    }
+    let size = size as usize;
+    let compressed = reader.take(max_compressed_len(size) as u64);
+    let mut decoder = FrameDecoder::new(compressed);
+    let mut encoding = vec![0u8; size];
+    decoder.read_exact(&mut encoding)?;
+    Ok(encoding)
+}
+
+pub fn write_request<W: Write, T: SimpleSerialize>(
+    writer: &mut W,
+    request: &T,
+) -> Result<(), CodecError> {
+    let encoding = serialize(request).map_err(SimpleSerializeError::from)?;
+    write_payload(writer, &encoding)
+}
+
+pub fn read_request<R: Read, T: SimpleSerialize>(
+    reader: &mut R,
+    fork: Fork,
+) -> Result<T, CodecError> {
+    let encoding = read_payload(reader, max_chunk_size(fork))?;
+    Ok(T::deserialize(&encoding).map_err(SimpleSerializeError::from)?)
+}
+
+/// The result byte of a response chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseCode {
+    Success,
+    InvalidRequest,
+    ServerError,
+    ResourceUnavailable,
+    // NOTE: other codes are reserved, but must be handled as errors
+    Other(u8),
+}
+
+impl From<u8> for ResponseCode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Success,
+            1 => Self::InvalidRequest,
+            2 => Self::ServerError,
+            3 => Self::ResourceUnavailable,
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl From<ResponseCode> for u8 {
+    fn from(value: ResponseCode) -> Self {
+        match value {
+            ResponseCode::Success => 0,
+            ResponseCode::InvalidRequest => 1,
+            ResponseCode::ServerError => 2,
+            ResponseCode::ResourceUnavailable => 3,
+            ResponseCode::Other(other) => other,
+        }
+    }
+}
+
+/// A single chunk of a response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResponseChunk {
+    /// The SSZ encoding of a response message, with the fork digest of the message if
+    /// the protocol uses context bytes.
+    Success {
+        context: Option<ForkDigest>,
+        encoding: Vec<u8>,
+    },
+    Error {
+        code: ResponseCode,
+        message: String,
+    },
+}
+
+impl ResponseChunk {
+    /// Decodes a successful chunk of a protocol without context bytes.
+    pub fn decode<T: SimpleSerialize>(&self) -> Result<T, CodecError> {
+        match self {
+            Self::Success { encoding, .. } => {
+                Ok(T::deserialize(encoding).map_err(SimpleSerializeError::from)?)
+            }
+            Self::Error { code, message } => {
+                Err(CodecError::ErrorResponse { code: *code, message: message.clone() })
+            }
+        }
+    }
+
+    /// Decodes a successful chunk of a protocol with context bytes, using `schedule` to
+    /// find the fork of the message.
+    pub fn decode_for_fork<T: ForkTyped>(
+        &self,
+        schedule: &ForkDigestSchedule,
+    ) -> Result<T, CodecError> {
+        match self {
+            Self::Success { context, encoding } => {
+                let context = context.ok_or(CodecError::MissingContext)?;
+                let fork = schedule
+                    .fork_for_digest(&context)
+                    .ok_or(CodecError::UnknownForkDigest(context))?
+                    .fork;
+                T::deserialize_for_fork(fork, encoding)
+            }
+            Self::Error { code, message } => {
+                Err(CodecError::ErrorResponse { code: *code, message: message.clone() })
+            }
+        }
+    }
+}
+
+/// Writes a successful response chunk with the SSZ encoding of `response`.
+///
+/// `context` must be provided for (and only for) protocols with context bytes.
+pub fn write_response_chunk<W: Write, T: SimpleSerialize>(
+    writer: &mut W,
+    context: Option<ForkDigest>,
+    response: &T,
+) -> Result<(), CodecError> {
+    writer.write_all(&[ResponseCode::Success.into()])?;
+    if let Some(context) = context {
+        writer.write_all(&context)?;
+    }
+    write_request(writer, response)
+}
+
+/// Writes an error response chunk; `message` is truncated to the maximum error message length.
+pub fn write_error_chunk<W: Write>(
+    writer: &mut W,
+    code: ResponseCode,
+    message: &str,
+) -> Result<(), CodecError> {
+    debug_assert!(code != ResponseCode::Success);
+    writer.write_all(&[code.into()])?;
+    let message = message.as_bytes();
+    let len = message.len().min(MAX_ERROR_MESSAGE_LENGTH);
+    let message = ErrorMessage::try_from(&message[..len]).map_err(SimpleSerializeError::from)?;
+    write_request(writer, &message)
+}
+
+/// Reads the next response chunk of `protocol` from `reader`, or `None` at the end of the stream.
+pub fn read_response_chunk<R: Read>(
+    reader: &mut R,
+    protocol: Protocol,
+    fork: Fork,
+) -> Result<Option<ResponseChunk>, CodecError> {
+    let mut code = [0u8];
+    if reader.read(&mut code)? == 0 {
+        return Ok(None)
+    }
+    let code = ResponseCode::from(code[0]);
+    if code != ResponseCode::Success {
+        let encoding = read_payload(reader, MAX_ERROR_MESSAGE_LENGTH)?;
+        let message = ErrorMessage::deserialize(&encoding).map_err(SimpleSerializeError::from)?;
+        let message = String::from_utf8_lossy(message.as_ref()).to_string();
+        return Ok(Some(ResponseChunk::Error { code, message }))
+    }
+    let context = if protocol.has_context_bytes() {
+        let mut context = ForkDigest::default();
+        reader.read_exact(&mut context)?;
+        Some(context)
+    } else {
+        None
+    };
+    let encoding = read_payload(reader, max_chunk_size(fork))?;
+    Ok(Some(ResponseChunk::Success { context, encoding }))
+}
+
+/// Reads all response chunks of `protocol` from `reader` until the end of the stream,
+/// enforcing the maximum number of chunks for the protocol while `fork` is current.
+pub fn read_response<R: Read>(
+    reader: &mut R,
+    protocol: Protocol,
+    fork: Fork,
+) -> Result<Vec<ResponseChunk>, CodecError> {
+    let max_chunks = protocol.max_response_chunks(fork);
+    let mut chunks = vec![];
+    while let Some(chunk) = read_response_chunk(reader, protocol, fork)? {
+        if chunks.len() == max_chunks {
+            return Err(CodecError::TooManyChunks(max_chunks))
+        }
+        chunks.push(chunk);
+    }
+    Ok(chunks)
+}
+
+/// Types of response messages whose SSZ encoding depends on the fork given by the context bytes.
+pub trait ForkTyped: Sized {
+    fn deserialize_for_fork(fork: Fork, encoding: &[u8]) -> Result<Self, CodecError>;
+}
+
+// NOTE: `SignedBeaconBlock` has no Electra variant, use `electra::SignedBeaconBlock` instead
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    > ForkTyped
+    for SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >
+{
+    fn deserialize_for_fork(fork: Fork, encoding: &[u8]) -> Result<Self, CodecError> {
+        let block = match fork {
+            Fork::Phase0 => Self::Phase0(
+                Deserialize::deserialize(encoding).map_err(SimpleSerializeError::from)?,
+            ),
+            Fork::Altair => Self::Altair(
+                Deserialize::deserialize(encoding).map_err(SimpleSerializeError::from)?,
+            ),
+            Fork::Bellatrix => Self::Bellatrix(
+                Deserialize::deserialize(encoding).map_err(SimpleSerializeError::from)?,
+            ),
+            Fork::Capella => Self::Capella(
+                Deserialize::deserialize(encoding).map_err(SimpleSerializeError::from)?,
+            ),
+            Fork::Deneb => {
+                Self::Deneb(Deserialize::deserialize(encoding).map_err(SimpleSerializeError::from)?)
+            }
+            fork => return Err(CodecError::UnsupportedFork(fork)),
+        };
+        Ok(block)
+    }
+}
+
+// Implements `ForkTyped` for types with a single SSZ encoding used by each of `$forks`.
+macro_rules! impl_fork_typed {
+    (@impl [$(const $param:ident: usize),*], $forks:pat, $ty:ty) => {
+        impl<$(const $param: usize),*> ForkTyped for $ty {
+            fn deserialize_for_fork(fork: Fork, encoding: &[u8]) -> Result<Self, CodecError> {
+                match fork {
+                    $forks => Ok(Self::deserialize(encoding).map_err(SimpleSerializeError::from)?),
+                    fork => Err(CodecError::UnsupportedFork(fork)),
+                }
+            }
+        }
+    };
+    ($params:tt, $forks:pat, $($ty:ty),+ $(,)?) => {
+        $(impl_fork_typed!(@impl $params, $forks, $ty);)+
+    };
+}
+
+// NOTE: Fulu leaves the block unchanged from Electra
+impl_fork_typed!(
+    [
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_SLOT: usize,
+        const MAX_COMMITTEES_PER_SLOT: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+        const MAX_CONSOLIDATIONS: usize
+    ],
+    Fork::Electra | Fork::Fulu,
+    electra::SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_SLOT,
+        MAX_COMMITTEES_PER_SLOT,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_DEPOSIT_RECEIPTS_PER_PAYLOAD,
+        MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        MAX_CONSOLIDATIONS,
+    >,
+);
+
+// NOTE: Fulu serves data column sidecars in place of blob sidecars
+impl_fork_typed!(
+    [const BYTES_PER_BLOB: usize, const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH: usize],
+    Fork::Deneb | Fork::Electra,
+    BlobSidecar<BYTES_PER_BLOB, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH>,
+);
+
+// The light client messages are defined from Altair, with the header extended by Capella and
+// Deneb. Later forks use the Deneb messages.
+impl_fork_typed!(
+    [const SYNC_COMMITTEE_SIZE: usize],
+    Fork::Altair | Fork::Bellatrix,
+    altair::LightClientBootstrap<SYNC_COMMITTEE_SIZE>,
+    altair::LightClientUpdate<SYNC_COMMITTEE_SIZE>,
+    altair::LightClientFinalityUpdate<SYNC_COMMITTEE_SIZE>,
+    altair::LightClientOptimisticUpdate<SYNC_COMMITTEE_SIZE>,
+);
+
+impl_fork_typed!(
+    [const SYNC_COMMITTEE_SIZE: usize, const LOGS_BLOOM: usize, const EXTRA_DATA: usize],
+    Fork::Capella,
+    capella::LightClientBootstrap<SYNC_COMMITTEE_SIZE, LOGS_BLOOM, EXTRA_DATA>,
+    capella::LightClientUpdate<SYNC_COMMITTEE_SIZE, LOGS_BLOOM, EXTRA_DATA>,
+    capella::LightClientFinalityUpdate<SYNC_COMMITTEE_SIZE, LOGS_BLOOM, EXTRA_DATA>,
+    capella::LightClientOptimisticUpdate<SYNC_COMMITTEE_SIZE, LOGS_BLOOM, EXTRA_DATA>,
+);
+
+impl_fork_typed!(
+    [const SYNC_COMMITTEE_SIZE: usize, const LOGS_BLOOM: usize, const EXTRA_DATA: usize],
+    Fork::Deneb | Fork::Electra | Fork::Fulu,
+    deneb::LightClientBootstrap<SYNC_COMMITTEE_SIZE, LOGS_BLOOM, EXTRA_DATA>,
+    deneb::LightClientUpdate<SYNC_COMMITTEE_SIZE, LOGS_BLOOM, EXTRA_DATA>,
+    deneb::LightClientFinalityUpdate<SYNC_COMMITTEE_SIZE, LOGS_BLOOM, EXTRA_DATA>,
+    deneb::LightClientOptimisticUpdate<SYNC_COMMITTEE_SIZE, LOGS_BLOOM, EXTRA_DATA>,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        networking::req_resp::messages::{BeaconBlocksByRangeRequest, Status},
+        phase0::mainnet::SignedBeaconBlock as Phase0SignedBeaconBlock,
+        primitives::Root,
+        state_transition::Context,
+        types::mainnet::SignedBeaconBlock,
+    };
+    use std::io::Cursor;
+
+    #[test]
+    fn test_varint() {
+        for value in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut buffer = vec![];
+            write_varint(&mut buffer, value).unwrap();
+            assert_eq!(read_varint(&mut buffer.as_slice()).unwrap(), value);
+        }
+        assert_eq!(read_varint(&mut [0xac, 0x02].as_slice()).unwrap(), 300);
+        assert!(read_varint(&mut [0x80, 0x00].as_slice()).is_err());
+        assert!(read_varint(&mut [0xff; 11].as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_request_roundtrip() {
+        let request = BeaconBlocksByRangeRequest { start_slot: 32, count: 64, step: 1 };
+        let mut stream = vec![];
+        write_request(&mut stream, &request).unwrap();
+        let recovered: BeaconBlocksByRangeRequest =
+            read_request(&mut stream.as_slice(), Fork::Deneb).unwrap();
+        assert_eq!(recovered, request);
+    }
+
+    #[test]
+    fn test_payload_limit() {
+        let mut stream = vec![];
+        write_payload(&mut stream, &vec![0u8; MAX_CHUNK_SIZE + 1]).unwrap();
+        let result = read_payload(&mut stream.as_slice(), MAX_CHUNK_SIZE);
+        assert!(matches!(result, Err(CodecError::PayloadTooLarge { .. })));
+    }
+
+    #[test]
+    fn test_response_stream() {
+        let status = Status { head_slot: 12, ..Default::default() };
+        let mut stream = vec![];
+        write_response_chunk(&mut stream, None, &status).unwrap();
+        let chunks =
+            read_response(&mut Cursor::new(stream), Protocol::Status, Fork::Deneb).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].decode::<Status>().unwrap(), status);
+
+        let mut stream = vec![];
+        write_error_chunk(&mut stream, ResponseCode::ResourceUnavailable, "no blocks").unwrap();
+        let chunks = read_response(&mut stream.as_slice(), Protocol::Status, Fork::Deneb).unwrap();
+        assert_eq!(
+            chunks,
+            [ResponseChunk::Error {
+                code: ResponseCode::ResourceUnavailable,
+                message: "no blocks".to_string(),
+            }]
+        );
+
+        let mut stream = vec![];
+        write_response_chunk(&mut stream, None, &status).unwrap();
+        write_response_chunk(&mut stream, None, &status).unwrap();
+        let result = read_response(&mut stream.as_slice(), Protocol::Status, Fork::Deneb);
+        assert!(matches!(result, Err(CodecError::TooManyChunks(1))));
+    }
+
+    #[test]
+    fn test_context_bytes() {
+        let context = Context::for_mainnet();
+        let schedule = ForkDigestSchedule::new(Root::default(), &context).unwrap();
+        let phase0 = schedule.forks()[0];
+        assert_eq!(phase0.fork, Fork::Phase0);
+
+        let block = Phase0SignedBeaconBlock::default();
+        let mut stream = vec![];
+        write_response_chunk(&mut stream, Some(phase0.digest), &block).unwrap();
+        let chunks =
+            read_response(&mut stream.as_slice(), Protocol::BeaconBlocksByRangeV2, Fork::Deneb)
+                .unwrap();
+        let recovered: SignedBeaconBlock = chunks[0].decode_for_fork(&schedule).unwrap();
+        assert_eq!(recovered, SignedBeaconBlock::Phase0(block));
+
+        let unknown = ResponseChunk::Success { context: Some([0xff; 4]), encoding: vec![] };
+        let result = unknown.decode_for_fork::<SignedBeaconBlock>(&schedule);
+        assert!(matches!(result, Err(CodecError::UnknownForkDigest(_))));
+    }
+
+    #[test]
+    fn test_fork_typed() {
+        let update = crate::altair::mainnet::LightClientOptimisticUpdate {
+            signature_slot: 12,
+            ..Default::default()
+        };
+        let encoding = serialize(&update).unwrap();
+        for fork in [Fork::Altair, Fork::Bellatrix] {
+            let recovered =
+                crate::altair::mainnet::LightClientOptimisticUpdate::deserialize_for_fork(
+                    fork, &encoding,
+                )
+                .unwrap();
+            assert_eq!(recovered.signature_slot, 12);
+        }
+        let result = crate::altair::mainnet::LightClientOptimisticUpdate::deserialize_for_fork(
+            Fork::Capella,
+            &encoding,
+        );
+        assert!(matches!(result, Err(CodecError::UnsupportedFork(Fork::Capella))));
+
+        let bootstrap = crate::deneb::mainnet::LightClientBootstrap::default();
+        let encoding = serialize(&bootstrap).unwrap();
+        for fork in [Fork::Deneb, Fork::Electra, Fork::Fulu] {
+            crate::deneb::mainnet::LightClientBootstrap::deserialize_for_fork(fork, &encoding)
+                .unwrap();
+        }
+        let result =
+            crate::capella::mainnet::LightClientBootstrap::deserialize_for_fork(Fork::Deneb, &[]);
+        assert!(matches!(result, Err(CodecError::UnsupportedFork(Fork::Deneb))));
+
+        let block = crate::electra::mainnet::SignedBeaconBlock::default();
+        let encoding = serialize(&block).unwrap();
+        for fork in [Fork::Electra, Fork::Fulu] {
+            let recovered =
+                crate::electra::mainnet::SignedBeaconBlock::deserialize_for_fork(fork, &encoding)
+                    .unwrap();
+            assert_eq!(recovered, block);
+        }
+        let result = SignedBeaconBlock::deserialize_for_fork(Fork::Electra, &encoding);
+        assert!(matches!(result, Err(CodecError::UnsupportedFork(Fork::Electra))));
+
+        // blob sidecars are not served from Fulu
+        let sidecar = crate::deneb::mainnet::BlobSidecar::default();
+        let encoding = serialize(&sidecar).unwrap();
+        let result =
+            crate::deneb::mainnet::BlobSidecar::deserialize_for_fork(Fork::Fulu, &encoding);
+        assert!(matches!(result, Err(CodecError::UnsupportedFork(Fork::Fulu))));
+    }
+}
@@ -0,0 +1,95 @@
+//! SSZ types for the requests of the req/resp protocols.
+use crate::{
+    deneb::{networking::MAX_REQUEST_BLOB_SIDECARS, BlobIdentifier},
+    phase0::networking::MAX_REQUEST_BLOCKS,
+    primitives::{Epoch, ForkDigest, Root, Slot},
+    ssz::prelude::*,
+};
+
+pub const MAX_ERROR_MESSAGE_LENGTH: usize = 256;
+
+pub type ErrorMessage = ByteList<MAX_ERROR_MESSAGE_LENGTH>;
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct Status {
+    pub fork_digest: ForkDigest,
+    pub finalized_root: Root,
+    #[serde(with = "crate::serde::as_str")]
+    pub finalized_epoch: Epoch,
+    pub head_root: Root,
+    #[serde(with = "crate::serde::as_str")]
+    pub head_slot: Slot,
+}
+
+/// Reasons for a `Goodbye` message defined in the spec; clients may send others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoodbyeReason {
+    ClientShutdown,
+    IrrelevantNetwork,
+    FaultOrError,
+    Other(u64),
+}
+
+impl From<u64> for GoodbyeReason {
+    fn from(value: u64) -> Self {
+        match value {
+            1 => Self::ClientShutdown,
+            2 => Self::IrrelevantNetwork,
+            3 => Self::FaultOrError,
+            other => Self::Other(other),
+        }
+    }
+}
+
+impl From<GoodbyeReason> for u64 {
+    fn from(value: GoodbyeReason) -> Self {
+        match value {
+            GoodbyeReason::ClientShutdown => 1,
+            GoodbyeReason::IrrelevantNetwork => 2,
+            GoodbyeReason::FaultOrError => 3,
+            GoodbyeReason::Other(other) => other,
+        }
+    }
+}
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct BeaconBlocksByRangeRequest {
+    #[serde(with = "crate::serde::as_str")]
+    pub start_slot: Slot,
+    #[serde(with = "crate::serde::as_str")]
+    pub count: u64,
+    // NOTE: deprecated, must be set to 1
+    #[serde(with = "crate::serde::as_str")]
+    pub step: u64,
+}
+
+// NOTE: bounded by `MAX_REQUEST_BLOCKS_DENEB` from Deneb, see `Protocol::max_response_chunks`
+pub type BeaconBlocksByRootRequest = List<Root, MAX_REQUEST_BLOCKS>;
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct BlobSidecarsByRangeRequest {
+    #[serde(with = "crate::serde::as_str")]
+    pub start_slot: Slot,
+    #[serde(with = "crate::serde::as_str")]
+    pub count: u64,
+}
+
+pub type BlobSidecarsByRootRequest = List<BlobIdentifier, MAX_REQUEST_BLOB_SIDECARS>;
+
+pub type LightClientBootstrapRequest = Root;
+
+#[derive(
+    Default, Debug, Clone, SimpleSerialize, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
+pub struct LightClientUpdatesByRangeRequest {
+    #[serde(with = "crate::serde::as_str")]
+    pub start_period: u64,
+    #[serde(with = "crate::serde::as_str")]
+    pub count: u64,
+}
@@ -0,0 +1,13 @@
+//! The req/resp protocols of the consensus p2p network with the `ssz_snappy` encoding.
+//!
+//! The codec works over any `std::io::Read` or `std::io::Write` so it can be used with
+//! any transport that provides a byte stream per request.
+pub mod codec;
+pub mod messages;
+pub mod protocol;
+
+pub use codec::{
+    read_request, read_response, read_response_chunk, write_error_chunk, write_request,
+    write_response_chunk, CodecError, ForkTyped, ResponseChunk, ResponseCode,
+};
+pub use protocol::Protocol;
@@ -0,0 +1,127 @@
+use crate::{
+    deneb::networking::{MAX_REQUEST_BLOB_SIDECARS, MAX_REQUEST_BLOCKS_DENEB},
+    networking::req_resp::CodecError,
+    phase0::networking::MAX_REQUEST_BLOCKS,
+    Fork,
+};
+use std::{fmt, str::FromStr};
+
+const PROTOCOL_PREFIX: &str = "/eth2/beacon_chain/req";
+const SSZ_SNAPPY_ENCODING: &str = "ssz_snappy";
+
+pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: usize = 128;
+
+/// The req/resp protocols of the consensus p2p network, one variant per protocol version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    Status,
+    Goodbye,
+    Ping,
+    MetaDataV1,
+    MetaDataV2,
+    MetaDataV3,
+    BeaconBlocksByRangeV2,
+    BeaconBlocksByRootV2,
+    BlobSidecarsByRange,
+    BlobSidecarsByRoot,
+    LightClientBootstrap,
+    LightClientUpdatesByRange,
+    LightClientFinalityUpdate,
+    LightClientOptimisticUpdate,
+}
+
+impl Protocol {
+    fn name_and_version(&self) -> (&'static str, u8) {
+        match self {
+            Self::Status => ("status", 1),
+            Self::Goodbye => ("goodbye", 1),
+            Self::Ping => ("ping", 1),
+            Self::MetaDataV1 => ("metadata", 1),
+            Self::MetaDataV2 => ("metadata", 2),
+            Self::MetaDataV3 => ("metadata", 3),
+            Self::BeaconBlocksByRangeV2 => ("beacon_blocks_by_range", 2),
+            Self::BeaconBlocksByRootV2 => ("beacon_blocks_by_root", 2),
+            Self::BlobSidecarsByRange => ("blob_sidecars_by_range", 1),
+            Self::BlobSidecarsByRoot => ("blob_sidecars_by_root", 1),
+            Self::LightClientBootstrap => ("light_client_bootstrap", 1),
+            Self::LightClientUpdatesByRange => ("light_client_updates_by_range", 1),
+            Self::LightClientFinalityUpdate => ("light_client_finality_update", 1),
+            Self::LightClientOptimisticUpdate => ("light_client_optimistic_update", 1),
+        }
+    }
+
+    /// Whether the request carries a payload; some protocols are requested with an empty stream.
+    pub fn has_request_payload(&self) -> bool {
+        !matches!(
+            self,
+            Self::MetaDataV1 |
+                Self::MetaDataV2 |
+                Self::MetaDataV3 |
+                Self::LightClientFinalityUpdate |
+                Self::LightClientOptimisticUpdate
+        )
+    }
+
+    /// Whether successful response chunks are prefixed with the fork digest of their payload.
+    pub fn has_context_bytes(&self) -> bool {
+        matches!(
+            self,
+            Self::BeaconBlocksByRangeV2 |
+                Self::BeaconBlocksByRootV2 |
+                Self::BlobSidecarsByRange |
+                Self::BlobSidecarsByRoot |
+                Self::LightClientBootstrap |
+                Self::LightClientUpdatesByRange |
+                Self::LightClientFinalityUpdate |
+                Self::LightClientOptimisticUpdate
+        )
+    }
+
+    /// Maximum number of response chunks for a single request while `fork` is current.
+    pub fn max_response_chunks(&self, fork: Fork) -> usize {
+        match self {
+            Self::BeaconBlocksByRangeV2 | Self::BeaconBlocksByRootV2 => match fork {
+                Fork::Phase0 | Fork::Altair | Fork::Bellatrix | Fork::Capella => MAX_REQUEST_BLOCKS,
+                Fork::Deneb | Fork::Electra | Fork::Fulu => MAX_REQUEST_BLOCKS_DENEB,
+            },
+            Self::BlobSidecarsByRange | Self::BlobSidecarsByRoot => MAX_REQUEST_BLOB_SIDECARS,
+            Self::LightClientUpdatesByRange => MAX_REQUEST_LIGHT_CLIENT_UPDATES,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for Protocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (name, version) = self.name_and_version();
+        write!(f, "{PROTOCOL_PREFIX}/{name}/{version}/{SSZ_SNAPPY_ENCODING}")
+    }
+}
+
+const ALL_PROTOCOLS: [Protocol; 14] = [
+    Protocol::Status,
+    Protocol::Goodbye,
+    Protocol::Ping,
+    Protocol::MetaDataV1,
+    Protocol::MetaDataV2,
+    Protocol::MetaDataV3,
+    Protocol::BeaconBlocksByRangeV2,
+    Protocol::BeaconBlocksByRootV2,
+    Protocol::BlobSidecarsByRange,
+    Protocol::BlobSidecarsByRoot,
+    Protocol::LightClientBootstrap,
+    Protocol::LightClientUpdatesByRange,
+    Protocol::LightClientFinalityUpdate,
+    Protocol::LightClientOptimisticUpdate,
+];
+
+impl FromStr for Protocol {
+    type Err = CodecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ALL_PROTOCOLS
+            .into_iter()
+            .find(|protocol| protocol.to_string() == s)
+            .ok_or_else(|| CodecError::UnknownProtocol(s.to_string()))
+    }
+}
@@ -0,0 +1,1607 @@
+//! Gossip validation for the `deneb` fork.
+//!
+//! The `state` given to each function should be a state for the epoch of the message (e.g. the
+//! head state, advanced to the message's slot if needed) so that the committees, proposers and
+//! sync committees it implies are those the message is checked against.
+use crate::{
+    altair::validator::is_sync_committee_aggregator,
+    crypto::{fast_aggregate_verify, hash, kzg::verify_blob_kzg_proof},
+    deneb::{
+        compute_domain, compute_epoch_at_slot, compute_start_slot_at_epoch,
+        compute_timestamp_at_slot, get_beacon_committee, get_beacon_proposer_index,
+        get_committee_count_per_slot, get_current_epoch, get_domain, get_indexed_attestation,
+        is_active_validator, is_slashable_attestation_data, is_slashable_validator,
+        is_valid_indexed_attestation, networking::BLOB_SIDECAR_SUBNET_COUNT,
+        verify_blob_sidecar_inclusion_proof, Attestation, AttesterSlashing, BeaconState,
+        BlobSidecar, ProposerSlashing, SignedAggregateAndProof, SignedBeaconBlock,
+        SignedBlsToExecutionChange, SignedContributionAndProof, SignedVoluntaryExit,
+        SyncAggregatorSelectionData, SYNC_COMMITTEE_SUBNET_COUNT,
+    },
+    domains::DomainType,
+    error::{
+        InvalidAttesterSlashing, InvalidBlsToExecutionChange, InvalidOperation,
+        InvalidProposerSlashing, InvalidVoluntaryExit,
+    },
+    networking::validation::{
+        current_slot_range, ignore, reject, reject_operation, IgnoreReason, RejectReason,
+        SeenCache, ValidationResult,
+    },
+    phase0::{networking::ATTESTATION_SUBNET_COUNT, validator::is_aggregator},
+    primitives::{
+        BlobIndex, BlsPublicKey, Slot, ValidatorIndex, BLS_WITHDRAWAL_PREFIX, FAR_FUTURE_EPOCH,
+    },
+    signing::{compute_signing_root, verify_signed_data},
+    ssz::prelude::*,
+    state_transition::Context,
+    Error,
+};
+use std::{collections::HashSet, time::Duration};
+
+pub fn compute_subnet_for_attestation(
+    committees_per_slot: usize,
+    slot: Slot,
+    committee_index: usize,
+    context: &Context,
+) -> u64 {
+    let slots_since_epoch_start = (slot % context.slots_per_epoch) as usize;
+    let committees_since_epoch_start = committees_per_slot * slots_since_epoch_start;
+    ((committees_since_epoch_start + committee_index) % ATTESTATION_SUBNET_COUNT) as u64
+}
+
+pub fn compute_subnet_for_blob_sidecar(blob_index: BlobIndex) -> u64 {
+    (blob_index % BLOB_SIDECAR_SUBNET_COUNT) as u64
+}
+
+// Checks the propagation range of attestations, as extended in `deneb` to the current and
+// previous epochs.
+fn check_attestation_slot(
+    slot: Slot,
+    genesis_time: u64,
+    now: Duration,
+    context: &Context,
+) -> Option<IgnoreReason> {
+    let (earliest_slot, latest_slot) = current_slot_range(genesis_time, now, context);
+    if slot > latest_slot {
+        return Some(IgnoreReason::FutureSlot { slot, current_slot: latest_slot })
+    }
+    let earliest_epoch = compute_epoch_at_slot(earliest_slot, context);
+    if compute_epoch_at_slot(slot, context) + 1 < earliest_epoch {
+        return Some(IgnoreReason::PastSlot { slot, current_slot: earliest_slot })
+    }
+    None
+}
+
+fn get_sync_subcommittee_pubkeys<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    subcommittee_index: u64,
+    context: &Context,
+) -> &[BlsPublicKey] {
+    // Use the state's next sync committee if the next slot is in the next sync committee period
+    let current_period =
+        get_current_epoch(state, context) / context.epochs_per_sync_committee_period;
+    let next_slot_epoch = compute_epoch_at_slot(state.slot + 1, context);
+    let sync_committee =
+        if current_period == next_slot_epoch / context.epochs_per_sync_committee_period {
+            &state.current_sync_committee
+        } else {
+            &state.next_sync_committee
+        };
+
+    let sync_subcommittee_size = SYNC_COMMITTEE_SIZE / SYNC_COMMITTEE_SUBNET_COUNT;
+    let i = subcommittee_index as usize * sync_subcommittee_size;
+    &sync_committee.public_keys[i..i + sync_subcommittee_size]
+}
+
+/// Validates a block received on the `beacon_block` topic at time `now` (since the unix epoch).
+pub fn validate_beacon_block<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const MAX_BYTES_PER_TRANSACTION: usize,
+    const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+    const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+    const MAX_PROPOSER_SLASHINGS: usize,
+    const MAX_ATTESTER_SLASHINGS: usize,
+    const MAX_ATTESTATIONS: usize,
+    const MAX_DEPOSITS: usize,
+    const MAX_VOLUNTARY_EXITS: usize,
+    const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+    const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+>(
+    signed_block: &SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >,
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    seen: &mut SeenCache,
+    now: Duration,
+    context: &Context,
+) -> Result<ValidationResult, Error> {
+    let block = &signed_block.message;
+    let (_, current_slot) = current_slot_range(state.genesis_time, now, context);
+    if block.slot > current_slot {
+        return ignore(IgnoreReason::FutureSlot { slot: block.slot, current_slot })
+    }
+    let finalized_slot = compute_start_slot_at_epoch(state.finalized_checkpoint.epoch, context);
+    if block.slot <= finalized_slot {
+        return ignore(IgnoreReason::FinalizedSlot { slot: block.slot, finalized_slot })
+    }
+    if seen.has_block(block.slot, block.proposer_index) {
+        return ignore(IgnoreReason::DuplicateBlock {
+            slot: block.slot,
+            proposer_index: block.proposer_index,
+        })
+    }
+
+    if state.slot != block.slot {
+        return ignore(IgnoreReason::UnknownProposerShuffling {
+            slot: block.slot,
+            state_slot: state.slot,
+        })
+    }
+    let expected = get_beacon_proposer_index(state, context)?;
+    if block.proposer_index != expected {
+        return reject(RejectReason::UnexpectedProposer {
+            proposer_index: block.proposer_index,
+            expected,
+        })
+    }
+    let Some(proposer) = state.validators.get(block.proposer_index) else {
+        return reject(RejectReason::InvalidValidatorIndex(block.proposer_index))
+    };
+    let epoch = compute_epoch_at_slot(block.slot, context);
+    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
+    if verify_signed_data(block, &signed_block.signature, &proposer.public_key, domain).is_err() {
+        return reject(RejectReason::InvalidSignature("proposer"))
+    }
+
+    let commitments = block.body.blob_kzg_commitments.len();
+    if commitments > context.max_blobs_per_block {
+        return reject(RejectReason::TooManyBlobCommitments {
+            count: commitments,
+            limit: context.max_blobs_per_block,
+        })
+    }
+    let expected = compute_timestamp_at_slot(state, block.slot, context)?;
+    let provided = block.body.execution_payload.timestamp;
+    if provided != expected {
+        return reject(RejectReason::InvalidTimestamp { provided, expected })
+    }
+
+    seen.insert_block(block.slot, block.proposer_index);
+    Ok(ValidationResult::Accept)
+}
+
+/// Validates an aggregate received on the `beacon_aggregate_and_proof` topic at time `now`
+/// (since the unix epoch).
+pub fn validate_aggregate_and_proof<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    signed_aggregate_and_proof: &SignedAggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>,
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    seen: &mut SeenCache,
+    now: Duration,
+    context: &Context,
+) -> Result<ValidationResult, Error> {
+    let aggregate_and_proof = &signed_aggregate_and_proof.message;
+    let aggregator_index = aggregate_and_proof.aggregator_index;
+    let aggregate = &aggregate_and_proof.aggregate;
+    let data = &aggregate.data;
+    let epoch = compute_epoch_at_slot(data.slot, context);
+
+    if let Some(reason) = check_attestation_slot(data.slot, state.genesis_time, now, context) {
+        return ignore(reason)
+    }
+    let committee_count = get_committee_count_per_slot(state, data.target.epoch, context);
+    if data.index >= committee_count {
+        return reject(RejectReason::InvalidCommitteeIndex { index: data.index, committee_count })
+    }
+    if data.target.epoch != epoch {
+        return reject(RejectReason::TargetEpochMismatch {
+            slot: data.slot,
+            target_epoch: data.target.epoch,
+        })
+    }
+    if aggregate.aggregation_bits.not_any() {
+        return reject(RejectReason::NoParticipants)
+    }
+    let committee = get_beacon_committee(state, data.slot, data.index, context)?;
+    if aggregate.aggregation_bits.len() != committee.len() {
+        return reject(RejectReason::AggregationBitsLength {
+            expected: committee.len(),
+            provided: aggregate.aggregation_bits.len(),
+        })
+    }
+
+    let data_root = data.hash_tree_root()?;
+    let bits = aggregate.aggregation_bits.iter().map(|bit| *bit).collect::<Vec<_>>();
+    if seen.has_aggregate(data.slot, data_root, &bits) {
+        return ignore(IgnoreReason::DuplicateAggregate)
+    }
+    if seen.has_aggregator(aggregator_index, data.target.epoch) {
+        return ignore(IgnoreReason::DuplicateAggregator {
+            aggregator_index,
+            epoch: data.target.epoch,
+        })
+    }
+
+    if !is_aggregator(committee.len(), &aggregate_and_proof.selection_proof, context) {
+        return reject(RejectReason::NotAggregator(aggregator_index))
+    }
+    if !committee.contains(&aggregator_index) {
+        return reject(RejectReason::AggregatorNotInCommittee(aggregator_index))
+    }
+    let Some(aggregator) = state.validators.get(aggregator_index) else {
+        return reject(RejectReason::InvalidValidatorIndex(aggregator_index))
+    };
+    let public_key = &aggregator.public_key;
+    let domain = get_domain(state, DomainType::SelectionProof, Some(epoch), context)?;
+    if verify_signed_data(&data.slot, &aggregate_and_proof.selection_proof, public_key, domain)
+        .is_err()
+    {
+        return reject(RejectReason::InvalidSignature("selection proof"))
+    }
+    let domain = get_domain(state, DomainType::AggregateAndProof, Some(epoch), context)?;
+    let signature = &signed_aggregate_and_proof.signature;
+    if verify_signed_data(aggregate_and_proof, signature, public_key, domain).is_err() {
+        return reject(RejectReason::InvalidSignature("aggregator"))
+    }
+    let indexed_attestation = get_indexed_attestation(state, aggregate, context)?;
+    if is_valid_indexed_attestation(state, &indexed_attestation, context).is_err() {
+        return reject(RejectReason::InvalidSignature("aggregate"))
+    }
+
+    seen.insert_aggregate(data.slot, data_root, bits);
+    seen.insert_aggregator(aggregator_index, data.target.epoch);
+    Ok(ValidationResult::Accept)
+}
+
+/// Validates an unaggregated attestation received on the `beacon_attestation_{subnet_id}` topic
+/// at time `now` (since the unix epoch).
+pub fn validate_attestation<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    attestation: &Attestation<MAX_VALIDATORS_PER_COMMITTEE>,
+    subnet_id: u64,
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    seen: &mut SeenCache,
+    now: Duration,
+    context: &Context,
+) -> Result<ValidationResult, Error> {
+    let data = &attestation.data;
+    let committee_count = get_committee_count_per_slot(state, data.target.epoch, context);
+    if data.index >= committee_count {
+        return reject(RejectReason::InvalidCommitteeIndex { index: data.index, committee_count })
+    }
+    let expected = compute_subnet_for_attestation(committee_count, data.slot, data.index, context);
+    if subnet_id != expected {
+        return reject(RejectReason::WrongSubnet { subnet_id, expected })
+    }
+    if let Some(reason) = check_attestation_slot(data.slot, state.genesis_time, now, context) {
+        return ignore(reason)
+    }
+    if data.target.epoch != compute_epoch_at_slot(data.slot, context) {
+        return reject(RejectReason::TargetEpochMismatch {
+            slot: data.slot,
+            target_epoch: data.target.epoch,
+        })
+    }
+    let participants = attestation.aggregation_bits.count_ones();
+    if participants != 1 {
+        return reject(RejectReason::NotSingleParticipant(participants))
+    }
+    let committee = get_beacon_committee(state, data.slot, data.index, context)?;
+    if attestation.aggregation_bits.len() != committee.len() {
+        return reject(RejectReason::AggregationBitsLength {
+            expected: committee.len(),
+            provided: attestation.aggregation_bits.len(),
+        })
+    }
+
+    let position = attestation.aggregation_bits.first_one().expect("has one participant");
+    let validator_index = committee[position];
+    if seen.has_attester(validator_index, data.target.epoch) {
+        return ignore(IgnoreReason::DuplicateAttestation {
+            validator_index,
+            epoch: data.target.epoch,
+        })
+    }
+    let indexed_attestation = get_indexed_attestation(state, attestation, context)?;
+    if is_valid_indexed_attestation(state, &indexed_attestation, context).is_err() {
+        return reject(RejectReason::InvalidSignature("attestation"))
+    }
+
+    seen.insert_attester(validator_index, data.target.epoch);
+    Ok(ValidationResult::Accept)
+}
+
+/// Validates a message received on the `voluntary_exit` topic.
+pub fn validate_voluntary_exit<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    signed_voluntary_exit: &SignedVoluntaryExit,
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    seen: &mut SeenCache,
+    context: &Context,
+) -> Result<ValidationResult, Error> {
+    let voluntary_exit = &signed_voluntary_exit.message;
+    let validator_index = voluntary_exit.validator_index;
+    if seen.has_voluntary_exit(validator_index) {
+        return ignore(IgnoreReason::DuplicateVoluntaryExit(validator_index))
+    }
+    let Some(validator) = state.validators.get(validator_index) else {
+        return reject_operation(InvalidOperation::VoluntaryExit(
+            InvalidVoluntaryExit::InvalidIndex(validator_index),
+        ))
+    };
+    let current_epoch = get_current_epoch(state, context);
+    if !is_active_validator(validator, current_epoch) {
+        return reject_operation(InvalidOperation::VoluntaryExit(
+            InvalidVoluntaryExit::InactiveValidator(current_epoch),
+        ))
+    }
+    if validator.exit_epoch != FAR_FUTURE_EPOCH {
+        return reject_operation(InvalidOperation::VoluntaryExit(
+            InvalidVoluntaryExit::ValidatorAlreadyExited {
+                index: validator_index,
+                epoch: validator.exit_epoch,
+            },
+        ))
+    }
+    if current_epoch < voluntary_exit.epoch {
+        return reject_operation(InvalidOperation::VoluntaryExit(InvalidVoluntaryExit::EarlyExit {
+            current_epoch,
+            exit_epoch: voluntary_exit.epoch,
+        }))
+    }
+    let minimum_time_active =
+        validator.activation_eligibility_epoch + context.shard_committee_period;
+    if current_epoch < minimum_time_active {
+        return reject_operation(InvalidOperation::VoluntaryExit(
+            InvalidVoluntaryExit::ValidatorIsNotActiveForLongEnough {
+                current_epoch,
+                minimum_time_active,
+            },
+        ))
+    }
+    let domain = compute_domain(
+        DomainType::VoluntaryExit,
+        Some(context.capella_fork_version),
+        Some(state.genesis_validators_root),
+        context,
+    )?;
+    let signature = &signed_voluntary_exit.signature;
+    if verify_signed_data(voluntary_exit, signature, &validator.public_key, domain).is_err() {
+        return reject(RejectReason::InvalidSignature("voluntary exit"))
+    }
+
+    seen.insert_voluntary_exit(validator_index);
+    Ok(ValidationResult::Accept)
+}
+
+/// Validates a message received on the `proposer_slashing` topic.
+pub fn validate_proposer_slashing<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    proposer_slashing: &ProposerSlashing,
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    seen: &mut SeenCache,
+    context: &Context,
+) -> Result<ValidationResult, Error> {
+    let header_1 = &proposer_slashing.signed_header_1.message;
+    let header_2 = &proposer_slashing.signed_header_2.message;
+    let proposer_index = header_1.proposer_index;
+    if seen.has_proposer_slashing(proposer_index) {
+        return ignore(IgnoreReason::DuplicateProposerSlashing(proposer_index))
+    }
+    if header_1.slot != header_2.slot {
+        return reject_operation(InvalidOperation::ProposerSlashing(
+            InvalidProposerSlashing::SlotMismatch(header_1.slot, header_2.slot),
+        ))
+    }
+    if header_1.proposer_index != header_2.proposer_index {
+        return reject_operation(InvalidOperation::ProposerSlashing(
+            InvalidProposerSlashing::ProposerMismatch(proposer_index, header_2.proposer_index),
+        ))
+    }
+    if header_1 == header_2 {
+        return reject_operation(InvalidOperation::ProposerSlashing(
+            InvalidProposerSlashing::HeadersAreEqual(header_1.clone()),
+        ))
+    }
+    let Some(proposer) = state.validators.get(proposer_index) else {
+        return reject_operation(InvalidOperation::ProposerSlashing(
+            InvalidProposerSlashing::InvalidIndex(proposer_index),
+        ))
+    };
+    if !is_slashable_validator(proposer, get_current_epoch(state, context)) {
+        return reject_operation(InvalidOperation::ProposerSlashing(
+            InvalidProposerSlashing::ProposerIsNotSlashable(proposer_index),
+        ))
+    }
+    let epoch = compute_epoch_at_slot(header_1.slot, context);
+    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
+    for signed_header in [&proposer_slashing.signed_header_1, &proposer_slashing.signed_header_2] {
+        let signature = &signed_header.signature;
+        if verify_signed_data(&signed_header.message, signature, &proposer.public_key, domain)
+            .is_err()
+        {
+            return reject(RejectReason::InvalidSignature("proposer"))
+        }
+    }
+
+    seen.insert_proposer_slashing(proposer_index);
+    Ok(ValidationResult::Accept)
+}
+
+/// Validates a message received on the `attester_slashing` topic.
+pub fn validate_attester_slashing<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    attester_slashing: &AttesterSlashing<MAX_VALIDATORS_PER_COMMITTEE>,
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    seen: &mut SeenCache,
+    context: &Context,
+) -> Result<ValidationResult, Error> {
+    let attestation_1 = &attester_slashing.attestation_1;
+    let attestation_2 = &attester_slashing.attestation_2;
+    let indices_1 = attestation_1.attesting_indices.iter().collect::<HashSet<_>>();
+    let slashed_indices = attestation_2
+        .attesting_indices
+        .iter()
+        .filter(|index| indices_1.contains(index))
+        .copied()
+        .collect::<Vec<ValidatorIndex>>();
+    if slashed_indices.is_empty() {
+        return reject_operation(InvalidOperation::AttesterSlashing(
+            InvalidAttesterSlashing::NoSlashings(slashed_indices),
+        ))
+    }
+    if seen.has_attester_slashings(&slashed_indices) {
+        return ignore(IgnoreReason::DuplicateAttesterSlashing)
+    }
+    if !is_slashable_attestation_data(&attestation_1.data, &attestation_2.data) {
+        return reject_operation(InvalidOperation::AttesterSlashing(
+            InvalidAttesterSlashing::NotSlashable(
+                Box::new(attestation_1.data.clone()),
+                Box::new(attestation_2.data.clone()),
+            ),
+        ))
+    }
+    for attestation in [attestation_1, attestation_2] {
+        match is_valid_indexed_attestation(state, attestation, context) {
+            Ok(()) => {}
+            Err(Error::InvalidBlock(err)) => return reject(RejectReason::InvalidOperation(err)),
+            Err(_) => return reject(RejectReason::InvalidSignature("attester slashing")),
+        }
+    }
+    // NOTE: the attesting indices are known to be in the state given the checks above
+    let current_epoch = get_current_epoch(state, context);
+    if !slashed_indices
+        .iter()
+        .any(|&index| is_slashable_validator(&state.validators[index], current_epoch))
+    {
+        return reject_operation(InvalidOperation::AttesterSlashing(
+            InvalidAttesterSlashing::NoSlashings(slashed_indices),
+        ))
+    }
+
+    seen.insert_attester_slashings(&slashed_indices);
+    Ok(ValidationResult::Accept)
+}
+
+/// Validates a message received on the `sync_committee_contribution_and_proof` topic at time
+/// `now` (since the unix epoch).
+pub fn validate_contribution_and_proof<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const SYNC_SUBCOMMITTEE_SIZE: usize,
+>(
+    signed_contribution_and_proof: &SignedContributionAndProof<SYNC_SUBCOMMITTEE_SIZE>,
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    seen: &mut SeenCache,
+    now: Duration,
+    context: &Context,
+) -> Result<ValidationResult, Error> {
+    let contribution_and_proof = &signed_contribution_and_proof.message;
+    let aggregator_index = contribution_and_proof.aggregator_index;
+    let contribution = &contribution_and_proof.contribution;
+    let slot = contribution.slot;
+    let subcommittee_index = contribution.subcommittee_index;
+
+    let (earliest_slot, latest_slot) = current_slot_range(state.genesis_time, now, context);
+    if slot > latest_slot {
+        return ignore(IgnoreReason::FutureSlot { slot, current_slot: latest_slot })
+    }
+    if slot < earliest_slot {
+        return ignore(IgnoreReason::PastSlot { slot, current_slot: earliest_slot })
+    }
+    if subcommittee_index >= SYNC_COMMITTEE_SUBNET_COUNT as u64 {
+        return reject(RejectReason::InvalidSubcommitteeIndex(subcommittee_index))
+    }
+    if contribution.aggregation_bits.not_any() {
+        return reject(RejectReason::NoParticipants)
+    }
+    if !is_sync_committee_aggregator(&contribution_and_proof.selection_proof, context) {
+        return reject(RejectReason::NotAggregator(aggregator_index))
+    }
+    let Some(aggregator) = state.validators.get(aggregator_index) else {
+        return reject(RejectReason::InvalidValidatorIndex(aggregator_index))
+    };
+    let public_key = &aggregator.public_key;
+    let subcommittee_public_keys =
+        get_sync_subcommittee_pubkeys(state, subcommittee_index, context);
+    if !subcommittee_public_keys.contains(public_key) {
+        return reject(RejectReason::AggregatorNotInCommittee(aggregator_index))
+    }
+
+    let block_root = contribution.beacon_block_root;
+    let bits = contribution.aggregation_bits.iter().map(|bit| *bit).collect::<Vec<_>>();
+    if seen.has_contribution(slot, block_root, subcommittee_index, &bits) {
+        return ignore(IgnoreReason::DuplicateContribution)
+    }
+    if seen.has_contribution_aggregator(aggregator_index, slot, subcommittee_index) {
+        return ignore(IgnoreReason::DuplicateContributionAggregator {
+            aggregator_index,
+            slot,
+            subcommittee_index,
+        })
+    }
+
+    let epoch = compute_epoch_at_slot(slot, context);
+    let selection_data = SyncAggregatorSelectionData { slot, subcommittee_index };
+    let domain = get_domain(state, DomainType::SyncCommitteeSelectionProof, Some(epoch), context)?;
+    let selection_proof = &contribution_and_proof.selection_proof;
+    if verify_signed_data(&selection_data, selection_proof, public_key, domain).is_err() {
+        return reject(RejectReason::InvalidSignature("selection proof"))
+    }
+    let domain = get_domain(state, DomainType::ContributionAndProof, Some(epoch), context)?;
+    let signature = &signed_contribution_and_proof.signature;
+    if verify_signed_data(contribution_and_proof, signature, public_key, domain).is_err() {
+        return reject(RejectReason::InvalidSignature("aggregator"))
+    }
+    let participant_public_keys = subcommittee_public_keys
+        .iter()
+        .zip(bits.iter())
+        .filter_map(|(public_key, bit)| if *bit { Some(public_key) } else { None })
+        .collect::<Vec<_>>();
+    let domain = get_domain(state, DomainType::SyncCommittee, Some(epoch), context)?;
+    let signing_root = compute_signing_root(&block_root, domain)?;
+    if fast_aggregate_verify(
+        &participant_public_keys,
+        signing_root.as_ref(),
+        &contribution.signature,
+    )
+    .is_err()
+    {
+        return reject(RejectReason::InvalidSignature("contribution"))
+    }
+
+    seen.insert_contribution(slot, block_root, subcommittee_index, bits);
+    seen.insert_contribution_aggregator(aggregator_index, slot, subcommittee_index);
+    Ok(ValidationResult::Accept)
+}
+
+/// Validates a message received on the `bls_to_execution_change` topic at time `now` (since the
+/// unix epoch).
+pub fn validate_bls_to_execution_change<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+>(
+    signed_address_change: &SignedBlsToExecutionChange,
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    seen: &mut SeenCache,
+    now: Duration,
+    context: &Context,
+) -> Result<ValidationResult, Error> {
+    let (_, current_slot) = current_slot_range(state.genesis_time, now, context);
+    let current_epoch = compute_epoch_at_slot(current_slot, context);
+    if current_epoch < context.capella_fork_epoch {
+        return ignore(IgnoreReason::PreCapella(current_epoch))
+    }
+    let address_change = &signed_address_change.message;
+    let validator_index = address_change.validator_index;
+    if seen.has_bls_to_execution_change(validator_index) {
+        return ignore(IgnoreReason::DuplicateBlsToExecutionChange(validator_index))
+    }
+    let Some(validator) = state.validators.get(validator_index) else {
+        return reject_operation(InvalidOperation::BlsToExecutionChange(
+            InvalidBlsToExecutionChange::ValidatorIndexOutOfBounds(validator_index),
+        ))
+    };
+    let withdrawal_credentials = &validator.withdrawal_credentials;
+    if withdrawal_credentials[0] != BLS_WITHDRAWAL_PREFIX {
+        return reject_operation(InvalidOperation::BlsToExecutionChange(
+            InvalidBlsToExecutionChange::WithdrawalCredentialsPrefix(withdrawal_credentials[0]),
+        ))
+    }
+    let public_key = &address_change.from_bls_public_key;
+    if withdrawal_credentials[1..] != hash(public_key.as_ref())[1..] {
+        return reject_operation(InvalidOperation::BlsToExecutionChange(
+            InvalidBlsToExecutionChange::PublicKeyMismatch(public_key.clone()),
+        ))
+    }
+    let domain = compute_domain(
+        DomainType::BlsToExecutionChange,
+        None,
+        Some(state.genesis_validators_root),
+        context,
+    )?;
+    let signature = &signed_address_change.signature;
+    if verify_signed_data(address_change, signature, public_key, domain).is_err() {
+        return reject(RejectReason::InvalidSignature("BLS to execution change"))
+    }
+
+    seen.insert_bls_to_execution_change(validator_index);
+    Ok(ValidationResult::Accept)
+}
+
+/// Validates a sidecar received on the `blob_sidecar_{subnet_id}` topic at time `now` (since the
+/// unix epoch).
+///
+/// `BlockBody` is the type of block body the sidecar's commitment inclusion proof is against.
+pub fn validate_blob_sidecar<
+    const SLOTS_PER_HISTORICAL_ROOT: usize,
+    const HISTORICAL_ROOTS_LIMIT: usize,
+    const ETH1_DATA_VOTES_BOUND: usize,
+    const VALIDATOR_REGISTRY_LIMIT: usize,
+    const EPOCHS_PER_HISTORICAL_VECTOR: usize,
+    const EPOCHS_PER_SLASHINGS_VECTOR: usize,
+    const MAX_VALIDATORS_PER_COMMITTEE: usize,
+    const SYNC_COMMITTEE_SIZE: usize,
+    const BYTES_PER_LOGS_BLOOM: usize,
+    const MAX_EXTRA_DATA_BYTES: usize,
+    const BYTES_PER_BLOB: usize,
+    const KZG_COMMITMENT_INCLUSION_PROOF_DEPTH: usize,
+    BlockBody: SimpleSerialize,
+>(
+    blob_sidecar: &BlobSidecar<BYTES_PER_BLOB, KZG_COMMITMENT_INCLUSION_PROOF_DEPTH>,
+    subnet_id: u64,
+    state: &BeaconState<
+        SLOTS_PER_HISTORICAL_ROOT,
+        HISTORICAL_ROOTS_LIMIT,
+        ETH1_DATA_VOTES_BOUND,
+        VALIDATOR_REGISTRY_LIMIT,
+        EPOCHS_PER_HISTORICAL_VECTOR,
+        EPOCHS_PER_SLASHINGS_VECTOR,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+    >,
+    seen: &mut SeenCache,
+    now: Duration,
+    context: &Context,
+) -> Result<ValidationResult, Error> {
+    let index = blob_sidecar.index;
+    if index >= context.max_blobs_per_block {
+        return reject(RejectReason::InvalidBlobIndex { index, limit: context.max_blobs_per_block })
+    }
+    let expected = compute_subnet_for_blob_sidecar(index);
+    if subnet_id != expected {
+        return reject(RejectReason::WrongSubnet { subnet_id, expected })
+    }
+
+    let signed_header = &blob_sidecar.signed_block_header;
+    let header = &signed_header.message;
+    let (_, current_slot) = current_slot_range(state.genesis_time, now, context);
+    if header.slot > current_slot {
+        return ignore(IgnoreReason::FutureSlot { slot: header.slot, current_slot })
+    }
+    let finalized_slot = compute_start_slot_at_epoch(state.finalized_checkpoint.epoch, context);
+    if header.slot <= finalized_slot {
+        return ignore(IgnoreReason::FinalizedSlot { slot: header.slot, finalized_slot })
+    }
+    if seen.has_blob_sidecar(header.slot, header.proposer_index, index) {
+        return ignore(IgnoreReason::DuplicateBlobSidecar {
+            slot: header.slot,
+            proposer_index: header.proposer_index,
+            index,
+        })
+    }
+
+    if state.slot != header.slot {
+        return ignore(IgnoreReason::UnknownProposerShuffling {
+            slot: header.slot,
+            state_slot: state.slot,
+        })
+    }
+    let expected = get_beacon_proposer_index(state, context)?;
+    if header.proposer_index != expected {
+        return reject(RejectReason::UnexpectedProposer {
+            proposer_index: header.proposer_index,
+            expected,
+        })
+    }
+    let Some(proposer) = state.validators.get(header.proposer_index) else {
+        return reject(RejectReason::InvalidValidatorIndex(header.proposer_index))
+    };
+    let epoch = compute_epoch_at_slot(header.slot, context);
+    let domain = get_domain(state, DomainType::BeaconProposer, Some(epoch), context)?;
+    if verify_signed_data(header, &signed_header.signature, &proposer.public_key, domain).is_err() {
+        return reject(RejectReason::InvalidSignature("proposer"))
+    }
+    if verify_blob_sidecar_inclusion_proof::<
+        KZG_COMMITMENT_INCLUSION_PROOF_DEPTH,
+        BYTES_PER_BLOB,
+        BlockBody,
+    >(blob_sidecar)
+    .is_err()
+    {
+        return reject(RejectReason::InvalidInclusionProof)
+    }
+    if verify_blob_kzg_proof(
+        &blob_sidecar.blob,
+        &blob_sidecar.kzg_commitment,
+        &blob_sidecar.kzg_proof,
+        &context.kzg_settings,
+    )
+    .is_err()
+    {
+        return reject(RejectReason::InvalidKzgProof)
+    }
+
+    seen.insert_blob_sidecar(header.slot, header.proposer_index, index);
+    Ok(ValidationResult::Accept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        crypto::{
+            aggregate,
+            kzg::{blob_to_kzg_commitment, compute_blob_kzg_proof},
+            SecretKey,
+        },
+        deneb::minimal::{
+            self as spec, AttestationData, BeaconBlockHeader, Checkpoint, SignedBeaconBlockHeader,
+        },
+        genesis::{self, bls_withdrawal_credentials, interop_secret_keys, GenesisValidator},
+        primitives::{BlsSignature, Epoch, Root},
+        signing::sign_with_domain,
+    };
+
+    const VALIDATOR_COUNT: usize = 64;
+    const GENESIS_TIME: u64 = 1_600_000_000;
+
+    struct Harness {
+        context: Context,
+        secret_keys: Vec<SecretKey>,
+        state: spec::BeaconState,
+    }
+
+    impl Harness {
+        // A minimal `deneb` genesis state advanced (without processing) to `slot`.
+        fn new(slot: Slot) -> Self {
+            let mut context = Context::for_minimal();
+            context.capella_fork_epoch = 0;
+            context.deneb_fork_epoch = 0;
+            let secret_keys = interop_secret_keys(VALIDATOR_COUNT).unwrap();
+            let validators = secret_keys.iter().map(|secret_key| {
+                let credentials = bls_withdrawal_credentials(&secret_key.public_key());
+                GenesisValidator::from_secret_key(
+                    secret_key,
+                    credentials,
+                    context.max_effective_balance,
+                    &context,
+                )
+                .unwrap()
+            });
+            let builder = genesis::GenesisBuilder::new(&context)
+                .with_validators(validators.collect::<Vec<_>>())
+                .with_genesis_time(GENESIS_TIME);
+            let mut state = genesis::minimal::build_deneb(&builder, Default::default()).unwrap();
+            state.slot = slot;
+            Self { context, secret_keys, state }
+        }
+
+        fn now(&self, slot: Slot) -> Duration {
+            Duration::from_secs(GENESIS_TIME + slot * self.context.seconds_per_slot)
+        }
+
+        fn domain(&self, domain_type: DomainType, epoch: Epoch) -> crate::primitives::Domain {
+            get_domain(&self.state, domain_type, Some(epoch), &self.context).unwrap()
+        }
+
+        fn sign<T: HashTreeRoot>(
+            &self,
+            data: &T,
+            index: ValidatorIndex,
+            domain_type: DomainType,
+            epoch: Epoch,
+        ) -> BlsSignature {
+            let domain = self.domain(domain_type, epoch);
+            sign_with_domain(data, &self.secret_keys[index], domain).unwrap()
+        }
+
+        fn proposer(&self) -> ValidatorIndex {
+            get_beacon_proposer_index(&self.state, &self.context).unwrap()
+        }
+
+        fn attestation(&self, slot: Slot, bits: &[bool]) -> spec::Attestation {
+            let context = &self.context;
+            let epoch = compute_epoch_at_slot(slot, context);
+            let data = AttestationData {
+                slot,
+                index: 0,
+                target: Checkpoint { epoch, ..Default::default() },
+                ..Default::default()
+            };
+            let committee = get_beacon_committee(&self.state, slot, 0, context).unwrap();
+            let signatures = committee
+                .iter()
+                .zip(bits)
+                .filter(|(_, bit)| **bit)
+                .map(|(index, _)| self.sign(&data, *index, DomainType::BeaconAttester, epoch))
+                .collect::<Vec<_>>();
+            spec::Attestation {
+                aggregation_bits: Bitlist::try_from(bits).unwrap(),
+                data,
+                signature: aggregate(&signatures).unwrap_or_default(),
+            }
+        }
+
+        fn indexed_attestation(
+            &self,
+            indices: &[ValidatorIndex],
+            beacon_block_root: Root,
+        ) -> spec::IndexedAttestation {
+            let data = AttestationData {
+                beacon_block_root,
+                target: Checkpoint { epoch: 0, ..Default::default() },
+                ..Default::default()
+            };
+            let signatures = indices
+                .iter()
+                .map(|index| self.sign(&data, *index, DomainType::BeaconAttester, 0))
+                .collect::<Vec<_>>();
+            spec::IndexedAttestation {
+                attesting_indices: List::try_from(indices.to_vec()).unwrap(),
+                data,
+                signature: aggregate(&signatures).unwrap(),
+            }
+        }
+
+        fn signed_header(&self, header: BeaconBlockHeader) -> SignedBeaconBlockHeader {
+            let epoch = compute_epoch_at_slot(header.slot, &self.context);
+            let signature =
+                self.sign(&header, header.proposer_index, DomainType::BeaconProposer, epoch);
+            SignedBeaconBlockHeader { message: header, signature }
+        }
+    }
+
+    fn root(byte: u8) -> Root {
+        Root::try_from([byte; 32].as_ref()).unwrap()
+    }
+
+    fn assert_ignored(result: Result<ValidationResult, Error>) {
+        assert!(matches!(result, Ok(ValidationResult::Ignore(..))), "{result:?}");
+    }
+
+    fn assert_rejected(result: Result<ValidationResult, Error>) {
+        assert!(matches!(result, Ok(ValidationResult::Reject(..))), "{result:?}");
+    }
+
+    // Builds the inclusion proof of the first commitment in `body`, which has exactly one.
+    fn commitment_inclusion_proof(body: &spec::BeaconBlockBody) -> Vec<Root> {
+        let hash_pair = |a: &Root, b: &Root| {
+            Root::try_from(hash([a.as_ref(), b.as_ref()].concat()).as_ref()).unwrap()
+        };
+        let mut zero_hashes = vec![Root::default()];
+        for i in 0..4 {
+            zero_hashes.push(hash_pair(&zero_hashes[i], &zero_hashes[i]));
+        }
+        let fields = [
+            body.randao_reveal.hash_tree_root(),
+            body.eth1_data.hash_tree_root(),
+            body.graffiti.hash_tree_root(),
+            body.proposer_slashings.hash_tree_root(),
+            body.attester_slashings.hash_tree_root(),
+            body.attestations.hash_tree_root(),
+            body.deposits.hash_tree_root(),
+            body.voluntary_exits.hash_tree_root(),
+            body.sync_aggregate.hash_tree_root(),
+            body.execution_payload.hash_tree_root(),
+            body.bls_to_execution_changes.hash_tree_root(),
+        ]
+        .map(Result::unwrap);
+        let mut length = [0u8; 32];
+        length[0] = 1;
+
+        // the path to the commitment within the list, its length and then the body's fields
+        let mut proof = zero_hashes[..4].to_vec();
+        proof.push(Root::try_from(length.as_ref()).unwrap());
+        proof.push(fields[10]);
+        proof.push(hash_pair(&fields[8], &fields[9]));
+        proof.push(zero_hashes[2]);
+        let layer = fields[..8].chunks(2).map(|pair| hash_pair(&pair[0], &pair[1]));
+        let layer = layer.collect::<Vec<_>>();
+        let layer = layer.chunks(2).map(|pair| hash_pair(&pair[0], &pair[1])).collect::<Vec<_>>();
+        proof.push(hash_pair(&layer[0], &layer[1]));
+        proof
+    }
+
+    #[test]
+    fn test_attestation_subnets() {
+        let context = Context::for_mainnet();
+        assert_eq!(compute_subnet_for_attestation(4, 0, 0, &context), 0);
+        assert_eq!(compute_subnet_for_attestation(4, 1, 3, &context), 7);
+        // subnets wrap around within an epoch
+        assert_eq!(compute_subnet_for_attestation(4, 16, 1, &context), 1);
+        assert_eq!(compute_subnet_for_attestation(4, 33, 0, &context), 4);
+        assert_eq!(compute_subnet_for_blob_sidecar(5), 5);
+        assert_eq!(compute_subnet_for_blob_sidecar(7), 1);
+    }
+
+    #[test]
+    fn test_attestation_slot_range() {
+        let context = Context::for_mainnet();
+        let genesis_time = 1_000;
+        let slot_duration = Duration::from_secs(context.seconds_per_slot);
+        let now = Duration::from_secs(genesis_time) + slot_duration * 70;
+
+        assert!(check_attestation_slot(70, genesis_time, now, &context).is_none());
+        // current and previous epochs are in range
+        assert!(check_attestation_slot(32, genesis_time, now, &context).is_none());
+        assert!(matches!(
+            check_attestation_slot(31, genesis_time, now, &context),
+            Some(IgnoreReason::PastSlot { .. })
+        ));
+        assert!(matches!(
+            check_attestation_slot(71, genesis_time, now, &context),
+            Some(IgnoreReason::FutureSlot { .. })
+        ));
+        // slots starting within the clock disparity are accepted
+        let now = now + slot_duration - Duration::from_millis(100);
+        assert!(check_attestation_slot(71, genesis_time, now, &context).is_none());
+    }
+
+    #[test]
+    fn test_validate_beacon_block() {
+        let harness = Harness::new(1);
+        let context = &harness.context;
+        let now = harness.now(1);
+        let mut seen = SeenCache::default();
+
+        let sign = |block: spec::BeaconBlock| {
+            let signature =
+                harness.sign(&block, block.proposer_index, DomainType::BeaconProposer, 0);
+            spec::SignedBeaconBlock { message: block, signature }
+        };
+        let mut block = spec::BeaconBlock { slot: 1, ..Default::default() };
+        block.proposer_index = harness.proposer();
+        block.body.execution_payload.timestamp =
+            compute_timestamp_at_slot(&harness.state, 1, context).unwrap();
+        let signed_block = sign(block.clone());
+
+        let result = validate_beacon_block(&signed_block, &harness.state, &mut seen, now, context);
+        assert!(result.unwrap().is_accept());
+        // a second block from the same proposer for the slot is ignored
+        let result = validate_beacon_block(&signed_block, &harness.state, &mut seen, now, context);
+        assert_ignored(result);
+
+        let mut seen = SeenCache::default();
+        let result = validate_beacon_block(
+            &signed_block,
+            &harness.state,
+            &mut seen,
+            harness.now(0),
+            context,
+        );
+        assert!(matches!(result, Ok(ValidationResult::Ignore(IgnoreReason::FutureSlot { .. }))));
+
+        let mut invalid_block = block.clone();
+        invalid_block.body.execution_payload.timestamp += 1;
+        let result =
+            validate_beacon_block(&sign(invalid_block), &harness.state, &mut seen, now, context);
+        assert!(matches!(
+            result,
+            Ok(ValidationResult::Reject(RejectReason::InvalidTimestamp { .. }))
+        ));
+
+        let mut invalid_block = block;
+        invalid_block.proposer_index = (invalid_block.proposer_index + 1) % 64;
+        let result =
+            validate_beacon_block(&sign(invalid_block), &harness.state, &mut seen, now, context);
+        assert!(matches!(
+            result,
+            Ok(ValidationResult::Reject(RejectReason::UnexpectedProposer { .. }))
+        ));
+
+        let mut invalid_block = signed_block;
+        invalid_block.signature = harness.secret_keys[0].sign(b"not the block");
+        let result = validate_beacon_block(&invalid_block, &harness.state, &mut seen, now, context);
+        assert!(matches!(
+            result,
+            Ok(ValidationResult::Reject(RejectReason::InvalidSignature("proposer")))
+        ));
+    }
+
+    #[test]
+    fn test_validate_aggregate_and_proof() {
+        let harness = Harness::new(1);
+        let context = &harness.context;
+        let now = harness.now(1);
+        let mut seen = SeenCache::default();
+
+        let committee = get_beacon_committee(&harness.state, 1, 0, context).unwrap();
+        let aggregator_index = committee[0];
+        // NOTE: every member of the small minimal committees is an aggregator
+        let selection_proof = harness.sign(&1u64, aggregator_index, DomainType::SelectionProof, 0);
+        let aggregate_and_proof = spec::AggregateAndProof {
+            aggregator_index,
+            aggregate: harness.attestation(1, &vec![true; committee.len()]),
+            selection_proof,
+        };
+        let signature =
+            harness.sign(&aggregate_and_proof, aggregator_index, DomainType::AggregateAndProof, 0);
+        let signed_aggregate_and_proof =
+            spec::SignedAggregateAndProof { message: aggregate_and_proof, signature };
+
+        let result = validate_aggregate_and_proof(
+            &signed_aggregate_and_proof,
+            &harness.state,
+            &mut seen,
+            now,
+            context,
+        );
+        assert!(result.unwrap().is_accept());
+        let result = validate_aggregate_and_proof(
+            &signed_aggregate_and_proof,
+            &harness.state,
+            &mut seen,
+            now,
+            context,
+        );
+        assert_ignored(result);
+
+        let mut invalid = signed_aggregate_and_proof;
+        invalid.message.aggregate = harness.attestation(1, &vec![false; committee.len()]);
+        let result = validate_aggregate_and_proof(
+            &invalid,
+            &harness.state,
+            &mut SeenCache::default(),
+            now,
+            context,
+        );
+        assert_rejected(result);
+    }
+
+    #[test]
+    fn test_validate_attestation() {
+        let harness = Harness::new(1);
+        let context = &harness.context;
+        let now = harness.now(1);
+        let mut seen = SeenCache::default();
+
+        let committee_count = get_committee_count_per_slot(&harness.state, 0, context);
+        let subnet_id = compute_subnet_for_attestation(committee_count, 1, 0, context);
+        let committee = get_beacon_committee(&harness.state, 1, 0, context).unwrap();
+        let mut bits = vec![false; committee.len()];
+        bits[0] = true;
+        let attestation = harness.attestation(1, &bits);
+
+        let result =
+            validate_attestation(&attestation, subnet_id, &harness.state, &mut seen, now, context);
+        assert!(result.unwrap().is_accept());
+        let result =
+            validate_attestation(&attestation, subnet_id, &harness.state, &mut seen, now, context);
+        assert_ignored(result);
+
+        let mut seen = SeenCache::default();
+        let result = validate_attestation(
+            &attestation,
+            subnet_id + 1,
+            &harness.state,
+            &mut seen,
+            now,
+            context,
+        );
+        assert_rejected(result);
+
+        let attestation = harness.attestation(1, &vec![true; committee.len()]);
+        let result =
+            validate_attestation(&attestation, subnet_id, &harness.state, &mut seen, now, context);
+        assert_rejected(result);
+    }
+
+    #[test]
+    fn test_validate_voluntary_exit() {
+        let harness = Harness::new(64 * 8);
+        let context = &harness.context;
+        let mut seen = SeenCache::default();
+        assert!(get_current_epoch(&harness.state, context) >= context.shard_committee_period);
+
+        let domain = compute_domain(
+            DomainType::VoluntaryExit,
+            Some(context.capella_fork_version),
+            Some(harness.state.genesis_validators_root),
+            context,
+        )
+        .unwrap();
+        let voluntary_exit = spec::VoluntaryExit { epoch: 0, validator_index: 1 };
+        let signature = sign_with_domain(&voluntary_exit, &harness.secret_keys[1], domain).unwrap();
+        let signed_voluntary_exit =
+            spec::SignedVoluntaryExit { message: voluntary_exit, signature };
+
+        let result =
+            validate_voluntary_exit(&signed_voluntary_exit, &harness.state, &mut seen, context);
+        assert!(result.unwrap().is_accept());
+        let result =
+            validate_voluntary_exit(&signed_voluntary_exit, &harness.state, &mut seen, context);
+        assert_ignored(result);
+
+        let mut invalid = signed_voluntary_exit.clone();
+        invalid.message.validator_index = 2;
+        let result = validate_voluntary_exit(&invalid, &harness.state, &mut seen, context);
+        assert!(matches!(
+            result,
+            Ok(ValidationResult::Reject(RejectReason::InvalidSignature("voluntary exit")))
+        ));
+
+        // the state is not modified by validation
+        assert_eq!(harness.state.validators[1].exit_epoch, FAR_FUTURE_EPOCH);
+        let harness = Harness::new(8);
+        let result = validate_voluntary_exit(
+            &signed_voluntary_exit,
+            &harness.state,
+            &mut SeenCache::default(),
+            &harness.context,
+        );
+        assert_rejected(result);
+    }
+
+    #[test]
+    fn test_validate_proposer_slashing() {
+        let harness = Harness::new(1);
+        let context = &harness.context;
+        let mut seen = SeenCache::default();
+
+        let header = BeaconBlockHeader { slot: 1, proposer_index: 3, ..Default::default() };
+        let proposer_slashing = spec::ProposerSlashing {
+            signed_header_1: harness.signed_header(header.clone()),
+            signed_header_2: harness
+                .signed_header(BeaconBlockHeader { body_root: root(1), ..header.clone() }),
+        };
+
+        let result =
+            validate_proposer_slashing(&proposer_slashing, &harness.state, &mut seen, context);
+        assert!(result.unwrap().is_accept());
+        let result =
+            validate_proposer_slashing(&proposer_slashing, &harness.state, &mut seen, context);
+        assert_ignored(result);
+        assert!(!harness.state.validators[3].slashed);
+
+        let mut seen = SeenCache::default();
+        let invalid = spec::ProposerSlashing {
+            signed_header_1: harness.signed_header(header.clone()),
+            signed_header_2: harness.signed_header(header),
+        };
+        let result = validate_proposer_slashing(&invalid, &harness.state, &mut seen, context);
+        assert_rejected(result);
+
+        let mut invalid = proposer_slashing;
+        invalid.signed_header_2.signature = invalid.signed_header_1.signature.clone();
+        let result = validate_proposer_slashing(&invalid, &harness.state, &mut seen, context);
+        assert_rejected(result);
+    }
+
+    #[test]
+    fn test_validate_attester_slashing() {
+        let harness = Harness::new(1);
+        let context = &harness.context;
+        let mut seen = SeenCache::default();
+
+        let attester_slashing = spec::AttesterSlashing {
+            attestation_1: harness.indexed_attestation(&[1, 2], root(1)),
+            attestation_2: harness.indexed_attestation(&[2, 3], root(2)),
+        };
+        let result =
+            validate_attester_slashing(&attester_slashing, &harness.state, &mut seen, context);
+        assert!(result.unwrap().is_accept());
+        let result =
+            validate_attester_slashing(&attester_slashing, &harness.state, &mut seen, context);
+        assert_ignored(result);
+
+        // no validator attested to both, even if the slashing is otherwise a duplicate
+        let disjoint = spec::AttesterSlashing {
+            attestation_1: harness.indexed_attestation(&[1], root(1)),
+            attestation_2: harness.indexed_attestation(&[2], root(2)),
+        };
+        let result = validate_attester_slashing(&disjoint, &harness.state, &mut seen, context);
+        assert_rejected(result);
+
+        let mut seen = SeenCache::default();
+        let not_slashable = spec::AttesterSlashing {
+            attestation_1: harness.indexed_attestation(&[1, 2], root(1)),
+            attestation_2: harness.indexed_attestation(&[2, 3], root(1)),
+        };
+        let result = validate_attester_slashing(&not_slashable, &harness.state, &mut seen, context);
+        assert_rejected(result);
+
+        let mut invalid = attester_slashing;
+        invalid.attestation_2.signature = invalid.attestation_1.signature.clone();
+        let result = validate_attester_slashing(&invalid, &harness.state, &mut seen, context);
+        assert_rejected(result);
+    }
+
+    #[test]
+    fn test_validate_contribution_and_proof() {
+        let harness = Harness::new(1);
+        let context = &harness.context;
+        let now = harness.now(1);
+        let mut seen = SeenCache::default();
+
+        let public_keys = get_sync_subcommittee_pubkeys(&harness.state, 0, context);
+        let aggregator_index = harness
+            .state
+            .validators
+            .iter()
+            .position(|validator| validator.public_key == public_keys[0])
+            .unwrap();
+        let block_root = root(1);
+        let mut aggregation_bits = Bitvector::default();
+        aggregation_bits.set(0, true);
+        let contribution = spec::SyncCommitteeContribution {
+            slot: 1,
+            beacon_block_root: block_root,
+            subcommittee_index: 0,
+            aggregation_bits,
+            signature: harness.sign(&block_root, aggregator_index, DomainType::SyncCommittee, 0),
+        };
+        // NOTE: every member of the small minimal subcommittees is an aggregator
+        let selection_data = SyncAggregatorSelectionData { slot: 1, subcommittee_index: 0 };
+        let selection_proof = harness.sign(
+            &selection_data,
+            aggregator_index,
+            DomainType::SyncCommitteeSelectionProof,
+            0,
+        );
+        let contribution_and_proof =
+            spec::ContributionAndProof { aggregator_index, contribution, selection_proof };
+        let signature = harness.sign(
+            &contribution_and_proof,
+            aggregator_index,
+            DomainType::ContributionAndProof,
+            0,
+        );
+        let signed_contribution_and_proof =
+            spec::SignedContributionAndProof { message: contribution_and_proof, signature };
+
+        let result = validate_contribution_and_proof(
+            &signed_contribution_and_proof,
+            &harness.state,
+            &mut seen,
+            now,
+            context,
+        );
+        assert!(result.unwrap().is_accept());
+        let result = validate_contribution_and_proof(
+            &signed_contribution_and_proof,
+            &harness.state,
+            &mut seen,
+            now,
+            context,
+        );
+        assert_ignored(result);
+
+        let mut invalid = signed_contribution_and_proof;
+        invalid.message.contribution.subcommittee_index = SYNC_COMMITTEE_SUBNET_COUNT as u64;
+        let result = validate_contribution_and_proof(
+            &invalid,
+            &harness.state,
+            &mut SeenCache::default(),
+            now,
+            context,
+        );
+        assert_rejected(result);
+    }
+
+    #[test]
+    fn test_validate_bls_to_execution_change() {
+        let harness = Harness::new(1);
+        let context = &harness.context;
+        let now = harness.now(1);
+        let mut seen = SeenCache::default();
+
+        let domain = compute_domain(
+            DomainType::BlsToExecutionChange,
+            None,
+            Some(harness.state.genesis_validators_root),
+            context,
+        )
+        .unwrap();
+        let address_change = spec::BlsToExecutionChange {
+            validator_index: 4,
+            from_bls_public_key: harness.secret_keys[4].public_key(),
+            ..Default::default()
+        };
+        let signature = sign_with_domain(&address_change, &harness.secret_keys[4], domain).unwrap();
+        let signed_address_change =
+            spec::SignedBlsToExecutionChange { message: address_change, signature };
+
+        let result = validate_bls_to_execution_change(
+            &signed_address_change,
+            &harness.state,
+            &mut seen,
+            now,
+            context,
+        );
+        assert!(result.unwrap().is_accept());
+        let result = validate_bls_to_execution_change(
+            &signed_address_change,
+            &harness.state,
+            &mut seen,
+            now,
+            context,
+        );
+        assert_ignored(result);
+
+        let mut seen = SeenCache::default();
+        let mut invalid = signed_address_change.clone();
+        invalid.message.from_bls_public_key = harness.secret_keys[5].public_key();
+        let result =
+            validate_bls_to_execution_change(&invalid, &harness.state, &mut seen, now, context);
+        assert_rejected(result);
+
+        let mut invalid = signed_address_change;
+        invalid.signature = harness.secret_keys[4].sign(b"not the change");
+        let result =
+            validate_bls_to_execution_change(&invalid, &harness.state, &mut seen, now, context);
+        assert_rejected(result);
+    }
+
+    #[test]
+    fn test_validate_blob_sidecar() {
+        let harness = Harness::new(1);
+        let context = &harness.context;
+        let now = harness.now(1);
+        let mut seen = SeenCache::default();
+
+        let blob = spec::Blob::default();
+        let kzg_commitment = blob_to_kzg_commitment(&blob, &context.kzg_settings).unwrap();
+        let kzg_proof =
+            compute_blob_kzg_proof(&blob, &kzg_commitment, &context.kzg_settings).unwrap();
+        let body = spec::BeaconBlockBody {
+            blob_kzg_commitments: List::try_from(vec![kzg_commitment.clone()]).unwrap(),
+            ..Default::default()
+        };
+        let header = BeaconBlockHeader {
+            slot: 1,
+            proposer_index: harness.proposer(),
+            body_root: body.hash_tree_root().unwrap(),
+            ..Default::default()
+        };
+        let blob_sidecar = spec::BlobSidecar {
+            index: 0,
+            blob,
+            kzg_commitment,
+            kzg_proof,
+            signed_block_header: harness.signed_header(header),
+            kzg_commitment_inclusion_proof: Vector::try_from(commitment_inclusion_proof(&body))
+                .unwrap(),
+        };
+
+        let validate = |blob_sidecar: &spec::BlobSidecar, subnet_id, seen: &mut SeenCache| {
+            validate_blob_sidecar::<
+                { spec::SLOTS_PER_HISTORICAL_ROOT },
+                { spec::HISTORICAL_ROOTS_LIMIT },
+                { spec::ETH1_DATA_VOTES_BOUND },
+                { spec::VALIDATOR_REGISTRY_LIMIT },
+                { spec::EPOCHS_PER_HISTORICAL_VECTOR },
+                { spec::EPOCHS_PER_SLASHINGS_VECTOR },
+                { spec::MAX_VALIDATORS_PER_COMMITTEE },
+                { spec::SYNC_COMMITTEE_SIZE },
+                { spec::BYTES_PER_LOGS_BLOOM },
+                { spec::MAX_EXTRA_DATA_BYTES },
+                { spec::BYTES_PER_BLOB },
+                { spec::KZG_COMMITMENT_INCLUSION_PROOF_DEPTH },
+                spec::BeaconBlockBody,
+            >(blob_sidecar, subnet_id, &harness.state, seen, now, context)
+        };
+        assert!(validate(&blob_sidecar, 0, &mut seen).unwrap().is_accept());
+        assert_ignored(validate(&blob_sidecar, 0, &mut seen));
+
+        let mut seen = SeenCache::default();
+        assert_rejected(validate(&blob_sidecar, 1, &mut seen));
+
+        let mut invalid = blob_sidecar.clone();
+        invalid.kzg_commitment_inclusion_proof[0] = root(1);
+        assert_rejected(validate(&invalid, 0, &mut seen));
+
+        let mut invalid = blob_sidecar;
+        invalid.signed_block_header.message.slot = 2;
+        assert_ignored(validate(&invalid, 0, &mut seen));
+    }
+}
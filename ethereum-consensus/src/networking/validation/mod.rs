@@ -0,0 +1,146 @@
+//! Validation of gossip messages, following the `[IGNORE]` and `[REJECT]` conditions of the
+//! consensus p2p spec.
+//!
+//! Validation is performed against a `BeaconState` provided by the caller, along with a
+//! [`SeenCache`] of messages accepted so far. Conditions that require a view of fork choice (e.g.
+//! whether the parent block is known or descends from the finalized checkpoint) are not covered
+//! and are left to the caller.
+pub mod deneb;
+mod seen;
+
+use crate::{
+    clock::convert_timestamp_nanos_to_slot,
+    error::{InvalidBlock, InvalidOperation},
+    phase0::networking::MAXIMUM_GOSSIP_CLOCK_DISPARITY,
+    primitives::{BlobIndex, Epoch, Slot, ValidatorIndex},
+    state_transition::Context,
+    Error,
+};
+pub use seen::SeenCache;
+use std::time::Duration;
+use thiserror::Error;
+
+/// The outcome of validating a gossip message.
+#[derive(Debug)]
+pub enum ValidationResult {
+    /// The message is valid and should be forwarded to peers.
+    Accept,
+    /// The message should not be forwarded, without penalizing the sender.
+    Ignore(IgnoreReason),
+    /// The message is invalid and the sender should be penalized.
+    Reject(RejectReason),
+}
+
+impl ValidationResult {
+    pub fn is_accept(&self) -> bool {
+        matches!(self, Self::Accept)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum IgnoreReason {
+    #[error("message for slot {slot} is from the future (current slot is {current_slot})")]
+    FutureSlot { slot: Slot, current_slot: Slot },
+    #[error("message for slot {slot} is too old (current slot is {current_slot})")]
+    PastSlot { slot: Slot, current_slot: Slot },
+    #[error("message for slot {slot} is not later than the finalized slot {finalized_slot}")]
+    FinalizedSlot { slot: Slot, finalized_slot: Slot },
+    #[error("proposer shuffling for slot {slot} is unknown to a state at slot {state_slot}")]
+    UnknownProposerShuffling { slot: Slot, state_slot: Slot },
+    #[error("already seen a block from proposer {proposer_index} for slot {slot}")]
+    DuplicateBlock { slot: Slot, proposer_index: ValidatorIndex },
+    #[error("already seen an aggregate including the same attesters")]
+    DuplicateAggregate,
+    #[error("already seen an aggregate from aggregator {aggregator_index} for epoch {epoch}")]
+    DuplicateAggregator { aggregator_index: ValidatorIndex, epoch: Epoch },
+    #[error("already seen an attestation from validator {validator_index} for epoch {epoch}")]
+    DuplicateAttestation { validator_index: ValidatorIndex, epoch: Epoch },
+    #[error("already seen a voluntary exit for validator {0}")]
+    DuplicateVoluntaryExit(ValidatorIndex),
+    #[error("already seen a proposer slashing for validator {0}")]
+    DuplicateProposerSlashing(ValidatorIndex),
+    #[error("already seen attester slashings for all slashable validators")]
+    DuplicateAttesterSlashing,
+    #[error("already seen a contribution including the same participants")]
+    DuplicateContribution,
+    #[error(
+        "already seen a contribution from aggregator {aggregator_index} for slot {slot} and subcommittee {subcommittee_index}"
+    )]
+    DuplicateContributionAggregator {
+        aggregator_index: ValidatorIndex,
+        slot: Slot,
+        subcommittee_index: u64,
+    },
+    #[error("already seen a BLS to execution change for validator {0}")]
+    DuplicateBlsToExecutionChange(ValidatorIndex),
+    #[error("already seen blob sidecar {index} from proposer {proposer_index} for slot {slot}")]
+    DuplicateBlobSidecar { slot: Slot, proposer_index: ValidatorIndex, index: BlobIndex },
+    #[error("message is not valid before the capella fork (current epoch is {0})")]
+    PreCapella(Epoch),
+}
+
+#[derive(Debug, Error)]
+pub enum RejectReason {
+    #[error("invalid {0} signature")]
+    InvalidSignature(&'static str),
+    #[error("validator {0} is not in the state")]
+    InvalidValidatorIndex(ValidatorIndex),
+    #[error("block proposed by {proposer_index} but expected proposer is {expected}")]
+    UnexpectedProposer { proposer_index: ValidatorIndex, expected: ValidatorIndex },
+    #[error("block has {count} blob commitments but the limit is {limit}")]
+    TooManyBlobCommitments { count: usize, limit: usize },
+    #[error("execution payload has timestamp {provided} but expected {expected}")]
+    InvalidTimestamp { provided: u64, expected: u64 },
+    #[error("committee index {index} is not less than the committee count {committee_count}")]
+    InvalidCommitteeIndex { index: usize, committee_count: usize },
+    #[error("target epoch {target_epoch} does not match slot {slot}")]
+    TargetEpochMismatch { slot: Slot, target_epoch: Epoch },
+    #[error("message has no participants")]
+    NoParticipants,
+    #[error("unaggregated attestation has {0} participants")]
+    NotSingleParticipant(usize),
+    #[error("expected {expected} aggregation bits but message has {provided}")]
+    AggregationBitsLength { expected: usize, provided: usize },
+    #[error("message received on subnet {subnet_id} but belongs to subnet {expected}")]
+    WrongSubnet { subnet_id: u64, expected: u64 },
+    #[error("validator {0} is not an aggregator")]
+    NotAggregator(ValidatorIndex),
+    #[error("aggregator {0} is not a member of the committee")]
+    AggregatorNotInCommittee(ValidatorIndex),
+    #[error("subcommittee index {0} is out of range")]
+    InvalidSubcommitteeIndex(u64),
+    #[error("blob index {index} is not less than the limit {limit}")]
+    InvalidBlobIndex { index: BlobIndex, limit: usize },
+    #[error("invalid commitment inclusion proof")]
+    InvalidInclusionProof,
+    #[error("invalid KZG proof")]
+    InvalidKzgProof,
+    #[error("{0}")]
+    InvalidOperation(Box<InvalidBlock>),
+}
+
+fn ignore(reason: IgnoreReason) -> Result<ValidationResult, Error> {
+    Ok(ValidationResult::Ignore(reason))
+}
+
+fn reject(reason: RejectReason) -> Result<ValidationResult, Error> {
+    Ok(ValidationResult::Reject(reason))
+}
+
+fn reject_operation(operation: InvalidOperation) -> Result<ValidationResult, Error> {
+    reject(RejectReason::InvalidOperation(Box::new(InvalidBlock::InvalidOperation(operation))))
+}
+
+/// The range of slots that may be current at `now` (the duration since the unix epoch), allowing
+/// for `MAXIMUM_GOSSIP_CLOCK_DISPARITY` in either direction.
+pub fn current_slot_range(genesis_time: u64, now: Duration, context: &Context) -> (Slot, Slot) {
+    let genesis_time = Duration::from_secs(genesis_time).as_nanos();
+    let seconds_per_slot = Duration::from_secs(context.seconds_per_slot).as_nanos();
+    let slot_at = |time: Duration| {
+        convert_timestamp_nanos_to_slot(time.as_nanos(), genesis_time, seconds_per_slot)
+            .unwrap_or_default()
+    };
+    let earliest = slot_at(now.saturating_sub(MAXIMUM_GOSSIP_CLOCK_DISPARITY));
+    let latest = slot_at(now + MAXIMUM_GOSSIP_CLOCK_DISPARITY);
+    (earliest, latest)
+}
@@ -0,0 +1,198 @@
+use crate::{
+    primitives::{BlobIndex, Epoch, Root, Slot, ValidatorIndex},
+    state_transition::Context,
+};
+use std::collections::{HashMap, HashSet};
+
+// Returns `true` if every participant in `bits` is also in `other`.
+fn is_subset(bits: &[bool], other: &[bool]) -> bool {
+    bits.len() == other.len() && bits.iter().zip(other).all(|(bit, other)| !bit || *other)
+}
+
+/// Tracks gossip messages that have already been accepted, to implement the conditions of the
+/// form "the message is the first valid message received for ...".
+///
+/// Entries keyed by slot or epoch can be dropped with [`SeenCache::prune`] once their messages
+/// can no longer be valid.
+#[derive(Debug, Default)]
+pub struct SeenCache {
+    block_proposers: HashSet<(Slot, ValidatorIndex)>,
+    aggregates: HashMap<(Slot, Root), Vec<Vec<bool>>>,
+    aggregators: HashSet<(ValidatorIndex, Epoch)>,
+    attesters: HashSet<(ValidatorIndex, Epoch)>,
+    voluntary_exits: HashSet<ValidatorIndex>,
+    proposer_slashings: HashSet<ValidatorIndex>,
+    attester_slashings: HashSet<ValidatorIndex>,
+    contributions: HashMap<(Slot, Root, u64), Vec<Vec<bool>>>,
+    contribution_aggregators: HashSet<(ValidatorIndex, Slot, u64)>,
+    bls_to_execution_changes: HashSet<ValidatorIndex>,
+    blob_sidecars: HashSet<(Slot, ValidatorIndex, BlobIndex)>,
+}
+
+impl SeenCache {
+    /// Drops entries for messages from before the previous epoch of `current_slot`.
+    ///
+    /// Operations that can only be included once (exits, slashings and BLS to execution changes)
+    /// are retained.
+    pub fn prune(&mut self, current_slot: Slot, context: &Context) {
+        let current_epoch = current_slot / context.slots_per_epoch;
+        let min_epoch = current_epoch.saturating_sub(1);
+        let min_slot = min_epoch * context.slots_per_epoch;
+        self.block_proposers.retain(|(slot, _)| *slot >= min_slot);
+        self.aggregates.retain(|(slot, _), _| *slot >= min_slot);
+        self.aggregators.retain(|(_, epoch)| *epoch >= min_epoch);
+        self.attesters.retain(|(_, epoch)| *epoch >= min_epoch);
+        self.contributions.retain(|(slot, ..), _| *slot >= min_slot);
+        self.contribution_aggregators.retain(|(_, slot, _)| *slot >= min_slot);
+        self.blob_sidecars.retain(|(slot, ..)| *slot >= min_slot);
+    }
+
+    pub(super) fn has_block(&self, slot: Slot, proposer_index: ValidatorIndex) -> bool {
+        self.block_proposers.contains(&(slot, proposer_index))
+    }
+
+    pub(super) fn insert_block(&mut self, slot: Slot, proposer_index: ValidatorIndex) {
+        self.block_proposers.insert((slot, proposer_index));
+    }
+
+    // An aggregate is known if a previous aggregate of the same data is a (non-strict) superset.
+    pub(super) fn has_aggregate(&self, slot: Slot, data_root: Root, bits: &[bool]) -> bool {
+        self.aggregates
+            .get(&(slot, data_root))
+            .map(|seen| seen.iter().any(|other| is_subset(bits, other)))
+            .unwrap_or_default()
+    }
+
+    pub(super) fn insert_aggregate(&mut self, slot: Slot, data_root: Root, bits: Vec<bool>) {
+        self.aggregates.entry((slot, data_root)).or_default().push(bits);
+    }
+
+    pub(super) fn has_aggregator(&self, aggregator_index: ValidatorIndex, epoch: Epoch) -> bool {
+        self.aggregators.contains(&(aggregator_index, epoch))
+    }
+
+    pub(super) fn insert_aggregator(&mut self, aggregator_index: ValidatorIndex, epoch: Epoch) {
+        self.aggregators.insert((aggregator_index, epoch));
+    }
+
+    pub(super) fn has_attester(&self, validator_index: ValidatorIndex, epoch: Epoch) -> bool {
+        self.attesters.contains(&(validator_index, epoch))
+    }
+
+    pub(super) fn insert_attester(&mut self, validator_index: ValidatorIndex, epoch: Epoch) {
+        self.attesters.insert((validator_index, epoch));
+    }
+
+    pub(super) fn has_voluntary_exit(&self, validator_index: ValidatorIndex) -> bool {
+        self.voluntary_exits.contains(&validator_index)
+    }
+
+    pub(super) fn insert_voluntary_exit(&mut self, validator_index: ValidatorIndex) {
+        self.voluntary_exits.insert(validator_index);
+    }
+
+    pub(super) fn has_proposer_slashing(&self, proposer_index: ValidatorIndex) -> bool {
+        self.proposer_slashings.contains(&proposer_index)
+    }
+
+    pub(super) fn insert_proposer_slashing(&mut self, proposer_index: ValidatorIndex) {
+        self.proposer_slashings.insert(proposer_index);
+    }
+
+    // Slashings are known if every index has been slashed before, and never known if empty.
+    pub(super) fn has_attester_slashings(&self, indices: &[ValidatorIndex]) -> bool {
+        !indices.is_empty() && indices.iter().all(|index| self.attester_slashings.contains(index))
+    }
+
+    pub(super) fn insert_attester_slashings(&mut self, indices: &[ValidatorIndex]) {
+        self.attester_slashings.extend(indices);
+    }
+
+    pub(super) fn has_contribution(
+        &self,
+        slot: Slot,
+        block_root: Root,
+        subcommittee_index: u64,
+        bits: &[bool],
+    ) -> bool {
+        self.contributions
+            .get(&(slot, block_root, subcommittee_index))
+            .map(|seen| seen.iter().any(|other| is_subset(bits, other)))
+            .unwrap_or_default()
+    }
+
+    pub(super) fn insert_contribution(
+        &mut self,
+        slot: Slot,
+        block_root: Root,
+        subcommittee_index: u64,
+        bits: Vec<bool>,
+    ) {
+        self.contributions.entry((slot, block_root, subcommittee_index)).or_default().push(bits);
+    }
+
+    pub(super) fn has_contribution_aggregator(
+        &self,
+        aggregator_index: ValidatorIndex,
+        slot: Slot,
+        subcommittee_index: u64,
+    ) -> bool {
+        self.contribution_aggregators.contains(&(aggregator_index, slot, subcommittee_index))
+    }
+
+    pub(super) fn insert_contribution_aggregator(
+        &mut self,
+        aggregator_index: ValidatorIndex,
+        slot: Slot,
+        subcommittee_index: u64,
+    ) {
+        self.contribution_aggregators.insert((aggregator_index, slot, subcommittee_index));
+    }
+
+    pub(super) fn has_bls_to_execution_change(&self, validator_index: ValidatorIndex) -> bool {
+        self.bls_to_execution_changes.contains(&validator_index)
+    }
+
+    pub(super) fn insert_bls_to_execution_change(&mut self, validator_index: ValidatorIndex) {
+        self.bls_to_execution_changes.insert(validator_index);
+    }
+
+    pub(super) fn has_blob_sidecar(
+        &self,
+        slot: Slot,
+        proposer_index: ValidatorIndex,
+        index: BlobIndex,
+    ) -> bool {
+        self.blob_sidecars.contains(&(slot, proposer_index, index))
+    }
+
+    pub(super) fn insert_blob_sidecar(
+        &mut self,
+        slot: Slot,
+        proposer_index: ValidatorIndex,
+        index: BlobIndex,
+    ) {
+        self.blob_sidecars.insert((slot, proposer_index, index));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_supersets() {
+        let mut seen = SeenCache::default();
+        let root = Root::default();
+        seen.insert_aggregate(1, root, vec![true, true, false]);
+
+        assert!(seen.has_aggregate(1, root, &[true, false, false]));
+        assert!(seen.has_aggregate(1, root, &[true, true, false]));
+        assert!(!seen.has_aggregate(1, root, &[true, false, true]));
+        assert!(!seen.has_aggregate(2, root, &[true, false, false]));
+
+        let context = Context::for_minimal();
+        seen.prune(3 * context.slots_per_epoch, &context);
+        assert!(!seen.has_aggregate(1, root, &[true, false, false]));
+    }
+}
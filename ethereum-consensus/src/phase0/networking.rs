@@ -1,4 +1,4 @@
-use crate::{primitives::Epoch, ssz::prelude::Bitvector};
+use crate::{primitives::Epoch, ssz::prelude::*};
 use std::time::Duration;
 
 pub const ATTESTATION_SUBNET_COUNT: usize = 64;
@@ -10,8 +10,16 @@ pub const TTFB_TIMEOUT: Duration = Duration::from_secs(5);
 pub const RESP_TIMEOUT: Duration = Duration::from_secs(10);
 pub const ATTESTATION_PROPAGATION_SLOT_RANGE: usize = 32;
 pub const MAXIMUM_GOSSIP_CLOCK_DISPARITY: Duration = Duration::from_millis(500);
+pub const SUBNETS_PER_NODE: usize = 2;
+pub const EPOCHS_PER_SUBNET_SUBSCRIPTION: Epoch = 256;
+pub const ATTESTATION_SUBNET_EXTRA_BITS: usize = 0;
+pub const ATTESTATION_SUBNET_PREFIX_BITS: usize =
+    ATTESTATION_SUBNET_COUNT.ilog2() as usize + ATTESTATION_SUBNET_EXTRA_BITS;
+pub const NODE_ID_BITS: usize = 256;
 
-#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(
+    Default, Debug, SimpleSerialize, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize,
+)]
 pub struct MetaData {
     #[serde(with = "crate::serde::as_str")]
     pub seq_number: u64,
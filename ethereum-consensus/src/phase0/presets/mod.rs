@@ -36,4 +36,5 @@ pub struct Preset {
     pub max_attestations: usize,
     pub max_deposits: usize,
     pub max_voluntary_exits: usize,
+    pub target_aggregators_per_committee: usize,
 }
@@ -52,7 +52,9 @@ pub use crate::{
         },
         slot_processing::{process_slot, process_slots},
         state_transition::{state_transition, state_transition_block_in_slot},
-        validator::{AggregateAndProof, Eth1Block, SignedAggregateAndProof, Validator},
+        validator::{
+            is_aggregator, AggregateAndProof, Eth1Block, SignedAggregateAndProof, Validator,
+        },
     },
     primitives::*,
     signing::*,
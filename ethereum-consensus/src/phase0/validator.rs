@@ -1,7 +1,9 @@
 use crate::{
+    crypto::hash,
     phase0::operations::Attestation,
     primitives::{BlsPublicKey, BlsSignature, Bytes32, Epoch, Gwei, Root, ValidatorIndex},
     ssz::prelude::*,
+    state_transition::Context,
 };
 
 #[derive(
@@ -45,3 +47,21 @@ pub struct SignedAggregateAndProof<const MAX_VALIDATORS_PER_COMMITTEE: usize> {
     pub message: AggregateAndProof<MAX_VALIDATORS_PER_COMMITTEE>,
     pub signature: BlsSignature,
 }
+
+/// Returns whether the `selection_proof` of a member of a committee with `committee_length`
+/// members selects it to aggregate the committee's attestations.
+pub fn is_aggregator(
+    committee_length: usize,
+    selection_proof: &BlsSignature,
+    context: &Context,
+) -> bool {
+    let modulo = committee_length / context.target_aggregators_per_committee;
+    is_selected_aggregator(selection_proof, modulo as u64)
+}
+
+pub(crate) fn is_selected_aggregator(selection_proof: &BlsSignature, modulo: u64) -> bool {
+    let digest = hash(selection_proof.as_ref());
+    let value =
+        u64::from_le_bytes(digest.as_ref()[..8].try_into().expect("digest has at least 8 bytes"));
+    value % modulo.max(1) == 0
+}
@@ -1,14 +1,14 @@
 use crate::{
     altair, bellatrix, capella,
     clock::{self, Clock, SystemTimeProvider},
-    configs::{self, Config},
+    configs::{self, BlobParameters, Config},
     crypto::{kzg_settings_from_json, KzgSettings},
     deneb::{self, presets::TRUSTED_SETUP_JSON},
     electra,
     execution_engine::ExecutionEngine,
     networks::Network,
     phase0,
-    primitives::{Epoch, ExecutionAddress, Gwei, Hash32, Slot, Version, U256},
+    primitives::{Epoch, ExecutionAddress, Gwei, Hash32, Root, Slot, Version, U256},
     Error, Fork,
 };
 use std::sync::Arc;
@@ -51,6 +51,7 @@ pub struct Context {
     pub max_attestations: usize,
     pub max_deposits: usize,
     pub max_voluntary_exits: usize,
+    pub target_aggregators_per_committee: usize,
 
     // altair preset
     pub inactivity_penalty_quotient_altair: u64,
@@ -60,6 +61,7 @@ pub struct Context {
     pub epochs_per_sync_committee_period: Epoch,
     pub min_sync_committee_participants: usize,
     pub update_timeout: usize,
+    pub target_aggregators_per_sync_subcommittee: usize,
 
     // bellatrix preset
     pub inactivity_penalty_quotient_bellatrix: u64,
@@ -117,6 +119,8 @@ pub struct Context {
     pub deneb_fork_epoch: Epoch,
     pub electra_fork_version: Version,
     pub electra_fork_epoch: Epoch,
+    pub fulu_fork_version: Version,
+    pub fulu_fork_epoch: Epoch,
 
     pub seconds_per_slot: u64,
     pub seconds_per_eth1_block: u64,
@@ -133,12 +137,18 @@ pub struct Context {
     pub max_per_epoch_activation_exit_churn_limit: u64,
     pub churn_limit_quotient: u64,
 
+    pub max_blobs_per_block_electra: u64,
+    pub blob_schedule: Vec<BlobParameters>,
+
     pub proposer_score_boost: u64,
 
     pub deposit_chain_id: usize,
     pub deposit_network_id: usize,
     pub deposit_contract_address: ExecutionAddress,
 
+    // The genesis time of a custom network, which its config does not determine.
+    genesis_time: Option<u64>,
+
     // Provides an implementation of `execution_engine::ExecutionEngine`.
     #[cfg(feature = "spec-tests")]
     // This field is exposed so that the execution engine behavior can be mocked when testing.
@@ -239,6 +249,7 @@ impl Context {
             max_attestations: phase0_preset.max_attestations,
             max_deposits: phase0_preset.max_deposits,
             max_voluntary_exits: phase0_preset.max_voluntary_exits,
+            target_aggregators_per_committee: phase0_preset.target_aggregators_per_committee,
             // altair
             inactivity_penalty_quotient_altair: altair_preset.inactivity_penalty_quotient_altair,
             min_slashing_penalty_quotient_altair: altair_preset
@@ -249,6 +260,8 @@ impl Context {
             epochs_per_sync_committee_period: altair_preset.epochs_per_sync_committee_period,
             min_sync_committee_participants: altair_preset.min_sync_committee_participants,
             update_timeout: altair_preset.update_timeout,
+            target_aggregators_per_sync_subcommittee: altair_preset
+                .target_aggregators_per_sync_subcommittee,
             // bellatrix
             inactivity_penalty_quotient_bellatrix: bellatrix_preset
                 .inactivity_penalty_quotient_bellatrix,
@@ -305,6 +318,8 @@ impl Context {
             deneb_fork_epoch: config.deneb_fork_epoch,
             electra_fork_version: config.electra_fork_version,
             electra_fork_epoch: config.electra_fork_epoch,
+            fulu_fork_version: config.fulu_fork_version,
+            fulu_fork_epoch: config.fulu_fork_epoch,
             seconds_per_slot: config.seconds_per_slot,
             seconds_per_eth1_block: config.seconds_per_eth1_block,
             min_validator_withdrawability_delay: config.min_validator_withdrawability_delay,
@@ -319,10 +334,13 @@ impl Context {
             max_per_epoch_activation_exit_churn_limit: config
                 .max_per_epoch_activation_exit_churn_limit,
             churn_limit_quotient: config.churn_limit_quotient,
+            max_blobs_per_block_electra: config.max_blobs_per_block_electra,
+            blob_schedule: config.blob_schedule.clone(),
             proposer_score_boost: config.proposer_score_boost,
             deposit_chain_id: config.deposit_chain_id,
             deposit_network_id: config.deposit_network_id,
             deposit_contract_address: config.deposit_contract_address.clone(),
+            genesis_time: None,
             execution_engine: DEFAULT_EXECUTION_ENGINE_VALIDITY,
             kzg_settings: Arc::new(kzg_settings),
         }
@@ -425,7 +443,9 @@ impl Context {
 
     pub fn fork_for(&self, slot: Slot) -> Fork {
         let epoch = slot / self.slots_per_epoch;
-        if epoch >= self.electra_fork_epoch {
+        if epoch >= self.fulu_fork_epoch {
+            Fork::Fulu
+        } else if epoch >= self.electra_fork_epoch {
             Fork::Electra
         } else if epoch >= self.deneb_fork_epoch {
             Fork::Deneb
@@ -448,10 +468,34 @@ impl Context {
             Fork::Capella => self.capella_fork_version,
             Fork::Deneb => self.deneb_fork_version,
             Fork::Electra => self.electra_fork_version,
+            Fork::Fulu => self.fulu_fork_version,
         }
     }
 
+    /// The blob parameters in effect at `epoch` (`get_blob_parameters` in the Fulu spec).
+    pub fn blob_parameters(&self, epoch: Epoch) -> BlobParameters {
+        self.blob_schedule
+            .iter()
+            .filter(|parameters| parameters.epoch <= epoch)
+            .max_by_key(|parameters| parameters.epoch)
+            .copied()
+            .unwrap_or(BlobParameters {
+                epoch: self.electra_fork_epoch,
+                max_blobs_per_block: self.max_blobs_per_block_electra,
+            })
+    }
+
+    /// Set the genesis time of the network, e.g. as read from the genesis state of a custom
+    /// network, which then also has a [`Context::clock`].
+    pub fn with_genesis_time(mut self, genesis_time: u64) -> Self {
+        self.genesis_time = Some(genesis_time);
+        self
+    }
+
     pub fn genesis_time(&self) -> Result<u64, Error> {
+        if let Some(genesis_time) = self.genesis_time {
+            return Ok(genesis_time)
+        }
         match &self.name {
             Network::Mainnet => Ok(crate::clock::MAINNET_GENESIS_TIME),
             Network::Sepolia => Ok(crate::clock::SEPOLIA_GENESIS_TIME),
@@ -461,20 +505,27 @@ impl Context {
         }
     }
 
+    /// Return a clock for this network, or `None` for a custom network without a genesis time
+    /// given to [`Context::with_genesis_time`].
     pub fn clock(&self) -> Option<Clock<SystemTimeProvider>> {
-        match self.name {
-            Network::Mainnet => Some(clock::for_mainnet()),
-            Network::Sepolia => Some(clock::for_sepolia()),
-            Network::Goerli => Some(clock::for_goerli()),
-            Network::Holesky => Some(clock::for_holesky()),
-            _ => None,
-        }
+        let genesis_time = self.genesis_time().ok()?;
+        Some(self.clock_at(genesis_time))
     }
 
     pub fn clock_at(&self, genesis_time: u64) -> Clock<SystemTimeProvider> {
         clock::from_system_time(genesis_time, self.seconds_per_slot, self.slots_per_epoch)
     }
 
+    /// Return a fork-aware clock for this network given its genesis, which also supports custom
+    /// networks; see [`Clock::from_context`].
+    pub fn clock_with_genesis(
+        &self,
+        genesis_time: u64,
+        genesis_validators_root: Root,
+    ) -> Result<Clock<SystemTimeProvider>, Error> {
+        Clock::from_context(self, genesis_time, genesis_validators_root, SystemTimeProvider)
+    }
+
     pub fn network(&self) -> &Network {
         &self.name
     }
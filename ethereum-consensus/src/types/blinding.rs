@@ -0,0 +1,362 @@
+//! Conversions between the full and blinded blocks of each fork with an execution payload.
+use crate::{
+    deneb::BlobsBundle,
+    types::{
+        BeaconBlock, BlindedBeaconBlock, ExecutionPayload, SignedBeaconBlock,
+        SignedBlindedBeaconBlock,
+    },
+    Error,
+};
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    >
+    BeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >
+{
+    /// Returns the blinded block committing to the execution payload of this block.
+    ///
+    /// Blocks from before `bellatrix` have no execution payload and cannot be blinded.
+    pub fn blind(
+        &self,
+    ) -> Result<
+        BlindedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+        Error,
+    > {
+        let block = match self {
+            Self::Bellatrix(block) => BlindedBeaconBlock::Bellatrix(block.blind()?),
+            Self::Capella(block) => BlindedBeaconBlock::Capella(block.blind()?),
+            Self::Deneb(block) => BlindedBeaconBlock::Deneb(block.blind()?),
+            block => return Err(Error::MissingExecutionPayload(block.version())),
+        };
+        Ok(block)
+    }
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    >
+    SignedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BYTES_PER_TRANSACTION,
+        MAX_TRANSACTIONS_PER_PAYLOAD,
+        MAX_WITHDRAWALS_PER_PAYLOAD,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >
+{
+    /// Returns the signed blinded block committing to the execution payload of this block.
+    ///
+    /// Blocks from before `bellatrix` have no execution payload and cannot be blinded.
+    pub fn blind(
+        &self,
+    ) -> Result<
+        SignedBlindedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+        Error,
+    > {
+        let block = match self {
+            Self::Bellatrix(block) => SignedBlindedBeaconBlock::Bellatrix(block.blind()?),
+            Self::Capella(block) => SignedBlindedBeaconBlock::Capella(block.blind()?),
+            Self::Deneb(block) => SignedBlindedBeaconBlock::Deneb(block.blind()?),
+            block => return Err(Error::MissingExecutionPayload(block.version())),
+        };
+        Ok(block)
+    }
+}
+
+impl<
+        const MAX_PROPOSER_SLASHINGS: usize,
+        const MAX_VALIDATORS_PER_COMMITTEE: usize,
+        const MAX_ATTESTER_SLASHINGS: usize,
+        const MAX_ATTESTATIONS: usize,
+        const MAX_DEPOSITS: usize,
+        const MAX_VOLUNTARY_EXITS: usize,
+        const SYNC_COMMITTEE_SIZE: usize,
+        const BYTES_PER_LOGS_BLOOM: usize,
+        const MAX_EXTRA_DATA_BYTES: usize,
+        const MAX_BLS_TO_EXECUTION_CHANGES: usize,
+        const MAX_BLOB_COMMITMENTS_PER_BLOCK: usize,
+    >
+    SignedBlindedBeaconBlock<
+        MAX_PROPOSER_SLASHINGS,
+        MAX_VALIDATORS_PER_COMMITTEE,
+        MAX_ATTESTER_SLASHINGS,
+        MAX_ATTESTATIONS,
+        MAX_DEPOSITS,
+        MAX_VOLUNTARY_EXITS,
+        SYNC_COMMITTEE_SIZE,
+        BYTES_PER_LOGS_BLOOM,
+        MAX_EXTRA_DATA_BYTES,
+        MAX_BLS_TO_EXECUTION_CHANGES,
+        MAX_BLOB_COMMITMENTS_PER_BLOCK,
+    >
+{
+    /// Returns the signed full block with `execution_payload`, e.g. as revealed by a relay, which
+    /// must be from the same fork as this block and match the header it commits to.
+    ///
+    /// From `deneb`, the blobs in `blobs_bundle` must match the commitments in the block. A missing
+    /// bundle is only valid for a block without blobs.
+    pub fn unblind<
+        const MAX_BYTES_PER_TRANSACTION: usize,
+        const MAX_TRANSACTIONS_PER_PAYLOAD: usize,
+        const MAX_WITHDRAWALS_PER_PAYLOAD: usize,
+        const BYTES_PER_BLOB: usize,
+    >(
+        &self,
+        execution_payload: ExecutionPayload<
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+        >,
+        blobs_bundle: Option<&BlobsBundle<BYTES_PER_BLOB>>,
+    ) -> Result<
+        SignedBeaconBlock<
+            MAX_PROPOSER_SLASHINGS,
+            MAX_VALIDATORS_PER_COMMITTEE,
+            MAX_ATTESTER_SLASHINGS,
+            MAX_ATTESTATIONS,
+            MAX_DEPOSITS,
+            MAX_VOLUNTARY_EXITS,
+            SYNC_COMMITTEE_SIZE,
+            BYTES_PER_LOGS_BLOOM,
+            MAX_EXTRA_DATA_BYTES,
+            MAX_BYTES_PER_TRANSACTION,
+            MAX_TRANSACTIONS_PER_PAYLOAD,
+            MAX_WITHDRAWALS_PER_PAYLOAD,
+            MAX_BLS_TO_EXECUTION_CHANGES,
+            MAX_BLOB_COMMITMENTS_PER_BLOCK,
+        >,
+        Error,
+    > {
+        let block = match (self, execution_payload) {
+            (Self::Bellatrix(block), ExecutionPayload::Bellatrix(payload)) => {
+                SignedBeaconBlock::Bellatrix(block.unblind(payload)?)
+            }
+            (Self::Capella(block), ExecutionPayload::Capella(payload)) => {
+                SignedBeaconBlock::Capella(block.unblind(payload)?)
+            }
+            (Self::Deneb(block), ExecutionPayload::Deneb(payload)) => {
+                let empty_bundle = BlobsBundle::default();
+                let blobs_bundle = blobs_bundle.unwrap_or(&empty_bundle);
+                SignedBeaconBlock::Deneb(block.unblind(payload, blobs_bundle)?)
+            }
+            (block, payload) => {
+                return Err(Error::IncorrectFork {
+                    expected: block.version(),
+                    provided: payload.version(),
+                })
+            }
+        };
+        Ok(block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bellatrix::mainnet as bellatrix,
+        capella::mainnet as capella,
+        crypto::KzgCommitment,
+        deneb::mainnet as deneb,
+        electra::mainnet as electra,
+        phase0::mainnet as phase0,
+        ssz::prelude::*,
+        types::mainnet::{ExecutionPayload, SignedBeaconBlock},
+        Error, Fork,
+    };
+
+    #[test]
+    fn test_blind_and_unblind_bellatrix() {
+        let mut inner = bellatrix::SignedBeaconBlock::default();
+        inner.message.body.execution_payload.gas_limit = 30_000_000;
+        let payload = inner.message.body.execution_payload.clone();
+        let block = SignedBeaconBlock::Bellatrix(inner);
+
+        let blinded = block.blind().unwrap();
+        assert_eq!(
+            blinded.message().hash_tree_root().unwrap(),
+            block.message().hash_tree_root().unwrap()
+        );
+
+        let no_blobs: Option<&deneb::BlobsBundle> = None;
+        let unblinded = blinded.unblind(ExecutionPayload::Bellatrix(payload.clone()), no_blobs);
+        assert_eq!(unblinded.unwrap(), block);
+
+        let mut other_payload = payload;
+        other_payload.gas_limit += 1;
+        let result = blinded.unblind(ExecutionPayload::Bellatrix(other_payload), no_blobs);
+        assert!(matches!(result, Err(Error::ExecutionPayloadHeaderMismatch { .. })));
+    }
+
+    #[test]
+    fn test_blind_and_unblind_capella() {
+        let mut inner = capella::SignedBeaconBlock::default();
+        inner.message.body.execution_payload.gas_limit = 30_000_000;
+        inner.message.body.execution_payload.withdrawals.push(Default::default());
+        let payload = inner.message.body.execution_payload.clone();
+        let block = SignedBeaconBlock::Capella(inner);
+
+        let blinded = block.blind().unwrap();
+        assert_eq!(
+            blinded.message().hash_tree_root().unwrap(),
+            block.message().hash_tree_root().unwrap()
+        );
+
+        let no_blobs: Option<&deneb::BlobsBundle> = None;
+        let unblinded = blinded.unblind(ExecutionPayload::Capella(payload.clone()), no_blobs);
+        assert_eq!(unblinded.unwrap(), block);
+
+        let mut other_payload = payload;
+        other_payload.withdrawals = Default::default();
+        let result = blinded.unblind(ExecutionPayload::Capella(other_payload), no_blobs);
+        assert!(matches!(result, Err(Error::ExecutionPayloadHeaderMismatch { .. })));
+    }
+
+    #[test]
+    fn test_unblind_with_incorrect_fork() {
+        let block = SignedBeaconBlock::Capella(Default::default());
+        let blinded = block.blind().unwrap();
+        let payload = ExecutionPayload::Bellatrix(Default::default());
+        let no_blobs: Option<&deneb::BlobsBundle> = None;
+        let result = blinded.unblind(payload, no_blobs);
+        assert!(matches!(
+            result,
+            Err(Error::IncorrectFork { expected: Fork::Capella, provided: Fork::Bellatrix })
+        ));
+    }
+
+    #[test]
+    fn test_blind_without_execution_payload() {
+        let block = SignedBeaconBlock::Phase0(phase0::SignedBeaconBlock::default());
+        let result = block.blind();
+        assert!(matches!(result, Err(Error::MissingExecutionPayload(Fork::Phase0))));
+    }
+
+    #[test]
+    fn test_blind_and_unblind() {
+        let mut inner = deneb::SignedBeaconBlock::default();
+        inner.message.body.execution_payload.gas_limit = 30_000_000;
+        let payload = inner.message.body.execution_payload.clone();
+        let block = SignedBeaconBlock::Deneb(inner);
+
+        let blinded = block.blind().unwrap();
+        assert_eq!(
+            blinded.message().hash_tree_root().unwrap(),
+            block.message().hash_tree_root().unwrap()
+        );
+
+        let blobs_bundle = deneb::BlobsBundle::default();
+        let unblinded =
+            blinded.unblind(ExecutionPayload::Deneb(payload.clone()), Some(&blobs_bundle)).unwrap();
+        assert_eq!(unblinded, block);
+
+        let mut other_payload = payload;
+        other_payload.gas_limit += 1;
+        let result = blinded.unblind(ExecutionPayload::Deneb(other_payload), Some(&blobs_bundle));
+        assert!(matches!(result, Err(Error::ExecutionPayloadHeaderMismatch { .. })));
+    }
+
+    #[test]
+    fn test_blind_and_unblind_electra() {
+        let mut block = electra::SignedBeaconBlock::default();
+        block.message.body.execution_payload.gas_limit = 30_000_000;
+        block.message.body.execution_payload.withdrawal_requests.push(Default::default());
+        block.message.body.blob_kzg_commitments.push(KzgCommitment::default());
+        let payload = block.message.body.execution_payload.clone();
+
+        let blinded = block.blind().unwrap();
+        assert_eq!(
+            blinded.message.hash_tree_root().unwrap(),
+            block.message.hash_tree_root().unwrap()
+        );
+
+        let mut blobs_bundle = electra::BlobsBundle::default();
+        blobs_bundle.commitments.push(KzgCommitment::default());
+        blobs_bundle.proofs.push(Default::default());
+        blobs_bundle.blobs.push(Default::default());
+        let unblinded = blinded.unblind(payload.clone(), &blobs_bundle).unwrap();
+        assert_eq!(unblinded, block);
+
+        let result = blinded.unblind(payload.clone(), &electra::BlobsBundle::default());
+        assert!(matches!(result, Err(Error::BlobsBundleMismatch)));
+
+        let mut other_payload = payload;
+        other_payload.withdrawal_requests = Default::default();
+        let result = blinded.unblind(other_payload, &blobs_bundle);
+        assert!(matches!(result, Err(Error::ExecutionPayloadHeaderMismatch { .. })));
+    }
+}
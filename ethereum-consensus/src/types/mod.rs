@@ -9,6 +9,7 @@ mod beacon_block_body;
 mod beacon_state;
 mod blinded_beacon_block;
 mod blinded_beacon_block_body;
+mod blinding;
 mod execution_payload;
 mod execution_payload_header;
 mod presets;
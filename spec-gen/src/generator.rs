@@ -96,6 +96,7 @@ impl Fork {
             Self::Electra => &[
                 "beacon_block",
                 "beacon_state",
+                "blinded_beacon_block",
                 "block_processing",
                 "constants",
                 "epoch_processing",
@@ -104,6 +105,7 @@ impl Fork {
                 "genesis",
                 "helpers",
                 "operations",
+                "validator",
             ],
         }
     }